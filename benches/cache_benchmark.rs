@@ -1,68 +1,78 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lru_cache::bench::{self, Access, Workload};
 use lru_cache::lru::{Cache, traits::CacheTrait};
+use lru_cache::lru::doubles::{NullCache, UnboundedMapCache};
+
+/// Pattern d'accès réaliste partagé par les comparaisons.
+const REALISTIC: Workload = Workload::Realistic { hot: 200, cold: 2000 };
+
+/// Exécute un accès de la charge de travail sur n'importe quelle implémentation.
+fn step<C: CacheTrait<u64, String>>(cache: &mut C, access: Access) {
+    black_box(bench::apply(cache, access, &|k| k, &bench::string_value));
+}
+
+/// Mesure une charge de travail par lots de `batch` accès.
+fn bench_workload<C: CacheTrait<u64, String>>(
+    b: &mut criterion::Bencher,
+    mut cache: C,
+    workload: Workload,
+    batch: usize,
+) {
+    let mut accesses = workload.accesses();
+    b.iter(|| {
+        for access in accesses.by_ref().take(batch) {
+            step(&mut cache, access);
+        }
+    });
+}
 
 fn cache_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("Cache Operations");
     
     // Test de remplissage du cache
     group.bench_function("cache fill", |b| {
-        let mut cache = Cache::new(1000);
-        b.iter(|| {
-            for i in 0..1000 {
-                cache.put(black_box(i), black_box(format!("value_{}", i)));
-            }
-        });
+        bench_workload(b, Cache::new(1000), Workload::Fill { keys: 1000 }, 1000);
     });
 
     // Test d'accès séquentiel avec rotation
     group.bench_function("sequential access with rotation", |b| {
         let mut cache = Cache::new(1000);
         // Remplissage initial
-        for i in 0..1000 {
-            cache.put(i, format!("value_{}", i));
-        }
-        b.iter(|| {
-            for i in 0..2000 {
-                let key = i % 1500;
-                black_box(cache.get(&black_box(key)));
-            }
-        });
+        bench::run(&mut cache, Workload::Fill { keys: 1000 }.accesses().take(1000), |k| k, bench::string_value);
+        bench_workload(b, cache, Workload::SequentialRotation { keys: 1500 }, 2000);
     });
 
     // Test de remplacement LRU
     group.bench_function("lru replacement", |b| {
-        let mut cache = Cache::new(1000);
-        b.iter(|| {
-            for i in 0..2000 {
-                cache.put(black_box(i), black_box(format!("value_{}", i)));
-                if i % 2 == 0 {
-                    black_box(cache.get(&black_box(i - 1)));
-                }
-            }
-        });
+        bench_workload(b, Cache::new(1000), Workload::Replacement, 3000);
     });
 
     // Test de pattern d'accès réaliste
     group.bench_function("realistic access pattern", |b| {
-        let mut cache = Cache::new(1000);
-        let mut i = 0;
-        b.iter(|| {
-            let key = if i % 5 == 0 {
-                black_box(i % 2000)
-            } else {
-                black_box(i % 200)
-            };
-            if i % 3 == 0 {
-                cache.put(black_box(key), black_box(format!("value_{}", key)));
-            } else {
-                black_box(cache.get(&black_box(key)));
-            }
-            i += 1;
-        });
+        bench_workload(b, Cache::new(1000), REALISTIC, 1);
+    });
+
+    group.finish();
+}
+
+fn baseline_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Cache Baselines");
+
+    // Comparaison du LRU avec « ne rien cacher » et « tout cacher »
+    group.bench_function("lru", |b| {
+        bench_workload(b, Cache::new(1000), REALISTIC, 1);
+    });
+
+    group.bench_function("null", |b| {
+        bench_workload(b, NullCache::new(), REALISTIC, 1);
+    });
+
+    group.bench_function("unbounded map", |b| {
+        bench_workload(b, UnboundedMapCache::new(), REALISTIC, 1);
     });
 
     group.finish();
 }
 
-criterion_group!(benches, cache_benchmark);
-criterion_main!(benches);
\ No newline at end of file
+criterion_group!(benches, cache_benchmark, baseline_benchmark);
+criterion_main!(benches);
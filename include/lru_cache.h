@@ -0,0 +1,57 @@
+/*
+ * Interface C du cache LRU (fonctionnalité `ffi`).
+ *
+ * Bibliothèque dynamique : cargo rustc --lib --release --features ffi --crate-type cdylib
+ *
+ * Clés et valeurs : octets UTF-8 sans tabulation ni saut de ligne, afin de
+ * rester compatibles avec les fichiers de persistance. Une poignée peut être
+ * partagée entre threads.
+ */
+#ifndef LRU_CACHE_H
+#define LRU_CACHE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define LRU_CACHE_OK 0
+#define LRU_CACHE_NOT_FOUND 1
+#define LRU_CACHE_INVALID_ARGUMENT (-1)
+#define LRU_CACHE_IO_ERROR (-2)
+#define LRU_CACHE_POISONED (-3)
+
+typedef struct LruCache LruCache;
+
+/* Crée un cache vide ; NULL si la capacité est 0. */
+LruCache *lru_cache_new(size_t capacity);
+
+/* Crée un cache chargé depuis un fichier de persistance s'il existe ; NULL en cas d'erreur. */
+LruCache *lru_cache_open(const char *path, size_t capacity);
+
+/* Libère un cache (NULL accepté). */
+void lru_cache_free(LruCache *cache);
+
+int lru_cache_put(LruCache *cache, const uint8_t *key, size_t key_len,
+                  const uint8_t *value, size_t value_len);
+
+/* En cas de succès, *value est à libérer avec lru_cache_free_value. */
+int lru_cache_get(LruCache *cache, const uint8_t *key, size_t key_len,
+                  uint8_t **value, size_t *value_len);
+
+void lru_cache_free_value(uint8_t *value, size_t value_len);
+
+int lru_cache_remove(LruCache *cache, const uint8_t *key, size_t key_len);
+
+size_t lru_cache_len(const LruCache *cache);
+
+/* Sauvegarde au format de Cache::persist. */
+int lru_cache_persist(LruCache *cache, const char *path);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LRU_CACHE_H */
@@ -0,0 +1,174 @@
+//! Outils de mesure réutilisables pour comparer des caches.
+//!
+//! Ce module regroupe les charges de travail et les mesures du benchmark
+//! criterion de la bibliothèque (`benches/cache_benchmark.rs`), afin qu'une
+//! application puisse évaluer ses propres types de clés et de valeurs, ou
+//! ses propres politiques, avec exactement la même méthodologie.
+//!
+//! Une `Workload` produit une suite d'accès (`Access`) portant sur des
+//! numéros de clés. `apply` exécute un accès sur n'importe quel
+//! `CacheTrait`, en convertissant le numéro en clé et en valeur par les
+//! fonctions fournies ; `run` exécute une suite d'accès et mesure le débit et
+//! le taux de succès. Les clés et les valeurs passent par `black_box`, comme
+//! dans le benchmark, pour que l'optimiseur ne supprime pas le travail
+//! mesuré.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::bench::{self, Workload};
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::doubles::NullCache;
+//!
+//! let workload = Workload::Realistic { hot: 200, cold: 2000 };
+//! let lru = bench::run(&mut Cache::new(1000), workload.accesses().take(10_000), |k| k, bench::string_value);
+//! let null = bench::run(&mut NullCache::new(), workload.accesses().take(10_000), |k| k, bench::string_value);
+//!
+//! assert!(lru.hit_ratio() > 0.5);
+//! assert_eq!(null.hit_ratio(), 0.0);
+//! assert_eq!(lru.operations, 10_000);
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use crate::lru::traits::CacheTrait;
+
+/// Accès élémentaire d'une charge de travail, portant sur un numéro de clé.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Access {
+    /// Lecture de la clé
+    Get(u64),
+    /// Écriture de la clé
+    Put(u64),
+}
+
+/// Charges de travail du benchmark de la bibliothèque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Workload {
+    /// Écritures des clés `0..keys`, en boucle
+    Fill {
+        /// Nombre de clés distinctes
+        keys: u64,
+    },
+    /// Lectures des clés `0..keys`, en boucle
+    SequentialRotation {
+        /// Nombre de clés distinctes
+        keys: u64,
+    },
+    /// Écriture de clés toujours nouvelles, suivie une fois sur deux de la
+    /// lecture de la clé précédente
+    Replacement,
+    /// Un accès sur cinq porte sur les clés `0..cold`, les autres sur les
+    /// clés chaudes `0..hot` ; un accès sur trois est une écriture
+    Realistic {
+        /// Nombre de clés chaudes
+        hot: u64,
+        /// Nombre total de clés
+        cold: u64,
+    },
+}
+
+impl Workload {
+    /// Retourne la suite infinie des accès de la charge de travail.
+    pub fn accesses(self) -> impl Iterator<Item = Access> {
+        (0u64..).flat_map(move |i| {
+            let (first, second) = match self {
+                Workload::Fill { keys } => (Access::Put(i % keys.max(1)), None),
+                Workload::SequentialRotation { keys } => (Access::Get(i % keys.max(1)), None),
+                Workload::Replacement => {
+                    let previous = (i % 2 == 0 && i > 0).then(|| Access::Get(i - 1));
+                    (Access::Put(i), previous)
+                }
+                Workload::Realistic { hot, cold } => {
+                    let key = if i % 5 == 0 { i % cold.max(1) } else { i % hot.max(1) };
+                    let access = if i % 3 == 0 { Access::Put(key) } else { Access::Get(key) };
+                    (access, None)
+                }
+            };
+            std::iter::once(first).chain(second)
+        })
+    }
+}
+
+/// Valeur utilisée par le benchmark de la bibliothèque : `value_<numéro>`.
+pub fn string_value(key: u64) -> String {
+    format!("value_{}", key)
+}
+
+/// Exécute un accès sur le cache et indique, pour une lecture, si elle a
+/// réussi.
+pub fn apply<C, K, V, FK, FV>(cache: &mut C, access: Access, key: &FK, value: &FV) -> Option<bool>
+where
+    C: CacheTrait<K, V> + ?Sized,
+    FK: Fn(u64) -> K,
+    FV: Fn(u64) -> V,
+{
+    match access {
+        Access::Get(k) => Some(black_box(cache.get(&black_box(key(k)))).is_some()),
+        Access::Put(k) => {
+            cache.put(black_box(key(k)), black_box(value(k)));
+            None
+        }
+    }
+}
+
+/// Résultat de l'exécution d'une suite d'accès.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RunReport {
+    /// Nombre total d'accès
+    pub operations: u64,
+    /// Lectures
+    pub gets: u64,
+    /// Lectures réussies
+    pub hits: u64,
+    /// Écritures
+    pub puts: u64,
+    /// Durée totale d'exécution
+    pub elapsed: Duration,
+}
+
+impl RunReport {
+    /// Retourne la proportion de lectures réussies, ou 0 sans lecture.
+    pub fn hit_ratio(&self) -> f64 {
+        if self.gets == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.gets as f64
+        }
+    }
+
+    /// Retourne le débit, en accès par seconde.
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            0.0
+        } else {
+            self.operations as f64 / secs
+        }
+    }
+}
+
+/// Exécute la suite d'accès sur le cache et mesure le débit et le taux de
+/// succès.
+pub fn run<C, K, V, I, FK, FV>(cache: &mut C, accesses: I, key: FK, value: FV) -> RunReport
+where
+    C: CacheTrait<K, V> + ?Sized,
+    I: IntoIterator<Item = Access>,
+    FK: Fn(u64) -> K,
+    FV: Fn(u64) -> V,
+{
+    let mut report = RunReport::default();
+    let start = Instant::now();
+    for access in accesses {
+        report.operations += 1;
+        match apply(cache, access, &key, &value) {
+            Some(hit) => {
+                report.gets += 1;
+                report.hits += u64::from(hit);
+            }
+            None => report.puts += 1,
+        }
+    }
+    report.elapsed = start.elapsed();
+    report
+}
@@ -0,0 +1,258 @@
+mod reload;
+mod settings;
+mod shutdown;
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+use lru_cache::lru::{Cache, sync::SyncCache, traits::CacheTrait};
+use lru_cache::lru::duration::HumanDuration;
+use lru_cache::lru::format;
+use lru_cache::lru::sensitive::Sensitive;
+use reload::ReloadTrigger;
+use std::time::{SystemTime, UNIX_EPOCH};
+use settings::Settings;
+use shutdown::Shutdown;
+
+fn save_cache(cache: &Cache<String, String>, path: &Path) -> io::Result<()> {
+    // Créer le dossier du fichier s'il n'existe pas
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+
+    // Sauvegarder chaque paire clé-valeur
+    let mut writer = BufWriter::new(&mut file);
+    for (key, value) in cache.iter() {
+        writeln!(writer, "{}:{}", key, value)?;
+    }
+    writer.flush()?;
+    drop(writer);
+
+    // S'assurer que les données sont sur disque avant de rendre la main
+    file.sync_all()
+}
+
+/// Valeur telle qu'affichée, masquée si demandé.
+fn shown(value: &str, redact: bool) -> String {
+    if redact {
+        Sensitive::new(value).to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn load_cache(cache: &mut Cache<String, String>, path: &Path, redact: bool) -> io::Result<()> {
+    // Si le fichier n'existe pas, on retourne sans erreur
+    if !path.exists() {
+        println!("Aucun cache existant trouvé. Création d'un nouveau cache.");
+        return Ok(());
+    }
+
+    println!("Chargement du cache existant...");
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+
+    for line in reader.lines() {
+        let line = line?;
+        if let Some((key, value)) = line.split_once(':') {
+            println!("Chargé: {} -> {}", key, shown(value, redact));
+            cache.put(key.to_string(), value.to_string());
+        }
+    }
+
+    Ok(())
+}
+
+fn get_timestamp() -> String {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        .to_string()
+}
+
+fn to_io_error<E: std::fmt::Display>(err: E) -> io::Error {
+    io::Error::other(err.to_string())
+}
+
+/// Ajoute une entrée horodatée, sauvegarde puis affiche le cache.
+fn run_once(settings: &Settings) -> io::Result<()> {
+    let mut cache = Cache::new(settings.capacity);
+
+    // Charger les données existantes
+    load_cache(&mut cache, &settings.file, settings.redact)?;
+
+    // Ajouter de nouvelles données avec un timestamp
+    let timestamp = get_timestamp();
+    println!("\nAjout de nouvelles données avec timestamp {}:", timestamp);
+
+    let new_key = format!("nouvelle_clé_{}", timestamp);
+    let new_value = format!("nouvelle_valeur_{}", timestamp);
+
+    println!("Ajout: {} -> {}", new_key, shown(&new_value, settings.redact));
+    cache.put(new_key, new_value);
+
+    // Sauvegarder le cache
+    save_cache(&cache, &settings.file)?;
+
+    println!("\nCache sauvegardé avec succès dans {}!", settings.file.display());
+    println!("\nContenu actuel du cache:");
+    for (key, value) in cache.iter() {
+        println!("{}: {}", key, shown(value, settings.redact));
+    }
+
+    Ok(())
+}
+
+/// Délai maximal entre deux vérifications d'une demande de rechargement.
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Garde le cache en mémoire, le sert aux répliques si une adresse d'écoute
+/// est configurée, et le sauvegarde à intervalle régulier. Les réglages sont
+/// rechargés à chaud lorsque le fichier de configuration change ou à la
+/// réception de SIGHUP. SIGINT et SIGTERM arrêtent le démon après une
+/// dernière sauvegarde.
+fn run_daemon(settings: Settings) -> io::Result<()> {
+    let mut cache = Cache::try_new(settings.capacity).map_err(to_io_error)?;
+    cache.set_default_ttl(settings.ttl);
+    load_cache(&mut cache, &settings.file, settings.redact)?;
+    let cache = SyncCache::from_cache(cache);
+    let shutdown = Shutdown::install()?;
+
+    // Le serveur de réplication est arrêté au retour de la boucle, avant la
+    // sauvegarde finale
+    let settings = serve_until_shutdown(&cache, settings, &shutdown)?;
+    cache
+        .with_lock(|cache| save_cache(cache, &settings.file))
+        .map_err(to_io_error)??;
+    println!("Arrêt: cache sauvegardé dans {}", settings.file.display());
+    Ok(())
+}
+
+/// Boucle du démon : rechargements et sauvegardes périodiques jusqu'à la
+/// demande d'arrêt. Retourne les réglages en vigueur à l'arrêt.
+fn serve_until_shutdown(
+    cache: &SyncCache<String, String>,
+    mut settings: Settings,
+    shutdown: &Shutdown,
+) -> io::Result<Settings> {
+    let _server = serve(cache, &settings)?;
+    let mut trigger = ReloadTrigger::new(&settings)?;
+
+    println!(
+        "Mode démon: sauvegarde de {} toutes les {}",
+        settings.file.display(),
+        HumanDuration(settings.flush_interval)
+    );
+    let mut last_flush = Instant::now();
+    loop {
+        thread::sleep(RELOAD_CHECK_INTERVAL.min(settings.flush_interval));
+        if shutdown.requested() {
+            return Ok(settings);
+        }
+        if trigger.poll() {
+            match Settings::from_env_and_args() {
+                Ok(next) => settings = reload::apply(cache, &settings, next),
+                Err(err) => eprintln!("Rechargement ignoré: {}", err),
+            }
+        }
+        if last_flush.elapsed() >= settings.flush_interval {
+            cache
+                .with_lock(|cache| save_cache(cache, &settings.file))
+                .map_err(to_io_error)??;
+            last_flush = Instant::now();
+        }
+    }
+}
+
+#[cfg(feature = "tcp-sync")]
+fn serve(
+    cache: &SyncCache<String, String>,
+    settings: &Settings,
+) -> io::Result<Option<lru_cache::lru::tcp_sync::SyncHandle>> {
+    const REPLICATION_LOG: usize = 10_000;
+    const REPLICATION_INTERVAL: Duration = Duration::from_millis(100);
+
+    let Some(addr) = settings.listen.as_deref() else {
+        return Ok(None);
+    };
+    cache
+        .with_lock(|cache| cache.enable_replication_log(REPLICATION_LOG))
+        .map_err(to_io_error)?;
+    let server = cache
+        .serve_replication(addr, REPLICATION_INTERVAL)
+        .map_err(to_io_error)?;
+    println!("Réplication servie sur {}", server.local_addr());
+    Ok(Some(server))
+}
+
+#[cfg(not(feature = "tcp-sync"))]
+fn serve(_cache: &SyncCache<String, String>, settings: &Settings) -> io::Result<Option<()>> {
+    match settings.listen {
+        Some(_) => Err(io::Error::other(
+            "l'écoute réseau requiert la fonctionnalité `tcp-sync`",
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Affiche la description d'un fichier de persistance, lue depuis son
+/// en-tête.
+fn describe_file(path: &Path) -> io::Result<()> {
+    let Some(description) = format::describe(path).map_err(to_io_error)? else {
+        println!("{}: aucun en-tête (format antérieur à la version 1)", path.display());
+        return Ok(());
+    };
+    let created = description.created.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let age = SystemTime::now().duration_since(description.created).unwrap_or_default();
+    println!("Fichier: {}", path.display());
+    println!("Version du format: {}", description.format_version);
+    println!("Codec: {}", description.codec);
+    println!("Entrées: {}", description.entries);
+    println!("Capacité: {}", description.capacity);
+    println!("Créé: {} (il y a {})", created, HumanDuration(Duration::from_secs(age.as_secs())));
+    Ok(())
+}
+
+/// Exécute les commandes qui n'ouvrent pas de cache, si les arguments en
+/// désignent une.
+fn run_tool(args: &[String]) -> Option<io::Result<()>> {
+    match args.first().map(String::as_str) {
+        Some("--describe") => Some(match args.get(1) {
+            Some(path) => describe_file(Path::new(path)),
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput, settings::USAGE)),
+        }),
+        Some("--format-doc") => {
+            print!("{}", format::format_documentation());
+            Some(Ok(()))
+        }
+        _ => None,
+    }
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(result) = run_tool(&args) {
+        return result;
+    }
+
+    let settings = Settings::from_env_and_args().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2);
+    });
+
+    if settings.daemon || settings.listen.is_some() {
+        run_daemon(settings)
+    } else {
+        run_once(&settings)
+    }
+}
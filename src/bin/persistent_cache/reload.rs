@@ -0,0 +1,91 @@
+//! Rechargement à chaud des réglages en mode démon.
+//!
+//! Les réglages sont relus lorsque le fichier de configuration change (date
+//! de modification) ou, avec la fonctionnalité `signals`, à la réception de
+//! SIGHUP. Seuls les changements sûrs sont appliqués au cache en place :
+//! capacité, durée de vie par défaut et intervalle de sauvegarde. Les autres
+//! (fichier de persistance, adresse d'écoute) demandent un redémarrage et
+//! sont seulement signalés.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use lru_cache::lru::duration::HumanDuration;
+use lru_cache::lru::sync::SyncCache;
+use crate::settings::Settings;
+
+/// Détecte les demandes de rechargement.
+pub struct ReloadTrigger {
+    config: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    #[cfg(feature = "signals")]
+    hangup: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl ReloadTrigger {
+    /// Surveille le fichier de configuration des réglages et, si possible,
+    /// le signal SIGHUP.
+    pub fn new(settings: &Settings) -> io::Result<Self> {
+        let config = settings.config.clone();
+        let modified = config.as_ref().and_then(|path| modified(path));
+        Ok(ReloadTrigger {
+            config,
+            modified,
+            #[cfg(feature = "signals")]
+            hangup: {
+                let flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+                signal_hook::flag::register(signal_hook::consts::SIGHUP, std::sync::Arc::clone(&flag))?;
+                flag
+            },
+        })
+    }
+
+    /// Indique si un rechargement a été demandé depuis le dernier appel.
+    pub fn poll(&mut self) -> bool {
+        #[cfg(feature = "signals")]
+        let hangup = self.hangup.swap(false, std::sync::atomic::Ordering::Relaxed);
+        #[cfg(not(feature = "signals"))]
+        let hangup = false;
+
+        let current = self.config.as_ref().and_then(|path| modified(path));
+        let changed = current.is_some() && current != self.modified;
+        self.modified = current;
+        hangup || changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Applique au cache les changements sûrs entre les anciens et les nouveaux
+/// réglages, et retourne les réglages désormais en vigueur.
+pub fn apply(cache: &SyncCache<String, String>, current: &Settings, next: Settings) -> Settings {
+    if next.capacity != current.capacity || next.ttl != current.ttl {
+        let applied = cache.with_lock(|cache| {
+            cache.set_default_ttl(next.ttl);
+            cache.resize(next.capacity)
+        });
+        match applied {
+            Ok(Ok(())) => match next.ttl {
+                Some(ttl) => println!("Capacité: {}, durée de vie: {}", next.capacity, HumanDuration(ttl)),
+                None => println!("Capacité: {}, sans durée de vie", next.capacity),
+            },
+            Ok(Err(err)) | Err(err) => eprintln!("Rechargement de la capacité impossible: {}", err),
+        }
+    }
+    if next.flush_interval != current.flush_interval {
+        println!("Sauvegarde toutes les {}", HumanDuration(next.flush_interval));
+    }
+    if next.file != current.file || next.listen != current.listen {
+        eprintln!("Le fichier de persistance et l'adresse d'écoute ne changent qu'au redémarrage");
+    }
+
+    Settings {
+        file: current.file.clone(),
+        listen: current.listen.clone(),
+        daemon: current.daemon,
+        ..next
+    }
+}
@@ -0,0 +1,313 @@
+//! Réglages du binaire `persistent_cache`.
+//!
+//! Chaque réglage est lu, de la source la moins prioritaire à la plus
+//! prioritaire : valeur par défaut, fichier de configuration (`--config` ou
+//! `LRU_CACHE_CONFIG`, avec la fonctionnalité `config`), variables
+//! d'environnement, puis options de la ligne de commande. Un conteneur peut
+//! ainsi être configuré par son environnement seul, tandis qu'une option
+//! explicite garde le dernier mot.
+//!
+//! | Option          | Variable                | Défaut                 |
+//! |-----------------|-------------------------|------------------------|
+//! | `--capacity`    | `LRU_CACHE_CAPACITY`    | 5                      |
+//! | `--file`        | `LRU_CACHE_FILE`        | `cache/cache_data.txt` |
+//! | `--flush-secs`  | `LRU_CACHE_FLUSH_SECS`  | 60 (mode démon)        |
+//! | `--listen`      | `LRU_CACHE_LISTEN`      | aucune                 |
+//! | `--config`      | `LRU_CACHE_CONFIG`      | aucun                  |
+//! | `--daemon`      | `LRU_CACHE_DAEMON`      | désactivé              |
+//! | `--redact`      | `LRU_CACHE_REDACT`      | désactivé              |
+//!
+//! L'intervalle de sauvegarde accepte un nombre de secondes ou une durée
+//! lisible (`500ms`, `2h30m`). La durée de vie par défaut des entrées
+//! (`ttl`) n'est lue que depuis le fichier de configuration. Avec
+//! `--redact`, les valeurs ne sont jamais affichées en clair.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use lru_cache::lru::duration::parse_duration;
+
+const DEFAULT_CAPACITY: usize = 5;
+const DEFAULT_FILE: &str = "cache/cache_data.txt";
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+pub const USAGE: &str = "Usage: persistent_cache [--capacity N] [--file CHEMIN] [--flush-secs DURÉE] \
+[--listen ADRESSE] [--config CHEMIN] [--daemon] [--redact]
+       persistent_cache --describe CHEMIN
+       persistent_cache --format-doc";
+
+/// Réglages résolus du binaire.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// Nombre maximal d'entrées
+    pub capacity: usize,
+    /// Fichier de persistance
+    pub file: PathBuf,
+    /// Intervalle entre deux sauvegardes en mode démon
+    pub flush_interval: Duration,
+    /// Adresse d'écoute de la réplication TCP
+    pub listen: Option<String>,
+    /// Fichier de configuration lu
+    pub config: Option<PathBuf>,
+    /// Reste actif et sauvegarde périodiquement au lieu de s'arrêter
+    pub daemon: bool,
+    /// Durée de vie par défaut des entrées
+    pub ttl: Option<Duration>,
+    /// Masque les valeurs dans les affichages
+    pub redact: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            capacity: DEFAULT_CAPACITY,
+            file: PathBuf::from(DEFAULT_FILE),
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            listen: None,
+            config: None,
+            daemon: false,
+            ttl: None,
+            redact: false,
+        }
+    }
+}
+
+/// Réglages fournis par une source, chacun pouvant être absent.
+#[derive(Debug, Default)]
+struct Overrides {
+    capacity: Option<usize>,
+    file: Option<PathBuf>,
+    flush_interval: Option<Duration>,
+    listen: Option<String>,
+    config: Option<PathBuf>,
+    daemon: Option<bool>,
+    redact: Option<bool>,
+}
+
+impl Overrides {
+    fn from_env<E>(env: E) -> Result<Self, String>
+    where
+        E: Fn(&str) -> Option<String>,
+    {
+        let var = |name: &str| env(name).filter(|value| !value.trim().is_empty());
+        Ok(Overrides {
+            capacity: var("LRU_CACHE_CAPACITY").map(|v| parse("LRU_CACHE_CAPACITY", &v)).transpose()?,
+            file: var("LRU_CACHE_FILE").map(PathBuf::from),
+            flush_interval: var("LRU_CACHE_FLUSH_SECS")
+                .map(|v| parse_interval("LRU_CACHE_FLUSH_SECS", &v))
+                .transpose()?,
+            listen: var("LRU_CACHE_LISTEN"),
+            config: var("LRU_CACHE_CONFIG").map(PathBuf::from),
+            daemon: var("LRU_CACHE_DAEMON").map(|v| parse_flag("LRU_CACHE_DAEMON", &v)).transpose()?,
+            redact: var("LRU_CACHE_REDACT").map(|v| parse_flag("LRU_CACHE_REDACT", &v)).transpose()?,
+        })
+    }
+
+    fn from_args<I>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut overrides = Overrides::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            if name == "--daemon" {
+                overrides.daemon = Some(true);
+                continue;
+            }
+            if name == "--redact" {
+                overrides.redact = Some(true);
+                continue;
+            }
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("valeur manquante pour {}", name))
+            };
+            match name.as_str() {
+                "--capacity" => overrides.capacity = Some(parse(&name, &value()?)?),
+                "--file" => overrides.file = Some(PathBuf::from(value()?)),
+                "--flush-secs" => overrides.flush_interval = Some(parse_interval(&name, &value()?)?),
+                "--listen" => overrides.listen = Some(value()?),
+                "--config" => overrides.config = Some(PathBuf::from(value()?)),
+                _ => return Err(format!("option inconnue: {}\n{}", name, USAGE)),
+            }
+        }
+        Ok(overrides)
+    }
+
+    fn apply(self, settings: &mut Settings) {
+        if let Some(capacity) = self.capacity {
+            settings.capacity = capacity;
+        }
+        if let Some(file) = self.file {
+            settings.file = file;
+        }
+        if let Some(interval) = self.flush_interval {
+            settings.flush_interval = interval;
+        }
+        if self.listen.is_some() {
+            settings.listen = self.listen;
+        }
+        if self.config.is_some() {
+            settings.config = self.config;
+        }
+        if let Some(daemon) = self.daemon {
+            settings.daemon = daemon;
+        }
+        if let Some(redact) = self.redact {
+            settings.redact = redact;
+        }
+    }
+}
+
+impl Settings {
+    /// Résout les réglages du processus courant.
+    pub fn from_env_and_args() -> Result<Self, String> {
+        Self::resolve(std::env::args().skip(1), |name| std::env::var(name).ok())
+    }
+
+    /// Résout les réglages à partir des arguments et de l'environnement
+    /// donnés, les options l'emportant sur l'environnement, lui-même
+    /// prioritaire sur le fichier de configuration.
+    pub fn resolve<I, E>(args: I, env: E) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+        E: Fn(&str) -> Option<String>,
+    {
+        let flags = Overrides::from_args(args)?;
+        let vars = Overrides::from_env(env)?;
+
+        let mut settings = Settings::default();
+        if let Some(path) = flags.config.as_ref().or(vars.config.as_ref()) {
+            settings.apply_config_file(path.clone())?;
+        }
+        vars.apply(&mut settings);
+        flags.apply(&mut settings);
+
+        if settings.capacity == 0 {
+            return Err("la capacité doit être strictement positive".to_string());
+        }
+        if settings.flush_interval.is_zero() {
+            return Err("l'intervalle de sauvegarde doit être strictement positif".to_string());
+        }
+        Ok(settings)
+    }
+
+    #[cfg(feature = "config")]
+    fn apply_config_file(&mut self, path: PathBuf) -> Result<(), String> {
+        let config = lru_cache::lru::config::CacheConfig::from_file(&path)
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        self.capacity = config.capacity;
+        if let Some(file) = config.persistence_path {
+            self.file = file;
+        }
+        if let Some(interval) = config.flush_interval {
+            self.flush_interval = interval;
+        }
+        self.ttl = config.ttl;
+        self.config = Some(path);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "config"))]
+    fn apply_config_file(&mut self, path: PathBuf) -> Result<(), String> {
+        Err(format!(
+            "{}: la lecture d'un fichier de configuration requiert la fonctionnalité `config`",
+            path.display()
+        ))
+    }
+}
+
+fn parse<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("valeur invalide pour {}: {}", name, value))
+}
+
+fn parse_interval(name: &str, value: &str) -> Result<Duration, String> {
+    parse_duration(value).map_err(|_| format!("valeur invalide pour {}: {}", name, value))
+}
+
+fn parse_flag(name: &str, value: &str) -> Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(format!("valeur invalide pour {}: {}", name, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn resolve(args: &[&str], vars: &[(&str, &str)]) -> Result<Settings, String> {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Settings::resolve(args.iter().map(|arg| arg.to_string()), |name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(resolve(&[], &[]).unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn test_flags_override_environment() {
+        let vars = [
+            ("LRU_CACHE_CAPACITY", "100"),
+            ("LRU_CACHE_FILE", "/data/cache.txt"),
+            ("LRU_CACHE_FLUSH_SECS", "5"),
+            ("LRU_CACHE_LISTEN", "0.0.0.0:7000"),
+            ("LRU_CACHE_DAEMON", "true"),
+        ];
+        let settings = resolve(&[], &vars).unwrap();
+        assert_eq!(settings.capacity, 100);
+        assert_eq!(settings.file, PathBuf::from("/data/cache.txt"));
+        assert_eq!(settings.flush_interval, Duration::from_secs(5));
+        assert_eq!(settings.listen.as_deref(), Some("0.0.0.0:7000"));
+        assert!(settings.daemon);
+
+        let settings = resolve(&["--capacity", "7", "--flush-secs=1m30s"], &vars).unwrap();
+        assert_eq!(settings.capacity, 7);
+        assert_eq!(settings.flush_interval, Duration::from_secs(90));
+        assert_eq!(settings.file, PathBuf::from("/data/cache.txt"));
+        assert!(!settings.redact);
+        assert!(resolve(&["--redact"], &[]).unwrap().redact);
+        assert!(resolve(&[], &[("LRU_CACHE_REDACT", "on")]).unwrap().redact);
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_environment_overrides_config_file() {
+        let path = std::env::temp_dir().join(format!("persistent_cache_{}.toml", std::process::id()));
+        std::fs::write(&path, "capacity = 50\npersistence_path = \"/srv/cache.txt\"\nflush_interval = 10\nttl = 300").unwrap();
+        let config = path.to_str().unwrap();
+
+        let settings = resolve(&["--config", config], &[("LRU_CACHE_CAPACITY", "80")]).unwrap();
+        assert_eq!(settings.capacity, 80);
+        assert_eq!(settings.file, PathBuf::from("/srv/cache.txt"));
+        assert_eq!(settings.flush_interval, Duration::from_secs(10));
+        assert_eq!(settings.ttl, Some(Duration::from_secs(300)));
+
+        let settings = resolve(&[], &[("LRU_CACHE_CONFIG", config)]).unwrap();
+        assert_eq!(settings.capacity, 50);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_values_are_reported() {
+        assert!(resolve(&[], &[("LRU_CACHE_CAPACITY", "beaucoup")]).is_err());
+        assert!(resolve(&["--capacity", "0"], &[]).is_err());
+        assert!(resolve(&["--file"], &[]).is_err());
+        assert!(resolve(&["--inconnue"], &[]).is_err());
+        // Une variable vide est ignorée
+        assert_eq!(resolve(&[], &[("LRU_CACHE_CAPACITY", "")]).unwrap().capacity, DEFAULT_CAPACITY);
+    }
+}
@@ -0,0 +1,52 @@
+//! Arrêt propre du mode démon.
+//!
+//! Avec la fonctionnalité `signals`, SIGINT et SIGTERM demandent l'arrêt :
+//! la boucle du démon cesse de servir les répliques, sauvegarde une dernière
+//! fois le cache sur disque puis se termine normalement. Un second signal
+//! reçu pendant cet arrêt termine le processus immédiatement.
+
+use std::io;
+
+/// Demande d'arrêt reçue par signal.
+pub struct Shutdown {
+    #[cfg(feature = "signals")]
+    requested: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl Shutdown {
+    /// Installe les gestionnaires de SIGINT et SIGTERM.
+    #[cfg(feature = "signals")]
+    pub fn install() -> io::Result<Self> {
+        use signal_hook::consts::{SIGINT, SIGTERM};
+        use signal_hook::flag;
+        use std::sync::Arc;
+        use std::sync::atomic::AtomicBool;
+
+        let requested = Arc::new(AtomicBool::new(false));
+        for signal in [SIGINT, SIGTERM] {
+            // Le premier signal lève le drapeau, le suivant quitte sans attendre
+            flag::register_conditional_shutdown(signal, 1, Arc::clone(&requested))?;
+            flag::register(signal, Arc::clone(&requested))?;
+        }
+        Ok(Shutdown { requested })
+    }
+
+    /// Sans la fonctionnalité `signals`, les signaux gardent leur effet par
+    /// défaut et aucun arrêt n'est jamais demandé.
+    #[cfg(not(feature = "signals"))]
+    pub fn install() -> io::Result<Self> {
+        Ok(Shutdown {})
+    }
+
+    /// Indique si l'arrêt a été demandé.
+    #[cfg(feature = "signals")]
+    pub fn requested(&self) -> bool {
+        self.requested.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Indique si l'arrêt a été demandé.
+    #[cfg(not(feature = "signals"))]
+    pub fn requested(&self) -> bool {
+        false
+    }
+}
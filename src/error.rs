@@ -1,9 +1,31 @@
 //! Module de gestion des erreurs pour le cache LRU.
 
 use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+use crate::messages;
 
 /// Énumération des erreurs possibles lors de l'utilisation du cache.
+///
+/// L'énumération est marquée `#[non_exhaustive]` : de nouvelles variantes
+/// pourront être ajoutées sans casser le code appelant, qui doit donc
+/// toujours prévoir un bras `_` lorsqu'il filtre les erreurs.
+///
+/// # Exemples
+///
+/// ```
+/// use lru_cache::error::CacheError;
+///
+/// fn est_recuperable(err: &CacheError) -> bool {
+///     match err {
+///         CacheError::LoadTimeout { .. } | CacheError::Poisoned => true,
+///         CacheError::Corrupted { line, .. } => *line > 0,
+///         _ => false,
+///     }
+/// }
+/// ```
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum CacheError {
     /// Erreur liée à la capacité du cache
     CapacityError(String),
@@ -11,14 +33,142 @@ pub enum CacheError {
     IoError(io::Error),
     /// Erreur de parsing lors du chargement du cache
     ParseError(String),
+    /// Le contenu du fichier de persistance est invalide
+    Corrupted {
+        /// Chemin du fichier, s'il est connu
+        path: Option<PathBuf>,
+        /// Numéro de la ligne fautive (à partir de 1)
+        line: usize,
+        /// Description du problème rencontré
+        reason: String,
+    },
+    /// La clé dépasse la taille maximale autorisée
+    KeyTooLarge {
+        /// Description de la clé concernée
+        key: String,
+        /// Taille de la clé
+        size: usize,
+        /// Taille maximale autorisée
+        max: usize,
+    },
+    /// La clé a été refusée par le validateur configuré
+    InvalidKey {
+        /// Raison du refus
+        reason: String,
+    },
+    /// Le poids de la valeur dépasse le poids maximal autorisé par entrée
+    ValueTooLarge {
+        /// Poids de la valeur
+        weight: usize,
+        /// Poids maximal autorisé
+        max: usize,
+    },
+    /// Erreur lors de la sérialisation ou de la désérialisation d'une valeur
+    Serialization(String),
+    /// Le verrou protégeant le cache a été empoisonné par un thread paniqué
+    Poisoned,
+    /// L'entrée a été modifiée depuis la lecture de sa version
+    VersionConflict {
+        /// Version attendue (`None` si l'entrée devait être absente)
+        expected: Option<u64>,
+        /// Version actuelle (`None` si l'entrée est absente)
+        actual: Option<u64>,
+    },
+    /// Les opérations demandées ne sont plus (ou pas) dans le journal de réplication
+    ReplicationGap {
+        /// Numéro de séquence demandé
+        requested: u64,
+        /// Plus ancien numéro de séquence disponible (`None` si le journal
+        /// n'est pas activé)
+        oldest: Option<u64>,
+    },
+    /// Le chargement d'une valeur n'a pas abouti dans le délai imparti
+    LoadTimeout {
+        /// Délai dépassé
+        timeout: Duration,
+    },
+    /// Le chargeur a paniqué, ou a échoué pendant un chargement partagé
+    /// avec d'autres appelants
+    LoadFailed {
+        /// Description de l'échec
+        reason: String,
+    },
+    /// Le chargement de la clé a échoué récemment et n'est pas relancé
+    /// avant la fin du délai de carence
+    RecentLoadFailure {
+        /// Message de l'erreur du dernier chargement
+        reason: String,
+        /// Temps restant avant qu'un nouveau chargement soit tenté
+        retry_in: Duration,
+    },
+    /// L'échéance fixée par l'appelant est passée avant la fin de
+    /// l'opération (attente d'un verrou ou d'un chargement)
+    Timeout {
+        /// Temps attendu avant d'abandonner
+        waited: Duration,
+    },
 }
 
 impl std::fmt::Display for CacheError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            CacheError::CapacityError(msg) => write!(f, "Erreur de capacité: {}", msg),
-            CacheError::IoError(err) => write!(f, "Erreur I/O: {}", err),
-            CacheError::ParseError(msg) => write!(f, "Erreur de parsing: {}", msg),
+            CacheError::CapacityError(msg) => write!(f, "{}: {}", messages::CAPACITY_ERROR, msg),
+            CacheError::IoError(err) => write!(f, "{}: {}", messages::IO_ERROR, err),
+            CacheError::ParseError(msg) => write!(f, "{}: {}", messages::PARSE_ERROR, msg),
+            CacheError::Corrupted { path: Some(path), line, reason } => write!(
+                f,
+                "{} {} ({} {}): {}",
+                messages::CORRUPTED_FILE,
+                path.display(),
+                messages::LINE,
+                line,
+                reason
+            ),
+            CacheError::Corrupted { path: None, line, reason } => {
+                write!(f, "{} ({} {}): {}", messages::CORRUPTED_DATA, messages::LINE, line, reason)
+            }
+            CacheError::KeyTooLarge { key, size, max } => {
+                write!(f, "{}: {} ({} > {})", messages::KEY_TOO_LARGE, key, size, max)
+            }
+            CacheError::InvalidKey { reason } => write!(f, "{}: {}", messages::INVALID_KEY, reason),
+            CacheError::ValueTooLarge { weight, max } => {
+                write!(f, "{} ({} > {})", messages::VALUE_TOO_LARGE, weight, max)
+            }
+            CacheError::Serialization(msg) => write!(f, "{}: {}", messages::SERIALIZATION, msg),
+            CacheError::Poisoned => write!(f, "{}", messages::POISONED),
+            CacheError::VersionConflict { expected, actual } => write!(
+                f,
+                "{} ({} != {})",
+                messages::VERSION_CONFLICT,
+                VersionDisplay(*expected),
+                VersionDisplay(*actual)
+            ),
+            CacheError::ReplicationGap { requested, oldest: Some(oldest) } => write!(
+                f,
+                "{} ({} < {})",
+                messages::REPLICATION_GAP,
+                requested,
+                oldest
+            ),
+            CacheError::ReplicationGap { .. } => write!(f, "{}", messages::REPLICATION_DISABLED),
+            CacheError::LoadTimeout { timeout } => write!(f, "{} ({:?})", messages::LOAD_TIMEOUT, timeout),
+            CacheError::LoadFailed { reason } => write!(f, "{}: {}", messages::LOAD_FAILED, reason),
+            CacheError::RecentLoadFailure { reason, retry_in } => {
+                write!(f, "{} {:?}: {}", messages::RECENT_LOAD_FAILURE, retry_in, reason)
+            }
+            CacheError::Timeout { waited } => write!(f, "{} ({:?})", messages::DEADLINE_EXCEEDED, waited),
+        }
+    }
+}
+
+/// Affiche une version éventuelle d'entrée.
+struct VersionDisplay(Option<u64>);
+
+impl std::fmt::Display for VersionDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.0 {
+            Some(version) => write!(f, "{}", version),
+            None => write!(f, "{}", messages::ABSENT),
         }
     }
 }
@@ -30,4 +180,10 @@ impl std::error::Error for CacheError {
             _ => None,
         }
     }
-} 
\ No newline at end of file
+}
+
+impl From<io::Error> for CacheError {
+    fn from(err: io::Error) -> Self {
+        CacheError::IoError(err)
+    }
+}
@@ -0,0 +1,236 @@
+//! Interface C de la bibliothèque.
+//!
+//! Disponible avec la fonctionnalité `ffi`. La bibliothèque est alors
+//! utilisable depuis C ou C++ en se liant à la bibliothèque dynamique
+//! (`cdylib`), avec l'en-tête `include/lru_cache.h`. Le paquet ne produit
+//! pas de bibliothèque dynamique à chaque compilation, ce qui ralentirait
+//! les utilisateurs Rust ; elle se construit explicitement :
+//!
+//! ```text
+//! cargo rustc --lib --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! Le cache exposé est un `SyncCache` : une même poignée peut être partagée
+//! entre threads.
+//!
+//! Clés et valeurs sont passées comme des tranches d'octets (pointeur et
+//! longueur). Pour rester compatibles avec les fichiers de persistance du
+//! cache Rust, elles doivent être en UTF-8 et ne contenir ni tabulation ni
+//! saut de ligne ; les autres sont refusées avec `LRU_CACHE_INVALID_ARGUMENT`.
+//!
+//! Les valeurs retournées par `lru_cache_get` sont allouées par la
+//! bibliothèque et doivent être libérées avec `lru_cache_free_value`.
+
+use std::ffi::{c_char, c_int, CStr};
+use std::path::Path;
+use std::ptr;
+use std::slice;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::sync::SyncCache;
+use crate::lru::traits::CacheTrait;
+
+/// Opération réussie.
+pub const LRU_CACHE_OK: c_int = 0;
+/// Clé absente.
+pub const LRU_CACHE_NOT_FOUND: c_int = 1;
+/// Argument invalide : pointeur nul, octets non UTF-8, tabulation ou saut
+/// de ligne.
+pub const LRU_CACHE_INVALID_ARGUMENT: c_int = -1;
+/// Erreur d'entrée/sortie ou fichier de persistance invalide.
+pub const LRU_CACHE_IO_ERROR: c_int = -2;
+/// Verrou du cache empoisonné.
+pub const LRU_CACHE_POISONED: c_int = -3;
+
+/// Cache opaque manipulé par l'interface C.
+pub struct LruCache {
+    inner: SyncCache<String, String>,
+}
+
+fn status(err: &CacheError) -> c_int {
+    match err {
+        CacheError::Poisoned => LRU_CACHE_POISONED,
+        CacheError::CapacityError(_) | CacheError::KeyTooLarge { .. } | CacheError::InvalidKey { .. } => {
+            LRU_CACHE_INVALID_ARGUMENT
+        }
+        _ => LRU_CACHE_IO_ERROR,
+    }
+}
+
+/// Lit une tranche d'octets C en texte persistable.
+unsafe fn text(data: *const u8, len: usize) -> Option<String> {
+    if data.is_null() && len > 0 {
+        return None;
+    }
+    let bytes = if len == 0 { &[][..] } else { slice::from_raw_parts(data, len) };
+    let text = std::str::from_utf8(bytes).ok()?;
+    if text.contains(['\t', '\n', '\r']) {
+        return None;
+    }
+    Some(text.to_string())
+}
+
+unsafe fn path<'a>(path: *const c_char) -> Option<&'a Path> {
+    if path.is_null() {
+        return None;
+    }
+    CStr::from_ptr(path).to_str().ok().map(Path::new)
+}
+
+/// Crée un cache vide de la capacité donnée, ou retourne `NULL` si la
+/// capacité est 0.
+#[no_mangle]
+pub extern "C" fn lru_cache_new(capacity: usize) -> *mut LruCache {
+    match SyncCache::try_new(capacity) {
+        Ok(inner) => Box::into_raw(Box::new(LruCache { inner })),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Crée un cache chargé depuis un fichier de persistance, s'il existe, ou
+/// retourne `NULL` en cas d'erreur.
+///
+/// # Safety
+///
+/// `path` doit être nul ou désigner une chaîne C valide terminée par 0.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_open(path: *const c_char, capacity: usize) -> *mut LruCache {
+    let Some(path) = self::path(path) else {
+        return ptr::null_mut();
+    };
+    match Cache::new_persistent(capacity, path) {
+        Ok(cache) => Box::into_raw(Box::new(LruCache { inner: SyncCache::from_cache(cache) })),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Libère un cache. Un pointeur nul est ignoré.
+///
+/// # Safety
+///
+/// `cache` doit être nul ou provenir de `lru_cache_new` ou
+/// `lru_cache_open`, et ne plus être utilisé ensuite.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_free(cache: *mut LruCache) {
+    if !cache.is_null() {
+        drop(Box::from_raw(cache));
+    }
+}
+
+/// Ajoute ou met à jour une entrée.
+///
+/// # Safety
+///
+/// `cache` doit être une poignée valide ; `key` et `value` doivent désigner
+/// respectivement `key_len` et `value_len` octets lisibles.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_put(
+    cache: *mut LruCache,
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> c_int {
+    let (Some(cache), Some(key), Some(value)) = (cache.as_ref(), text(key, key_len), text(value, value_len)) else {
+        return LRU_CACHE_INVALID_ARGUMENT;
+    };
+    match cache.inner.put(key, value) {
+        Ok(()) => LRU_CACHE_OK,
+        Err(err) => status(&err),
+    }
+}
+
+/// Recherche une entrée. Si elle existe, `*value` et `*value_len` reçoivent
+/// une copie de la valeur, à libérer avec `lru_cache_free_value`, et
+/// `LRU_CACHE_OK` est retourné ; sinon `LRU_CACHE_NOT_FOUND`.
+///
+/// # Safety
+///
+/// `cache` doit être une poignée valide, `key` doit désigner `key_len`
+/// octets lisibles, `value` et `value_len` doivent être des pointeurs
+/// valides en écriture.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_get(
+    cache: *mut LruCache,
+    key: *const u8,
+    key_len: usize,
+    value: *mut *mut u8,
+    value_len: *mut usize,
+) -> c_int {
+    let (Some(cache), Some(key)) = (cache.as_ref(), text(key, key_len)) else {
+        return LRU_CACHE_INVALID_ARGUMENT;
+    };
+    if value.is_null() || value_len.is_null() {
+        return LRU_CACHE_INVALID_ARGUMENT;
+    }
+    match cache.inner.get(&key) {
+        Ok(Some(found)) => {
+            let bytes = found.into_bytes().into_boxed_slice();
+            *value_len = bytes.len();
+            *value = Box::into_raw(bytes) as *mut u8;
+            LRU_CACHE_OK
+        }
+        Ok(None) => LRU_CACHE_NOT_FOUND,
+        Err(err) => status(&err),
+    }
+}
+
+/// Libère une valeur retournée par `lru_cache_get`. Un pointeur nul est
+/// ignoré.
+///
+/// # Safety
+///
+/// `value` et `value_len` doivent être exactement ceux retournés par
+/// `lru_cache_get`, et la valeur ne doit plus être utilisée ensuite.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_free_value(value: *mut u8, value_len: usize) {
+    if !value.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(value, value_len)));
+    }
+}
+
+/// Supprime une entrée ; retourne `LRU_CACHE_NOT_FOUND` si elle n'existait
+/// pas.
+///
+/// # Safety
+///
+/// `cache` doit être une poignée valide et `key` désigner `key_len` octets
+/// lisibles.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_remove(cache: *mut LruCache, key: *const u8, key_len: usize) -> c_int {
+    let (Some(cache), Some(key)) = (cache.as_ref(), text(key, key_len)) else {
+        return LRU_CACHE_INVALID_ARGUMENT;
+    };
+    match cache.inner.with_lock(|cache| cache.remove(&key)) {
+        Ok(Some(_)) => LRU_CACHE_OK,
+        Ok(None) => LRU_CACHE_NOT_FOUND,
+        Err(err) => status(&err),
+    }
+}
+
+/// Retourne le nombre d'entrées, ou 0 si la poignée est nulle.
+///
+/// # Safety
+///
+/// `cache` doit être nul ou une poignée valide.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_len(cache: *const LruCache) -> usize {
+    cache.as_ref().and_then(|cache| cache.inner.len().ok()).unwrap_or(0)
+}
+
+/// Sauvegarde le cache dans un fichier, au format de `Cache::persist`.
+///
+/// # Safety
+///
+/// `cache` doit être une poignée valide et `path` une chaîne C valide
+/// terminée par 0.
+#[no_mangle]
+pub unsafe extern "C" fn lru_cache_persist(cache: *mut LruCache, path: *const c_char) -> c_int {
+    let (Some(cache), Some(path)) = (cache.as_ref(), self::path(path)) else {
+        return LRU_CACHE_INVALID_ARGUMENT;
+    };
+    match cache.inner.with_lock(|cache| cache.persist(path)) {
+        Ok(Ok(())) => LRU_CACHE_OK,
+        Ok(Err(err)) | Err(err) => status(&err),
+    }
+}
@@ -10,6 +10,7 @@
 //! - Cache générique supportant différents types de clés et valeurs
 //! - Politique d'éviction LRU
 //! - Persistance optionnelle sur disque
+//! - Version thread-safe avec politique d'empoisonnement configurable
 //! - Interface trait pour l'extensibilité
 //! 
 //! # Exemple d'utilisation
@@ -33,5 +34,11 @@
 //! assert_eq!(cache.get(&"clé2"), None); // clé2 a été évincée
 //! ```
 
+pub mod bench;
 pub mod error;
-pub mod lru;
\ No newline at end of file
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod lru;
+pub mod messages;
+#[cfg(feature = "python")]
+pub mod python;
\ No newline at end of file
@@ -0,0 +1,218 @@
+//! Module implémentant un cache adaptatif entre récence et fréquence (ARC).
+//!
+//! `AdaptiveCache` suit l'algorithme ARC (Megiddo et Modha, 2003). Les
+//! entrées vues une seule fois vivent dans une liste de récence, celles
+//! redemandées dans une liste de fréquence. Chaque liste est doublée d'une
+//! liste fantôme qui ne retient que les clés de ses dernières victimes :
+//! une clé qui revient alors qu'elle figure dans un fantôme montre que la
+//! liste correspondante aurait mérité plus de place. La cible de récence se
+//! déplace en conséquence, si bien que le cache favorise tantôt la récence,
+//! tantôt la fréquence, selon la charge observée.
+//!
+//! `AdaptiveStats::recency_bias` expose la part du cache actuellement
+//! accordée à la récence.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::adaptive::AdaptiveCache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = AdaptiveCache::new(2);
+//! cache.put("a", 1);
+//! cache.get(&"a"); // "a" passe dans la liste de fréquence
+//! cache.put("b", 2);
+//! cache.put("c", 3); // évince "b", vue une seule fois
+//! cache.put("b", 2); // touche le fantôme de récence
+//!
+//! assert_eq!(cache.get(&"b"), Some(&2));
+//! assert_eq!(cache.stats().recency_ghost_hits, 1);
+//! assert!(cache.stats().recency_bias > 0.0);
+//! ```
+
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Statistiques d'un `AdaptiveCache`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AdaptiveStats {
+    /// Lectures réussies
+    pub hits: u64,
+    /// Lectures infructueuses
+    pub misses: u64,
+    /// Retours de clés récemment évincées de la liste de récence
+    pub recency_ghost_hits: u64,
+    /// Retours de clés récemment évincées de la liste de fréquence
+    pub frequency_ghost_hits: u64,
+    /// Nombre d'entrées visé pour la liste de récence
+    pub recency_target: usize,
+    /// Part du cache accordée à la récence, entre 0 (fréquence seule) et 1
+    pub recency_bias: f64,
+}
+
+/// Cache ajustant en continu sa préférence entre récence et fréquence.
+#[derive(Debug)]
+pub struct AdaptiveCache<K, V>
+where
+    K: Hash + Eq,
+{
+    capacity: usize,
+    recent: Cache<K, V>,
+    frequent: Cache<K, V>,
+    recent_ghosts: Cache<K, ()>,
+    frequent_ghosts: Cache<K, ()>,
+    recency_target: usize,
+    stats: AdaptiveStats,
+}
+
+impl<K, V> AdaptiveCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Crée un cache de la capacité donnée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la capacité est 0.
+    pub fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Crée un cache de la capacité donnée, sans paniquer.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la capacité est 0.
+    pub fn try_new(capacity: usize) -> Result<Self, CacheError> {
+        Ok(AdaptiveCache {
+            capacity,
+            recent: Cache::try_new(capacity)?,
+            frequent: Cache::try_new(capacity)?,
+            recent_ghosts: Cache::try_new(capacity)?,
+            frequent_ghosts: Cache::try_new(capacity)?,
+            recency_target: 0,
+            stats: AdaptiveStats::default(),
+        })
+    }
+
+    /// Retourne les statistiques du cache, dont sa préférence actuelle.
+    pub fn stats(&self) -> AdaptiveStats {
+        AdaptiveStats {
+            recency_target: self.recency_target,
+            recency_bias: self.recency_target as f64 / self.capacity as f64,
+            ..self.stats
+        }
+    }
+
+    /// Libère une place en évinçant la plus ancienne entrée de la liste de
+    /// récence ou de celle de fréquence, selon la cible, vers son fantôme.
+    fn replace(&mut self, frequent_ghost_hit: bool) {
+        if self.len() < self.capacity {
+            return;
+        }
+        let recent = self.recent.len();
+        let from_recent = recent > 0
+            && (recent > self.recency_target || (frequent_ghost_hit && recent == self.recency_target));
+        if from_recent {
+            if let Some((key, _)) = self.recent.evict(1).pop() {
+                self.recent_ghosts.put(key, ());
+            }
+        } else if let Some((key, _)) = self.frequent.evict(1).pop() {
+            self.frequent_ghosts.put(key, ());
+        }
+    }
+
+    /// Insère une clé absente des listes résidentes et fantômes.
+    fn insert_new(&mut self, key: K, value: V) {
+        let recent_side = self.recent.len() + self.recent_ghosts.len();
+        let total = recent_side + self.frequent.len() + self.frequent_ghosts.len();
+        if recent_side == self.capacity {
+            if self.recent.len() < self.capacity {
+                self.recent_ghosts.evict(1);
+                self.replace(false);
+            } else {
+                self.recent.evict(1);
+            }
+        } else if total >= self.capacity {
+            if total == 2 * self.capacity {
+                self.frequent_ghosts.evict(1);
+            }
+            self.replace(false);
+        }
+        self.recent.put(key, value);
+    }
+}
+
+impl<K, V> CacheRead<K, V> for AdaptiveCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.recent.peek(key).or_else(|| self.frequent.peek(key))
+    }
+
+    fn len(&self) -> usize {
+        self.recent.len() + self.frequent.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<K, V> CacheTrait<K, V> for AdaptiveCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        // Une deuxième lecture fait passer l'entrée dans la liste de fréquence
+        if let Some(value) = self.recent.remove(key) {
+            self.frequent.put(key.clone(), value);
+        }
+        if self.frequent.contains(key) {
+            self.stats.hits += 1;
+            return self.frequent.get(key);
+        }
+        self.stats.misses += 1;
+        None
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if self.frequent.contains(&key) || self.recent.remove(&key).is_some() {
+            self.frequent.put(key, value);
+            return;
+        }
+
+        if self.recent_ghosts.contains(&key) {
+            let delta = (self.frequent_ghosts.len() / self.recent_ghosts.len()).max(1);
+            self.recency_target = (self.recency_target + delta).min(self.capacity);
+            self.stats.recency_ghost_hits += 1;
+            self.recent_ghosts.remove(&key);
+            self.replace(false);
+            self.frequent.put(key, value);
+        } else if self.frequent_ghosts.contains(&key) {
+            let delta = (self.recent_ghosts.len() / self.frequent_ghosts.len()).max(1);
+            self.recency_target = self.recency_target.saturating_sub(delta);
+            self.stats.frequency_ghost_hits += 1;
+            self.frequent_ghosts.remove(&key);
+            self.replace(true);
+            self.frequent.put(key, value);
+        } else {
+            self.insert_new(key, value);
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.recent.remove(key).or_else(|| self.frequent.remove(key))
+    }
+
+    fn clear(&mut self) {
+        self.recent.clear();
+        self.frequent.clear();
+        self.recent_ghosts.clear();
+        self.frequent_ghosts.clear();
+        self.recency_target = 0;
+    }
+}
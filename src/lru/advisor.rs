@@ -0,0 +1,203 @@
+//! Module implémentant le conseiller de dimensionnement du cache.
+//!
+//! Activé par `CacheBuilder::capacity_advisor`, le conseiller tient une liste
+//! fantôme des clés évincées, sans leurs valeurs, avec le rang de leur
+//! éviction. Une lecture manquée sur une clé fantôme évincée il y a `d`
+//! évictions aurait réussi dans un cache plus grand de `d` emplacements :
+//! en cumulant ces distances, `Cache::capacity_advice` estime le taux de
+//! succès qu'obtiendrait le cache avec 1,5 ou 2 fois sa capacité, de quoi
+//! dimensionner un cache à partir de sa charge réelle.
+//!
+//! L'estimation suppose une éviction LRU : elle est indicative en éviction
+//! échantillonnée. La liste fantôme retient au plus autant de clés que la
+//! capacité du cache à sa construction.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::builder().capacity(4).capacity_advisor().build().unwrap();
+//! // Un parcours cyclique de 6 clés ne réussit jamais avec 4 emplacements
+//! for _ in 0..10 {
+//!     for k in 0..6 {
+//!         if cache.get(&k).is_none() {
+//!             cache.put(k, k);
+//!         }
+//!     }
+//! }
+//!
+//! let advice = cache.capacity_advice().unwrap();
+//! assert_eq!(advice.hit_ratio, 0.0);
+//! assert_eq!(advice.estimates[0].capacity, 6);
+//! assert!(advice.estimates[0].hit_ratio > 0.8);
+//! println!("{}", advice);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use crate::lru::Cache;
+use crate::messages;
+
+/// Multiples de la capacité pour lesquels le taux de succès est estimé.
+pub const ADVICE_FACTORS: [f64; 2] = [1.5, 2.0];
+
+/// Taux de succès estimé pour une capacité donnée.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CapacityEstimate {
+    /// Multiple de la capacité actuelle
+    pub factor: f64,
+    /// Capacité correspondante
+    pub capacity: usize,
+    /// Taux de succès estimé, entre 0 et 1
+    pub hit_ratio: f64,
+}
+
+/// Conseil de dimensionnement retourné par `Cache::capacity_advice`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapacityAdvice {
+    /// Capacité actuelle du cache
+    pub capacity: usize,
+    /// Lectures réussies depuis l'activation du conseiller
+    pub hits: u64,
+    /// Lectures manquées depuis l'activation du conseiller
+    pub misses: u64,
+    /// Taux de succès observé, entre 0 et 1
+    pub hit_ratio: f64,
+    /// Taux de succès estimés pour chaque multiple de `ADVICE_FACTORS`
+    pub estimates: Vec<CapacityEstimate>,
+}
+
+impl fmt::Display for CapacityAdvice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} (x1): {:.1} %",
+            messages::HIT_RATIO_AT_CAPACITY,
+            self.capacity,
+            self.hit_ratio * 100.0
+        )?;
+        for estimate in &self.estimates {
+            write!(
+                f,
+                "\n{} {} (x{}): {:.1} %",
+                messages::HIT_RATIO_AT_CAPACITY,
+                estimate.capacity,
+                estimate.factor,
+                estimate.hit_ratio * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Liste fantôme et compteurs du conseiller.
+#[derive(Debug)]
+pub(crate) struct CapacityAdvisor<K> {
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    /// Rang d'éviction de chaque clé fantôme
+    ghosts: HashMap<K, u64>,
+    /// Lectures manquées sur une clé fantôme, par distance d'éviction - 1
+    ghost_hits: Vec<u64>,
+}
+
+impl<K> CapacityAdvisor<K>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Crée un conseiller suivant les clés évincées jusqu'à `depth`
+    /// évictions en arrière.
+    pub(crate) fn new(depth: usize) -> Self {
+        CapacityAdvisor {
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+            ghosts: HashMap::new(),
+            ghost_hits: vec![0; depth],
+        }
+    }
+
+    /// Compte une lecture.
+    pub(crate) fn record_read(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+
+    /// Compte une lecture manquée sur une clé fantôme.
+    pub(crate) fn record_miss(&mut self, key: &K) {
+        if let Some(rank) = self.ghosts.remove(key) {
+            let distance = (self.evictions - rank) as usize;
+            if let Some(count) = self.ghost_hits.get_mut(distance) {
+                *count += 1;
+            }
+        }
+    }
+
+    /// Ajoute une clé évincée à la liste fantôme.
+    pub(crate) fn record_eviction(&mut self, key: &K) {
+        self.evictions += 1;
+        self.ghosts.insert(key.clone(), self.evictions);
+        // Les clés trop anciennes sont oubliées par lots
+        let depth = self.ghost_hits.len() as u64;
+        if self.ghosts.len() > 2 * self.ghost_hits.len() {
+            let evictions = self.evictions;
+            self.ghosts.retain(|_, rank| evictions - *rank < depth);
+        }
+    }
+
+    /// Retire une clé de nouveau présente dans le cache.
+    pub(crate) fn forget(&mut self, key: &K) {
+        if !self.ghosts.is_empty() {
+            self.ghosts.remove(key);
+        }
+    }
+
+    fn hit_ratio_with(&self, extra: usize) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        let ghost_hits: u64 = self.ghost_hits.iter().take(extra).sum();
+        (self.hits + ghost_hits) as f64 / total as f64
+    }
+
+    /// Établit le conseil pour un cache de la capacité indiquée.
+    pub(crate) fn advice(&self, capacity: usize) -> CapacityAdvice {
+        let estimates = ADVICE_FACTORS
+            .iter()
+            .map(|&factor| {
+                let target = (capacity as f64 * factor).ceil() as usize;
+                CapacityEstimate {
+                    factor,
+                    capacity: target,
+                    hit_ratio: self.hit_ratio_with(target - capacity),
+                }
+            })
+            .collect();
+        CapacityAdvice {
+            capacity,
+            hits: self.hits,
+            misses: self.misses,
+            hit_ratio: self.hit_ratio_with(0),
+            estimates,
+        }
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne le conseil de dimensionnement, si le conseiller a été activé
+    /// sur le constructeur.
+    pub fn capacity_advice(&self) -> Option<CapacityAdvice> {
+        self.observers.advisor.as_ref().map(|advisor| advisor.advice(self.capacity))
+    }
+}
@@ -0,0 +1,80 @@
+//! Module exposant des parcours filtrés par âge et par inactivité.
+//!
+//! Chaque entrée retient son instant d'insertion. Avec
+//! `CacheBuilder::track_access_times` (ou les statistiques), elle retient
+//! aussi son dernier accès, daté à chaque lecture réussie et à chaque mise à
+//! jour. Une tâche de maintenance peut ainsi ne parcourir que les entrées
+//! anciennes ou délaissées, sans filtrer elle-même une itération complète.
+//!
+//! Les deux parcours suivent l'ordre d'utilisation, du moins récemment
+//! utilisé au plus récemment utilisé, et ignorent les entrées expirées.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::clock::{Clock, ManualClock};
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let clock = ManualClock::new();
+//! let mut cache = Cache::builder()
+//!     .capacity(10)
+//!     .clock(Arc::new(clock.clone()))
+//!     .track_access_times()
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.put("ancienne", 1);
+//! clock.advance(Duration::from_secs(60));
+//! cache.put("recente", 2);
+//! let depuis = clock.now();
+//! clock.advance(Duration::from_secs(5));
+//! cache.get(&"recente");
+//!
+//! let anciennes: Vec<_> = cache.iter_older_than(Duration::from_secs(30)).collect();
+//! assert_eq!(anciennes, vec![(&"ancienne", &1)]);
+//! let inactives: Vec<_> = cache.iter_idle_since(depuis).collect();
+//! assert_eq!(inactives, vec![(&"ancienne", &1)]);
+//! ```
+
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+use crate::lru::{Cache, Entry};
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne un itérateur sur les entrées insérées depuis au moins la
+    /// durée donnée.
+    pub fn iter_older_than(&self, age: Duration) -> impl Iterator<Item = (&K, &V)> {
+        let now = self.clock.now();
+        self.iter_where(move |entry| now.saturating_duration_since(entry.inserted) >= age)
+    }
+
+    /// Retourne un itérateur sur les entrées qui n'ont pas été consultées
+    /// depuis l'instant donné.
+    ///
+    /// Sans `CacheBuilder::track_access_times` ni statistiques, le dernier
+    /// accès d'une entrée est son insertion.
+    pub fn iter_idle_since(&self, since: Instant) -> impl Iterator<Item = (&K, &V)> {
+        self.iter_where(move |entry| entry.accessed <= since)
+    }
+
+    /// Parcourt les entrées valides satisfaisant le prédicat, dans l'ordre
+    /// d'utilisation.
+    fn iter_where<F>(&self, filter: F) -> impl Iterator<Item = (&K, &V)>
+    where
+        F: Fn(&Entry<V>) -> bool,
+    {
+        self.recency_order().filter_map(move |key| {
+            if self.is_expired(key) {
+                return None;
+            }
+            let entry = self.elements.get(key)?;
+            filter(entry).then_some((key, &entry.value))
+        })
+    }
+}
@@ -0,0 +1,254 @@
+//! Module implémentant une sauvegarde archivée lisible sans désérialisation.
+//!
+//! Disponible avec la fonctionnalité `rkyv`. `Cache::persist_archived` écrit
+//! les entrées au format rkyv, précédées de l'en-tête binaire décrit par le
+//! module `format`. `ArchivedCacheFile::open` projette ensuite le fichier en
+//! mémoire et le valide une seule fois : les valeurs sont lues directement
+//! dans les octets du fichier, sans allocation ni désérialisation. Un cache
+//! de grosses structures, surtout lu, démarre ainsi en un temps qui ne dépend
+//! plus du coût de désérialisation de ses valeurs.
+//!
+//! Le fichier est remplacé par renommage lors d'une nouvelle sauvegarde : une
+//! projection déjà ouverte continue de voir l'ancien contenu.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::archived::ArchivedCacheFile;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let path = std::env::temp_dir().join(format!("lru_archived_doc_{}.rkyv", std::process::id()));
+//! let mut cache: Cache<String, Vec<u32>> = Cache::new(100);
+//! cache.put("premiers".to_string(), vec![2, 3, 5, 7]);
+//! cache.persist_archived(&path).unwrap();
+//!
+//! let archive = ArchivedCacheFile::<String, Vec<u32>>::open(&path).unwrap();
+//! let premiers = archive.get("premiers").unwrap();
+//! assert_eq!(premiers.len(), 4);
+//! assert_eq!(premiers[3], 7);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::hash::Hash;
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use memmap2::Mmap;
+use rkyv::api::high::{HighDeserializer, HighSerializer, HighValidator};
+use rkyv::bytecheck::CheckBytes;
+use rkyv::rancor;
+use rkyv::ser::allocator::ArenaHandle;
+use rkyv::util::AlignedVec;
+use rkyv::with::{Identity, Inline, Map, MapKV};
+use rkyv::{Archive, Deserialize, Serialize};
+use crate::error::CacheError;
+use crate::messages;
+use crate::lru::Cache;
+use crate::lru::format::{FileDescription, ARCHIVE_HEADER_LEN};
+use crate::lru::traits::CacheTrait;
+
+/// Contenu archivé : clés et valeurs de la moins à la plus récemment
+/// utilisée, et position de chaque clé.
+#[derive(Archive, Serialize)]
+struct Snapshot<'a, K: Hash + Eq, V> {
+    #[rkyv(with = Map<Inline>)]
+    keys: Vec<&'a K>,
+    #[rkyv(with = Map<Inline>)]
+    values: Vec<&'a V>,
+    #[rkyv(with = MapKV<Inline, Identity>)]
+    index: HashMap<&'a K, u64>,
+}
+
+fn serialization_error(err: rancor::Error) -> CacheError {
+    CacheError::Serialization(err.to_string())
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone + for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, rancor::Error>>,
+    K::Archived: Hash + Eq,
+    V: for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, rancor::Error>>,
+{
+    /// Sauvegarde le cache au format archivé, lisible par
+    /// `ArchivedCacheFile` sans désérialisation.
+    ///
+    /// Le fichier est écrit à côté puis renommé : il n'est jamais visible à
+    /// moitié écrit.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Serialization` si une entrée ne peut pas être
+    /// archivée, et `CacheError::IoError` si le fichier ne peut pas être
+    /// écrit.
+    pub fn persist_archived<P: AsRef<Path>>(&self, path: P) -> Result<(), CacheError> {
+        let path = path.as_ref();
+        let entries: Vec<_> = self.iter().collect();
+        let snapshot = Snapshot {
+            keys: entries.iter().map(|(key, _)| *key).collect(),
+            values: entries.iter().map(|(_, value)| *value).collect(),
+            index: entries.iter().enumerate().map(|(position, (key, _))| (*key, position as u64)).collect(),
+        };
+        let bytes = rkyv::to_bytes::<rancor::Error>(&snapshot).map_err(serialization_error)?;
+
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".tmp");
+        let tmp = path.with_file_name(name);
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        writer.write_all(&FileDescription::archived(entries.len(), self.capacity()).archive_header())?;
+        writer.write_all(&bytes)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&tmp, path)?;
+
+        self.mark_synced();
+        Ok(())
+    }
+}
+
+/// Fichier archivé projeté en mémoire, dont les entrées se lisent sur place.
+///
+/// Les valeurs retournées sont les formes archivées de `V`
+/// (`V::Archived`, par exemple `ArchivedString` pour `String`).
+#[derive(Debug)]
+pub struct ArchivedCacheFile<K, V> {
+    path: PathBuf,
+    map: Mmap,
+    description: FileDescription,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> ArchivedCacheFile<K, V>
+where
+    K: Hash + Eq + Archive + 'static,
+    K::Archived: Hash + Eq + for<'a> CheckBytes<HighValidator<'a, rancor::Error>>,
+    V: Archive + 'static,
+    V::Archived: for<'a> CheckBytes<HighValidator<'a, rancor::Error>>,
+{
+    /// Ouvre et valide un fichier écrit par `Cache::persist_archived`.
+    ///
+    /// La validation parcourt l'archive une fois, sans rien désérialiser ;
+    /// les lectures suivantes ne font plus aucune vérification.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si le fichier ne peut pas être lu ou
+    /// projeté, `CacheError::Corrupted` si son en-tête n'est pas celui d'un
+    /// fichier archivé pris en charge, et `CacheError::Serialization` si
+    /// l'archive est invalide.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, CacheError> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path)?;
+        // SAFETY: le fichier n'est jamais réécrit en place par
+        // `persist_archived`, qui le remplace par renommage
+        let map = unsafe { Mmap::map(&file)? };
+
+        let corrupted = |reason: String| CacheError::Corrupted {
+            path: Some(path.clone()),
+            line: 1,
+            reason,
+        };
+        let description = FileDescription::parse_archive_header(&map)
+            .unwrap_or_else(|| Err(messages::INVALID_FILE_HEADER.to_string()))
+            .map_err(corrupted)?;
+        rkyv::access::<ArchivedSnapshot<'static, K, V>, rancor::Error>(&map[ARCHIVE_HEADER_LEN..])
+            .map_err(serialization_error)?;
+
+        Ok(ArchivedCacheFile {
+            path,
+            map,
+            description,
+            _marker: PhantomData,
+        })
+    }
+
+    fn snapshot(&self) -> &ArchivedSnapshot<'static, K, V> {
+        // SAFETY: l'archive a été validée par `open` et la projection est en
+        // lecture seule
+        unsafe { rkyv::access_unchecked(&self.map[ARCHIVE_HEADER_LEN..]) }
+    }
+
+    /// Retourne la forme archivée de la valeur associée à la clé.
+    ///
+    /// La clé peut être de tout type comparable à la clé archivée et haché
+    /// comme elle, par exemple `str` pour des clés `String`.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V::Archived>
+    where
+        Q: Hash + Eq + ?Sized,
+        K::Archived: PartialEq<Q>,
+    {
+        let snapshot = self.snapshot();
+        let position = snapshot.index.get_with(key, |key, archived| archived == key)?;
+        snapshot.values.get(position.to_native() as usize)
+    }
+
+    /// Vérifie si la clé est présente dans le fichier.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Q: Hash + Eq + ?Sized,
+        K::Archived: PartialEq<Q>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Parcourt les entrées archivées, de la moins à la plus récemment
+    /// utilisée au moment de la sauvegarde.
+    pub fn iter(&self) -> impl Iterator<Item = (&K::Archived, &V::Archived)> {
+        let snapshot = self.snapshot();
+        snapshot.keys.iter().zip(snapshot.values.iter())
+    }
+
+    /// Retourne le nombre d'entrées du fichier.
+    pub fn len(&self) -> usize {
+        self.snapshot().keys.len()
+    }
+
+    /// Vérifie si le fichier ne contient aucune entrée.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retourne la capacité du cache sauvegardé.
+    pub fn capacity(&self) -> usize {
+        self.description.capacity
+    }
+
+    /// Retourne la description lue dans l'en-tête du fichier.
+    pub fn description(&self) -> &FileDescription {
+        &self.description
+    }
+
+    /// Retourne le chemin du fichier projeté.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<K, V> ArchivedCacheFile<K, V>
+where
+    K: Hash + Eq + Clone + Archive + 'static,
+    K::Archived: Hash + Eq + for<'a> CheckBytes<HighValidator<'a, rancor::Error>> + Deserialize<K, HighDeserializer<rancor::Error>>,
+    V: Archive + 'static,
+    V::Archived: for<'a> CheckBytes<HighValidator<'a, rancor::Error>> + Deserialize<V, HighDeserializer<rancor::Error>>,
+{
+    /// Désérialise toutes les entrées dans un cache ordinaire de la
+    /// capacité sauvegardée, en conservant leur ordre d'utilisation.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Serialization` si une entrée ne peut pas être
+    /// désérialisée, et `CacheError::CapacityError` si la capacité
+    /// sauvegardée vaut 0.
+    pub fn to_cache(&self) -> Result<Cache<K, V>, CacheError> {
+        let mut cache = Cache::try_new(self.capacity())?;
+        for (key, value) in self.iter() {
+            let key = rkyv::deserialize::<K, rancor::Error>(key).map_err(serialization_error)?;
+            let value = rkyv::deserialize::<V, rancor::Error>(value).map_err(serialization_error)?;
+            cache.put(key, value);
+        }
+        cache.mark_synced();
+        Ok(cache)
+    }
+}
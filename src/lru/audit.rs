@@ -0,0 +1,213 @@
+//! Module implémentant le journal d'audit des mutations du cache.
+//!
+//! Une fois activé via le constructeur, le journal enregistre chaque
+//! insertion, mise à jour et suppression (avec sa cause) dans un tampon
+//! circulaire consultable par `Cache::audit_log`. Un fichier peut en outre
+//! recevoir chaque enregistrement, à raison d'une ligne par mutation :
+//!
+//! ```text
+//! <millisecondes depuis l'époque Unix>\t<opération>\t<clé>\t<cause>
+//! ```
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::audit::AuditOp;
+//! use lru_cache::lru::events::RemovalCause;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::builder()
+//!     .capacity(1)
+//!     .audit_log(100)
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.put("carte", 1);
+//! cache.put("iban", 2); // évince "carte"
+//!
+//! let journal: Vec<_> = cache.audit_log().collect();
+//! assert_eq!(journal.len(), 3);
+//! assert_eq!(journal[1].op, AuditOp::Remove);
+//! assert_eq!(journal[1].key, "\"carte\"");
+//! assert_eq!(journal[1].cause, Some(RemovalCause::Capacity));
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::hash::Hash;
+use std::io::{self, LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::lru::Cache;
+use crate::lru::events::{Mutation, RemovalCause};
+use crate::messages;
+
+/// Nombre d'enregistrements conservés lorsque seul un fichier est configuré.
+pub(crate) const DEFAULT_AUDIT_CAPACITY: usize = 1024;
+
+type FormatFn<K> = dyn Fn(&K) -> String + Send + Sync;
+
+/// Opération enregistrée dans le journal d'audit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuditOp {
+    /// Ajout d'une nouvelle entrée
+    Insert,
+    /// Remplacement ou fusion de la valeur d'une entrée
+    Update,
+    /// Suppression d'une entrée
+    Remove,
+}
+
+impl AuditOp {
+    /// Retourne un identifiant stable de l'opération, indépendant de la langue.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOp::Insert => "insert",
+            AuditOp::Update => "update",
+            AuditOp::Remove => "remove",
+        }
+    }
+}
+
+/// Enregistrement du journal d'audit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// Instant de la mutation
+    pub timestamp: SystemTime,
+    /// Opération effectuée
+    pub op: AuditOp,
+    /// Représentation de la clé concernée
+    pub key: String,
+    /// Cause de la suppression, pour les opérations `Remove`
+    pub cause: Option<RemovalCause>,
+}
+
+/// Configuration du journal d'audit, conservée par le constructeur.
+pub(crate) struct AuditConfig<K> {
+    pub(crate) capacity: usize,
+    pub(crate) path: Option<PathBuf>,
+    format_key: Arc<FormatFn<K>>,
+}
+
+impl<K> AuditConfig<K> {
+    pub(crate) fn new(capacity: usize) -> Self
+    where
+        K: fmt::Debug,
+    {
+        AuditConfig {
+            capacity,
+            path: None,
+            format_key: Arc::new(|key: &K| format!("{:?}", key)),
+        }
+    }
+
+    /// Crée le journal, en ouvrant le fichier en ajout s'il est configuré.
+    pub(crate) fn open(self) -> io::Result<AuditLog<K>> {
+        let sink = match &self.path {
+            Some(path) => Some(open_sink(path)?),
+            None => None,
+        };
+        Ok(AuditLog {
+            capacity: self.capacity,
+            records: VecDeque::with_capacity(self.capacity.min(DEFAULT_AUDIT_CAPACITY)),
+            format_key: self.format_key,
+            sink,
+        })
+    }
+}
+
+impl<K> Clone for AuditConfig<K> {
+    fn clone(&self) -> Self {
+        AuditConfig {
+            capacity: self.capacity,
+            path: self.path.clone(),
+            format_key: Arc::clone(&self.format_key),
+        }
+    }
+}
+
+impl<K> fmt::Debug for AuditConfig<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AuditConfig")
+            .field("capacity", &self.capacity)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+fn open_sink(path: &Path) -> io::Result<LineWriter<std::fs::File>> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    Ok(LineWriter::new(file))
+}
+
+/// Journal d'audit : tampon circulaire et fichier optionnel.
+pub(crate) struct AuditLog<K> {
+    capacity: usize,
+    records: VecDeque<AuditRecord>,
+    format_key: Arc<FormatFn<K>>,
+    sink: Option<LineWriter<std::fs::File>>,
+}
+
+impl<K> AuditLog<K> {
+    /// Enregistre une mutation.
+    pub(crate) fn record<V>(&mut self, mutation: &Mutation<'_, K, V>) {
+        let (op, cause) = match mutation {
+            Mutation::Insert { .. } => (AuditOp::Insert, None),
+            Mutation::Update { .. } => (AuditOp::Update, None),
+            Mutation::Remove { cause, .. } => (AuditOp::Remove, Some(*cause)),
+        };
+        let record = AuditRecord {
+            timestamp: SystemTime::now(),
+            op,
+            key: (self.format_key)(mutation.key()),
+            cause,
+        };
+
+        if let Some(sink) = self.sink.as_mut() {
+            let millis = record
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis());
+            let cause = record.cause.map_or("-", |cause| cause.as_str());
+            if let Err(err) = writeln!(sink, "{}\t{}\t{}\t{}", millis, op.as_str(), record.key, cause) {
+                log::warn!("{}: {}", messages::LOG_AUDIT_SINK_FAILED, err);
+            }
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    pub(crate) fn records(&self) -> impl Iterator<Item = &AuditRecord> {
+        self.records.iter()
+    }
+}
+
+impl<K> fmt::Debug for AuditLog<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AuditLog")
+            .field("capacity", &self.capacity)
+            .field("records", &self.records.len())
+            .field("sink", &self.sink.is_some())
+            .finish()
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne les enregistrements du journal d'audit, du plus ancien au
+    /// plus récent. L'itérateur est vide si le journal n'est pas activé.
+    pub fn audit_log(&self) -> impl Iterator<Item = &AuditRecord> {
+        self.observers.audit.iter().flat_map(|audit| audit.records())
+    }
+}
@@ -0,0 +1,159 @@
+//! Module déplaçant l'éviction vers un thread d'arrière-plan.
+//!
+//! Avec `SyncCache::spawn_eviction_worker`, une insertion dans un cache plein
+//! n'évince plus rien elle-même : elle réveille le thread d'éviction, qui
+//! ramène le cache à son seuil bas (`CacheBuilder::low_watermark`, ou la
+//! capacité) dès qu'il obtient le verrou. Les threads écrivains ne paient
+//! donc pas le coût des évictions.
+//!
+//! Le cache peut ainsi dépasser temporairement sa capacité, d'au plus
+//! `max_backlog` entrées : au-delà, l'insertion revient à l'éviction
+//! synchrone, ce qui borne la mémoire si le thread prend du retard.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::time::Duration;
+//! use lru_cache::lru::sync::SyncCache;
+//!
+//! let cache = SyncCache::new(100);
+//! let worker = cache.spawn_eviction_worker(50, Duration::from_millis(100));
+//! for i in 0..1000 {
+//!     cache.put(i, i).unwrap();
+//! }
+//!
+//! // Jamais plus que la capacité et le retard toléré
+//! assert!(cache.len().unwrap() <= 150);
+//! worker.stop();
+//! assert!(cache.len().unwrap() <= 100);
+//! ```
+
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+use crate::lru::sync::SyncCache;
+
+/// Lien entre un cache et son thread d'éviction.
+#[derive(Debug)]
+pub(crate) struct BackgroundEviction {
+    max_backlog: usize,
+    wake: SyncSender<()>,
+}
+
+impl BackgroundEviction {
+    /// Réveille le thread, sauf s'il a déjà un réveil en attente.
+    fn wake(&self) {
+        let _ = self.wake.try_send(());
+    }
+}
+
+/// Poignée du thread d'éviction.
+///
+/// Le thread est arrêté lorsque la poignée est abandonnée ; le cache revient
+/// alors à l'éviction synchrone.
+#[derive(Debug)]
+pub struct EvictionWorker {
+    stopping: Arc<AtomicBool>,
+    wake: SyncSender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl EvictionWorker {
+    /// Arrête le thread et attend sa terminaison.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stopping.store(true, Ordering::Release);
+        let _ = self.wake.try_send(());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for EvictionWorker {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Ramène le cache à son seuil bas s'il dépasse sa capacité, et retourne
+    /// le nombre d'entrées évincées.
+    pub fn evict_to_watermark(&mut self) -> usize {
+        if self.elements.len() <= self.capacity {
+            return 0;
+        }
+        let target = self.low_watermark.unwrap_or(self.capacity);
+        let excess = self.elements.len() - target;
+        self.evict_batch(excess, RemovalCause::Capacity);
+        excess
+    }
+
+    /// Confie l'éviction au thread d'arrière-plan, s'il existe et si le
+    /// retard toléré n'est pas atteint.
+    pub(crate) fn defer_eviction(&self) -> bool {
+        match self.background_eviction.as_ref() {
+            Some(background) if self.elements.len() < self.capacity + background.max_backlog => {
+                background.wake();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Send + 'static,
+{
+    /// Lance un thread qui évince à la place des écrivains, réveillé par les
+    /// insertions dans un cache plein et au moins à l'intervalle donné.
+    ///
+    /// Au-delà de `max_backlog` entrées en trop, les insertions évincent de
+    /// nouveau elles-mêmes. Le thread s'arrête lorsque la poignée retournée
+    /// est abandonnée, ou lorsque le verrou du cache est empoisonné sans
+    /// possibilité de reprise.
+    pub fn spawn_eviction_worker(&self, max_backlog: usize, interval: Duration) -> EvictionWorker {
+        let (wake, woken) = mpsc::sync_channel(1);
+        let stopping = Arc::new(AtomicBool::new(false));
+        let _ = self.with_lock(|cache| {
+            cache.background_eviction = Some(BackgroundEviction { max_backlog, wake: wake.clone() });
+        });
+
+        let cache = self.clone();
+        let stop = Arc::clone(&stopping);
+        let thread = thread::spawn(move || loop {
+            if let Err(RecvTimeoutError::Disconnected) = woken.recv_timeout(interval) {
+                break;
+            }
+            let stopping = stop.load(Ordering::Acquire);
+            let evicted = cache.with_lock(|cache| {
+                if stopping {
+                    cache.background_eviction = None;
+                }
+                cache.evict_to_watermark()
+            });
+            if stopping || evicted.is_err() {
+                break;
+            }
+        });
+
+        EvictionWorker {
+            stopping,
+            wake,
+            thread: Some(thread),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Module implémentant les valeurs binaires `bytes::Bytes`.
+//!
+//! Disponible avec la fonctionnalité `bytes`. Un `Cache<K, Bytes>` stocke des
+//! blocs d'octets opaques (blobs compressés, réponses sérialisées...) :
+//! `get_slice` les lit sans copie, et `get_shared` retourne un `Bytes`
+//! partageant le même tampon, qui reste valide après l'éviction de l'entrée.
+//! `put_from_reader` remplit une entrée directement depuis un flux, en une
+//! seule allocation de la longueur annoncée.
+//!
+//! `Display` et `FromStr` ne conviennent pas à des octets quelconques :
+//! `persist_bytes` et `new_persistent_bytes` utilisent le codec `bytes`
+//! (voir le module `format`), qui écrit chaque valeur brute précédée de sa
+//! longueur. Seules les clés passent par `Display` et `FromStr`.
+//!
+//! # Exemple
+//!
+//! ```
+//! use bytes::Bytes;
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let path = std::env::temp_dir().join(format!("lru_blob_doc_{}.bin", std::process::id()));
+//! let mut cache: Cache<String, Bytes> = Cache::new(100);
+//! cache.put_from_reader("blob".to_string(), &[0x00, 0xff, b'\n', 0x7f][..], 4).unwrap();
+//! assert_eq!(cache.get_slice(&"blob".to_string()), Some(&[0x00, 0xff, b'\n', 0x7f][..]));
+//! cache.persist_bytes(&path).unwrap();
+//!
+//! let mut restored = Cache::<String, Bytes>::new_persistent_bytes(100, &path).unwrap();
+//! let shared = restored.get_shared(&"blob".to_string()).unwrap();
+//! restored.clear();
+//! assert_eq!(shared.len(), 4);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::hash::Hash;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+use bytes::{Bytes, BytesMut};
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::format::{self, FileDescription, BYTES_CODEC};
+use crate::lru::stats::TimedOp;
+use crate::lru::traits::CacheTrait;
+use crate::messages;
+
+impl<K> Cache<K, Bytes>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne les octets associés à la clé, sans copie, et marque l'entrée
+    /// comme la plus récemment utilisée.
+    pub fn get_slice(&mut self, key: &K) -> Option<&[u8]> {
+        self.get(key).map(|bytes| &bytes[..])
+    }
+
+    /// Retourne un `Bytes` partageant le tampon de la valeur, sans copie, et
+    /// marque l'entrée comme la plus récemment utilisée.
+    pub fn get_shared(&mut self, key: &K) -> Option<Bytes> {
+        self.get(key).cloned()
+    }
+
+    /// Lit exactement `len` octets du flux et les insère sous la clé.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si le flux échoue ou se termine avant
+    /// `len` octets (rien n'est alors inséré), et les erreurs de
+    /// `Cache::try_put`.
+    pub fn put_from_reader<R: Read>(&mut self, key: K, mut reader: R, len: usize) -> Result<(), CacheError> {
+        let mut buffer = BytesMut::zeroed(len);
+        reader.read_exact(&mut buffer)?;
+        self.try_put(key, buffer.freeze())
+    }
+}
+
+impl<K> Cache<K, Bytes>
+where
+    K: Hash + Eq + Clone + Display,
+{
+    /// Sauvegarde le cache avec le codec `bytes`, de l'entrée la moins à la
+    /// plus récemment utilisée.
+    ///
+    /// Le fichier est écrit à côté puis renommé : il n'est jamais visible à
+    /// moitié écrit. Les clés ne doivent contenir ni tabulation ni saut de
+    /// ligne.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si le fichier ne peut pas être écrit.
+    pub fn persist_bytes<P: AsRef<Path>>(&self, path: P) -> Result<(), CacheError> {
+        let start = self.start_timer();
+        let path = path.as_ref();
+        let mut body = Vec::new();
+        let mut entries = 0;
+        for (key, value) in self.iter() {
+            writeln!(body, "{}\t{}", key, value.len())?;
+            body.extend_from_slice(value);
+            body.push(b'\n');
+            entries += 1;
+        }
+        let description = FileDescription {
+            codec: BYTES_CODEC.to_string(),
+            checksum: Some(format::checksum(&body)),
+            ..FileDescription::new(entries, self.capacity())
+        };
+
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".tmp");
+        let tmp = path.with_file_name(name);
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        writeln!(writer, "{}", description.header_line())?;
+        writer.write_all(&body)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&tmp, path)?;
+
+        self.mark_synced();
+        self.record_latency(TimedOp::Persist, start);
+        Ok(())
+    }
+}
+
+impl<K> Cache<K, Bytes>
+where
+    K: Hash + Eq + Clone + FromStr,
+{
+    /// Crée un cache de la capacité donnée, initialisé avec le contenu du
+    /// fichier écrit par `persist_bytes`, s'il existe.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si :
+    /// * La capacité est 0 (`CacheError::CapacityError`)
+    /// * Le fichier existe mais ne peut pas être lu (`CacheError::IoError`)
+    /// * Le fichier n'est pas au codec `bytes`, est tronqué, ou sa somme de
+    ///   contrôle est incorrecte (`CacheError::Corrupted`)
+    pub fn new_persistent_bytes<P: AsRef<Path>>(capacity: usize, path: P) -> Result<Self, CacheError> {
+        let mut cache = Self::try_new(capacity)?;
+        let path = path.as_ref();
+        let content = match fs::read(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(cache),
+            Err(err) => return Err(err.into()),
+        };
+        let entries = parse_bytes_entries(&content).map_err(|(line, reason)| CacheError::Corrupted {
+            path: Some(path.to_path_buf()),
+            line,
+            reason,
+        })?;
+        for (key, value) in entries {
+            cache.put(key, value);
+        }
+        cache.mark_synced();
+        Ok(cache)
+    }
+}
+
+/// Lit les entrées d'un fichier au codec `bytes`. Une erreur porte le
+/// numéro de la ligne fautive et sa raison.
+fn parse_bytes_entries<K: FromStr>(content: &[u8]) -> Result<Vec<(K, Bytes)>, (usize, String)> {
+    let header_len = content.iter().position(|&byte| byte == b'\n').map_or(content.len(), |eol| eol + 1);
+    let header = String::from_utf8_lossy(&content[..header_len]);
+    let description = FileDescription::parse_header_with(header.trim_end_matches(['\n', '\r']), &[BYTES_CODEC])
+        .unwrap_or_else(|| Err(messages::INVALID_FILE_HEADER.to_string()))
+        .map_err(|reason| (1, reason))?;
+
+    let body = &content[header_len..];
+    if description.checksum.is_some_and(|expected| expected != format::checksum(body)) {
+        return Err((1, messages::CHECKSUM_MISMATCH.to_string()));
+    }
+
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    // Ligne de l'entrée courante, tenue à jour au fil de la lecture
+    let mut line = 2;
+    while offset < body.len() {
+        let invalid = || (line, messages::INVALID_LINE_FORMAT.to_string());
+        let truncated = || (line, messages::TRUNCATED_ENTRY.to_string());
+
+        let eol = body[offset..].iter().position(|&byte| byte == b'\n').ok_or_else(truncated)?;
+        let text = std::str::from_utf8(&body[offset..offset + eol]).map_err(|_| invalid())?;
+        let (key, len) = text.split_once('\t').ok_or_else(invalid)?;
+        let len: usize = len.parse().map_err(|_| invalid())?;
+        let key = K::from_str(key).map_err(|_| (line, format!("{}: {}", messages::UNPARSABLE_KEY, key)))?;
+
+        let start = offset + eol + 1;
+        let end = start.checked_add(len).ok_or_else(truncated)?;
+        if body.get(end) != Some(&b'\n') {
+            return Err(truncated());
+        }
+        let value = &body[start..end];
+        entries.push((key, Bytes::copy_from_slice(value)));
+        line += 2 + value.iter().filter(|&&byte| byte == b'\n').count();
+        offset = end + 1;
+    }
+    Ok(entries)
+}
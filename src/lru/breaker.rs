@@ -0,0 +1,90 @@
+//! Module implémentant un disjoncteur protégeant les appels au chargeur.
+//!
+//! Après `failure_threshold` échecs consécutifs, le disjoncteur s'ouvre : le
+//! chargeur n'est plus appelé pendant `open_for`, et les lectures se
+//! contentent des valeurs déjà présentes. Une fois ce délai écoulé, le
+//! disjoncteur passe à l'état semi-ouvert et laisse passer un unique appel
+//! d'essai : un succès le referme, un échec le rouvre pour un nouveau délai.
+//! Une source en panne ne subit ainsi pas des vagues d'appels synchronisés
+//! qui expirent tous en même temps.
+
+use std::time::{Duration, Instant};
+
+/// État d'un disjoncteur.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Les appels passent normalement
+    Closed,
+    /// Les appels sont bloqués jusqu'à la fin du délai d'ouverture
+    Open,
+    /// Un appel d'essai est en cours
+    HalfOpen,
+}
+
+/// Disjoncteur comptant les échecs consécutifs d'un chargeur.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_for: Duration,
+    failures: u32,
+    state: BreakerState,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Crée un disjoncteur qui s'ouvre après `failure_threshold` échecs
+    /// consécutifs (au moins 1) et reste ouvert pendant `open_for`.
+    pub fn new(failure_threshold: u32, open_for: Duration) -> Self {
+        CircuitBreaker {
+            failure_threshold: failure_threshold.max(1),
+            open_for,
+            failures: 0,
+            state: BreakerState::Closed,
+            opened_at: None,
+        }
+    }
+
+    /// Retourne l'état courant du disjoncteur.
+    pub fn state(&self) -> BreakerState {
+        self.state
+    }
+
+    /// Retourne le nombre d'échecs consécutifs enregistrés.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Indique si un appel peut avoir lieu, en passant à l'état semi-ouvert
+    /// si le délai d'ouverture est écoulé.
+    pub(crate) fn allow(&mut self, now: Instant) -> bool {
+        match self.state {
+            BreakerState::Closed => true,
+            BreakerState::Open => {
+                let elapsed = self.opened_at.is_none_or(|opened| now.saturating_duration_since(opened) >= self.open_for);
+                if elapsed {
+                    self.state = BreakerState::HalfOpen;
+                }
+                elapsed
+            }
+            // Un seul appel d'essai à la fois
+            BreakerState::HalfOpen => false,
+        }
+    }
+
+    /// Enregistre le succès d'un appel et referme le disjoncteur.
+    pub(crate) fn record_success(&mut self) {
+        self.failures = 0;
+        self.state = BreakerState::Closed;
+        self.opened_at = None;
+    }
+
+    /// Enregistre l'échec d'un appel, en ouvrant le disjoncteur si le seuil
+    /// est atteint ou si l'appel d'essai a échoué.
+    pub(crate) fn record_failure(&mut self, now: Instant) {
+        self.failures = self.failures.saturating_add(1);
+        if self.state == BreakerState::HalfOpen || self.failures >= self.failure_threshold {
+            self.state = BreakerState::Open;
+            self.opened_at = Some(now);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Module implémentant l'expiration par tranches de temps.
+//!
+//! Avec `CacheBuilder::time_buckets(ttl, width)`, les clés sont regroupées
+//! par tranche de `width` selon leur instant d'insertion, et une tranche
+//! entière expire `ttl` après sa fin. Une entrée vit donc entre `ttl` et
+//! `ttl + width`. Contrairement à `put_with_ttl`, aucune échéance n'est
+//! tenue par entrée : une insertion ajoute la clé à la tranche courante, et
+//! l'expiration retire d'un bloc les tranches dépassées, dès l'insertion
+//! suivante ou lors de `purge_expired`. Ce mode convient aux caches qui
+//! reçoivent des milliers d'insertions par seconde, comme la télémétrie.
+//!
+//! Une mise à jour ne change pas la tranche d'une clé : elle expire avec
+//! les clés insérées en même temps qu'elle.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::clock::ManualClock;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let clock = ManualClock::new();
+//! let mut cache = Cache::builder()
+//!     .capacity(1000)
+//!     .clock(Arc::new(clock.clone()))
+//!     .time_buckets(Duration::from_secs(120), Duration::from_secs(60))
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.put("cpu", 42);
+//! clock.advance(Duration::from_secs(150));
+//! cache.put("mem", 7);
+//! assert_eq!(cache.time_bucket_count(), 2);
+//!
+//! // La première tranche expire 120 s après sa fin, soit à 180 s
+//! clock.advance(Duration::from_secs(40));
+//! assert!(!cache.contains(&"cpu"));
+//! assert_eq!(cache.purge_expired(), 1);
+//! assert_eq!(cache.time_bucket_count(), 1);
+//! ```
+
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+
+/// Tranches de temps et clés insérées pendant chacune.
+#[derive(Debug)]
+pub(crate) struct TimeBuckets<K> {
+    ttl: Duration,
+    width: Duration,
+    origin: Instant,
+    /// Tranches non vides, de la plus ancienne à la plus récente
+    buckets: VecDeque<(u64, Vec<K>)>,
+}
+
+impl<K> TimeBuckets<K> {
+    pub(crate) fn new(ttl: Duration, width: Duration, origin: Instant) -> Self {
+        TimeBuckets { ttl, width, origin, buckets: VecDeque::new() }
+    }
+
+    /// Numéro de la tranche contenant l'instant donné.
+    fn index_of(&self, instant: Instant) -> u64 {
+        let elapsed = instant.saturating_duration_since(self.origin);
+        (elapsed.as_nanos() / self.width.as_nanos()) as u64
+    }
+
+    /// Instant d'expiration de la tranche donnée : `ttl` après sa fin.
+    fn deadline(&self, index: u64) -> Instant {
+        let end = (index as u128 + 1) * self.width.as_nanos();
+        self.origin + Duration::from_nanos(end as u64) + self.ttl
+    }
+
+    /// Instant d'expiration de la tranche contenant l'instant d'insertion.
+    pub(crate) fn deadline_of(&self, inserted: Instant) -> Instant {
+        self.deadline(self.index_of(inserted))
+    }
+
+    /// Indique si la tranche contenant l'instant d'insertion a expiré.
+    pub(crate) fn is_expired(&self, inserted: Instant, now: Instant) -> bool {
+        self.deadline_of(inserted) <= now
+    }
+
+    fn push(&mut self, key: K, now: Instant) {
+        let index = self.index_of(now);
+        match self.buckets.back_mut() {
+            Some((last, keys)) if *last == index => keys.push(key),
+            _ => self.buckets.push_back((index, vec![key])),
+        }
+    }
+
+    /// Range la clé dans la tranche de l'instant d'insertion donné, si cette
+    /// tranche est encore suivie.
+    pub(crate) fn push_at(&mut self, key: K, inserted: Instant) {
+        let index = self.index_of(inserted);
+        if let Some((_, keys)) = self.buckets.iter_mut().find(|(i, _)| *i == index) {
+            keys.push(key);
+        }
+    }
+
+    /// Retire la plus ancienne tranche si elle a expiré.
+    fn pop_expired(&mut self, now: Instant) -> Option<Vec<K>> {
+        let (index, _) = self.buckets.front()?;
+        if self.deadline(*index) > now {
+            return None;
+        }
+        self.buckets.pop_front().map(|(_, keys)| keys)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.buckets.clear();
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Range une nouvelle clé dans la tranche courante, après avoir retiré
+    /// les tranches expirées.
+    pub(crate) fn bucket_key(&mut self, key: &K) {
+        if self.time_buckets.is_none() {
+            return;
+        }
+        self.drop_expired_buckets();
+        let now = self.clock.now();
+        if let Some(buckets) = self.time_buckets.as_mut() {
+            buckets.push(key.clone(), now);
+        }
+    }
+
+    /// Supprime d'un bloc les entrées des tranches expirées et retourne leur
+    /// nombre.
+    pub(crate) fn drop_expired_buckets(&mut self) -> usize {
+        let now = self.clock.now();
+        let mut count = 0;
+        while let Some(keys) = self.time_buckets.as_mut().and_then(|buckets| buckets.pop_expired(now)) {
+            for key in keys {
+                // Une clé supprimée puis réinsérée appartient à une tranche
+                // plus récente et n'est pas concernée
+                let expired = match (self.elements.get(&key), self.time_buckets.as_ref()) {
+                    (Some(entry), Some(buckets)) => buckets.is_expired(entry.inserted, now),
+                    _ => false,
+                };
+                if expired && self.remove_entry(&key, RemovalCause::Expired).is_some() {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Retourne le nombre de tranches de temps contenant encore des clés,
+    /// ou 0 hors de ce mode.
+    pub fn time_bucket_count(&self) -> usize {
+        self.time_buckets.as_ref().map_or(0, |buckets| buckets.buckets.len())
+    }
+}
@@ -0,0 +1,236 @@
+//! Module implémentant les poignées d'écriture tamponnées du cache partagé.
+//!
+//! Lorsque de nombreux threads écrivent dans un même `SyncCache`, chaque
+//! écriture prend le verrou et les threads passent leur temps à l'attendre.
+//! `SyncCache::buffered_handle` donne à un thread une poignée qui accumule
+//! ses écritures dans un petit tampon local, puis les applique au cache
+//! partagé d'un seul coup, sous une seule prise de verrou.
+//!
+//! Le tampon est versé :
+//! - opportunément, sans attendre, dès qu'il est à moitié plein et que le
+//!   verrou est libre ;
+//! - en attendant le verrou lorsqu'il est plein ;
+//! - à la première opération qui suit `max_delay` depuis la plus ancienne
+//!   écriture en attente (voir `BufferedHandle::with_max_delay`) ;
+//! - sur demande (`BufferedHandle::flush`) et à l'abandon de la poignée.
+//!
+//! Les lectures d'une poignée voient ses propres écritures en attente. Les
+//! autres threads ne les voient qu'une fois versées : le cache partagé est en
+//! retard d'au plus un tampon par poignée. Les écritures d'une poignée sont
+//! appliquées dans leur ordre ; celles de poignées différentes s'entrelacent
+//! au gré des versements.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::thread;
+//! use lru_cache::lru::sync::SyncCache;
+//!
+//! let cache = SyncCache::new(1000);
+//! let threads: Vec<_> = (0..4)
+//!     .map(|t| {
+//!         let mut handle = cache.buffered_handle(16);
+//!         thread::spawn(move || {
+//!             for i in 0..100 {
+//!                 handle.put(t * 100 + i, i).unwrap();
+//!             }
+//!             // Le reste du tampon est versé à l'abandon de la poignée
+//!         })
+//!     })
+//!     .collect();
+//! for thread in threads {
+//!     thread.join().unwrap();
+//! }
+//! assert_eq!(cache.len().unwrap(), 400);
+//! ```
+
+use std::hash::Hash;
+use std::sync::TryLockError;
+use std::time::{Duration, Instant};
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::sync::SyncCache;
+use crate::lru::traits::CacheTrait;
+
+/// Poignée d'écriture tamponnée sur un `SyncCache`, propre à un thread.
+///
+/// Chaque écriture en attente est une insertion (`Some`) ou une suppression
+/// (`None`).
+#[derive(Debug)]
+pub struct BufferedHandle<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    cache: SyncCache<K, V>,
+    pending: Vec<(K, Option<V>)>,
+    capacity: usize,
+    max_delay: Option<Duration>,
+    oldest: Option<Instant>,
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Crée une poignée dont le tampon local retient au plus `capacity`
+    /// écritures (au moins une) avant de les verser dans ce cache.
+    pub fn buffered_handle(&self, capacity: usize) -> BufferedHandle<K, V> {
+        let capacity = capacity.max(1);
+        BufferedHandle {
+            cache: self.clone(),
+            pending: Vec::with_capacity(capacity),
+            capacity,
+            max_delay: None,
+            oldest: None,
+        }
+    }
+}
+
+impl<K, V> BufferedHandle<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Borne le temps pendant lequel une écriture peut rester en attente :
+    /// le tampon est versé à la première opération qui suit ce délai.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Retourne le nombre d'écritures en attente.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Retourne le cache partagé de la poignée.
+    pub fn cache(&self) -> &SyncCache<K, V> {
+        &self.cache
+    }
+
+    /// Ajoute ou met à jour une paire clé-valeur, d'abord dans le tampon.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un versement rencontre un verrou
+    /// empoisonné et que la politique est `PoisonPolicy::Propagate`.
+    pub fn put(&mut self, key: K, value: V) -> Result<(), CacheError> {
+        self.push(key, Some(value))
+    }
+
+    /// Supprime l'entrée, d'abord dans le tampon.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `BufferedHandle::put`.
+    pub fn remove(&mut self, key: K) -> Result<(), CacheError> {
+        self.push(key, None)
+    }
+
+    /// Récupère une copie de la valeur associée à la clé, en tenant compte
+    /// des écritures en attente de la poignée.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` selon la politique d'empoisonnement.
+    pub fn get(&mut self, key: &K) -> Result<Option<V>, CacheError> {
+        self.flush_if_late()?;
+        if let Some((_, write)) = self.pending.iter().rev().find(|(pending, _)| pending == key) {
+            return Ok(write.clone());
+        }
+        self.cache.get(key)
+    }
+
+    /// Verse toutes les écritures en attente dans le cache partagé, en
+    /// attendant le verrou. Retourne le nombre d'écritures versées.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` selon la politique d'empoisonnement ;
+    /// les écritures restent alors en attente.
+    pub fn flush(&mut self) -> Result<usize, CacheError> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        let pending = &mut self.pending;
+        let count = self.cache.with_lock(|cache| apply(cache, pending))?;
+        self.oldest = None;
+        Ok(count)
+    }
+
+    /// Verse les écritures en attente si le verrou est libre, sans
+    /// l'attendre. Retourne `true` si le tampon a été versé.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` selon la politique d'empoisonnement.
+    pub fn try_flush(&mut self) -> Result<bool, CacheError> {
+        if self.pending.is_empty() {
+            return Ok(true);
+        }
+        let mut guard = match self.cache.inner.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(poisoned)) => self.cache.recover(poisoned)?,
+            Err(TryLockError::WouldBlock) => return Ok(false),
+        };
+        apply(&mut guard, &mut self.pending);
+        drop(guard);
+        self.oldest = None;
+        Ok(true)
+    }
+
+    fn push(&mut self, key: K, write: Option<V>) -> Result<(), CacheError> {
+        self.pending.push((key, write));
+        self.oldest.get_or_insert_with(Instant::now);
+        if self.pending.len() >= self.capacity {
+            self.flush()?;
+        } else if self.pending.len() * 2 >= self.capacity {
+            self.try_flush()?;
+        } else {
+            self.flush_if_late()?;
+        }
+        Ok(())
+    }
+
+    fn flush_if_late(&mut self) -> Result<(), CacheError> {
+        let late = match (self.max_delay, self.oldest) {
+            (Some(max_delay), Some(oldest)) => oldest.elapsed() >= max_delay,
+            _ => false,
+        };
+        if late {
+            self.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Applique les écritures dans l'ordre et vide le tampon.
+fn apply<K, V>(cache: &mut Cache<K, V>, pending: &mut Vec<(K, Option<V>)>) -> usize
+where
+    K: Hash + Eq + Clone,
+{
+    let count = pending.len();
+    for (key, write) in pending.drain(..) {
+        match write {
+            Some(value) => cache.put(key, value),
+            None => {
+                cache.remove(&key);
+            }
+        }
+    }
+    count
+}
+
+impl<K, V> Drop for BufferedHandle<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    fn drop(&mut self) {
+        // Un verrou empoisonné perd les écritures en attente, comme il
+        // ferait échouer les écritures directes
+        let _ = self.flush();
+    }
+}
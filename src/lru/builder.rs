@@ -0,0 +1,559 @@
+//! Module fournissant un constructeur configurable pour le cache LRU.
+
+use std::fmt::{Debug, Display};
+use std::fs;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::error::CacheError;
+use crate::messages;
+use crate::lru::Cache;
+use crate::lru::advisor::CapacityAdvisor;
+use crate::lru::audit::{AuditConfig, DEFAULT_AUDIT_CAPACITY};
+use crate::lru::buckets::TimeBuckets;
+use crate::lru::checkpoint::CheckpointStore;
+use crate::lru::clock::Clock;
+use crate::lru::duplicate::{DuplicatePolicy, UpdateRecency};
+use crate::lru::duration::parse_duration;
+use crate::lru::frequency::{Decay, FrequencyDecay};
+use crate::lru::hooks::{CacheHooks, Hooks};
+use crate::lru::integrity::IntegrityReport;
+use crate::lru::intern::KeyInterner;
+use crate::lru::keys::KeyCheck;
+use crate::lru::order::UsageOrder;
+use crate::lru::overflow::{Overflow, StorageBackend};
+use crate::lru::placeholder::FailedLoads;
+use crate::lru::sampled::Sampler;
+use crate::lru::shrink::ShrinkPolicy;
+use crate::lru::stats::{StatsRecorder, StatsWindow};
+use crate::lru::weight::Weigher;
+use crate::lru::xfetch::EarlyExpiration;
+
+/// Constructeur permettant de configurer un `Cache` avant sa création.
+///
+/// Contrairement à `Cache::new`, la construction ne panique jamais : toute
+/// configuration invalide est signalée par une `CacheError`.
+///
+/// # Exemples
+///
+/// ```
+/// use lru_cache::lru::CacheBuilder;
+/// use lru_cache::lru::traits::CacheTrait;
+///
+/// let mut cache = CacheBuilder::new().capacity(2).build().unwrap();
+/// cache.put("clé", 42);
+/// assert_eq!(cache.get(&"clé"), Some(&42));
+///
+/// // Une capacité nulle est refusée
+/// assert!(CacheBuilder::<&str, i32>::new().capacity(0).build().is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CacheBuilder<K, V> {
+    capacity: Option<usize>,
+    time_to_live: Option<Duration>,
+    invalid_ttl: Option<String>,
+    time_buckets: Option<(Duration, Duration)>,
+    clock: Option<Arc<dyn Clock>>,
+    weigher: Option<Weigher<V>>,
+    max_weight: Option<usize>,
+    max_value_weight: Option<usize>,
+    key_checks: Vec<KeyCheck<K>>,
+    duplicate_policy: DuplicatePolicy<V>,
+    update_recency: UpdateRecency,
+    audit: Option<AuditConfig<K>>,
+    overflow: Option<Overflow<K, V>>,
+    frequency_decay: Option<FrequencyDecay>,
+    stats: Option<Option<StatsWindow>>,
+    time_operations: bool,
+    capacity_advisor: bool,
+    hooks: Hooks<K, V>,
+    intern_keys: bool,
+    track_access: bool,
+    indexed_scan: bool,
+    low_watermark: Option<usize>,
+    eviction_sample: Option<usize>,
+    checkpoints: Option<CheckpointStore>,
+    seed: Vec<(K, V)>,
+    deterministic: Option<u64>,
+    shrink_policy: ShrinkPolicy,
+    load_failure_cooldown: Option<Duration>,
+    early_expiration: Option<f64>,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> Default for CacheBuilder<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> CacheBuilder<K, V> {
+    /// Crée un constructeur sans configuration.
+    pub fn new() -> Self {
+        CacheBuilder {
+            capacity: None,
+            time_to_live: None,
+            invalid_ttl: None,
+            time_buckets: None,
+            clock: None,
+            weigher: None,
+            max_weight: None,
+            max_value_weight: None,
+            key_checks: Vec::new(),
+            duplicate_policy: DuplicatePolicy::Overwrite,
+            update_recency: UpdateRecency::Promote,
+            audit: None,
+            overflow: None,
+            frequency_decay: None,
+            stats: None,
+            time_operations: false,
+            capacity_advisor: false,
+            hooks: Hooks::default(),
+            intern_keys: false,
+            track_access: false,
+            indexed_scan: false,
+            low_watermark: None,
+            eviction_sample: None,
+            checkpoints: None,
+            seed: Vec::new(),
+            deterministic: None,
+            shrink_policy: ShrinkPolicy::Never,
+            load_failure_cooldown: None,
+            early_expiration: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Définit la capacité maximale du cache.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Définit la durée de vie appliquée par défaut aux entrées insérées
+    /// avec `put`.
+    pub fn time_to_live(mut self, ttl: Duration) -> Self {
+        self.time_to_live = Some(ttl);
+        self.invalid_ttl = None;
+        self
+    }
+
+    /// Définit la durée de vie par défaut à partir d'un texte lisible, comme
+    /// `"500ms"` ou `"2h30m"` (voir `duration::parse_duration`). Un texte
+    /// invalide est signalé par `build`.
+    pub fn time_to_live_str(mut self, ttl: &str) -> Self {
+        match parse_duration(ttl) {
+            Ok(ttl) => return self.time_to_live(ttl),
+            Err(CacheError::ParseError(invalid)) => self.invalid_ttl = Some(invalid),
+            Err(err) => self.invalid_ttl = Some(err.to_string()),
+        }
+        self
+    }
+
+    /// Regroupe les clés par tranches de `width` selon leur instant
+    /// d'insertion ; chaque tranche expire d'un bloc `ttl` après sa fin (voir
+    /// le module `buckets`).
+    pub fn time_buckets(mut self, ttl: Duration, width: Duration) -> Self {
+        self.time_buckets = Some((ttl, width));
+        self
+    }
+
+    /// Définit la source de temps utilisée par le cache.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Définit la fonction calculant le poids de chaque valeur.
+    pub fn weigher<F>(mut self, weigher: F) -> Self
+    where
+        F: Fn(&V) -> usize + Send + Sync + 'static,
+    {
+        self.weigher = Some(Weigher::new(weigher));
+        self
+    }
+
+    /// Définit le budget de poids total du cache.
+    pub fn max_weight(mut self, max_weight: usize) -> Self {
+        self.max_weight = Some(max_weight);
+        self
+    }
+
+    /// Définit le poids maximal d'une valeur individuelle.
+    pub fn max_value_weight(mut self, max_value_weight: usize) -> Self {
+        self.max_value_weight = Some(max_value_weight);
+        self
+    }
+
+    /// Enregistre un validateur appelé sur chaque clé avant son insertion.
+    /// 
+    /// Le validateur retourne la raison du refus en cas d'erreur.
+    pub fn key_validator<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&K) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.key_checks.push(KeyCheck::validator(validator));
+        self
+    }
+
+    /// Définit le comportement de `put` lorsque la clé existe déjà.
+    pub fn on_duplicate(mut self, policy: DuplicatePolicy<V>) -> Self {
+        self.duplicate_policy = policy;
+        self
+    }
+
+    /// Définit si `put` sur une clé existante la marque comme la plus
+    /// récemment utilisée (par défaut) ou la laisse à sa place.
+    pub fn update_recency(mut self, recency: UpdateRecency) -> Self {
+        self.update_recency = recency;
+        self
+    }
+
+    /// Confie les entrées évincées au stockage secondaire indiqué, consulté
+    /// ensuite lors des échecs de lecture.
+    pub fn overflow<S>(mut self, backend: Arc<S>) -> Self
+    where
+        S: StorageBackend<K, V> + 'static,
+    {
+        self.overflow = Some(Overflow::new(backend));
+        self
+    }
+
+    /// Enregistre des crochets appelés aux étapes du cycle de vie des
+    /// entrées. Plusieurs jeux de crochets peuvent être enregistrés.
+    pub fn hooks<H>(mut self, hooks: H) -> Self
+    where
+        H: CacheHooks<K, V> + 'static,
+    {
+        self.hooks.push(Arc::new(hooks));
+        self
+    }
+
+    /// Définit la politique de vieillissement des compteurs de lectures.
+    pub fn frequency_decay(mut self, decay: FrequencyDecay) -> Self {
+        self.frequency_decay = Some(decay);
+        self
+    }
+
+    /// Active les statistiques de lectures et de mutations (`Cache::stats`).
+    pub fn record_stats(mut self) -> Self {
+        self.stats.get_or_insert(None);
+        self
+    }
+
+    /// Active les statistiques et suit en outre le taux de succès sur la
+    /// fenêtre glissante indiquée.
+    pub fn stats_window(mut self, window: StatsWindow) -> Self {
+        self.stats = Some(Some(window));
+        self
+    }
+
+    /// Active les statistiques et mesure en outre la durée de `get`, `put`
+    /// et `persist` (`CacheStats::latency`).
+    pub fn time_operations(mut self) -> Self {
+        self.stats.get_or_insert(None);
+        self.time_operations = true;
+        self
+    }
+
+    /// Active le conseiller de dimensionnement (`Cache::capacity_advice`),
+    /// qui estime le taux de succès avec une capacité plus grande.
+    pub fn capacity_advisor(mut self) -> Self {
+        self.capacity_advisor = true;
+        self
+    }
+
+    /// Définit si la mémoire des structures internes est réservée pour
+    /// toujours (par défaut) ou rendue lorsque le cache se vide (voir
+    /// `shrink::ShrinkPolicy`).
+    pub fn shrink_policy(mut self, policy: ShrinkPolicy) -> Self {
+        self.shrink_policy = policy;
+        self
+    }
+
+    /// Mémorise pendant `cooldown` l'échec d'un chargement par
+    /// `Cache::try_get_or_insert_with` : les appels suivants pour la même
+    /// clé échouent aussitôt, sans relancer le chargeur.
+    pub fn load_failure_cooldown(mut self, cooldown: Duration) -> Self {
+        self.load_failure_cooldown = Some(cooldown);
+        self
+    }
+
+    /// Active l'expiration anticipée probabiliste de `Cache::get_fresh`
+    /// (voir le module `xfetch`) ; `beta` vaut habituellement 1.
+    pub fn early_expiration(mut self, beta: f64) -> Self {
+        self.early_expiration = Some(beta);
+        self
+    }
+
+    /// Date chaque lecture réussie, pour `Cache::iter_idle_since`.
+    pub fn track_access_times(mut self) -> Self {
+        self.track_access = true;
+        self
+    }
+
+    /// Tient un index trié des empreintes des clés : `Cache::scan` reprend
+    /// alors directement au curseur au lieu de parcourir toute la table, au
+    /// prix d'une mise à jour de l'index à chaque ajout et à chaque retrait.
+    pub fn indexed_scan(mut self) -> Self {
+        self.indexed_scan = true;
+        self
+    }
+
+    /// Évince par lots : une insertion qui atteint la capacité évince les
+    /// entrées les moins récemment utilisées jusqu'à n'en garder que `low`.
+    pub fn low_watermark(mut self, low: usize) -> Self {
+        self.low_watermark = Some(low);
+        self
+    }
+
+    /// Remplace l'ordre d'utilisation exact par une éviction échantillonnée :
+    /// chaque éviction supprime la moins récemment utilisée de `sample_size`
+    /// entrées tirées au hasard.
+    pub fn sampled_eviction(mut self, sample_size: usize) -> Self {
+        self.eviction_sample = Some(sample_size);
+        self
+    }
+
+    /// Active les points de reprise (`Cache::checkpoint`), écrits dans le
+    /// dossier indiqué, dont seuls les `keep` plus récents sont conservés.
+    pub fn checkpoints<P: Into<PathBuf>>(mut self, dir: P, keep: usize) -> Self {
+        self.checkpoints = Some(CheckpointStore { dir: dir.into(), keep });
+        self
+    }
+
+    /// Pré-remplit le cache avec les entrées indiquées, de la moins à la
+    /// plus récemment utilisée, avant que les hooks, statistiques et journal
+    /// d'audit ne soient branchés. Les appels successifs s'ajoutent ; au-delà
+    /// de la capacité, les premières entrées sont évincées.
+    ///
+    /// Un `SyncCache` construit avec `SyncCache::from_cache` démarre ainsi
+    /// rempli sans passer par son verrou entrée par entrée.
+    pub fn seed<I>(mut self, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.seed.extend(entries);
+        self
+    }
+
+    /// Active le mode déterministe : la fonction de hachage des clés et les
+    /// tirages de l'éviction échantillonnée sont dérivés de la graine (voir
+    /// le module `deterministic`).
+    pub fn deterministic(mut self, seed: u64) -> Self {
+        self.deterministic = Some(seed);
+        self
+    }
+
+    fn checked_capacity(&self) -> Result<usize, CacheError> {
+        match self.capacity {
+            Some(0) => Err(CacheError::CapacityError(messages::ZERO_CAPACITY.to_string())),
+            Some(capacity) if self.low_watermark.is_some_and(|low| low >= capacity) => {
+                Err(CacheError::CapacityError(messages::INVALID_WATERMARK.to_string()))
+            }
+            Some(capacity) => Ok(capacity),
+            None => Err(CacheError::CapacityError(messages::MISSING_CAPACITY.to_string())),
+        }
+    }
+
+    fn check_settings(&self) -> Result<(), CacheError> {
+        if let Some(invalid) = &self.invalid_ttl {
+            return Err(CacheError::ParseError(invalid.clone()));
+        }
+        if self.time_buckets.is_some_and(|(_, width)| width.is_zero()) {
+            return Err(CacheError::CapacityError(messages::ZERO_BUCKET_WIDTH.to_string()));
+        }
+        if self.max_weight == Some(0) {
+            return Err(CacheError::CapacityError(messages::ZERO_WEIGHT.to_string()));
+        }
+        if self.checkpoints.as_ref().is_some_and(|store| store.keep == 0) {
+            return Err(CacheError::CapacityError(messages::ZERO_CHECKPOINTS.to_string()));
+        }
+        if self.load_failure_cooldown.is_some_and(|cooldown| cooldown.is_zero()) {
+            return Err(CacheError::CapacityError(messages::ZERO_FAILURE_COOLDOWN.to_string()));
+        }
+        if self.early_expiration.is_some_and(|beta| !EarlyExpiration::is_valid_beta(beta)) {
+            return Err(CacheError::CapacityError(messages::INVALID_EARLY_EXPIRATION.to_string()));
+        }
+        self.shrink_policy.check()
+    }
+}
+
+impl<V> CacheBuilder<Arc<str>, V> {
+    /// Conserve les clés dans un registre qui survit aux évictions, afin
+    /// qu'une clé qui revient réutilise sa chaîne (`Cache::put_str`).
+    pub fn intern_keys(mut self) -> Self {
+        self.intern_keys = true;
+        self
+    }
+}
+
+impl<K, V> CacheBuilder<K, V>
+where
+    K: AsRef<[u8]>,
+{
+    /// Définit la taille maximale, en octets, des clés insérées.
+    pub fn max_key_length(mut self, max: usize) -> Self {
+        self.key_checks.push(KeyCheck::max_length(max));
+        self
+    }
+}
+
+impl<K, V> CacheBuilder<K, V>
+where
+    K: Debug,
+{
+    /// Active le journal d'audit, qui conserve les `capacity` dernières
+    /// mutations. Les clés y sont représentées via leur format `Debug`.
+    pub fn audit_log(mut self, capacity: usize) -> Self {
+        match self.audit.as_mut() {
+            Some(audit) => audit.capacity = capacity,
+            None => self.audit = Some(AuditConfig::new(capacity)),
+        }
+        self
+    }
+
+    /// Écrit en outre chaque enregistrement du journal d'audit, en ajout, dans
+    /// le fichier indiqué. Active le journal s'il ne l'est pas encore.
+    pub fn audit_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.audit
+            .get_or_insert_with(|| AuditConfig::new(DEFAULT_AUDIT_CAPACITY))
+            .path = Some(path.into());
+        self
+    }
+}
+
+impl<K, V> CacheBuilder<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Construit le cache configuré.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la capacité n'a pas été
+    /// définie ou vaut 0, si le budget de poids, la largeur des tranches de
+    /// temps, le nombre de points de reprise conservés ou le délai de
+    /// carence après un échec de chargement vaut 0, si le paramètre de
+    /// l'expiration anticipée ou le seuil de la politique de réduction de
+    /// la mémoire est invalide,
+    /// `CacheError::ParseError` si la durée de vie passée à
+    /// `time_to_live_str` est invalide, `CacheError::IoError` si le
+    /// fichier d'audit ou le dossier des points de reprise ne peut pas être
+    /// ouvert, et les erreurs de `Cache::try_put` si une entrée de `seed` est
+    /// refusée.
+    pub fn build(self) -> Result<Cache<K, V>, CacheError> {
+        self.check_settings()?;
+        let cache = Cache::try_new(self.checked_capacity()?)?;
+        self.configure(cache)
+    }
+
+    /// Applique la configuration du constructeur à un cache déjà créé.
+    fn configure(self, mut cache: Cache<K, V>) -> Result<Cache<K, V>, CacheError> {
+        cache.default_ttl = self.time_to_live;
+        cache.weigher = self.weigher;
+        cache.max_weight = self.max_weight;
+        cache.max_value_weight = self.max_value_weight;
+        cache.key_checks = self.key_checks;
+        cache.duplicate_policy = self.duplicate_policy;
+        cache.update_recency = self.update_recency;
+        cache.overflow = self.overflow;
+        cache.decay = self.frequency_decay.map(Decay::new);
+        cache.track_access = self.track_access;
+        cache.low_watermark = self.low_watermark;
+        cache.failed_loads = self.load_failure_cooldown.map(FailedLoads::new);
+        cache.early_expiration = self.early_expiration.map(|beta| match self.deterministic {
+            Some(seed) => EarlyExpiration::seeded(beta, seed),
+            None => EarlyExpiration::new(beta),
+        });
+        if self.shrink_policy != ShrinkPolicy::Never {
+            cache.use_shrink_policy(self.shrink_policy);
+        }
+        if let Some(seed) = self.deterministic {
+            cache.use_seeded_hasher(seed);
+        }
+        if self.indexed_scan {
+            cache.use_scan_index();
+        }
+        if let Some(sample_size) = self.eviction_sample {
+            cache.sampling = Some(match self.deterministic {
+                Some(seed) => Sampler::seeded(sample_size, seed),
+                None => Sampler::new(sample_size),
+            });
+            cache.usage_order = UsageOrder::new(cache.elements.hasher().clone());
+            cache.track_access = true;
+        }
+        if self.intern_keys {
+            cache.interner = Some(KeyInterner::new());
+        }
+        if let Some(clock) = self.clock {
+            cache.clock = clock;
+        }
+        if let Some((ttl, width)) = self.time_buckets {
+            cache.time_buckets = Some(TimeBuckets::new(ttl, width, cache.clock.now()));
+        }
+        // Les entrées initiales sont soumises aux règles configurées, mais
+        // ne sont pas vues par les observateurs
+        for (key, value) in self.seed {
+            cache.put_entry(key, value, cache.default_ttl, false)?;
+        }
+        cache.observers.hooks = self.hooks;
+        let time_operations = self.time_operations;
+        cache.observers.stats = self
+            .stats
+            .map(StatsRecorder::new)
+            .map(|stats| if time_operations { stats.with_latency() } else { stats });
+        if self.capacity_advisor {
+            cache.observers.advisor = Some(CapacityAdvisor::new(cache.capacity));
+        }
+        if let Some(audit) = self.audit {
+            cache.observers.audit = Some(audit.open()?);
+        }
+        if let Some(store) = self.checkpoints {
+            fs::create_dir_all(&store.dir)?;
+            cache.checkpoints = Some(store);
+        }
+        Ok(cache)
+    }
+}
+
+impl<K, V> CacheBuilder<K, V>
+where
+    K: Hash + Eq + Clone + Display + FromStr,
+    V: Display + FromStr,
+{
+    /// Construit un cache persistant initialisé depuis le fichier indiqué.
+    /// Les entrées de `seed` sont insérées après celles du fichier, comme
+    /// les plus récemment utilisées, et restent à sauvegarder.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les mêmes erreurs que `build` et `Cache::new_persistent`.
+    pub fn build_persistent<P: AsRef<Path>>(self, path: P) -> Result<Cache<K, V>, CacheError> {
+        self.check_settings()?;
+        let cache = Cache::new_persistent(self.checked_capacity()?, path)?;
+        let mut cache = self.configure(cache)?;
+        // Les entrées chargées sont soumises aux limites configurées
+        cache.enforce_limits();
+        Ok(cache)
+    }
+
+    /// Construit un cache persistant en réparant son fichier au besoin (voir
+    /// `Cache::new_persistent_repaired`), et retourne le compte rendu des
+    /// vérifications.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les mêmes erreurs que `build` et
+    /// `Cache::new_persistent_repaired`.
+    pub fn build_persistent_repaired<P: AsRef<Path>>(self, path: P) -> Result<(Cache<K, V>, IntegrityReport), CacheError> {
+        self.check_settings()?;
+        let (cache, report) = Cache::new_persistent_repaired(self.checked_capacity()?, path)?;
+        let mut cache = self.configure(cache)?;
+        cache.enforce_limits();
+        Ok((cache, report))
+    }
+}
@@ -0,0 +1,159 @@
+//! Module implémentant un cache stockant ses valeurs sérialisées.
+//!
+//! `BytesCache` conserve chaque valeur sous forme d'un bloc d'octets
+//! (`Box<[u8]>`) produit par un `Codec`, plutôt que sous sa forme native :
+//! une seule allocation par valeur, ce qui limite la fragmentation du tas, et
+//! un poids égal à la taille exacte du bloc, ce qui rend le budget de poids
+//! exact en octets. `get` désérialise la valeur ; `get_bytes` donne accès au
+//! bloc sans copie pour les consommateurs qui travaillent directement sur les
+//! octets (réponse réseau, écriture disque...).
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::bytes::{BytesCache, TextCodec};
+//!
+//! let builder = Cache::builder().capacity(100).max_weight(1024);
+//! let mut cache = BytesCache::new(builder, TextCodec).unwrap();
+//!
+//! cache.put("pi", &3.14_f64).unwrap();
+//! assert_eq!(cache.get::<f64>(&"pi").unwrap(), Some(3.14));
+//! assert_eq!(cache.get_bytes(&"pi"), Some(&b"3.14"[..]));
+//! assert_eq!(cache.total_bytes(), 4);
+//! ```
+
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+use crate::error::CacheError;
+use crate::lru::{Cache, CacheBuilder};
+use crate::lru::traits::{CacheRead, CacheTrait};
+use crate::messages;
+
+/// Format de sérialisation des valeurs d'un `BytesCache`.
+pub trait Codec<V> {
+    /// Sérialise la valeur.
+    fn encode(&self, value: &V) -> Result<Vec<u8>, CacheError>;
+
+    /// Désérialise une valeur précédemment produite par `encode`.
+    fn decode(&self, bytes: &[u8]) -> Result<V, CacheError>;
+}
+
+/// Codec textuel reposant sur `Display` et `FromStr`, comme les fichiers de
+/// persistance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextCodec;
+
+impl<V> Codec<V> for TextCodec
+where
+    V: Display + FromStr,
+{
+    fn encode(&self, value: &V) -> Result<Vec<u8>, CacheError> {
+        Ok(value.to_string().into_bytes())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<V, CacheError> {
+        let text = std::str::from_utf8(bytes).map_err(|err| CacheError::Serialization(err.to_string()))?;
+        text.parse()
+            .map_err(|_| CacheError::Serialization(format!("{}: {}", messages::UNPARSABLE_VALUE, text)))
+    }
+}
+
+/// Cache stockant ses valeurs sérialisées par un codec.
+#[derive(Debug)]
+pub struct BytesCache<K, C = TextCodec>
+where
+    K: Hash + Eq,
+{
+    cache: Cache<K, Box<[u8]>>,
+    codec: C,
+}
+
+impl<K, C> BytesCache<K, C>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Construit le cache configuré par le constructeur donné. Son peseur
+    /// est remplacé par la taille en octets des valeurs sérialisées.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `CacheBuilder::build`.
+    pub fn new(builder: CacheBuilder<K, Box<[u8]>>, codec: C) -> Result<Self, CacheError> {
+        Ok(BytesCache {
+            cache: builder.weigher(|bytes| bytes.len()).build()?,
+            codec,
+        })
+    }
+
+    /// Sérialise et insère une valeur.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs du codec et de `Cache::try_put`.
+    pub fn put<V>(&mut self, key: K, value: &V) -> Result<(), CacheError>
+    where
+        C: Codec<V>,
+    {
+        let bytes = self.codec.encode(value)?;
+        self.put_bytes(key, bytes)
+    }
+
+    /// Insère une valeur déjà sérialisée.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `Cache::try_put`.
+    pub fn put_bytes<B: Into<Box<[u8]>>>(&mut self, key: K, bytes: B) -> Result<(), CacheError> {
+        self.cache.try_put(key, bytes.into())
+    }
+
+    /// Désérialise et retourne la valeur associée à la clé.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs du codec.
+    pub fn get<V>(&mut self, key: &K) -> Result<Option<V>, CacheError>
+    where
+        C: Codec<V>,
+    {
+        let codec = &self.codec;
+        self.cache.get(key).map(|bytes| codec.decode(bytes)).transpose()
+    }
+
+    /// Retourne la valeur sérialisée associée à la clé, sans copie.
+    pub fn get_bytes(&mut self, key: &K) -> Option<&[u8]> {
+        self.cache.get(key).map(|bytes| &bytes[..])
+    }
+
+    /// Supprime l'entrée et retourne sa valeur sérialisée.
+    pub fn remove(&mut self, key: &K) -> Option<Box<[u8]>> {
+        self.cache.remove(key)
+    }
+
+    /// Vérifie si la clé est présente, sans modifier l'ordre d'utilisation.
+    pub fn contains(&self, key: &K) -> bool {
+        self.cache.contains(key)
+    }
+
+    /// Retourne le nombre d'entrées du cache.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Vérifie si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Retourne la taille totale, en octets, des valeurs sérialisées.
+    pub fn total_bytes(&self) -> usize {
+        self.cache.total_weight()
+    }
+
+    /// Retourne le cache sous-jacent.
+    pub fn inner(&self) -> &Cache<K, Box<[u8]>> {
+        &self.cache
+    }
+}
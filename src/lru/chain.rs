@@ -0,0 +1,126 @@
+//! Module fournissant un combinateur enchaînant plusieurs caches.
+//!
+//! `ChainCache` interroge une liste ordonnée de caches, du plus rapide (ou
+//! du plus petit) au plus lent : une lecture essaie chaque niveau dans
+//! l'ordre et recopie la valeur trouvée dans les niveaux précédents, tandis
+//! qu'une écriture est propagée à tous les niveaux.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::chain::ChainCache;
+//! use lru_cache::lru::doubles::UnboundedMapCache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = ChainCache::new(vec![
+//!     Box::new(Cache::new(1)),
+//!     Box::new(UnboundedMapCache::new()),
+//! ]);
+//!
+//! cache.put(1, "un");
+//! cache.put(2, "deux"); // évince 1 du premier niveau uniquement
+//!
+//! assert_eq!(cache.level(0).unwrap().peek(&1), None);
+//! assert_eq!(cache.get(&1), Some(&"un")); // trouvé au second niveau et promu
+//! assert_eq!(cache.level(0).unwrap().peek(&1), Some(&"un"));
+//! ```
+
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Cache composé d'une chaîne ordonnée de caches.
+pub struct ChainCache<K, V> {
+    levels: Vec<Box<dyn CacheTrait<K, V>>>,
+}
+
+impl<K, V> ChainCache<K, V> {
+    /// Crée une chaîne à partir des niveaux donnés, du premier consulté au
+    /// dernier.
+    pub fn new(levels: Vec<Box<dyn CacheTrait<K, V>>>) -> Self {
+        ChainCache { levels }
+    }
+
+    /// Retourne le nombre de niveaux de la chaîne.
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Retourne le niveau d'indice donné, s'il existe.
+    pub fn level(&self, index: usize) -> Option<&dyn CacheTrait<K, V>> {
+        self.levels.get(index).map(|level| level.as_ref())
+    }
+}
+
+impl<K, V> std::fmt::Debug for ChainCache<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ChainCache")
+            .field("depth", &self.levels.len())
+            .finish()
+    }
+}
+
+impl<K, V> CacheRead<K, V> for ChainCache<K, V> {
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.levels.iter().find_map(|level| level.peek(key))
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.levels.iter().any(|level| level.contains(key))
+    }
+
+    /// Retourne le nombre d'éléments du niveau le plus rempli, ce qui
+    /// constitue une borne inférieure du nombre de clés distinctes.
+    fn len(&self) -> usize {
+        self.levels.iter().map(|level| level.len()).max().unwrap_or(0)
+    }
+
+    /// Retourne la capacité du plus grand niveau.
+    fn capacity(&self) -> usize {
+        self.levels.iter().map(|level| level.capacity()).max().unwrap_or(0)
+    }
+}
+
+impl<K, V> CacheTrait<K, V> for ChainCache<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let mut found = None;
+        for (index, level) in self.levels.iter_mut().enumerate() {
+            if let Some(value) = level.get(key) {
+                found = Some((index, value.clone()));
+                break;
+            }
+        }
+
+        let (index, value) = found?;
+        // Promotion de la valeur dans les niveaux précédents
+        for level in &mut self.levels[..index] {
+            level.put(key.clone(), value.clone());
+        }
+        self.levels[index].peek(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if let Some((last, others)) = self.levels.split_last_mut() {
+            for level in others {
+                level.put(key.clone(), value.clone());
+            }
+            last.put(key, value);
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.levels
+            .iter_mut()
+            .map(|level| level.remove(key))
+            .fold(None, |first, removed| first.or(removed))
+    }
+
+    fn clear(&mut self) {
+        for level in &mut self.levels {
+            level.clear();
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Module implémentant les points de reprise d'un cache persistant.
+//!
+//! Configurés avec `CacheBuilder::checkpoints`, les points de reprise sont
+//! des sauvegardes numérotées (`checkpoint-<génération>.txt`, au format de
+//! `persist`) écrites dans un dossier dédié. `checkpoint` en ajoute un et ne
+//! conserve que les `keep` plus récents ; `rollback_to` rétablit le contenu
+//! d'un point de reprise, par exemple pour annuler un déploiement fautif qui
+//! a rempli le cache de valeurs erronées.
+//!
+//! Le fichier de persistance n'est pas modifié : les entrées rétablies sont
+//! considérées comme modifiées jusqu'à la prochaine sauvegarde.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let dir = std::env::temp_dir().join(format!("lru_checkpoint_doc_{}", std::process::id()));
+//! let mut cache: Cache<String, i32> = Cache::builder().capacity(10).checkpoints(&dir, 3).build().unwrap();
+//! cache.put("prix".to_string(), 10);
+//! let sain = cache.checkpoint().unwrap();
+//!
+//! // Un déploiement fautif corrompt les valeurs
+//! cache.put("prix".to_string(), -1);
+//! cache.rollback_to(sain).unwrap();
+//! assert_eq!(cache.peek(&"prix".to_string()), Some(&10));
+//! # std::fs::remove_dir_all(&dir).unwrap();
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::SystemTime;
+use crate::error::CacheError;
+use crate::messages;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+use crate::lru::format;
+use crate::lru::traits::CacheTrait;
+
+const PREFIX: &str = "checkpoint-";
+const EXTENSION: &str = ".txt";
+
+/// Dossier des points de reprise et nombre de points conservés.
+#[derive(Debug, Clone)]
+pub(crate) struct CheckpointStore {
+    pub(crate) dir: PathBuf,
+    pub(crate) keep: usize,
+}
+
+impl CheckpointStore {
+    fn path_of(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{}{:020}{}", PREFIX, id, EXTENSION))
+    }
+
+    /// Générations présentes dans le dossier, de la plus ancienne à la plus
+    /// récente.
+    fn ids(&self) -> io::Result<Vec<u64>> {
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let name = entry?.file_name();
+            let id: Option<u64> = name
+                .to_str()
+                .and_then(|name| name.strip_prefix(PREFIX)?.strip_suffix(EXTENSION)?.parse().ok());
+            ids.extend(id);
+        }
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// Supprime les points de reprise au-delà des `keep` plus récents.
+    fn prune(&self) -> io::Result<()> {
+        let ids = self.ids()?;
+        for id in &ids[..ids.len().saturating_sub(self.keep)] {
+            fs::remove_file(self.path_of(*id))?;
+        }
+        Ok(())
+    }
+}
+
+/// Point de reprise présent sur le disque.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointInfo {
+    /// Génération, strictement croissante
+    pub id: u64,
+    /// Fichier du point de reprise
+    pub path: PathBuf,
+    /// Date d'écriture
+    pub created: SystemTime,
+    /// Nombre d'entrées sauvegardées
+    pub entries: usize,
+}
+
+fn not_found(message: String) -> CacheError {
+    CacheError::IoError(io::Error::new(io::ErrorKind::NotFound, message))
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone + Display + FromStr,
+    V: Display + FromStr,
+{
+    fn checkpoint_store(&self) -> Result<&CheckpointStore, CacheError> {
+        self.checkpoints
+            .as_ref()
+            .ok_or_else(|| not_found(messages::NO_CHECKPOINTS.to_string()))
+    }
+
+    /// Sauvegarde le contenu actuel dans un nouveau point de reprise et
+    /// retourne sa génération. Les points de reprise les plus anciens sont
+    /// supprimés au-delà du nombre conservé.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si les points de reprise ne sont pas
+    /// configurés ou si le fichier ne peut pas être écrit.
+    pub fn checkpoint(&self) -> Result<u64, CacheError> {
+        let store = self.checkpoint_store()?;
+        let id = store.ids()?.last().map_or(1, |last| last + 1);
+        let path = store.path_of(id);
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        // Écrit à côté puis renommé : un point de reprise n'est jamais
+        // visible à moitié écrit
+        self.write_entries(Path::new(&tmp))?;
+        fs::rename(&tmp, &path)?;
+        store.prune()?;
+        Ok(id)
+    }
+
+    /// Remplace le contenu du cache par celui du point de reprise donné et
+    /// retourne le nombre d'entrées rétablies. Les points de reprise plus
+    /// récents sont conservés.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si les points de reprise ne sont pas
+    /// configurés ou si la génération n'existe pas (ou plus), et les erreurs
+    /// de lecture du fichier ; le cache n'est alors pas modifié.
+    pub fn rollback_to(&mut self, id: u64) -> Result<usize, CacheError> {
+        let path = self.checkpoint_store()?.path_of(id);
+        let Some(entries) = Self::read_file_entries(&path)? else {
+            return Err(not_found(format!("{}: {}", messages::UNKNOWN_CHECKPOINT, id)));
+        };
+
+        let restored: HashSet<&K> = entries.iter().map(|(key, _)| key).collect();
+        let stale: Vec<K> = self.elements.keys().filter(|key| !restored.contains(key)).cloned().collect();
+        for key in stale {
+            self.remove_entry(&key, RemovalCause::Explicit);
+        }
+        let count = entries.len();
+        for (key, value) in entries {
+            self.put(key, value);
+        }
+        Ok(count)
+    }
+
+    /// Liste les points de reprise présents, du plus ancien au plus récent.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si les points de reprise ne sont pas
+    /// configurés ou si le dossier ne peut pas être lu, et
+    /// `CacheError::Corrupted` si l'en-tête d'un point de reprise est
+    /// invalide.
+    pub fn checkpoints(&self) -> Result<Vec<CheckpointInfo>, CacheError> {
+        let store = self.checkpoint_store()?;
+        let mut checkpoints = Vec::new();
+        for id in store.ids()? {
+            let path = store.path_of(id);
+            let Some(description) = format::describe(&path)? else {
+                continue;
+            };
+            checkpoints.push(CheckpointInfo {
+                id,
+                path,
+                created: description.created,
+                entries: description.entries,
+            });
+        }
+        Ok(checkpoints)
+    }
+}
@@ -0,0 +1,73 @@
+//! Module définissant la source de temps utilisée par le cache.
+//!
+//! Toutes les fonctionnalités dépendant du temps (durées de vie, âges des
+//! entrées...) lisent l'heure via le trait `Clock`. En production le cache
+//! utilise `SystemClock` ; les tests peuvent lui substituer une `ManualClock`
+//! qu'ils font avancer explicitement.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::clock::{Clock, ManualClock};
+//! use std::time::Duration;
+//!
+//! let clock = ManualClock::new();
+//! let debut = clock.now();
+//! clock.advance(Duration::from_secs(5));
+//! assert_eq!(clock.now() - debut, Duration::from_secs(5));
+//! ```
+
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Source de temps monotone.
+pub trait Clock: Debug + Send + Sync {
+    /// Retourne l'instant courant.
+    fn now(&self) -> Instant;
+}
+
+/// Horloge reposant sur `Instant::now`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Horloge contrôlée manuellement, destinée aux tests.
+///
+/// Les clones partagent le même instant courant.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Crée une horloge arrêtée à l'instant présent.
+    pub fn new() -> Self {
+        ManualClock {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Fait avancer l'horloge de la durée donnée.
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        *now += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,419 @@
+//! Module implémentant la politique de remplacement CLOCK-Pro.
+//!
+//! CLOCK-Pro (Jiang, Chen et Zhang, 2005) approche LIRS avec le coût d'une
+//! horloge. Les pages résidentes sont chaudes ou froides ; une page froide
+//! évincée laisse une trace non résidente, dite « en test ». Une page
+//! redemandée pendant sa période de test revient directement chaude et
+//! agrandit la part du cache réservée aux pages froides, tandis qu'une trace
+//! expirée la réduit. Un parcours unique de nombreuses pages (lecture
+//! séquentielle d'un fichier) ne touche ainsi que les pages froides, sans
+//! chasser les pages chaudes.
+//!
+//! Toutes les pages, résidentes ou non, forment un anneau parcouru par trois
+//! aiguilles : l'aiguille froide évince, l'aiguille chaude refroidit les
+//! pages chaudes non référencées et l'aiguille de test retire les traces.
+//! Le nombre de traces est borné par la capacité.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::clock_pro::{ClockProCache, PageStatus};
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = ClockProCache::new(2);
+//! cache.put("a", 1);
+//! cache.put("b", 2);
+//! cache.put("c", 3); // évince "a", dont seule la trace reste
+//! assert_eq!(cache.status(&"a"), Some(PageStatus::Test));
+//! assert_eq!(cache.peek(&"a"), None);
+//!
+//! // Redemandée pendant son test, la page revient directement chaude
+//! cache.put("a", 1);
+//! assert_eq!(cache.status(&"a"), Some(PageStatus::Hot));
+//! assert_eq!(cache.get(&"a"), Some(&1));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::messages;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// État d'une page suivie par `ClockProCache`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageStatus {
+    /// Page résidente fréquemment utilisée
+    Hot,
+    /// Page résidente candidate à l'éviction
+    Cold,
+    /// Trace non résidente d'une page froide évincée récemment
+    Test,
+}
+
+/// Page de l'anneau, chaînée à ses voisines par indices.
+#[derive(Debug)]
+struct Page<K, V> {
+    key: K,
+    value: Option<V>,
+    status: PageStatus,
+    referenced: bool,
+    prev: usize,
+    next: usize,
+}
+
+/// Cache appliquant la politique CLOCK-Pro.
+#[derive(Debug)]
+pub struct ClockProCache<K, V> {
+    capacity: usize,
+    pages: Vec<Option<Page<K, V>>>,
+    free: Vec<usize>,
+    index: HashMap<K, usize>,
+    hand_hot: Option<usize>,
+    hand_cold: Option<usize>,
+    hand_test: Option<usize>,
+    hot: usize,
+    cold: usize,
+    test: usize,
+    cold_target: usize,
+}
+
+impl<K, V> ClockProCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Crée un cache de la capacité donnée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la capacité est 0.
+    pub fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Crée un cache de la capacité donnée, sans paniquer.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la capacité est 0.
+    pub fn try_new(capacity: usize) -> Result<Self, CacheError> {
+        if capacity == 0 {
+            return Err(CacheError::CapacityError(messages::ZERO_CAPACITY.to_string()));
+        }
+        Ok(ClockProCache {
+            capacity,
+            pages: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            index: HashMap::with_capacity(capacity),
+            hand_hot: None,
+            hand_cold: None,
+            hand_test: None,
+            hot: 0,
+            cold: 0,
+            test: 0,
+            cold_target: capacity,
+        })
+    }
+
+    /// Retourne l'état de la page associée à la clé, si elle est suivie.
+    pub fn status(&self, key: &K) -> Option<PageStatus> {
+        self.index.get(key).map(|&idx| self.page(idx).status)
+    }
+
+    /// Retourne le nombre de pages chaudes.
+    pub fn hot_count(&self) -> usize {
+        self.hot
+    }
+
+    /// Retourne le nombre de pages froides résidentes.
+    pub fn cold_count(&self) -> usize {
+        self.cold
+    }
+
+    /// Retourne le nombre de traces de pages non résidentes.
+    pub fn test_count(&self) -> usize {
+        self.test
+    }
+
+    /// Retourne la part du cache actuellement visée pour les pages froides.
+    pub fn cold_target(&self) -> usize {
+        self.cold_target
+    }
+
+    fn page(&self, idx: usize) -> &Page<K, V> {
+        self.pages[idx].as_ref().expect("page chaînée absente")
+    }
+
+    fn page_mut(&mut self, idx: usize) -> &mut Page<K, V> {
+        self.pages[idx].as_mut().expect("page chaînée absente")
+    }
+
+    /// Insère une nouvelle page juste derrière l'aiguille chaude, qui ne la
+    /// visitera qu'en dernier.
+    fn link(&mut self, key: K, value: V, status: PageStatus) {
+        let page = Page { key: key.clone(), value: Some(value), status, referenced: false, prev: 0, next: 0 };
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.pages[idx] = Some(page);
+                idx
+            }
+            None => {
+                self.pages.push(Some(page));
+                self.pages.len() - 1
+            }
+        };
+        match self.hand_hot {
+            Some(head) => {
+                let prev = self.page(head).prev;
+                self.page_mut(prev).next = idx;
+                self.page_mut(head).prev = idx;
+                let page = self.page_mut(idx);
+                page.prev = prev;
+                page.next = head;
+            }
+            None => {
+                let page = self.page_mut(idx);
+                page.prev = idx;
+                page.next = idx;
+                self.hand_hot = Some(idx);
+                self.hand_cold = Some(idx);
+                self.hand_test = Some(idx);
+            }
+        }
+        self.index.insert(key, idx);
+    }
+
+    /// Retire une page de l'anneau ; les aiguilles qui la désignaient passent
+    /// à la suivante.
+    fn unlink(&mut self, idx: usize) -> Page<K, V> {
+        let page = self.pages[idx].take().expect("page chaînée absente");
+        self.free.push(idx);
+        self.index.remove(&page.key);
+        if page.next == idx {
+            self.hand_hot = None;
+            self.hand_cold = None;
+            self.hand_test = None;
+            return page;
+        }
+        self.page_mut(page.prev).next = page.next;
+        self.page_mut(page.next).prev = page.prev;
+        for hand in [&mut self.hand_hot, &mut self.hand_cold, &mut self.hand_test] {
+            if *hand == Some(idx) {
+                *hand = Some(page.next);
+            }
+        }
+        page
+    }
+
+    /// Fait de la place pour une nouvelle page résidente.
+    ///
+    /// Les aiguilles avancent pas à pas, sans jamais s'appeler l'une
+    /// l'autre. Chaque boucle est bornée : deux tours d'anneau suffisent à
+    /// chaque aiguille pour trouver une page à traiter.
+    fn make_room(&mut self) {
+        if self.hot + self.cold < self.capacity {
+            return;
+        }
+        if self.index.len() < 2 {
+            // Anneau d'une seule page : les trois aiguilles la désignent
+            if let Some(idx) = self.hand_cold.filter(|&idx| self.page(idx).value.is_some()) {
+                self.retire(idx);
+            }
+            self.trim_tests();
+            return;
+        }
+        for _ in 0..self.ring_bound() {
+            if self.hot + self.cold < self.capacity {
+                return;
+            }
+            self.step_cold();
+            self.trim_tests();
+            self.cool_hot_pages();
+        }
+        // Filet de sécurité : la page sous l'aiguille froide est évincée
+        if self.hot + self.cold >= self.capacity {
+            if let Some(idx) = self.hand_cold {
+                self.retire(idx);
+                self.hand_cold = Some(self.page(idx).next);
+                self.trim_tests();
+            }
+        }
+    }
+
+    /// Nombre maximal de pas d'une aiguille pour traiter une page.
+    fn ring_bound(&self) -> usize {
+        2 * self.index.len() + 1
+    }
+
+    /// Évince une page résidente en ne gardant que sa trace.
+    fn retire(&mut self, idx: usize) {
+        let page = self.page_mut(idx);
+        let status = std::mem::replace(&mut page.status, PageStatus::Test);
+        page.value = None;
+        page.referenced = false;
+        match status {
+            PageStatus::Hot => self.hot -= 1,
+            PageStatus::Cold => self.cold -= 1,
+            PageStatus::Test => return,
+        }
+        self.test += 1;
+    }
+
+    /// Retire des traces tant qu'elles dépassent la capacité.
+    fn trim_tests(&mut self) {
+        for _ in 0..self.ring_bound() {
+            if self.test <= self.capacity {
+                return;
+            }
+            self.step_test();
+        }
+    }
+
+    /// Refroidit des pages chaudes tant qu'elles dépassent leur part.
+    fn cool_hot_pages(&mut self) {
+        for _ in 0..self.ring_bound() {
+            if self.hot <= self.capacity - self.cold_target {
+                return;
+            }
+            self.step_hot();
+        }
+    }
+
+    /// L'aiguille froide promeut la page froide référencée, ou l'évince en
+    /// ne gardant que sa trace, puis avance d'une page.
+    fn step_cold(&mut self) {
+        let Some(idx) = self.hand_cold else {
+            return;
+        };
+        let page = self.page_mut(idx);
+        if page.status == PageStatus::Cold {
+            if page.referenced {
+                page.status = PageStatus::Hot;
+                page.referenced = false;
+                self.cold -= 1;
+                self.hot += 1;
+            } else {
+                self.retire(idx);
+            }
+        }
+        self.hand_cold = Some(self.page(idx).next);
+    }
+
+    /// L'aiguille chaude efface la référence d'une page chaude, ou la
+    /// refroidit si elle n'a pas été référencée depuis son dernier passage,
+    /// puis avance d'une page.
+    fn step_hot(&mut self) {
+        let Some(idx) = self.hand_hot else {
+            return;
+        };
+        let page = self.page_mut(idx);
+        if page.status == PageStatus::Hot {
+            if page.referenced {
+                page.referenced = false;
+            } else {
+                page.status = PageStatus::Cold;
+                self.hot -= 1;
+                self.cold += 1;
+            }
+        }
+        self.hand_hot = Some(self.page(idx).next);
+    }
+
+    /// L'aiguille de test retire une trace dont la période est terminée, ce
+    /// qui réduit la part réservée aux pages froides, ou avance d'une page.
+    fn step_test(&mut self) {
+        let Some(idx) = self.hand_test else {
+            return;
+        };
+        if self.page(idx).status == PageStatus::Test {
+            // `unlink` avance les aiguilles qui désignaient la trace
+            self.unlink(idx);
+            self.test -= 1;
+            if self.cold_target > 1 {
+                self.cold_target -= 1;
+            }
+        } else {
+            self.hand_test = Some(self.page(idx).next);
+        }
+    }
+}
+
+impl<K, V> CacheRead<K, V> for ClockProCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        let idx = *self.index.get(key)?;
+        self.page(idx).value.as_ref()
+    }
+
+    fn len(&self) -> usize {
+        self.hot + self.cold
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<K, V> CacheTrait<K, V> for ClockProCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let idx = *self.index.get(key)?;
+        let page = self.page_mut(idx);
+        if page.value.is_some() {
+            page.referenced = true;
+        }
+        page.value.as_ref()
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let Some(&idx) = self.index.get(&key) else {
+            self.make_room();
+            self.link(key, value, PageStatus::Cold);
+            self.cold += 1;
+            return;
+        };
+        let page = self.page_mut(idx);
+        if page.value.is_some() {
+            page.value = Some(value);
+            page.referenced = true;
+            return;
+        }
+
+        // Redemandée pendant sa période de test : la page revient chaude
+        if self.cold_target < self.capacity {
+            self.cold_target += 1;
+        }
+        self.unlink(idx);
+        self.test -= 1;
+        self.make_room();
+        self.link(key, value, PageStatus::Hot);
+        self.hot += 1;
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let idx = *self.index.get(key)?;
+        let page = self.unlink(idx);
+        match page.status {
+            PageStatus::Hot => self.hot -= 1,
+            PageStatus::Cold => self.cold -= 1,
+            PageStatus::Test => self.test -= 1,
+        }
+        page.value
+    }
+
+    fn clear(&mut self) {
+        self.pages.clear();
+        self.free.clear();
+        self.index.clear();
+        self.hand_hot = None;
+        self.hand_cold = None;
+        self.hand_test = None;
+        self.hot = 0;
+        self.cold = 0;
+        self.test = 0;
+        self.cold_target = self.capacity;
+    }
+}
@@ -0,0 +1,236 @@
+//! Module fournissant un client répartissant les clés sur plusieurs caches.
+//!
+//! `ClusterClient` place chaque nœud sur un anneau de hachage cohérent
+//! (avec plusieurs points virtuels par nœud) : ajouter ou retirer un nœud ne
+//! déplace qu'une fraction des clés. Chaque clé est écrite sur les
+//! `replication` premiers nœuds distincts rencontrés sur l'anneau et lue sur
+//! le premier d'entre eux qui la détient. Les positions sur l'anneau ne
+//! dépendent ni du processus ni de la version de Rust : deux clients
+//! configurés avec les mêmes nœuds répartissent les clés de la même façon.
+//!
+//! Les nœuds sont de simples `CacheTrait` : caches locaux, ou clients d'un
+//! cache distant implémentant le trait. Le code appelant manipule le client
+//! comme n'importe quel autre cache, sans connaître la topologie.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::cluster::ClusterClient;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cluster = ClusterClient::new()
+//!     .with_replication(2)
+//!     .add_node("a", Box::new(Cache::new(100)))
+//!     .add_node("b", Box::new(Cache::new(100)))
+//!     .add_node("c", Box::new(Cache::new(100)));
+//!
+//! cluster.put("utilisateur:42", "Alice");
+//! assert_eq!(cluster.owners(&"utilisateur:42").len(), 2);
+//!
+//! // La clé reste lisible après la perte d'un de ses nœuds
+//! let proprietaire = cluster.owners(&"utilisateur:42")[0].to_string();
+//! cluster.remove_node(&proprietaire);
+//! assert_eq!(cluster.get(&"utilisateur:42"), Some(&"Alice"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use crate::lru::format::FnvHasher;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Nombre de points virtuels par défaut de chaque nœud sur l'anneau.
+const DEFAULT_VIRTUAL_NODES: usize = 64;
+
+/// Nœud du cluster : un nom stable et le cache associé.
+struct Node<K, V> {
+    name: String,
+    cache: Box<dyn CacheTrait<K, V>>,
+}
+
+/// Client répartissant les clés sur plusieurs caches par hachage cohérent.
+pub struct ClusterClient<K, V> {
+    nodes: Vec<Node<K, V>>,
+    ring: BTreeMap<u64, usize>,
+    replication: usize,
+    virtual_nodes: usize,
+}
+
+impl<K, V> Default for ClusterClient<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> ClusterClient<K, V> {
+    /// Crée un cluster vide, sans réplication.
+    pub fn new() -> Self {
+        ClusterClient {
+            nodes: Vec::new(),
+            ring: BTreeMap::new(),
+            replication: 1,
+            virtual_nodes: DEFAULT_VIRTUAL_NODES,
+        }
+    }
+
+    /// Définit le nombre de nœuds recevant chaque clé (au moins 1).
+    pub fn with_replication(mut self, replication: usize) -> Self {
+        self.replication = replication.max(1);
+        self
+    }
+
+    /// Définit le nombre de points virtuels de chaque nœud sur l'anneau
+    /// (au moins 1). Plus il est élevé, plus la répartition est homogène.
+    pub fn with_virtual_nodes(mut self, virtual_nodes: usize) -> Self {
+        self.virtual_nodes = virtual_nodes.max(1);
+        self.rebuild_ring();
+        self
+    }
+
+    /// Ajoute un nœud identifié par un nom stable. Un nœud portant déjà ce
+    /// nom est remplacé.
+    pub fn add_node<S: Into<String>>(mut self, name: S, cache: Box<dyn CacheTrait<K, V>>) -> Self {
+        let name = name.into();
+        self.nodes.retain(|node| node.name != name);
+        self.nodes.push(Node { name, cache });
+        self.rebuild_ring();
+        self
+    }
+
+    /// Retire le nœud portant ce nom et retourne son cache.
+    pub fn remove_node(&mut self, name: &str) -> Option<Box<dyn CacheTrait<K, V>>> {
+        let index = self.nodes.iter().position(|node| node.name == name)?;
+        let node = self.nodes.remove(index);
+        self.rebuild_ring();
+        Some(node.cache)
+    }
+
+    /// Retourne les noms des nœuds du cluster.
+    pub fn node_names(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().map(|node| node.name.as_str())
+    }
+
+    /// Retourne le cache du nœud portant ce nom.
+    pub fn node(&self, name: &str) -> Option<&dyn CacheTrait<K, V>> {
+        self.nodes
+            .iter()
+            .find(|node| node.name == name)
+            .map(|node| node.cache.as_ref())
+    }
+
+    fn rebuild_ring(&mut self) {
+        self.ring.clear();
+        for (index, node) in self.nodes.iter().enumerate() {
+            for replica in 0..self.virtual_nodes {
+                self.ring.insert(hash_of(&(&node.name, replica)), index);
+            }
+        }
+    }
+}
+
+impl<K, V> ClusterClient<K, V>
+where
+    K: Hash,
+{
+    /// Retourne les noms des nœuds responsables de la clé, par ordre de
+    /// préférence.
+    pub fn owners(&self, key: &K) -> Vec<&str> {
+        self.owner_indices(key)
+            .into_iter()
+            .map(|index| self.nodes[index].name.as_str())
+            .collect()
+    }
+
+    fn owner_indices(&self, key: &K) -> Vec<usize> {
+        let wanted = self.replication.min(self.nodes.len());
+        let point = hash_of(key);
+        let mut owners = Vec::with_capacity(wanted);
+        for &index in self.ring.range(point..).chain(self.ring.range(..point)).map(|(_, index)| index) {
+            if owners.len() == wanted {
+                break;
+            }
+            if !owners.contains(&index) {
+                owners.push(index);
+            }
+        }
+        owners
+    }
+}
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = FnvHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<K, V> std::fmt::Debug for ClusterClient<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ClusterClient")
+            .field("nodes", &self.nodes.iter().map(|node| &node.name).collect::<Vec<_>>())
+            .field("replication", &self.replication)
+            .field("virtual_nodes", &self.virtual_nodes)
+            .finish()
+    }
+}
+
+impl<K, V> CacheRead<K, V> for ClusterClient<K, V>
+where
+    K: Hash,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.owner_indices(key)
+            .into_iter()
+            .find_map(|index| self.nodes[index].cache.peek(key))
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.owner_indices(key)
+            .into_iter()
+            .any(|index| self.nodes[index].cache.contains(key))
+    }
+
+    /// Retourne le nombre total d'entrées stockées, réplicas compris.
+    fn len(&self) -> usize {
+        self.nodes.iter().map(|node| node.cache.len()).sum()
+    }
+
+    /// Retourne la somme des capacités des nœuds.
+    fn capacity(&self) -> usize {
+        self.nodes
+            .iter()
+            .fold(0, |total: usize, node| total.saturating_add(node.cache.capacity()))
+    }
+}
+
+impl<K, V> CacheTrait<K, V> for ClusterClient<K, V>
+where
+    K: Hash + Clone,
+    V: Clone,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let index = self
+            .owner_indices(key)
+            .into_iter()
+            .find(|&index| self.nodes[index].cache.contains(key))?;
+        self.nodes[index].cache.get(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        for index in self.owner_indices(&key) {
+            self.nodes[index].cache.put(key.clone(), value.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.owner_indices(key)
+            .into_iter()
+            .map(|index| self.nodes[index].cache.remove(key))
+            .fold(None, |first, removed| first.or(removed))
+    }
+
+    fn clear(&mut self) {
+        for node in &mut self.nodes {
+            node.cache.clear();
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Module implémentant le chargement coalescé du cache partagé.
+//!
+//! Lorsqu'une clé très demandée manque, tous les threads qui la lisent au
+//! même moment appellent la source ensemble : c'est l'effet de troupeau.
+//! `SyncCache::get_or_load` n'appelle le chargeur qu'une fois par clé
+//! absente : le premier thread charge la valeur, les suivants attendent son
+//! résultat. En cas d'échec, ils reçoivent `CacheError::LoadFailed` portant
+//! le message de l'erreur du chargeur, ou `messages::LOADER_PANICKED` si le
+//! chargeur a paniqué.
+//!
+//! # Vérification
+//!
+//! L'algorithme s'appuie sur les primitives du module `primitives` et peut
+//! donc être vérifié par `loom` :
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --test loom
+//! ```
+//!
+//! Pour des tests déterministes sans `loom`, `SyncCache::with_schedule_hook`
+//! installe une fonction appelée à chaque `SchedulePoint` : un test peut y
+//! bloquer un thread jusqu'à ce que les autres aient atteint un point donné,
+//! et reproduire ainsi exactement un entrelacement.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::sync::SyncCache;
+//!
+//! let cache = SyncCache::new(10);
+//! let appels = AtomicUsize::new(0);
+//! let chargeur = |cle: &u32| -> Result<u32, CacheError> {
+//!     appels.fetch_add(1, Ordering::SeqCst);
+//!     Ok(cle * 2)
+//! };
+//!
+//! assert_eq!(cache.get_or_load(21, &chargeur).unwrap(), 42);
+//! assert_eq!(cache.get_or_load(21, &chargeur).unwrap(), 42);
+//! assert_eq!(appels.load(Ordering::SeqCst), 1);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::loader::Loader;
+use crate::lru::primitives::{Arc, Condvar, Mutex, MutexGuard};
+use crate::lru::sync::SyncCache;
+use crate::messages;
+
+/// Point de l'algorithme de chargement signalé au crochet d'ordonnancement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchedulePoint {
+    /// Le thread va appeler le chargeur pour une clé absente
+    LoadStarted,
+    /// Le chargement est terminé et son résultat publié aux threads en
+    /// attente
+    LoadFinished,
+    /// Le thread va attendre le chargement mené par un autre thread
+    Waiting,
+}
+
+/// Crochet d'ordonnancement appelé à chaque `SchedulePoint`.
+#[derive(Clone)]
+pub(crate) struct ScheduleHook(std::sync::Arc<dyn Fn(SchedulePoint) + Send + Sync>);
+
+impl fmt::Debug for ScheduleHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ScheduleHook")
+    }
+}
+
+/// Chargements en cours, par clé.
+pub(crate) type Flights<K, V> = Arc<Mutex<HashMap<K, Arc<Flight<V>>>>>;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // Ces verrous ne protègent que l'attente : leur contenu reste cohérent
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Chargement en cours d'une clé, attendu par les autres threads.
+#[derive(Debug)]
+pub(crate) struct Flight<V> {
+    outcome: Mutex<Option<Result<V, String>>>,
+    done: Condvar,
+}
+
+impl<V: Clone> Flight<V> {
+    fn new() -> Self {
+        Flight {
+            outcome: Mutex::new(None),
+            done: Condvar::new(),
+        }
+    }
+
+    fn complete(&self, outcome: Result<V, String>) {
+        *lock(&self.outcome) = Some(outcome);
+        self.done.notify_all();
+    }
+
+    fn wait(&self) -> Result<V, String> {
+        let mut outcome = lock(&self.outcome);
+        loop {
+            if let Some(outcome) = outcome.as_ref() {
+                return outcome.clone();
+            }
+            outcome = self.done.wait(outcome).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+}
+
+/// Publie un échec si le chargeur panique, pour ne pas bloquer les threads
+/// en attente.
+struct Leader<'a, K: Hash + Eq + Clone, V: Clone> {
+    cache: &'a SyncCache<K, V>,
+    key: &'a K,
+    flight: &'a Flight<V>,
+    finished: bool,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Leader<'_, K, V> {
+    /// Retire le chargement des chargements en cours puis publie son
+    /// résultat.
+    fn finish(&mut self, outcome: Result<V, String>) {
+        lock(&self.cache.flights).remove(self.key);
+        self.flight.complete(outcome);
+        self.finished = true;
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Drop for Leader<'_, K, V> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.finish(Err(messages::LOADER_PANICKED.to_string()));
+        }
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Installe un crochet appelé à chaque `SchedulePoint` de
+    /// `SyncCache::get_or_load`, pour ordonner les threads d'un test.
+    pub fn with_schedule_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(SchedulePoint) + Send + Sync + 'static,
+    {
+        self.schedule_hook = Some(ScheduleHook(std::sync::Arc::new(hook)));
+        self
+    }
+
+    fn schedule(&self, point: SchedulePoint) {
+        if let Some(hook) = self.schedule_hook.as_ref() {
+            (hook.0)(point);
+        }
+    }
+
+    /// Récupère une copie de la valeur associée à la clé ou la charge avec
+    /// `loader`. Les appels concurrents pour une même clé absente
+    /// n'appellent le chargeur qu'une fois et partagent son résultat.
+    ///
+    /// # Errors
+    ///
+    /// Retourne l'erreur du chargeur au thread qui l'a appelé, et
+    /// `CacheError::LoadFailed` aux threads qui attendaient ce chargement. Retourne `CacheError::Poisoned` selon la politique
+    /// d'empoisonnement.
+    pub fn get_or_load<L>(&self, key: K, loader: &L) -> Result<V, CacheError>
+    where
+        L: Loader<K, V> + ?Sized,
+    {
+        if let Some(value) = self.get(&key)? {
+            return Ok(value);
+        }
+
+        let (flight, leads) = {
+            let mut flights = lock(&self.flights);
+            match flights.get(&key) {
+                Some(flight) => (Arc::clone(flight), false),
+                None => {
+                    let flight = Arc::new(Flight::new());
+                    flights.insert(key.clone(), Arc::clone(&flight));
+                    (flight, true)
+                }
+            }
+        };
+
+        if !leads {
+            self.schedule(SchedulePoint::Waiting);
+            return flight.wait().map_err(|reason| CacheError::LoadFailed { reason });
+        }
+
+        let mut leader = Leader {
+            cache: self,
+            key: &key,
+            flight: &flight,
+            finished: false,
+        };
+        // Un chargement terminé juste avant l'inscription a déjà rempli le
+        // cache : la valeur est insérée avant que le chargement soit retiré
+        if let Some(value) = self.get(&key)? {
+            leader.finish(Ok(value.clone()));
+            return Ok(value);
+        }
+
+        self.schedule(SchedulePoint::LoadStarted);
+        let result = loader.load(&key);
+        if let Ok(value) = &result {
+            self.put(key.clone(), value.clone())?;
+        }
+        leader.finish(result.as_ref().map(V::clone).map_err(CacheError::to_string));
+        drop(leader);
+        self.schedule(SchedulePoint::LoadFinished);
+        result
+    }
+}
@@ -0,0 +1,202 @@
+//! Module fournissant des fichiers de référence pour tester la compatibilité
+//! des formats de persistance.
+//!
+//! Un changement silencieux du format écrit par `Cache::persist` rendrait
+//! illisibles les fichiers écrits par les versions précédentes de la
+//! bibliothèque. Pour s'en prémunir, une application génère avec
+//! `write_fixture` (ou `write_fixtures`) un fichier de référence par version
+//! de format, le conserve dans son dépôt, puis vérifie avec `verify_fixture`
+//! (ou `verify_fixtures`), à chaque montée de version de la bibliothèque, que
+//! ces fichiers se chargent toujours à l'identique. La bibliothèque conserve
+//! elle-même les siens dans `tests/fixtures`.
+//!
+//! Le contenu de référence (`fixture_entries`) couvre les cas délicats du
+//! format texte : caractères accentués, espaces, signe `=`, valeur vide. La
+//! version 0 désigne le format sans en-tête des toutes premières versions.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::compat::{verify_fixtures, write_fixtures};
+//!
+//! let dir = std::env::temp_dir().join(format!("lru_compat_doc_{}", std::process::id()));
+//! std::fs::create_dir_all(&dir).unwrap();
+//!
+//! // Une fois, avec la version de la bibliothèque à protéger
+//! write_fixtures(&dir).unwrap();
+//!
+//! // Ensuite, dans les tests de chaque nouvelle version
+//! assert_eq!(verify_fixtures(&dir).unwrap(), 2);
+//! # std::fs::remove_dir_all(&dir).unwrap();
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::format::{self, FORMAT_VERSION};
+use crate::lru::traits::CacheTrait;
+use crate::messages;
+
+/// Versions de format pour lesquelles un fichier de référence peut être
+/// écrit ; 0 désigne le format sans en-tête.
+pub const FIXTURE_VERSIONS: [u32; 2] = [0, FORMAT_VERSION];
+
+/// Capacité du cache sauvegardé dans les fichiers de référence.
+pub const FIXTURE_CAPACITY: usize = 16;
+
+/// Extension des fichiers écrits par `write_fixtures`.
+pub const FIXTURE_EXTENSION: &str = "lru";
+
+/// Retourne le contenu de référence, de l'entrée la moins à la plus
+/// récemment utilisée.
+pub fn fixture_entries() -> Vec<(String, String)> {
+    [
+        ("alpha", "1"),
+        ("clé accentuée", "valeur é à ü"),
+        ("espace interne", "a b c"),
+        ("égal=signe", "x=y"),
+        ("valeur vide", ""),
+        ("unicode ✓", "日本語"),
+        ("-42", "3.14"),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_string(), value.to_string()))
+    .collect()
+}
+
+/// Retourne le nom du fichier de référence d'une version de format.
+pub fn fixture_file_name(version: u32) -> String {
+    format!("format_v{}.{}", version, FIXTURE_EXTENSION)
+}
+
+/// Écrit le fichier de référence de la version de format indiquée.
+///
+/// La version courante est écrite par `Cache::persist` lui-même ; les
+/// versions précédentes sont reproduites à l'octet près.
+///
+/// # Errors
+///
+/// Retourne `CacheError::ParseError` si la version n'est pas dans
+/// `FIXTURE_VERSIONS`, et `CacheError::IoError` si le fichier ne peut pas
+/// être écrit.
+pub fn write_fixture<P: AsRef<Path>>(version: u32, path: P) -> Result<(), CacheError> {
+    match version {
+        0 => {
+            let body: String = fixture_entries()
+                .iter()
+                .map(|(key, value)| format!("{}\t{}\n", key, value))
+                .collect();
+            fs::write(path, body)?;
+            Ok(())
+        }
+        FORMAT_VERSION => {
+            let mut cache = Cache::new(FIXTURE_CAPACITY);
+            for (key, value) in fixture_entries() {
+                cache.put(key, value);
+            }
+            cache.persist(path)
+        }
+        _ => Err(CacheError::ParseError(format!(
+            "{}: version={}",
+            messages::UNSUPPORTED_FILE_FORMAT,
+            version
+        ))),
+    }
+}
+
+/// Écrit dans le dossier un fichier de référence par version de
+/// `FIXTURE_VERSIONS` et retourne leurs chemins.
+///
+/// # Errors
+///
+/// Retourne les mêmes erreurs que `write_fixture`.
+pub fn write_fixtures<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, CacheError> {
+    FIXTURE_VERSIONS
+        .iter()
+        .map(|&version| {
+            let path = dir.as_ref().join(fixture_file_name(version));
+            write_fixture(version, &path).map(|()| path)
+        })
+        .collect()
+}
+
+/// Vérifie qu'un fichier de référence, écrit par n'importe quelle version de
+/// la bibliothèque, se charge avec exactement le contenu de référence, dans
+/// le même ordre d'utilisation.
+///
+/// # Errors
+///
+/// Retourne `CacheError::IoError` si le fichier n'existe pas ou ne peut pas
+/// être lu, et `CacheError::Corrupted` si son en-tête ou ses entrées ne
+/// peuvent pas être lus, ou si une entrée diffère du contenu de référence
+/// (la ligne indiquée est celle de la première différence).
+pub fn verify_fixture<P: AsRef<Path>>(path: P) -> Result<(), CacheError> {
+    let path = path.as_ref();
+    let header = format::describe(path)?;
+    let mismatch = |index: usize, (key, value): &(String, String)| CacheError::Corrupted {
+        path: Some(path.to_path_buf()),
+        line: index + 1 + usize::from(header.is_some()),
+        reason: format!("{}: {}\t{}", messages::FIXTURE_MISMATCH, key, value),
+    };
+
+    let expected = fixture_entries();
+    if let Some(description) = &header {
+        if description.entries != expected.len() || description.capacity != FIXTURE_CAPACITY {
+            return Err(CacheError::Corrupted {
+                path: Some(path.to_path_buf()),
+                line: 1,
+                reason: format!("{}: {}", messages::INVALID_FILE_HEADER, description),
+            });
+        }
+    }
+
+    let Some(loaded) = Cache::<String, String>::read_file_entries(path)? else {
+        return Err(CacheError::IoError(io::Error::from(io::ErrorKind::NotFound)));
+    };
+    for (index, entry) in expected.iter().enumerate() {
+        if loaded.get(index) != Some(entry) {
+            return Err(mismatch(index, entry));
+        }
+    }
+    if loaded.len() > expected.len() {
+        let (key, value) = &loaded[expected.len()];
+        return Err(CacheError::Corrupted {
+            path: Some(path.to_path_buf()),
+            line: expected.len() + 1 + usize::from(header.is_some()),
+            reason: format!("{}: {}\t{}", messages::INVALID_LINE_FORMAT, key, value),
+        });
+    }
+
+    // Le chargement complet doit restituer le même ordre d'utilisation
+    let cache = Cache::<String, String>::new_persistent(FIXTURE_CAPACITY, path)?;
+    for (index, (loaded, entry)) in cache.iter().zip(&expected).enumerate() {
+        if (loaded.0, loaded.1) != (&entry.0, &entry.1) {
+            return Err(mismatch(index, entry));
+        }
+    }
+    Ok(())
+}
+
+/// Vérifie tous les fichiers de référence (extension `FIXTURE_EXTENSION`) du
+/// dossier et retourne leur nombre.
+///
+/// # Errors
+///
+/// Retourne `CacheError::IoError` si le dossier ne peut pas être lu, et la
+/// première erreur de `verify_fixture`.
+pub fn verify_fixtures<P: AsRef<Path>>(dir: P) -> Result<usize, CacheError> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == FIXTURE_EXTENSION) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    for path in &paths {
+        verify_fixture(path)?;
+    }
+    Ok(paths.len())
+}
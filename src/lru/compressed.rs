@@ -0,0 +1,226 @@
+//! Module implémentant la compression transparente des valeurs.
+//!
+//! Disponible avec la fonctionnalité `compression`. `CompressedCache` stocke
+//! des valeurs binaires ou textuelles compressées (lz4 ou zstd) à l'insertion
+//! et les décompresse à la lecture : le cache échange du temps de calcul
+//! contre une capacité effective plusieurs fois supérieure lorsqu'il est borné
+//! par un budget de poids. Les petites valeurs, et celles que la compression
+//! n'améliore pas, sont stockées telles quelles.
+//!
+//! Le poids d'une entrée est sa taille stockée, compressée le cas échéant.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::compressed::{CompressedCache, Compression};
+//!
+//! let builder = Cache::builder().capacity(1000).max_weight(64 * 1024);
+//! let mut cache = CompressedCache::new(builder, Compression::Lz4).unwrap();
+//!
+//! let page = "<p>Bonjour</p>".repeat(500);
+//! cache.put("accueil", page.as_bytes()).unwrap();
+//! assert_eq!(cache.get_string(&"accueil").unwrap(), Some(page));
+//! assert!(cache.stats().ratio() > 3.0);
+//! ```
+
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::{Cache, CacheBuilder};
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Taille en dessous de laquelle une valeur n'est pas compressée.
+pub const DEFAULT_MIN_SIZE: usize = 64;
+
+/// Taille de l'en-tête précédant chaque valeur stockée : le format (1 octet)
+/// puis la taille d'origine (4 octets, petit-boutiste).
+const HEADER_LEN: usize = 5;
+
+const TAG_RAW: u8 = 0;
+const TAG_LZ4: u8 = 1;
+const TAG_ZSTD: u8 = 2;
+
+/// Algorithme de compression des valeurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// lz4 : compression et décompression très rapides
+    Lz4,
+    /// zstd au niveau indiqué (1 à 22) : meilleur taux, plus coûteux
+    Zstd(i32),
+}
+
+/// Statistiques de compression des entrées présentes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// Nombre d'entrées
+    pub entries: usize,
+    /// Nombre d'entrées stockées compressées
+    pub compressed_entries: usize,
+    /// Taille totale des valeurs d'origine, en octets
+    pub raw_bytes: usize,
+    /// Taille totale des valeurs stockées, en-têtes compris, en octets
+    pub stored_bytes: usize,
+}
+
+impl CompressionStats {
+    /// Retourne le taux de compression (taille d'origine / taille stockée),
+    /// ou 1 si le cache est vide.
+    pub fn ratio(&self) -> f64 {
+        if self.stored_bytes == 0 {
+            return 1.0;
+        }
+        self.raw_bytes as f64 / self.stored_bytes as f64
+    }
+}
+
+/// Cache stockant ses valeurs compressées.
+#[derive(Debug)]
+pub struct CompressedCache<K>
+where
+    K: Hash + Eq,
+{
+    cache: Cache<K, Box<[u8]>>,
+    compression: Compression,
+    min_size: usize,
+}
+
+impl<K> CompressedCache<K>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Construit le cache configuré par le constructeur donné (capacité,
+    /// budget de poids, durée de vie...). Son peseur est remplacé par la
+    /// taille stockée des valeurs.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `CacheBuilder::build`.
+    pub fn new(builder: CacheBuilder<K, Box<[u8]>>, compression: Compression) -> Result<Self, CacheError> {
+        Ok(CompressedCache {
+            cache: builder.weigher(|stored| stored.len()).build()?,
+            compression,
+            min_size: DEFAULT_MIN_SIZE,
+        })
+    }
+
+    /// Définit la taille en dessous de laquelle les valeurs sont stockées
+    /// sans compression.
+    pub fn with_min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Compresse et insère une valeur.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Serialization` si la valeur dépasse 4 Gio ou si
+    /// la compression échoue, et les erreurs de `Cache::try_put`.
+    pub fn put(&mut self, key: K, value: &[u8]) -> Result<(), CacheError> {
+        let stored = self.encode(value)?;
+        self.cache.try_put(key, stored)
+    }
+
+    /// Retourne une copie décompressée de la valeur associée à la clé.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Serialization` si la valeur stockée ne peut pas
+    /// être décompressée.
+    pub fn get(&mut self, key: &K) -> Result<Option<Vec<u8>>, CacheError> {
+        self.cache.get(key).map(|stored| decode(stored)).transpose()
+    }
+
+    /// Retourne la valeur associée à la clé sous forme de texte.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Serialization` si la valeur ne peut pas être
+    /// décompressée ou n'est pas de l'UTF-8 valide.
+    pub fn get_string(&mut self, key: &K) -> Result<Option<String>, CacheError> {
+        self.get(key)?
+            .map(|bytes| String::from_utf8(bytes).map_err(|err| CacheError::Serialization(err.to_string())))
+            .transpose()
+    }
+
+    /// Supprime l'entrée et indique si elle existait.
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.cache.remove(key).is_some()
+    }
+
+    /// Vérifie si la clé est présente, sans modifier l'ordre d'utilisation.
+    pub fn contains(&self, key: &K) -> bool {
+        self.cache.contains(key)
+    }
+
+    /// Retourne le nombre d'entrées du cache.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Vérifie si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Calcule les statistiques de compression des entrées présentes.
+    pub fn stats(&self) -> CompressionStats {
+        self.cache.iter().fold(CompressionStats::default(), |mut stats, (_, stored)| {
+            stats.entries += 1;
+            stats.raw_bytes += raw_len(stored);
+            stats.stored_bytes += stored.len();
+            if stored[0] != TAG_RAW {
+                stats.compressed_entries += 1;
+            }
+            stats
+        })
+    }
+
+    /// Retourne le cache sous-jacent, dont les valeurs sont stockées avec
+    /// leur en-tête.
+    pub fn inner(&self) -> &Cache<K, Box<[u8]>> {
+        &self.cache
+    }
+
+    fn encode(&self, value: &[u8]) -> Result<Box<[u8]>, CacheError> {
+        let len = u32::try_from(value.len()).map_err(|err| CacheError::Serialization(err.to_string()))?;
+        let compressed = if value.len() < self.min_size {
+            None
+        } else {
+            Some(match self.compression {
+                Compression::Lz4 => (TAG_LZ4, lz4_flex::block::compress(value)),
+                Compression::Zstd(level) => (
+                    TAG_ZSTD,
+                    zstd::bulk::compress(value, level).map_err(|err| CacheError::Serialization(err.to_string()))?,
+                ),
+            })
+        };
+        // Une valeur que la compression n'améliore pas est stockée telle quelle
+        let (tag, payload) = match compressed {
+            Some((tag, payload)) if payload.len() < value.len() => (tag, payload),
+            _ => (TAG_RAW, value.to_vec()),
+        };
+
+        let mut stored = Vec::with_capacity(HEADER_LEN + payload.len());
+        stored.push(tag);
+        stored.extend_from_slice(&len.to_le_bytes());
+        stored.extend_from_slice(&payload);
+        Ok(stored.into_boxed_slice())
+    }
+}
+
+fn raw_len(stored: &[u8]) -> usize {
+    let mut len = [0; 4];
+    len.copy_from_slice(&stored[1..HEADER_LEN]);
+    u32::from_le_bytes(len) as usize
+}
+
+fn decode(stored: &[u8]) -> Result<Vec<u8>, CacheError> {
+    let len = raw_len(stored);
+    let payload = &stored[HEADER_LEN..];
+    match stored[0] {
+        TAG_LZ4 => lz4_flex::block::decompress(payload, len).map_err(|err| CacheError::Serialization(err.to_string())),
+        TAG_ZSTD => zstd::bulk::decompress(payload, len).map_err(|err| CacheError::Serialization(err.to_string())),
+        _ => Ok(payload.to_vec()),
+    }
+}
@@ -0,0 +1,228 @@
+//! Module chargeant la configuration d'un cache depuis un fichier.
+//!
+//! Disponible avec la fonctionnalité `config`. `CacheConfig` décrit un cache
+//! (capacité, politique d'éviction, durée de vie, fichier de persistance,
+//! intervalle de sauvegarde...) dans un fichier TOML ou YAML, afin qu'un
+//! déploiement puisse ajuster le cache sans recompiler. Le format est choisi
+//! d'après l'extension du fichier. Les durées s'expriment en secondes
+//! (entier) ou sous forme lisible (`"500ms"`, `"2h30m"`, voir
+//! `duration::parse_duration`).
+//!
+//! ```toml
+//! capacity = 10000
+//! policy = "sampled"
+//! sample_size = 8
+//! ttl = "5m"
+//! update_recency = "keep"
+//! persistence_path = "cache/cache_data.txt"
+//! flush_interval = 60
+//! ```
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::time::Duration;
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::config::CacheConfig;
+//!
+//! let config = CacheConfig::from_toml("capacity = 100\nttl = 30\nrecord_stats = true").unwrap();
+//! assert_eq!(config.ttl, Some(Duration::from_secs(30)));
+//!
+//! let cache: Cache<String, String> = Cache::from_config(&config).unwrap();
+//! assert_eq!(cache.capacity(), 100);
+//! ```
+
+use std::fmt::Display;
+use std::fs;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use serde::{Deserialize, Deserializer};
+use crate::error::CacheError;
+use crate::messages;
+use crate::lru::{Cache, CacheBuilder};
+use crate::lru::duplicate::UpdateRecency;
+use crate::lru::duration::parse_duration;
+
+/// Politique d'éviction choisie par la configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyKind {
+    /// Ordre d'utilisation exact
+    #[default]
+    Lru,
+    /// Éviction échantillonnée (`CacheBuilder::sampled_eviction`)
+    Sampled,
+}
+
+/// Taille d'échantillon par défaut de la politique `sampled`.
+pub const DEFAULT_SAMPLE_SIZE: usize = 5;
+
+/// Description d'un cache lue depuis un fichier de configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Nombre maximal d'entrées
+    pub capacity: usize,
+    /// Politique d'éviction
+    #[serde(default)]
+    pub policy: PolicyKind,
+    /// Taille de l'échantillon de la politique `sampled`
+    #[serde(default)]
+    pub sample_size: Option<usize>,
+    /// Durée de vie par défaut des entrées
+    #[serde(default, deserialize_with = "seconds")]
+    pub ttl: Option<Duration>,
+    /// Seuil bas de l'éviction par lots
+    #[serde(default)]
+    pub low_watermark: Option<usize>,
+    /// Budget de poids total
+    #[serde(default)]
+    pub max_weight: Option<usize>,
+    /// Effet d'une mise à jour sur l'ordre d'utilisation
+    #[serde(default)]
+    pub update_recency: UpdateRecency,
+    /// Active les statistiques
+    #[serde(default)]
+    pub record_stats: bool,
+    /// Fichier de persistance
+    #[serde(default)]
+    pub persistence_path: Option<PathBuf>,
+    /// Intervalle entre deux sauvegardes du fichier de persistance
+    #[serde(default, deserialize_with = "seconds")]
+    pub flush_interval: Option<Duration>,
+}
+
+/// Durée écrite dans le fichier de configuration.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawDuration {
+    Seconds(u64),
+    Text(String),
+}
+
+/// Lit une durée exprimée en secondes ou sous forme lisible.
+fn seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    match Option::<RawDuration>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(RawDuration::Seconds(secs)) => Ok(Some(Duration::from_secs(secs))),
+        Some(RawDuration::Text(text)) => match parse_duration(&text) {
+            Ok(duration) => Ok(Some(duration)),
+            Err(CacheError::ParseError(msg)) => Err(serde::de::Error::custom(msg)),
+            Err(err) => Err(serde::de::Error::custom(err)),
+        },
+    }
+}
+
+impl CacheConfig {
+    /// Crée une configuration minimale de la capacité donnée.
+    pub fn new(capacity: usize) -> Self {
+        CacheConfig {
+            capacity,
+            policy: PolicyKind::Lru,
+            sample_size: None,
+            ttl: None,
+            low_watermark: None,
+            max_weight: None,
+            update_recency: UpdateRecency::Promote,
+            record_stats: false,
+            persistence_path: None,
+            flush_interval: None,
+        }
+    }
+
+    /// Lit la configuration du fichier donné, en TOML (`.toml`) ou en YAML
+    /// (`.yaml`, `.yml`).
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si le fichier ne peut pas être lu, et
+    /// `CacheError::ParseError` si son extension n'est pas reconnue ou si son
+    /// contenu est invalide.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, CacheError> {
+        let path = path.as_ref();
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        let parse = match extension.to_ascii_lowercase().as_str() {
+            "toml" => Self::from_toml,
+            "yaml" | "yml" => Self::from_yaml,
+            _ => {
+                return Err(CacheError::ParseError(format!(
+                    "{}: {}",
+                    messages::UNSUPPORTED_CONFIG_FORMAT,
+                    path.display()
+                )))
+            }
+        };
+        parse(&fs::read_to_string(path)?)
+    }
+
+    /// Lit une configuration TOML.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::ParseError` si le contenu est invalide.
+    pub fn from_toml(content: &str) -> Result<Self, CacheError> {
+        toml::from_str(content).map_err(|err| invalid(err.message()))
+    }
+
+    /// Lit une configuration YAML.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::ParseError` si le contenu est invalide.
+    pub fn from_yaml(content: &str) -> Result<Self, CacheError> {
+        serde_yaml::from_str(content).map_err(|err| invalid(&err.to_string()))
+    }
+
+    /// Retourne un constructeur configuré selon la description, hormis la
+    /// persistance qui reste à la charge de l'appelant.
+    pub fn builder<K, V>(&self) -> CacheBuilder<K, V>
+    where
+        K: Hash + Eq + Clone,
+    {
+        let mut builder = CacheBuilder::new().capacity(self.capacity);
+        if let Some(ttl) = self.ttl {
+            builder = builder.time_to_live(ttl);
+        }
+        if let Some(low) = self.low_watermark {
+            builder = builder.low_watermark(low);
+        }
+        if let Some(max) = self.max_weight {
+            builder = builder.max_weight(max);
+        }
+        builder = builder.update_recency(self.update_recency);
+        if self.record_stats {
+            builder = builder.record_stats();
+        }
+        if self.policy == PolicyKind::Sampled {
+            builder = builder.sampled_eviction(self.sample_size.unwrap_or(DEFAULT_SAMPLE_SIZE));
+        }
+        builder
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone + Display + FromStr,
+    V: Display + FromStr,
+{
+    /// Construit le cache décrit par la configuration, chargé depuis son
+    /// fichier de persistance s'il existe.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `CacheBuilder::build` et de
+    /// `Cache::new_persistent`.
+    pub fn from_config(config: &CacheConfig) -> Result<Self, CacheError> {
+        let mut cache = config.builder().build()?;
+        if let Some(path) = config.persistence_path.as_ref() {
+            cache.load_file_if_exists(path)?;
+        }
+        Ok(cache)
+    }
+}
+
+fn invalid(reason: &str) -> CacheError {
+    CacheError::ParseError(format!("{}: {}", messages::INVALID_CONFIG, reason.trim()))
+}
@@ -0,0 +1,196 @@
+//! Module implémentant les lectures bornées par une échéance.
+//!
+//! Sur un chemin de requête sensible à la latence, mieux vaut renoncer au
+//! cache que d'attendre indéfiniment un verrou très disputé ou un
+//! chargement lent. `SyncCache::get_with_deadline` et
+//! `ShardedCache::get_with_deadline` cessent d'attendre le verrou à
+//! l'échéance donnée et retournent `CacheError::Timeout` ; l'appelant peut
+//! alors se rabattre sur la source ou sur une réponse dégradée.
+//!
+//! `SyncCache::get_or_load_with_deadline` borne aussi le chargement d'une
+//! valeur absente, sur les threads dédiés décrits au module `timeout`. Les
+//! clones d'un `SyncCache` partagent leurs chargements en cours : une clé
+//! n'est chargée qu'une fois à la fois, et au plus `max_deadline_loads`
+//! chargements (`DEFAULT_MAX_LOADS` par défaut) tournent ensemble, si bien
+//! qu'une source bloquée n'accumule pas de threads. Le chargement n'est pas
+//! interrompu à l'échéance : il se termine en arrière-plan et sa valeur est
+//! insérée dans le cache, où elle profitera aux lectures suivantes. Une
+//! échéance déjà passée ne lance aucun chargement.
+//!
+//! L'attente d'un verrou se fait par tentatives espacées de pauses de plus
+//! en plus longues, plafonnées à `MAX_PAUSE`.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::sync::SyncCache;
+//!
+//! let cache = SyncCache::new(10);
+//! cache.put("clé", 1).unwrap();
+//!
+//! let echeance = Instant::now() + Duration::from_millis(5);
+//! assert_eq!(cache.get_with_deadline(&"clé", echeance).unwrap(), Some(1));
+//!
+//! // Verrou détenu ailleurs : la lecture abandonne à l'échéance
+//! cache.with_lock(|_| {
+//!     let echeance = Instant::now() + Duration::from_millis(5);
+//!     assert!(matches!(cache.get_with_deadline(&"clé", echeance), Err(CacheError::Timeout { .. })));
+//! }).unwrap();
+//! ```
+
+use std::hash::Hash;
+use std::sync::TryLockError;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::loader::Loader;
+use crate::lru::sharded::ShardedCache;
+use crate::lru::sync::SyncCache;
+use crate::lru::traits::CacheTrait;
+
+/// Pause maximale entre deux tentatives d'acquisition d'un verrou.
+pub const MAX_PAUSE: Duration = Duration::from_millis(1);
+
+/// Répète `attempt` jusqu'à ce qu'elle aboutisse ou que l'échéance passe.
+fn retry_until<R>(deadline: Instant, mut attempt: impl FnMut() -> Option<R>) -> Result<R, CacheError> {
+    let start = Instant::now();
+    let mut pause = Duration::from_micros(1);
+    loop {
+        if let Some(result) = attempt() {
+            return Ok(result);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(CacheError::Timeout { waited: now - start });
+        }
+        thread::sleep(pause.min(deadline - now));
+        pause = (pause * 2).min(MAX_PAUSE);
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Exécute une closure avec un accès exclusif au cache, si le verrou
+    /// peut être acquis avant l'échéance.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Timeout` si l'échéance passe avant
+    /// l'acquisition du verrou, et `CacheError::Poisoned` selon la politique
+    /// d'empoisonnement.
+    pub fn with_lock_until<R, F>(&self, deadline: Instant, f: F) -> Result<R, CacheError>
+    where
+        F: FnOnce(&mut Cache<K, V>) -> R,
+    {
+        let mut guard = retry_until(deadline, || match self.inner.try_lock() {
+            Ok(guard) => Some(Ok(guard)),
+            Err(TryLockError::Poisoned(poisoned)) => Some(self.recover(poisoned)),
+            Err(TryLockError::WouldBlock) => None,
+        })??;
+        Ok(f(&mut guard))
+    }
+
+    /// Récupère une copie de la valeur associée à la clé, sans attendre le
+    /// verrou au-delà de l'échéance.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `SyncCache::with_lock_until`.
+    pub fn get_with_deadline(&self, key: &K, deadline: Instant) -> Result<Option<V>, CacheError> {
+        self.with_lock_until(deadline, |cache| cache.get(key).cloned())
+    }
+
+    /// Fixe le nombre maximal de chargements simultanés de
+    /// `get_or_load_with_deadline` (au moins un), pour ce cache et ses clones
+    /// ultérieurs.
+    pub fn with_max_deadline_loads(mut self, max_loads: usize) -> Self {
+        self.max_deadline_loads = max_loads.max(1);
+        self
+    }
+
+    /// Retourne le nombre de chargements bornés par une échéance en cours,
+    /// abandonnés ou non.
+    pub fn deadline_loads_in_flight(&self) -> usize {
+        self.deadline_loads.len()
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    /// Récupère la valeur associée à la clé ou la charge avec `loader`,
+    /// sans attendre au-delà de l'échéance.
+    ///
+    /// Le chargement rejoint celui de la clé déjà en cours, s'il y en a un,
+    /// ou attend une place parmi les `max_deadline_loads` chargements
+    /// simultanés. Un chargement abandonné se poursuit en arrière-plan et sa
+    /// valeur est insérée dans le cache.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Timeout` si l'échéance passe avant
+    /// l'acquisition du verrou ou la fin du chargement, ou si elle est déjà
+    /// passée lorsque la valeur manque, les erreurs du
+    /// chargeur, `CacheError::LoadFailed` si le chargeur panique, et
+    /// `CacheError::Poisoned` selon la politique d'empoisonnement.
+    pub fn get_or_load_with_deadline<L>(&self, key: K, deadline: Instant, loader: L) -> Result<V, CacheError>
+    where
+        L: Loader<K, V> + Send + Sync + 'static,
+    {
+        let start = Instant::now();
+        if let Some(value) = self.get_with_deadline(&key, deadline)? {
+            return Ok(value);
+        }
+        if deadline <= Instant::now() {
+            return Err(CacheError::Timeout { waited: start.elapsed() });
+        }
+
+        let cache = self.clone();
+        let filling = move |key: &K| -> Result<V, CacheError> {
+            let value = loader.load(key)?;
+            // Le cache reçoit la valeur même si l'appelant a renoncé
+            let _ = cache.put(key.clone(), value.clone());
+            Ok(value)
+        };
+        self.deadline_loads
+            .load_until(&key, deadline, self.max_deadline_loads, filling)
+            .unwrap_or_else(|| Err(CacheError::Timeout { waited: start.elapsed() }))
+    }
+}
+
+impl<K, V> ShardedCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Récupère une copie de la valeur associée à la clé, sans attendre les
+    /// verrous au-delà de l'échéance.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Timeout` si l'échéance passe avant
+    /// l'acquisition des verrous, et `CacheError::Poisoned` si un verrou est
+    /// empoisonné.
+    pub fn get_with_deadline(&self, key: &K, deadline: Instant) -> Result<Option<V>, CacheError> {
+        let shards = retry_until(deadline, || match self.shards.try_read() {
+            Ok(shards) => Some(Ok(shards)),
+            Err(TryLockError::Poisoned(_)) => Some(Err(CacheError::Poisoned)),
+            Err(TryLockError::WouldBlock) => None,
+        })??;
+        let shard = &shards[self.shard_index(key, shards.len())];
+        let mut shard = retry_until(deadline, || match shard.try_lock() {
+            Ok(shard) => Some(Ok(shard)),
+            Err(TryLockError::Poisoned(_)) => Some(Err(CacheError::Poisoned)),
+            Err(TryLockError::WouldBlock) => None,
+        })??;
+        Ok(shard.get(key).cloned())
+    }
+}
@@ -0,0 +1,155 @@
+//! Module implémentant un cache partageant les valeurs identiques.
+//!
+//! `DedupCache` conserve chaque valeur distincte une seule fois, derrière un
+//! `Arc` partagé par toutes les entrées qui la contiennent : les valeurs
+//! égales (au sens de `Hash` et `Eq`) ne coûtent plus qu'un pointeur par
+//! entrée. C'est intéressant lorsque beaucoup de clés mènent au même
+//! contenu, par exemple des fragments rendus identiques.
+//!
+//! Le registre des valeurs est purgé des valeurs qui ne sont plus
+//! référencées par aucune entrée dès qu'il dépasse le double du nombre
+//! d'entrées, ce qui borne son coût sans suivre chaque éviction.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::sync::Arc;
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::dedup::DedupCache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = DedupCache::new(Cache::new(100));
+//! for page in 0..10 {
+//!     cache.put(page, "<footer>...</footer>".to_string());
+//! }
+//!
+//! assert_eq!(cache.get(&3), Some(&"<footer>...</footer>".to_string()));
+//! assert_eq!(cache.distinct_values(), 1);
+//! assert!(Arc::ptr_eq(&cache.get_shared(&0).unwrap(), &cache.get_shared(&9).unwrap()));
+//! ```
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::Arc;
+use crate::lru::Cache;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Cache stockant une seule copie de chaque valeur distincte.
+#[derive(Debug)]
+pub struct DedupCache<K, V>
+where
+    K: Hash + Eq,
+{
+    cache: Cache<K, Arc<V>>,
+    values: HashSet<Arc<V>>,
+}
+
+impl<K, V> DedupCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Hash + Eq,
+{
+    /// Encapsule le cache donné, qui doit être vide.
+    pub fn new(cache: Cache<K, Arc<V>>) -> Self {
+        DedupCache {
+            cache,
+            values: HashSet::new(),
+        }
+    }
+
+    /// Retourne la valeur partagée associée à la clé.
+    pub fn get_shared(&mut self, key: &K) -> Option<Arc<V>> {
+        self.cache.get(key).cloned()
+    }
+
+    /// Retourne le nombre de valeurs distinctes encore référencées.
+    pub fn distinct_values(&mut self) -> usize {
+        self.purge();
+        self.values.len()
+    }
+
+    /// Retourne une référence au cache encapsulé.
+    pub fn inner(&self) -> &Cache<K, Arc<V>> {
+        &self.cache
+    }
+
+    /// Retourne la copie partagée de la valeur, en l'enregistrant si elle
+    /// est nouvelle.
+    fn intern(&mut self, value: V) -> Arc<V> {
+        if let Some(shared) = self.values.get(&value) {
+            return Arc::clone(shared);
+        }
+        if self.values.len() >= 2 * self.cache.len().max(1) {
+            self.purge();
+        }
+        let shared = Arc::new(value);
+        self.values.insert(Arc::clone(&shared));
+        shared
+    }
+
+    /// Oublie les valeurs qui ne sont plus référencées que par le registre.
+    fn purge(&mut self) {
+        self.values.retain(|value| Arc::strong_count(value) > 1);
+    }
+}
+
+impl<K, V> CacheRead<K, V> for DedupCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.cache.peek(key).map(|value| &**value)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.cache.contains(key)
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        CacheRead::capacity(&self.cache)
+    }
+}
+
+impl<K, V> CacheTrait<K, V> for DedupCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Hash + Eq + Clone,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.cache.get(key).map(|value| &**value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let shared = self.intern(value);
+        self.cache.put(key, shared);
+    }
+
+    /// Supprime l'entrée et retourne sa valeur, copiée si d'autres entrées
+    /// la partagent encore.
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let shared = self.cache.remove(key)?;
+        self.values.remove(&*shared);
+        match Arc::try_unwrap(shared) {
+            Ok(value) => Some(value),
+            Err(shared) => {
+                // Encore partagée : la valeur reste enregistrée
+                let value = V::clone(&shared);
+                self.values.insert(shared);
+                Some(value)
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.cache.clear();
+        self.values.clear();
+    }
+}
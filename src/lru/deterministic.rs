@@ -0,0 +1,142 @@
+//! Module implémentant le mode déterministe du cache.
+//!
+//! Par défaut, la fonction de hachage des clés est initialisée au hasard à
+//! chaque création de cache, tout comme le générateur de l'éviction
+//! échantillonnée. Avec `CacheBuilder::deterministic(graine)`, les deux sont
+//! dérivés de la graine : deux caches construits avec la même graine et
+//! soumis à la même suite d'opérations se comportent exactement de la même
+//! façon, ce qui rend reproductibles les simulations et les tests comparant
+//! leur sortie à un fichier de référence.
+//!
+//! Les départages sont les suivants :
+//!
+//! * en LRU exact, `iter`, la persistance et l'éviction suivent l'ordre
+//!   d'utilisation, qui ne présente jamais d'égalité ;
+//! * en éviction échantillonnée, la victime est l'entrée de l'échantillon la
+//!   moins récemment utilisée, et en cas d'égalité la première tirée ; les
+//!   tirages comme l'ordre de `iter` dépendent alors de la graine ;
+//! * `clear` et `rollback_to` signalent leurs suppressions dans l'ordre de la
+//!   table, lui aussi fixé par la graine.
+//!
+//! Les instants proviennent de l'horloge du cache : une simulation
+//! reproductible utilise en outre une `ManualClock`. Les empreintes sont
+//! identiques d'une exécution et d'une plateforme à l'autre pour une même
+//! version de Rust, tant que le hachage des clés ne dépend pas de la
+//! plateforme (un `usize` ou un entier hors `u8` haché sur une plateforme
+//! gros-boutiste, par exemple).
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::clock::ManualClock;
+//! use lru_cache::lru::traits::CacheTrait;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let simulation = || {
+//!     let clock = ManualClock::new();
+//!     let mut cache = Cache::builder()
+//!         .capacity(50)
+//!         .clock(Arc::new(clock.clone()))
+//!         .sampled_eviction(3)
+//!         .deterministic(42)
+//!         .build()
+//!         .unwrap();
+//!     for i in 0..500u64 {
+//!         clock.advance(Duration::from_millis(i % 3));
+//!         cache.put(i * 7 % 211, i);
+//!     }
+//!     cache.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>()
+//! };
+//! assert_eq!(simulation(), simulation());
+//! ```
+
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::hash::{BuildHasher, Hash, Hasher};
+use hashbrown::HashMap;
+use crate::lru::Cache;
+
+/// Fonction de hachage des clés : aléatoire, ou dérivée d'une graine.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyHasher {
+    random: Option<RandomState>,
+    seed: u64,
+}
+
+impl KeyHasher {
+    /// Fonction initialisée au hasard.
+    pub(crate) fn random() -> Self {
+        KeyHasher { random: Some(RandomState::new()), seed: 0 }
+    }
+
+    /// Fonction dérivée de la graine.
+    pub(crate) fn seeded(seed: u64) -> Self {
+        KeyHasher { random: None, seed }
+    }
+
+    /// Retourne la graine, si la fonction en est dérivée.
+    pub(crate) fn seed(&self) -> Option<u64> {
+        self.random.is_none().then_some(self.seed)
+    }
+}
+
+impl Default for KeyHasher {
+    fn default() -> Self {
+        Self::random()
+    }
+}
+
+impl BuildHasher for KeyHasher {
+    type Hasher = DefaultHasher;
+
+    fn build_hasher(&self) -> DefaultHasher {
+        match &self.random {
+            Some(random) => random.build_hasher(),
+            None => {
+                let mut hasher = DefaultHasher::new();
+                hasher.write_u64(self.seed);
+                hasher
+            }
+        }
+    }
+}
+
+/// Mélange une graine (splitmix64) pour initialiser un générateur.
+pub(crate) fn mix_seed(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne la graine du mode déterministe, s'il est activé.
+    pub fn deterministic_seed(&self) -> Option<u64> {
+        self.elements.hasher().seed()
+    }
+
+    /// Passe la table des entrées à la fonction de hachage dérivée de la
+    /// graine. Les entrées déjà présentes y sont replacées dans l'ordre
+    /// d'utilisation, pour que la table ne garde aucune trace de l'ancienne
+    /// fonction.
+    pub(crate) fn use_seeded_hasher(&mut self, seed: u64) {
+        let mut elements = HashMap::with_capacity_and_hasher(self.capacity, KeyHasher::seeded(seed));
+        for key in self.usage_order.iter() {
+            if let Some((key, entry)) = self.elements.remove_entry(key) {
+                elements.insert(key, entry);
+            }
+        }
+        // L'ordre d'utilisation couvre toutes les entrées, sauf en éviction
+        // échantillonnée où aucun ordre ne peut être reconstitué
+        elements.extend(self.elements.drain());
+        self.elements = elements;
+        self.usage_order.set_hasher(KeyHasher::seeded(seed));
+        if self.scan_index.is_some() {
+            self.use_scan_index();
+        }
+    }
+}
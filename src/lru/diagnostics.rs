@@ -0,0 +1,132 @@
+//! Module tenant un registre global des caches, pour le diagnostic.
+//!
+//! Un processus qui utilise de nombreux caches gagne à pouvoir tous les
+//! décrire depuis un seul point d'entrée (page de diagnostic, commande
+//! d'administration). `register` inscrit un `SyncCache` sous un nom dans un
+//! registre propre au processus, et `snapshot` retourne l'état de tous les
+//! caches encore vivants : nombre d'entrées, capacité, mémoire estimée et
+//! statistiques.
+//!
+//! Le registre ne garde qu'une référence faible vers chaque cache : il ne
+//! prolonge pas leur durée de vie, et un cache abandonné disparaît du
+//! registre de lui-même. Il est indépendant des budgets de
+//! `registry::CacheRegistry`.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::diagnostics;
+//! use lru_cache::lru::sync::SyncCache;
+//!
+//! let users = SyncCache::from_cache(Cache::<u32, String>::builder().capacity(100).record_stats().build().unwrap());
+//! diagnostics::register("users", &users);
+//! users.put(1, "alice".to_string()).unwrap();
+//! users.get(&1).unwrap();
+//!
+//! let report = diagnostics::snapshot().into_iter().find(|report| report.name == "users").unwrap();
+//! assert_eq!((report.len, report.capacity, report.stats.hits), (1, 100, 1));
+//!
+//! drop(users);
+//! assert!(diagnostics::snapshot().iter().all(|report| report.name != "users"));
+//! ```
+
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, Weak};
+use crate::lru::Cache;
+use crate::lru::stats::CacheStats;
+use crate::lru::sync::SyncCache;
+
+/// État d'un cache inscrit au registre.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CacheReport {
+    /// Nom sous lequel le cache a été inscrit
+    pub name: String,
+    /// Nombre d'entrées
+    pub len: usize,
+    /// Capacité maximale
+    pub capacity: usize,
+    /// Empreinte mémoire estimée, en octets (voir
+    /// `Cache::estimated_memory_usage`)
+    pub memory_bytes: usize,
+    /// Statistiques du cache, à zéro si elles ne sont pas activées
+    pub stats: CacheStats,
+}
+
+/// Cache inscrit, vu indépendamment de ses types de clés et de valeurs.
+trait Inspect: Send + Sync {
+    fn report(&self, name: &str) -> CacheReport;
+}
+
+impl<K, V> Inspect for Mutex<Cache<K, V>>
+where
+    K: Hash + Eq + Clone + Send,
+    V: Send,
+{
+    fn report(&self, name: &str) -> CacheReport {
+        // Le diagnostic ne fait que lire : un verrou empoisonné est ignoré
+        let cache = self.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        CacheReport {
+            name: name.to_string(),
+            len: cache.len(),
+            capacity: cache.capacity(),
+            memory_bytes: cache.estimated_memory_usage().total(),
+            stats: cache.stats(),
+        }
+    }
+}
+
+/// Caches inscrits, par ordre d'inscription.
+static REGISTRY: Mutex<Vec<(String, Weak<dyn Inspect>)>> = Mutex::new(Vec::new());
+
+fn with_registry<R>(f: impl FnOnce(&mut Vec<(String, Weak<dyn Inspect>)>) -> R) -> R {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    // Les caches abandonnés sont oubliés à chaque accès
+    registry.retain(|(_, cache)| cache.strong_count() > 0);
+    f(&mut registry)
+}
+
+/// Inscrit le cache au registre sous le nom donné, en remplaçant le cache
+/// qui portait déjà ce nom.
+pub fn register<K, V>(name: &str, cache: &SyncCache<K, V>)
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Send + 'static,
+{
+    let inspect: Arc<dyn Inspect> = cache.inner.clone();
+    let weak = Arc::downgrade(&inspect);
+    with_registry(|registry| {
+        registry.retain(|(registered, _)| registered != name);
+        registry.push((name.to_string(), weak));
+    });
+}
+
+/// Retire du registre le cache inscrit sous ce nom. Retourne `true` s'il y
+/// figurait encore.
+pub fn unregister(name: &str) -> bool {
+    with_registry(|registry| {
+        let before = registry.len();
+        registry.retain(|(registered, _)| registered != name);
+        registry.len() != before
+    })
+}
+
+/// Retourne les noms des caches vivants, par ordre d'inscription.
+pub fn names() -> Vec<String> {
+    with_registry(|registry| registry.iter().map(|(name, _)| name.clone()).collect())
+}
+
+/// Décrit tous les caches vivants, par ordre d'inscription.
+///
+/// Le verrou de chaque cache est pris tour à tour, le temps de lire son
+/// état ; le registre lui-même n'est pas verrouillé pendant ces lectures.
+pub fn snapshot() -> Vec<CacheReport> {
+    let caches: Vec<(String, Arc<dyn Inspect>)> = with_registry(|registry| {
+        registry
+            .iter()
+            .filter_map(|(name, cache)| Some((name.clone(), cache.upgrade()?)))
+            .collect()
+    });
+    caches.iter().map(|(name, cache)| cache.report(name)).collect()
+}
@@ -0,0 +1,132 @@
+//! Module fournissant des implémentations simplifiées de `CacheTrait`.
+//!
+//! Ces caches servent de points de comparaison dans les tests et les
+//! benchmarks : `NullCache` ne conserve rien (« ne rien cacher ») et
+//! `UnboundedMapCache` conserve tout (« tout cacher »). Les substituer au
+//! cache LRU permet d'isoler l'effet de la politique d'éviction.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::doubles::{NullCache, UnboundedMapCache};
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! fn charger<C: CacheTrait<u32, u32>>(cache: &mut C) -> usize {
+//!     (0..100).for_each(|i| cache.put(i, i * 2));
+//!     cache.len()
+//! }
+//!
+//! assert_eq!(charger(&mut NullCache::new()), 0);
+//! assert_eq!(charger(&mut UnboundedMapCache::new()), 100);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Cache qui ne conserve aucune valeur : chaque lecture est un échec et
+/// chaque insertion est ignorée.
+#[derive(Debug, Clone, Copy)]
+pub struct NullCache<K, V> {
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> NullCache<K, V> {
+    /// Crée un nouveau cache vide.
+    pub fn new() -> Self {
+        NullCache { _marker: PhantomData }
+    }
+}
+
+impl<K, V> Default for NullCache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> CacheRead<K, V> for NullCache<K, V> {
+    fn peek(&self, _key: &K) -> Option<&V> {
+        None
+    }
+
+    fn len(&self) -> usize {
+        0
+    }
+
+    fn capacity(&self) -> usize {
+        0
+    }
+}
+
+impl<K, V> CacheTrait<K, V> for NullCache<K, V> {
+    fn get(&mut self, _key: &K) -> Option<&V> {
+        None
+    }
+
+    fn put(&mut self, _key: K, _value: V) {}
+
+    fn remove(&mut self, _key: &K) -> Option<V> {
+        None
+    }
+
+    fn clear(&mut self) {}
+}
+
+/// Cache sans limite de capacité reposant sur une simple `HashMap` :
+/// aucune entrée n'est jamais évincée.
+#[derive(Debug, Clone)]
+pub struct UnboundedMapCache<K, V> {
+    elements: HashMap<K, V>,
+}
+
+impl<K, V> UnboundedMapCache<K, V> {
+    /// Crée un nouveau cache vide.
+    pub fn new() -> Self {
+        UnboundedMapCache { elements: HashMap::new() }
+    }
+}
+
+impl<K, V> Default for UnboundedMapCache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> CacheRead<K, V> for UnboundedMapCache<K, V>
+where
+    K: Hash + Eq,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.elements.get(key)
+    }
+
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+}
+
+impl<K, V> CacheTrait<K, V> for UnboundedMapCache<K, V>
+where
+    K: Hash + Eq,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.elements.get(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        self.elements.insert(key, value);
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.elements.remove(key)
+    }
+
+    fn clear(&mut self) {
+        self.elements.clear();
+    }
+}
@@ -0,0 +1,198 @@
+//! Module définissant le comportement de `put` lorsque la clé existe déjà.
+//!
+//! Par défaut la nouvelle valeur remplace l'ancienne. Le constructeur permet
+//! de conserver plutôt la première valeur écrite (« la première écriture
+//! gagne »), ou de fusionner les deux valeurs via une fonction fournie par
+//! l'utilisateur. `put_with_outcome` indique ce qui s'est produit, sans
+//! nécessiter d'appel préalable à `contains`.
+//!
+//! Une mise à jour marque par défaut la clé comme la plus récemment
+//! utilisée. Pour une charge dominée par les écritures, où une mise à jour
+//! n'est pas une utilisation, `UpdateRecency::Keep` laisse la clé à sa place
+//! dans l'ordre d'utilisation : les écrivains ne chassent plus les entrées
+//! lues par les lecteurs.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::duplicate::{DuplicatePolicy, PutOutcome};
+//! use lru_cache::lru::traits::CacheRead;
+//!
+//! let mut cache = Cache::builder()
+//!     .capacity(10)
+//!     .on_duplicate(DuplicatePolicy::KeepExisting)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert!(matches!(cache.put_with_outcome("id", 1), Ok(PutOutcome::Inserted)));
+//! assert!(matches!(cache.put_with_outcome("id", 2), Ok(PutOutcome::Kept(2))));
+//!
+//! let mut compteurs = Cache::builder()
+//!     .capacity(10)
+//!     .on_duplicate(DuplicatePolicy::merge(|total: &mut u32, ajout| *total += ajout))
+//!     .build()
+//!     .unwrap();
+//!
+//! compteurs.put_with_outcome("vues", 3).unwrap();
+//! compteurs.put_with_outcome("vues", 4).unwrap();
+//! assert_eq!(compteurs.peek(&"vues"), Some(&7));
+//! ```
+
+use std::fmt;
+use std::hash::Hash;
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::error::CacheError;
+use crate::lru::Cache;
+
+type MergeFn<V> = dyn Fn(&mut V, V) + Send + Sync;
+
+/// Fonction fusionnant une nouvelle valeur dans la valeur existante.
+pub struct Merger<V>(Arc<MergeFn<V>>);
+
+impl<V> Merger<V> {
+    fn merge(&self, current: &mut V, value: V) {
+        (self.0)(current, value)
+    }
+}
+
+impl<V> Clone for Merger<V> {
+    fn clone(&self) -> Self {
+        Merger(Arc::clone(&self.0))
+    }
+}
+
+impl<V> fmt::Debug for Merger<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Merger")
+    }
+}
+
+/// Politique appliquée lors de l'insertion d'une clé déjà présente.
+pub enum DuplicatePolicy<V> {
+    /// La nouvelle valeur remplace l'ancienne (comportement par défaut).
+    Overwrite,
+    /// L'ancienne valeur est conservée et la nouvelle est rendue à l'appelant.
+    KeepExisting,
+    /// La nouvelle valeur est fusionnée dans l'ancienne.
+    Merge(Merger<V>),
+}
+
+impl<V> DuplicatePolicy<V> {
+    /// Crée une politique fusionnant la nouvelle valeur dans l'existante.
+    pub fn merge<F>(merge: F) -> Self
+    where
+        F: Fn(&mut V, V) + Send + Sync + 'static,
+    {
+        DuplicatePolicy::Merge(Merger(Arc::new(merge)))
+    }
+
+    /// Applique la politique à la valeur existante.
+    pub(crate) fn resolve(&self, current: &mut V, value: V) -> PutOutcome<V> {
+        match self {
+            DuplicatePolicy::Overwrite => PutOutcome::Replaced(mem::replace(current, value)),
+            DuplicatePolicy::KeepExisting => PutOutcome::Kept(value),
+            DuplicatePolicy::Merge(merger) => {
+                merger.merge(current, value);
+                PutOutcome::Merged
+            }
+        }
+    }
+}
+
+impl<V> Clone for DuplicatePolicy<V> {
+    fn clone(&self) -> Self {
+        match self {
+            DuplicatePolicy::Overwrite => DuplicatePolicy::Overwrite,
+            DuplicatePolicy::KeepExisting => DuplicatePolicy::KeepExisting,
+            DuplicatePolicy::Merge(merger) => DuplicatePolicy::Merge(merger.clone()),
+        }
+    }
+}
+
+impl<V> fmt::Debug for DuplicatePolicy<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DuplicatePolicy::Overwrite => f.write_str("Overwrite"),
+            DuplicatePolicy::KeepExisting => f.write_str("KeepExisting"),
+            DuplicatePolicy::Merge(_) => f.write_str("Merge"),
+        }
+    }
+}
+
+/// Effet d'une mise à jour sur l'ordre d'utilisation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum UpdateRecency {
+    /// La clé mise à jour devient la plus récemment utilisée (comportement
+    /// par défaut)
+    #[default]
+    Promote,
+    /// La clé mise à jour garde sa place dans l'ordre d'utilisation
+    Keep,
+}
+
+/// Résultat d'une insertion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PutOutcome<V> {
+    /// La clé était absente et a été ajoutée.
+    Inserted,
+    /// La clé existait ; l'ancienne valeur, remplacée, est retournée.
+    Replaced(V),
+    /// La clé existait ; l'ancienne valeur a été conservée et la valeur
+    /// proposée est retournée.
+    Kept(V),
+    /// La clé existait ; la valeur proposée a été fusionnée dans l'ancienne.
+    Merged,
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Ajoute une paire clé-valeur selon la politique de doublons configurée
+    /// et indique ce qui s'est produit.
+    ///
+    /// Comme `try_put`, la méthode applique la durée de vie par défaut et
+    /// retourne les refus de clé ou de poids. Une entrée conservée garde sa
+    /// position dans l'ordre d'utilisation et son échéance.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les mêmes erreurs que `try_put`. Si le résultat d'une fusion
+    /// dépasse les limites de poids, l'entrée est supprimée.
+    pub fn put_with_outcome(&mut self, key: K, value: V) -> Result<PutOutcome<V>, CacheError> {
+        self.put_entry(key, value, self.default_ttl, false)
+    }
+
+    /// Retourne l'effet d'une mise à jour sur l'ordre d'utilisation.
+    pub fn update_recency(&self) -> UpdateRecency {
+        self.update_recency
+    }
+
+    /// Insère l'entrée puis met à jour son échéance, sauf si l'ancienne valeur
+    /// a été conservée. `overwrite` ignore la politique de doublons.
+    pub(crate) fn put_entry(
+        &mut self,
+        key: K,
+        value: V,
+        ttl: Option<Duration>,
+        overwrite: bool,
+    ) -> Result<PutOutcome<V>, CacheError> {
+        let deadline = ttl.map(|ttl| self.clock.now() + ttl);
+        let outcome = self.insert_entry(key.clone(), value, overwrite)?;
+        if !matches!(outcome, PutOutcome::Kept(_)) {
+            match deadline {
+                Some(deadline) => self.expirations.set(key, deadline),
+                None => {
+                    if self.expirations.len() > 0 {
+                        self.expirations.remove(&key);
+                    }
+                }
+            }
+        }
+        Ok(outcome)
+    }
+}
@@ -0,0 +1,124 @@
+//! Module lisant et affichant les durées sous une forme lisible.
+//!
+//! Les durées de vie et intervalles de sauvegarde s'écrivent comme une suite
+//! de composantes `<entier><unité>`, par exemple `500ms`, `90s` ou `2h30m`.
+//! Les unités reconnues sont `d`, `h`, `m`, `s`, `ms`, `us` (ou `µs`) et
+//! `ns` ; un entier seul compte en secondes. `HumanDuration` affiche une
+//! durée dans ce même format, si bien que la valeur affichée peut être
+//! relue telle quelle.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::time::Duration;
+//! use lru_cache::lru::duration::{parse_duration, HumanDuration};
+//!
+//! assert_eq!(parse_duration("2h30m").unwrap(), Duration::from_secs(9000));
+//! assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+//! assert_eq!(parse_duration("45").unwrap(), Duration::from_secs(45));
+//! assert_eq!(HumanDuration(Duration::from_millis(1500)).to_string(), "1s500ms");
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use crate::error::CacheError;
+use crate::messages;
+
+/// Unités reconnues et leur valeur en nanosecondes, de la plus grande à la
+/// plus petite.
+const UNITS: [(&str, u128); 7] = [
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+/// Lit une durée telle que `500ms`, `90s`, `2h30m` ou `45` (secondes).
+///
+/// # Errors
+///
+/// Retourne `CacheError::ParseError` si le texte n'est pas une durée valide
+/// ou dépasse la durée maximale représentable.
+pub fn parse_duration(text: &str) -> Result<Duration, CacheError> {
+    let invalid = || CacheError::ParseError(format!("{}: {}", messages::INVALID_DURATION, text));
+    let trimmed = text.trim();
+    if !trimmed.is_empty() && trimmed.bytes().all(|byte| byte.is_ascii_digit()) {
+        return trimmed.parse().map(Duration::from_secs).map_err(|_| invalid());
+    }
+
+    let mut nanos: u128 = 0;
+    let mut rest = trimmed;
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let unit_len = rest[digits..]
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+            .unwrap_or(rest.len() - digits);
+        let (number, unit) = (&rest[..digits], &rest[digits..digits + unit_len]);
+        let unit = if unit == "µs" { "us" } else { unit };
+        let scale = UNITS.iter().find(|(name, _)| *name == unit).map(|(_, scale)| *scale);
+        let (Ok(number), Some(scale)) = (number.parse::<u128>(), scale) else {
+            return Err(invalid());
+        };
+        nanos = number
+            .checked_mul(scale)
+            .and_then(|value| nanos.checked_add(value))
+            .ok_or_else(invalid)?;
+        rest = rest[digits + unit_len..].trim_start();
+    }
+
+    let secs = u64::try_from(nanos / 1_000_000_000).map_err(|_| invalid())?;
+    Ok(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+}
+
+/// Durée affichée sous forme lisible (`2h30m`, `1s500ms`, `0s`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HumanDuration(pub Duration);
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_zero() {
+            return f.write_str("0s");
+        }
+        let mut rest = self.0.as_nanos();
+        for (unit, scale) in UNITS {
+            if rest >= scale {
+                write!(f, "{}{}", rest / scale, unit)?;
+                rest %= scale;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = CacheError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_duration(text).map(HumanDuration)
+    }
+}
+
+impl From<Duration> for HumanDuration {
+    fn from(duration: Duration) -> Self {
+        HumanDuration(duration)
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    fn from(duration: HumanDuration) -> Self {
+        duration.0
+    }
+}
+
+/// Sérialise une durée sous sa forme lisible.
+#[cfg(feature = "serde")]
+pub(crate) fn serialize<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&HumanDuration(*duration))
+}
@@ -0,0 +1,97 @@
+//! Module permettant de consulter un cache à clés composites sans les
+//! construire.
+//!
+//! Avec `K = (String, u32)`, un appel à `get` exige un tuple possédé, donc
+//! une copie de chaque partie de la clé. Le trait `Equivalent` décrit une
+//! forme empruntée de la clé, `(&str, &u32)` par exemple, qui se hache comme
+//! elle et sait s'y comparer : `get_by`, `peek_by`, `contains_by` et
+//! `remove_by` l'utilisent pour sonder la table sans allouer.
+//!
+//! Le trait est implémenté pour les tuples de deux et trois références dont
+//! chaque partie est une forme empruntée (`Borrow`) de la partie possédée.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::new(10);
+//! cache.put(("eu-west".to_string(), 1u32), "primaire");
+//!
+//! assert_eq!(cache.get_by(&("eu-west", &1)), Some(&"primaire"));
+//! assert!(!cache.contains_by(&("eu-west", &2)));
+//! assert_eq!(cache.remove_by(&("eu-west", &1)), Some("primaire"));
+//! ```
+
+use std::borrow::{Borrow, Cow};
+use std::hash::Hash;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+
+/// Forme empruntée d'une clé de type `K`.
+///
+/// Une implémentation doit se hacher exactement comme la clé à laquelle elle
+/// est équivalente, sans quoi la recherche échoue.
+pub trait Equivalent<K>: Hash {
+    /// Vérifie si la forme empruntée désigne la clé donnée.
+    fn equivalent(&self, key: &K) -> bool;
+
+    /// Construit la clé possédée correspondante.
+    fn to_key(&self) -> K;
+}
+
+macro_rules! tuple_equivalent {
+    ($(($owned:ident, $borrowed:ident, $idx:tt)),+) => {
+        impl<$($owned,)+ $($borrowed,)+> Equivalent<($($owned,)+)> for ($(&$borrowed,)+)
+        where
+            $($owned: Borrow<$borrowed>,)+
+            $($borrowed: ToOwned<Owned = $owned> + Hash + Eq + ?Sized,)+
+        {
+            fn equivalent(&self, key: &($($owned,)+)) -> bool {
+                $(*self.$idx == *key.$idx.borrow())&&+
+            }
+
+            fn to_key(&self) -> ($($owned,)+) {
+                ($(self.$idx.to_owned(),)+)
+            }
+        }
+    };
+}
+
+tuple_equivalent!((A, QA, 0), (B, QB, 1));
+tuple_equivalent!((A, QA, 0), (B, QB, 1), (C, QC, 2));
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Récupère la valeur associée à la clé décrite par sa forme empruntée,
+    /// comme `get`.
+    pub fn get_by<Q: Equivalent<K> + ?Sized>(&mut self, key: &Q) -> Option<&V> {
+        let hash = self.hash_key(key);
+        self.get_matching(hash, |candidate| key.equivalent(candidate), || Cow::Owned(key.to_key()))
+    }
+
+    /// Retourne la valeur associée à la clé sans modifier l'ordre
+    /// d'utilisation, comme `peek`.
+    pub fn peek_by<Q: Equivalent<K> + ?Sized>(&self, key: &Q) -> Option<&V> {
+        let hash = self.hash_key(key);
+        self.find_with_hash(hash, |candidate| key.equivalent(candidate))
+            .map(|(_, value)| value)
+    }
+
+    /// Vérifie si la clé est présente et valide, comme `contains`.
+    pub fn contains_by<Q: Equivalent<K> + ?Sized>(&self, key: &Q) -> bool {
+        self.peek_by(key).is_some()
+    }
+
+    /// Supprime l'entrée associée à la clé et retourne sa valeur, comme
+    /// `remove`.
+    pub fn remove_by<Q: Equivalent<K> + ?Sized>(&mut self, key: &Q) -> Option<V> {
+        let hash = self.hash_key(key);
+        let (owned, _) = self.elements.raw_entry().from_hash(hash, |candidate| key.equivalent(candidate))?;
+        let owned = owned.clone();
+        self.remove_entry(&owned, RemovalCause::Explicit)
+    }
+}
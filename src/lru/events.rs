@@ -0,0 +1,317 @@
+//! Module décrivant les mutations du cache et leur diffusion aux observateurs.
+//!
+//! Chaque insertion, mise à jour ou suppression d'une entrée est décrite par
+//! une `Mutation`. Les suppressions portent leur cause : suppression
+//! explicite, éviction pour libérer de la place, expiration...
+//! Les mutations ne sont construites que si au moins un observateur est
+//! actif : un cache sans observateur n'en paie pas le coût.
+//!
+//! `Cache::subscribe` expose ces mutations sous forme d'un flux de
+//! `CacheEvent`, permettant à d'autres composants (index secondaires, vues)
+//! de refléter l'état du cache. Le flux est borné (`DEFAULT_EVENT_BUFFER`
+//! événements par défaut, voir `Cache::subscribe_bounded`) : un abonné trop
+//! lent ne fait pas grossir la mémoire sans limite, il perd les événements
+//! qui ne tiennent plus dans son tampon, comptés par
+//! `Cache::dropped_events`. Un abonné dont le récepteur est abandonné est
+//! retiré à la mutation suivante.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::events::CacheEvent;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::new(1);
+//! let events = cache.subscribe();
+//!
+//! cache.put("a", 1);
+//! cache.put("b", 2);
+//!
+//! let events: Vec<_> = events.try_iter().collect();
+//! assert_eq!(events, vec![
+//!     CacheEvent::Inserted { key: "a", value: 1 },
+//!     CacheEvent::Evicted { key: "a", value: 1 },
+//!     CacheEvent::Inserted { key: "b", value: 2 },
+//! ]);
+//! ```
+
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, TrySendError};
+use crate::lru::Cache;
+use crate::lru::advisor::CapacityAdvisor;
+use crate::lru::audit::AuditLog;
+use crate::lru::hooks::Hooks;
+use crate::lru::replication::OpLog;
+use crate::lru::stats::StatsRecorder;
+
+/// Nombre d'événements qu'un abonné de `Cache::subscribe` peut laisser en
+/// attente avant que les suivants soient perdus.
+pub const DEFAULT_EVENT_BUFFER: usize = 1024;
+
+/// Cause de la suppression d'une entrée.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RemovalCause {
+    /// Suppression demandée par l'appelant
+    Explicit,
+    /// Éviction pour respecter la capacité du cache
+    Capacity,
+    /// Éviction pour respecter le budget de poids
+    Weight,
+    /// Durée de vie dépassée
+    Expired,
+    /// Valeur ou clé refusée par les limites configurées
+    Rejected,
+    /// Vidage complet du cache
+    Cleared,
+}
+
+impl RemovalCause {
+    /// Retourne un identifiant stable de la cause, indépendant de la langue.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RemovalCause::Explicit => "explicit",
+            RemovalCause::Capacity => "capacity",
+            RemovalCause::Weight => "weight",
+            RemovalCause::Expired => "expired",
+            RemovalCause::Rejected => "rejected",
+            RemovalCause::Cleared => "cleared",
+        }
+    }
+
+    /// Indique si la suppression a été décidée par le cache lui-même.
+    pub fn is_eviction(&self) -> bool {
+        matches!(self, RemovalCause::Capacity | RemovalCause::Weight)
+    }
+}
+
+/// Mutation subie par une entrée du cache.
+#[derive(Debug)]
+pub enum Mutation<'a, K, V> {
+    /// Une nouvelle entrée a été ajoutée
+    Insert {
+        /// Clé de l'entrée
+        key: &'a K,
+        /// Valeur insérée
+        value: &'a V,
+    },
+    /// La valeur d'une entrée existante a été remplacée ou fusionnée
+    Update {
+        /// Clé de l'entrée
+        key: &'a K,
+        /// Nouvelle valeur
+        value: &'a V,
+    },
+    /// Une entrée a été supprimée
+    Remove {
+        /// Clé de l'entrée
+        key: &'a K,
+        /// Valeur supprimée
+        value: &'a V,
+        /// Cause de la suppression
+        cause: RemovalCause,
+    },
+}
+
+impl<K, V> Mutation<'_, K, V> {
+    /// Retourne la clé concernée par la mutation.
+    pub fn key(&self) -> &K {
+        match self {
+            Mutation::Insert { key, .. } | Mutation::Update { key, .. } | Mutation::Remove { key, .. } => key,
+        }
+    }
+}
+
+/// Événement transmis aux abonnés, avec une copie des données concernées.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheEvent<K, V> {
+    /// Une nouvelle entrée a été ajoutée
+    Inserted {
+        /// Clé de l'entrée
+        key: K,
+        /// Valeur insérée
+        value: V,
+    },
+    /// La valeur d'une entrée existante a été remplacée ou fusionnée
+    Updated {
+        /// Clé de l'entrée
+        key: K,
+        /// Nouvelle valeur
+        value: V,
+    },
+    /// Une entrée a été supprimée, explicitement ou par expiration
+    Removed {
+        /// Clé de l'entrée
+        key: K,
+        /// Valeur supprimée
+        value: V,
+        /// Cause de la suppression
+        cause: RemovalCause,
+    },
+    /// Une entrée a été évincée pour respecter la capacité ou le budget
+    Evicted {
+        /// Clé de l'entrée
+        key: K,
+        /// Valeur évincée
+        value: V,
+    },
+}
+
+impl<K, V> CacheEvent<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    /// Construit l'événement correspondant à une mutation.
+    pub fn from_mutation(mutation: &Mutation<'_, K, V>) -> Self {
+        match *mutation {
+            Mutation::Insert { key, value } => CacheEvent::Inserted { key: key.clone(), value: value.clone() },
+            Mutation::Update { key, value } => CacheEvent::Updated { key: key.clone(), value: value.clone() },
+            Mutation::Remove { key, value, cause } if cause.is_eviction() => {
+                CacheEvent::Evicted { key: key.clone(), value: value.clone() }
+            }
+            Mutation::Remove { key, value, cause } => CacheEvent::Removed {
+                key: key.clone(),
+                value: value.clone(),
+                cause,
+            },
+        }
+    }
+}
+
+/// Observateur notifié de chaque mutation ; retourne `false` pour se désabonner.
+pub(crate) type Listener<K, V> = Box<dyn FnMut(&Mutation<'_, K, V>) -> bool + Send>;
+
+/// Observateurs notifiés des mutations du cache.
+pub(crate) struct Observers<K, V> {
+    pub(crate) advisor: Option<CapacityAdvisor<K>>,
+    pub(crate) audit: Option<AuditLog<K>>,
+    pub(crate) listeners: Vec<Listener<K, V>>,
+    pub(crate) replication: Option<OpLog<K, V>>,
+    pub(crate) stats: Option<StatsRecorder>,
+    pub(crate) hooks: Hooks<K, V>,
+    /// Événements perdus par des abonnés dont le tampon était plein
+    pub(crate) dropped_events: Arc<AtomicU64>,
+}
+
+impl<K, V> Default for Observers<K, V> {
+    fn default() -> Self {
+        Observers {
+            advisor: None,
+            audit: None,
+            listeners: Vec::new(),
+            replication: None,
+            stats: None,
+            hooks: Hooks::default(),
+            dropped_events: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for Observers<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Observers")
+            .field("advisor", &self.advisor.is_some())
+            .field("audit", &self.audit)
+            .field("listeners", &self.listeners.len())
+            .field("replication", &self.replication)
+            .field("stats", &self.stats)
+            .field("hooks", &self.hooks)
+            .field("dropped_events", &self.dropped_events)
+            .finish()
+    }
+}
+
+impl<K, V> Observers<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Indique si au moins un observateur doit être notifié.
+    pub(crate) fn is_active(&self) -> bool {
+        self.advisor.is_some()
+            || self.audit.is_some()
+            || !self.listeners.is_empty()
+            || self.replication.is_some()
+            || self.stats.is_some()
+            || !self.hooks.is_empty()
+    }
+
+    /// Notifie tous les observateurs d'une mutation.
+    pub(crate) fn notify(&mut self, mutation: &Mutation<'_, K, V>) {
+        if let Some(advisor) = self.advisor.as_mut() {
+            match *mutation {
+                Mutation::Insert { key, .. } => advisor.forget(key),
+                Mutation::Remove { key, cause, .. } if cause.is_eviction() => advisor.record_eviction(key),
+                _ => {}
+            }
+        }
+        if let Some(audit) = self.audit.as_mut() {
+            audit.record(mutation);
+        }
+        if let Some(replication) = self.replication.as_mut() {
+            replication.record(mutation);
+        }
+        if let Some(stats) = self.stats.as_mut() {
+            stats.record(mutation);
+        }
+        self.hooks.mutation(mutation);
+        self.listeners.retain_mut(|listener| listener(mutation));
+    }
+
+    /// Indique si une lecture manquée doit être signalée avec sa clé.
+    pub(crate) fn wants_misses(&self) -> bool {
+        self.advisor.is_some() || !self.hooks.is_empty()
+    }
+
+    /// Signale une lecture manquée au conseiller et aux crochets.
+    pub(crate) fn miss(&mut self, key: &K) {
+        if let Some(advisor) = self.advisor.as_mut() {
+            advisor.record_miss(key);
+        }
+        self.hooks.miss(key);
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    /// S'abonne aux mutations du cache, avec un tampon de
+    /// `DEFAULT_EVENT_BUFFER` événements.
+    ///
+    /// Chaque insertion, mise à jour, suppression et éviction est transmise,
+    /// avec une copie de la clé et de la valeur, dans l'ordre où elle se
+    /// produit. L'abonnement prend fin lorsque le récepteur est abandonné.
+    pub fn subscribe(&mut self) -> Receiver<CacheEvent<K, V>> {
+        self.subscribe_bounded(DEFAULT_EVENT_BUFFER)
+    }
+
+    /// S'abonne aux mutations du cache comme `subscribe`, avec un tampon de
+    /// `bound` événements (au moins un). Un événement qui ne tient pas dans
+    /// le tampon est perdu et compté par `dropped_events`.
+    pub fn subscribe_bounded(&mut self, bound: usize) -> Receiver<CacheEvent<K, V>> {
+        let (sender, receiver) = mpsc::sync_channel(bound.max(1));
+        let dropped = Arc::clone(&self.observers.dropped_events);
+        self.observers.listeners.push(Box::new(move |mutation: &Mutation<'_, K, V>| {
+            match sender.try_send(CacheEvent::from_mutation(mutation)) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            }
+        }));
+        receiver
+    }
+
+    /// Retourne le nombre d'événements perdus par les abonnés dont le
+    /// tampon était plein.
+    pub fn dropped_events(&self) -> u64 {
+        self.observers.dropped_events.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,146 @@
+//! Module surveillant le fichier de persistance d'un cache partagé.
+//!
+//! Disponible avec la fonctionnalité `watch`. `SyncCache::watch_persisted_file`
+//! suit les modifications du fichier (via `notify`) et recharge le cache sous
+//! un seul verrou avec `Cache::reload_from_file` : les lecteurs voient
+//! l'ancien contenu ou le nouveau, jamais un mélange. Le dossier parent est
+//! surveillé, si bien qu'un fichier remplacé par renommage, comme le font la
+//! plupart des outils de génération, est lui aussi détecté.
+//!
+//! Les événements rapprochés sont regroupés : le rechargement a lieu une
+//! fois le fichier stable depuis `SETTLE_DELAY`. Un fichier illisible ou
+//! invalide est ignoré (avec un avertissement journalisé) jusqu'à sa
+//! prochaine modification.
+//!
+//! # Exemple
+//!
+//! ```no_run
+//! use lru_cache::lru::reload::ConflictPolicy;
+//! use lru_cache::lru::sync::SyncCache;
+//!
+//! let cache: SyncCache<String, String> = SyncCache::new(1000);
+//! let _surveillance = cache
+//!     .watch_persisted_file("cache/cache_data.txt", ConflictPolicy::KeepMemory)
+//!     .unwrap();
+//! // Le cache suit désormais les régénérations du fichier
+//! ```
+
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use crate::error::CacheError;
+use crate::lru::reload::ConflictPolicy;
+use crate::lru::sync::SyncCache;
+use crate::messages;
+
+/// Délai sans nouvel événement avant de recharger le fichier.
+pub const SETTLE_DELAY: Duration = Duration::from_millis(50);
+
+/// Poignée de la surveillance du fichier de persistance.
+///
+/// La surveillance s'arrête lorsque la poignée est abandonnée.
+#[derive(Debug)]
+pub struct FileWatcher {
+    path: PathBuf,
+    watcher: Option<RecommendedWatcher>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FileWatcher {
+    /// Retourne le chemin surveillé.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Arrête la surveillance et attend la fin du thread de rechargement.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        // Abandonner l'observateur ferme le canal et termine le thread
+        self.watcher.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone + Display + FromStr + Send + 'static,
+    V: Display + FromStr + Send + 'static,
+{
+    /// Recharge le cache à chaque modification du fichier de persistance,
+    /// selon la politique donnée pour les entrées modifiées en mémoire.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si la surveillance du dossier du
+    /// fichier ne peut pas être mise en place.
+    pub fn watch_persisted_file<P: AsRef<Path>>(&self, path: P, policy: ConflictPolicy) -> Result<FileWatcher, CacheError> {
+        let path = path.as_ref().to_path_buf();
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let name = path.file_name().map(|name| name.to_os_string());
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            let touches_file = event.paths.iter().any(|changed| changed.file_name() == name.as_deref());
+            if touches_file && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                let _ = tx.send(());
+            }
+        })
+        .map_err(watch_error)?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive).map_err(watch_error)?;
+
+        let cache = self.clone();
+        let target = path.clone();
+        let thread = thread::spawn(move || {
+            while rx.recv().is_ok() {
+                // Attendre que les écritures en rafale soient terminées
+                loop {
+                    match rx.recv_timeout(SETTLE_DELAY) {
+                        Ok(()) => continue,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+                match cache.with_lock(|cache| cache.reload_from_file(&target, policy)) {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(err)) | Err(err) => log::warn!("{}: {}", messages::LOG_RELOAD_FAILED, err),
+                }
+            }
+        });
+
+        Ok(FileWatcher {
+            path,
+            watcher: Some(watcher),
+            thread: Some(thread),
+        })
+    }
+}
+
+fn watch_error(err: notify::Error) -> CacheError {
+    match err.kind {
+        notify::ErrorKind::Io(err) => CacheError::IoError(err),
+        _ => CacheError::IoError(io::Error::other(err)),
+    }
+}
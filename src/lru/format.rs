@@ -0,0 +1,338 @@
+//! Module décrivant le format des fichiers de persistance.
+//!
+//! Un fichier écrit par `Cache::persist` commence par une ligne d'en-tête,
+//! suivie d'une ligne `clé<TAB>valeur` par entrée, de la moins à la plus
+//! récemment utilisée :
+//!
+//! ```text
+//! #lru_cache version=1 codec=text entries=2 capacity=100 created=1760000000 checksum=3f6c0d1a9b2e4c57
+//! a<TAB>1
+//! b<TAB>2
+//! ```
+//!
+//! L'en-tête ne contient aucune tabulation, ce qui le distingue d'une entrée ;
+//! les fichiers sans en-tête, écrits par les versions précédentes, restent
+//! lisibles. `describe` identifie un fichier en ne lisant que son en-tête, et
+//! `format_documentation` produit la spécification du format courant.
+//!
+//! Les fichiers de valeurs binaires (codec `bytes`, voir le module `blob`)
+//! portent la même ligne d'en-tête, suivie pour chaque entrée d'une ligne
+//! `clé<TAB>longueur` puis des octets de la valeur et d'un saut de ligne.
+//!
+//! Les fichiers archivés (codec `rkyv`, voir le module `archived`) commencent
+//! par un en-tête binaire de `ARCHIVE_HEADER_LEN` octets portant les mêmes
+//! informations ; `describe` les reconnaît aussi.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::format::{describe, FORMAT_VERSION};
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let path = std::env::temp_dir().join(format!("lru_format_doc_{}.txt", std::process::id()));
+//! let mut cache: Cache<String, i32> = Cache::new(100);
+//! cache.put("a".to_string(), 1);
+//! cache.persist(&path).unwrap();
+//!
+//! let description = describe(&path).unwrap().unwrap();
+//! assert_eq!(description.format_version, FORMAT_VERSION);
+//! assert_eq!((description.entries, description.capacity), (1, 100));
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::Read;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::error::CacheError;
+use crate::messages;
+
+/// Version du format écrite par `Cache::persist`.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Codec des entrées : une ligne `clé<TAB>valeur` par entrée, via `Display`
+/// et `FromStr`.
+pub const TEXT_CODEC: &str = "text";
+
+/// Codec des valeurs binaires : une ligne `clé<TAB>longueur` suivie des
+/// octets bruts de la valeur.
+pub const BYTES_CODEC: &str = "bytes";
+
+/// Codec des fichiers archivés : entrées lisibles sans désérialisation
+/// depuis le fichier projeté en mémoire.
+pub const ARCHIVE_CODEC: &str = "rkyv";
+
+/// Début de la ligne d'en-tête.
+const HEADER_MAGIC: &str = "#lru_cache";
+
+/// Début de l'en-tête binaire des fichiers archivés.
+const ARCHIVE_MAGIC: &[u8; 8] = b"#lrurkyv";
+
+/// Taille de l'en-tête binaire des fichiers archivés : signature, version
+/// (u32, puis 4 octets réservés), nombre d'entrées, capacité et date
+/// d'écriture (u64 petit-boutistes), puis 8 octets réservés. Multiple de 16,
+/// elle préserve l'alignement de l'archive qui suit.
+pub const ARCHIVE_HEADER_LEN: usize = 48;
+
+/// Longueur maximale lue pour trouver l'en-tête.
+const MAX_HEADER_LEN: u64 = 512;
+
+/// Champs de l'en-tête et leur signification, dans l'ordre d'écriture.
+const HEADER_FIELDS: [(&str, &str); 6] = [
+    ("version", messages::FORMAT_FIELD_VERSION),
+    ("codec", messages::FORMAT_FIELD_CODEC),
+    ("entries", messages::FORMAT_FIELD_ENTRIES),
+    ("capacity", messages::FORMAT_FIELD_CAPACITY),
+    ("created", messages::FORMAT_FIELD_CREATED),
+    ("checksum", messages::FORMAT_FIELD_CHECKSUM),
+];
+
+/// Empreinte FNV-1a 64 bits, identique d'un processus à l'autre : somme de
+/// contrôle des lignes d'entrées d'un fichier, et nom des fichiers de
+/// `DirectoryStore`.
+pub(crate) fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = FnvHasher::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Hacheur FNV-1a 64 bits, sans graine : contrairement à `DefaultHasher`,
+/// il donne la même empreinte d'un processus et d'une version de Rust à
+/// l'autre.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = bytes.iter().fold(self.0, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Description d'un fichier de persistance, lue depuis son en-tête.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDescription {
+    /// Version du format
+    pub format_version: u32,
+    /// Encodage des entrées
+    pub codec: String,
+    /// Nombre d'entrées écrites
+    pub entries: usize,
+    /// Capacité du cache sauvegardé
+    pub capacity: usize,
+    /// Date d'écriture du fichier, à la seconde près
+    pub created: SystemTime,
+    /// Somme de contrôle des lignes d'entrées, si l'en-tête en porte une
+    pub checksum: Option<u64>,
+}
+
+impl FileDescription {
+    /// Décrit un fichier au format courant, écrit maintenant.
+    pub(crate) fn new(entries: usize, capacity: usize) -> Self {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        FileDescription {
+            format_version: FORMAT_VERSION,
+            codec: TEXT_CODEC.to_string(),
+            entries,
+            capacity,
+            created: UNIX_EPOCH + Duration::from_secs(secs),
+            checksum: None,
+        }
+    }
+
+    /// Retourne la ligne d'en-tête, sans saut de ligne.
+    pub(crate) fn header_line(&self) -> String {
+        format!("{} {}", HEADER_MAGIC, self)
+    }
+
+    /// Décrit un fichier archivé au format courant, écrit maintenant.
+    #[cfg(feature = "rkyv")]
+    pub(crate) fn archived(entries: usize, capacity: usize) -> Self {
+        FileDescription {
+            codec: ARCHIVE_CODEC.to_string(),
+            ..Self::new(entries, capacity)
+        }
+    }
+
+    /// Retourne l'en-tête binaire d'un fichier archivé.
+    #[cfg(feature = "rkyv")]
+    pub(crate) fn archive_header(&self) -> [u8; ARCHIVE_HEADER_LEN] {
+        let created = self.created.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let mut header = [0; ARCHIVE_HEADER_LEN];
+        header[..8].copy_from_slice(ARCHIVE_MAGIC);
+        header[8..12].copy_from_slice(&self.format_version.to_le_bytes());
+        header[16..24].copy_from_slice(&(self.entries as u64).to_le_bytes());
+        header[24..32].copy_from_slice(&(self.capacity as u64).to_le_bytes());
+        header[32..40].copy_from_slice(&created.to_le_bytes());
+        header
+    }
+
+    /// Analyse l'en-tête binaire d'un fichier archivé : `None` si les
+    /// octets n'en commencent pas un.
+    pub(crate) fn parse_archive_header(bytes: &[u8]) -> Option<Result<Self, String>> {
+        if !bytes.starts_with(ARCHIVE_MAGIC) {
+            return None;
+        }
+        let Some(header) = bytes.get(..ARCHIVE_HEADER_LEN) else {
+            return Some(Err(messages::INVALID_FILE_HEADER.to_string()));
+        };
+        let u64_at = |at: usize| u64::from_le_bytes(header[at..at + 8].try_into().unwrap_or_default());
+        let format_version = u32::from_le_bytes(header[8..12].try_into().unwrap_or_default());
+        if format_version > FORMAT_VERSION {
+            return Some(Err(format!(
+                "{}: version={} codec={}",
+                messages::UNSUPPORTED_FILE_FORMAT,
+                format_version,
+                ARCHIVE_CODEC
+            )));
+        }
+        let (Ok(entries), Ok(capacity)) = (usize::try_from(u64_at(16)), usize::try_from(u64_at(24))) else {
+            return Some(Err(messages::INVALID_FILE_HEADER.to_string()));
+        };
+        Some(Ok(FileDescription {
+            format_version,
+            codec: ARCHIVE_CODEC.to_string(),
+            entries,
+            capacity,
+            created: UNIX_EPOCH + Duration::from_secs(u64_at(32)),
+            checksum: None,
+        }))
+    }
+
+    /// Analyse une ligne d'en-tête : `None` si la ligne n'en est pas une,
+    /// une erreur si l'en-tête est mal formé ou d'un format non pris en
+    /// charge.
+    pub(crate) fn parse_header(line: &str) -> Option<Result<Self, String>> {
+        Self::parse_header_with(line, &[TEXT_CODEC])
+    }
+
+    /// Analyse une ligne d'en-tête dont le codec doit être l'un de ceux
+    /// indiqués.
+    pub(crate) fn parse_header_with(line: &str, codecs: &[&str]) -> Option<Result<Self, String>> {
+        let fields = line.strip_prefix(HEADER_MAGIC)?.strip_prefix(' ')?;
+        if line.contains('\t') {
+            return None;
+        }
+        Some(Self::parse_fields(fields, codecs))
+    }
+
+    fn parse_fields(fields: &str, codecs: &[&str]) -> Result<Self, String> {
+        let invalid = || format!("{}: {}", messages::INVALID_FILE_HEADER, fields);
+        let field = |name: &str| {
+            fields
+                .split(' ')
+                .find_map(|field| field.strip_prefix(name)?.strip_prefix('='))
+                .ok_or_else(invalid)
+        };
+        let number = |name: &str| field(name)?.parse::<u64>().map_err(|_| invalid());
+
+        let format_version = u32::try_from(number("version")?).map_err(|_| invalid())?;
+        let codec = field("codec")?.to_string();
+        if format_version > FORMAT_VERSION || !codecs.contains(&codec.as_str()) {
+            return Err(format!(
+                "{}: version={} codec={}",
+                messages::UNSUPPORTED_FILE_FORMAT,
+                format_version,
+                codec
+            ));
+        }
+        Ok(FileDescription {
+            format_version,
+            codec,
+            entries: usize::try_from(number("entries")?).map_err(|_| invalid())?,
+            capacity: usize::try_from(number("capacity")?).map_err(|_| invalid())?,
+            created: UNIX_EPOCH + Duration::from_secs(number("created")?),
+            checksum: match field("checksum") {
+                Ok(hex) => Some(u64::from_str_radix(hex, 16).map_err(|_| invalid())?),
+                Err(_) => None,
+            },
+        })
+    }
+}
+
+impl fmt::Display for FileDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let created = self.created.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        write!(
+            f,
+            "version={} codec={} entries={} capacity={} created={}",
+            self.format_version, self.codec, self.entries, self.capacity, created
+        )?;
+        if let Some(checksum) = self.checksum {
+            write!(f, " checksum={:016x}", checksum)?;
+        }
+        Ok(())
+    }
+}
+
+/// Décrit un fichier de persistance en ne lisant que son en-tête.
+///
+/// Retourne `None` si le fichier n'a pas d'en-tête (fichier vide ou écrit
+/// par une version précédente).
+///
+/// # Errors
+///
+/// Retourne `CacheError::IoError` si le fichier ne peut pas être lu, et
+/// `CacheError::Corrupted` si son en-tête est mal formé ou d'un format non
+/// pris en charge.
+pub fn describe<P: AsRef<Path>>(path: P) -> Result<Option<FileDescription>, CacheError> {
+    let path = path.as_ref();
+    let mut start = Vec::new();
+    File::open(path)?.take(MAX_HEADER_LEN).read_to_end(&mut start)?;
+    let header = FileDescription::parse_archive_header(&start).or_else(|| {
+        let line = start.split(|&byte| byte == b'\n').next().unwrap_or_default();
+        let line = String::from_utf8_lossy(line);
+        FileDescription::parse_header_with(line.trim_end_matches('\r'), &[TEXT_CODEC, BYTES_CODEC])
+    });
+    match header {
+        None => Ok(None),
+        Some(Ok(description)) => Ok(Some(description)),
+        Some(Err(reason)) => Err(CacheError::Corrupted {
+            path: Some(path.to_path_buf()),
+            line: 1,
+            reason,
+        }),
+    }
+}
+
+/// Produit la spécification, en Markdown, du format écrit par cette version
+/// de la bibliothèque.
+pub fn format_documentation() -> String {
+    let mut doc = format!("# {} {})\n\n", messages::FORMAT_DOC_TITLE, FORMAT_VERSION);
+    doc.push_str(&format!("{}\n\n", messages::FORMAT_DOC_HEADER));
+    doc.push_str(&format!("    {} {}\n\n", HEADER_MAGIC, FileDescription::new(2, 100)));
+    doc.push_str(&format!("{}\n\n", messages::FORMAT_DOC_FIELDS));
+    doc.push_str(&format!("{}\n|-------|---------------|\n", messages::FORMAT_DOC_COLUMNS));
+    for (name, meaning) in HEADER_FIELDS {
+        doc.push_str(&format!("| `{}` | {} |\n", name, meaning));
+    }
+    let paragraphs = [
+        messages::FORMAT_DOC_TEXT_ENTRIES.replace("{codec}", TEXT_CODEC),
+        messages::FORMAT_DOC_BYTES_ENTRIES.replace("{codec}", BYTES_CODEC),
+        messages::FORMAT_DOC_COMPATIBILITY.replace("{version}", &FORMAT_VERSION.to_string()),
+        messages::FORMAT_DOC_ARCHIVE
+            .replace("{codec}", ARCHIVE_CODEC)
+            .replace("{length}", &ARCHIVE_HEADER_LEN.to_string())
+            .replace("{magic}", &String::from_utf8_lossy(ARCHIVE_MAGIC)),
+    ];
+    for paragraph in paragraphs {
+        doc.push('\n');
+        doc.push_str(&paragraph);
+        doc.push('\n');
+    }
+    doc
+}
@@ -0,0 +1,105 @@
+//! Module implémentant le vieillissement des compteurs de lectures.
+//!
+//! Chaque entrée compte ses lectures (`hit_count`), ce qui sert notamment à
+//! repérer les entrées populaires à rafraîchir. Sans vieillissement, une clé
+//! très lue par le passé mais délaissée depuis reste « populaire » pour
+//! toujours. Une politique `FrequencyDecay` divise périodiquement tous les
+//! compteurs par deux, après un nombre de lectures ou une durée donnés : les
+//! compteurs reflètent alors la popularité récente.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::frequency::FrequencyDecay;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::builder()
+//!     .capacity(10)
+//!     .frequency_decay(FrequencyDecay::EveryOps(8))
+//!     .build()
+//!     .unwrap();
+//! cache.put("ancienne", 1);
+//! cache.put("récente", 2);
+//!
+//! for _ in 0..6 {
+//!     cache.get(&"ancienne");
+//! }
+//! for _ in 0..2 {
+//!     cache.get(&"récente"); // la 8e lecture divise les compteurs par deux
+//! }
+//! assert_eq!(cache.hit_count(&"ancienne"), Some(3));
+//! assert_eq!(cache.hit_count(&"récente"), Some(1));
+//! ```
+
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+use crate::lru::Cache;
+
+/// Politique de vieillissement des compteurs de lectures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrequencyDecay {
+    /// Divise les compteurs par deux toutes les `n` lectures réussies
+    EveryOps(u64),
+    /// Divise les compteurs par deux lors de la première lecture réussie
+    /// suivant l'écoulement de la durée donnée
+    Every(Duration),
+}
+
+/// État du vieillissement configuré sur un cache.
+#[derive(Debug, Clone)]
+pub(crate) struct Decay {
+    policy: FrequencyDecay,
+    ops: u64,
+    last: Option<Instant>,
+}
+
+impl Decay {
+    pub(crate) fn new(policy: FrequencyDecay) -> Self {
+        Decay { policy, ops: 0, last: None }
+    }
+
+    /// Enregistre une lecture et indique si les compteurs doivent vieillir.
+    fn tick(&mut self, now: Instant) -> bool {
+        match self.policy {
+            FrequencyDecay::EveryOps(n) => {
+                self.ops += 1;
+                if self.ops < n.max(1) {
+                    return false;
+                }
+                self.ops = 0;
+                true
+            }
+            FrequencyDecay::Every(interval) => {
+                let last = *self.last.get_or_insert(now);
+                if now.duration_since(last) < interval {
+                    return false;
+                }
+                self.last = Some(now);
+                true
+            }
+        }
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Divise immédiatement par deux les compteurs de lectures de toutes les
+    /// entrées.
+    pub fn decay_frequencies(&mut self) {
+        for entry in self.elements.values_mut() {
+            entry.hits /= 2;
+            entry.refreshed_hits /= 2;
+        }
+    }
+
+    /// Applique la politique de vieillissement après une lecture réussie.
+    pub(crate) fn record_hit_for_decay(&mut self) {
+        let now = self.clock.now();
+        if self.decay.as_mut().is_some_and(|decay| decay.tick(now)) {
+            self.decay_frequencies();
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Module fournissant une vue figée, en lecture seule, d'un cache.
+//!
+//! `Cache::freeze` capture l'état courant du cache dans un `FrozenCache`.
+//! L'instantané est copié une seule fois puis partagé derrière un `Arc` :
+//! cloner un `FrozenCache` est donc peu coûteux, et il peut être transmis à
+//! d'autres threads pendant que le cache d'origine continue d'évoluer.
+//!
+//! L'instantané range chaque clé et chaque valeur derrière un `Arc`, avec
+//! la version de l'entrée au moment du gel. `Cache::freeze` les copie une
+//! fois ; `Cache::refreeze` reprend ensuite de l'instantané précédent les
+//! entrées dont la version n'a pas changé, sans copier ni clé ni valeur :
+//! seules les entrées écrites depuis sont copiées, et geler régulièrement un
+//! cache peu modifié ne coûte que la table de pointeurs du nouvel
+//! instantané.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = Cache::new(2);
+//! cache.put("a", 1);
+//! let snapshot = cache.freeze();
+//!
+//! cache.put("b", 2);
+//! cache.put("c", 3); // évince "a" du cache, pas de l'instantané
+//!
+//! assert_eq!(snapshot.peek(&"a"), Some(&1));
+//! assert_eq!(snapshot.len(), 1);
+//!
+//! // "b" n'a pas changé : sa valeur est partagée avec le nouvel instantané
+//! let avant = cache.freeze();
+//! cache.put("c", 4);
+//! let apres = cache.refreeze(&avant);
+//! assert!(std::ptr::eq(avant.peek(&"b").unwrap(), apres.peek(&"b").unwrap()));
+//! assert_eq!(apres.peek(&"c"), Some(&4));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+use crate::lru::Cache;
+use crate::lru::traits::CacheRead;
+
+/// Contenu d'un instantané.
+#[derive(Debug)]
+pub(crate) struct Snapshot<K, V> {
+    /// Identité du cache gelé
+    origin: Arc<()>,
+    pub(crate) capacity: usize,
+    pub(crate) elements: HashMap<Arc<K>, FrozenEntry<V>>,
+    pub(crate) usage_order: Vec<Arc<K>>,
+}
+
+/// Valeur d'un instantané, partageable avec les instantanés suivants.
+#[derive(Debug)]
+pub(crate) struct FrozenEntry<V> {
+    /// Version de l'entrée au moment du gel
+    version: u64,
+    pub(crate) value: Arc<V>,
+}
+
+/// Instantané immuable d'un cache, partageable entre threads.
+///
+/// Les lectures ne mettent jamais à jour l'ordre d'utilisation.
+#[derive(Debug)]
+pub struct FrozenCache<K, V> {
+    snapshot: Arc<Snapshot<K, V>>,
+}
+
+impl<K, V> Clone for FrozenCache<K, V> {
+    fn clone(&self) -> Self {
+        FrozenCache {
+            snapshot: Arc::clone(&self.snapshot),
+        }
+    }
+}
+
+impl<K, V> FrozenCache<K, V>
+where
+    K: Hash + Eq,
+{
+    /// Crée la vue d'un instantané déjà partagé.
+    pub(crate) fn from_snapshot(snapshot: Arc<Snapshot<K, V>>) -> Self {
+        FrozenCache { snapshot }
+    }
+
+    /// Retourne un itérateur sur les paires clé-valeur, du moins récemment
+    /// utilisé au plus récemment utilisé au moment du gel.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.snapshot.usage_order.iter().filter_map(|key| {
+            self.snapshot.elements.get(key).map(|entry| (&**key, &*entry.value))
+        })
+    }
+
+    /// Parcourt les entrées comme `iter`. Un instantané n'est jamais
+    /// modifié : le parcours peut avoir lieu depuis plusieurs threads à la
+    /// fois, sans effet sur le cache d'origine ni sur ses statistiques.
+    pub fn peek_iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+}
+
+impl<K, V> CacheRead<K, V> for FrozenCache<K, V>
+where
+    K: Hash + Eq,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.snapshot.elements.get(key).map(|entry| &*entry.value)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.snapshot.elements.contains_key(key)
+    }
+
+    fn len(&self) -> usize {
+        self.snapshot.elements.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.snapshot.capacity
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Capture l'état courant du cache dans une vue en lecture seule.
+    pub fn freeze(&self) -> FrozenCache<K, V> {
+        FrozenCache::from_snapshot(Arc::new(self.snapshot(None)))
+    }
+
+    /// Capture l'état courant du cache comme `freeze`, en partageant avec
+    /// `previous`, un instantané antérieur de ce cache, les clés et les
+    /// valeurs des entrées qui n'ont pas été écrites depuis. Un instantané
+    /// d'un autre cache, dont les versions ne sont pas comparables, n'est
+    /// pas repris.
+    pub fn refreeze(&self, previous: &FrozenCache<K, V>) -> FrozenCache<K, V> {
+        FrozenCache::from_snapshot(Arc::new(self.snapshot(Some(&previous.snapshot))))
+    }
+
+    /// Copie l'état courant du cache, sans les entrées expirées, en
+    /// reprenant de `previous` les entrées dont la version n'a pas changé.
+    pub(crate) fn snapshot(&self, previous: Option<&Snapshot<K, V>>) -> Snapshot<K, V> {
+        // Les versions ne sont uniques qu'au sein d'un même cache
+        let previous = previous.filter(|previous| Arc::ptr_eq(&previous.origin, &self.identity));
+        let mut elements = HashMap::with_capacity(self.elements.len());
+        let mut usage_order = Vec::with_capacity(self.elements.len());
+        for key in self.recency_order().filter(|key| !self.is_expired(key)) {
+            let Some(entry) = self.elements.get(key) else {
+                continue;
+            };
+            let unchanged = previous
+                .and_then(|previous| previous.elements.get_key_value(key))
+                .filter(|(_, frozen)| frozen.version == entry.version);
+            let (key, value) = match unchanged {
+                Some((key, frozen)) => (Arc::clone(key), Arc::clone(&frozen.value)),
+                None => (Arc::new(key.clone()), Arc::new(entry.value.clone())),
+            };
+            elements.insert(Arc::clone(&key), FrozenEntry { version: entry.version, value });
+            usage_order.push(key);
+        }
+        Snapshot { origin: Arc::clone(&self.identity), capacity: self.capacity, elements, usage_order }
+    }
+}
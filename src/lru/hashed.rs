@@ -0,0 +1,130 @@
+//! Module exposant les accès par empreinte précalculée.
+//!
+//! Un appelant qui connaît déjà l'empreinte d'une clé (calculée par
+//! `hash_key`, par exemple conservée dans un index amont) peut la fournir à
+//! `get_with_hash` : la table des entrées n'a alors plus à hacher la clé.
+//! `find_with_hash` va plus loin et compare les clés candidates avec un
+//! prédicat, ce qui permet de chercher une clé composite sans la construire.
+//!
+//! L'empreinte doit provenir de `hash_key` sur le même cache : chaque cache
+//! a sa propre graine de hachage. Une empreinte erronée ne corrompt rien,
+//! mais la clé n'est alors pas trouvée.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::new(10);
+//! cache.put(("fr".to_string(), 42), "quarante-deux");
+//!
+//! let cle = ("fr".to_string(), 42);
+//! let empreinte = cache.hash_key(&cle);
+//! assert_eq!(cache.get_with_hash(empreinte, &cle), Some(&"quarante-deux"));
+//!
+//! // Recherche sans construire la clé possédée
+//! let trouve = cache.find_with_hash(empreinte, |(langue, n)| langue == "fr" && *n == 42);
+//! assert_eq!(trouve.map(|(_, valeur)| *valeur), Some("quarante-deux"));
+//! ```
+
+use std::borrow::Cow;
+use std::hash::{BuildHasher, Hash};
+use hashbrown::hash_map::RawEntryMut;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+use crate::lru::stats::TimedOp;
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Calcule l'empreinte d'une clé avec la fonction de hachage du cache.
+    pub fn hash_key<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+        self.elements.hasher().hash_one(key)
+    }
+
+    /// Récupère la valeur associée à la clé, comme `get`, à partir de son
+    /// empreinte calculée par `hash_key`.
+    pub fn get_with_hash(&mut self, hash: u64, key: &K) -> Option<&V> {
+        self.get_matching(hash, |candidate| candidate == key, || Cow::Borrowed(key))
+    }
+
+    /// Recherche, sans modifier l'ordre d'utilisation, l'entrée valide dont
+    /// l'empreinte est donnée et dont la clé satisfait le prédicat.
+    pub fn find_with_hash<F>(&self, hash: u64, mut is_match: F) -> Option<(&K, &V)>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        let (key, entry) = self.elements.raw_entry().from_hash(hash, |key| is_match(key))?;
+        if self.is_expired(key) {
+            return None;
+        }
+        Some((key, &entry.value))
+    }
+
+    /// Lecture commune à toutes les formes de clé : l'entrée est repérée par
+    /// son empreinte et le prédicat, et la clé possédée n'est construite
+    /// (`to_key`) que si une expiration, le débordement ou les crochets en
+    /// ont besoin.
+    pub(crate) fn get_matching<'k, F, T>(&mut self, hash: u64, is_match: F, to_key: T) -> Option<&V>
+    where
+        K: 'k,
+        F: Fn(&K) -> bool,
+        T: FnOnce() -> Cow<'k, K>,
+    {
+        let start = self.start_timer();
+        let expired = match self.elements.raw_entry().from_hash(hash, &is_match) {
+            Some((key, _)) => self.is_expired(key),
+            None => false,
+        };
+        if expired {
+            let key = to_key();
+            self.remove_entry(&key, RemovalCause::Expired);
+            self.record_read(false);
+            self.observers.miss(&key);
+            self.record_latency(TimedOp::Get, start);
+            return None;
+        }
+
+        if self.elements.raw_entry().from_hash(hash, &is_match).is_none() {
+            let key = (self.overflow.is_some() || self.observers.wants_misses()).then(to_key);
+            if let Some(key) = key.as_ref().filter(|_| self.overflow.is_some()) {
+                self.recall_overflow(key);
+            }
+            if self.elements.raw_entry().from_hash(hash, &is_match).is_none() {
+                self.record_read(false);
+                if let Some(key) = key.as_ref() {
+                    self.observers.miss(key);
+                }
+                self.record_latency(TimedOp::Get, start);
+                return None;
+            }
+        }
+
+        self.move_to_recently_used_by(hash, &is_match);
+        self.record_read(true);
+        if self.decay.is_some() {
+            self.record_hit_for_decay();
+        }
+        self.record_latency(TimedOp::Get, start);
+        let tracked = self.track_access || self.observers.stats.is_some();
+        let now = tracked.then(|| self.clock.now());
+        match self.elements.raw_entry_mut().from_hash(hash, &is_match) {
+            RawEntryMut::Occupied(occupied) => {
+                let (key, entry) = occupied.into_key_value();
+                if let Some(now) = now {
+                    if let Some(stats) = self.observers.stats.as_mut() {
+                        stats.record_idle_time(now.saturating_duration_since(entry.accessed));
+                    }
+                    entry.accessed = now;
+                }
+                entry.hits += 1;
+                entry.warmed = false;
+                self.observers.hooks.hit(key, &entry.value);
+                Some(&entry.value)
+            }
+            RawEntryMut::Vacant(_) => None,
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Module définissant les crochets du cycle de vie des entrées.
+//!
+//! Plutôt qu'un rappel dédié à chaque événement, `CacheHooks` regroupe les
+//! notifications d'un composant (statistiques, journalisation, métriques...)
+//! dans une seule implémentation, enregistrée sur le constructeur
+//! (`CacheBuilder::hooks`) ou sur le cache (`Cache::add_hooks`). Toutes les
+//! méthodes ont une implémentation vide par défaut : il suffit de redéfinir
+//! celles qui intéressent le composant.
+//!
+//! Les méthodes prennent `&self` et sont appelées sous l'emprunt du cache :
+//! elles doivent rester brèves et utiliser une mutabilité intérieure pour
+//! tenir leur état.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::hooks::CacheHooks;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! #[derive(Default)]
+//! struct Compteurs {
+//!     succes: AtomicU64,
+//!     evictions: AtomicU64,
+//! }
+//!
+//! impl CacheHooks<&'static str, i32> for Compteurs {
+//!     fn on_hit(&self, _key: &&'static str, _value: &i32) {
+//!         self.succes.fetch_add(1, Ordering::Relaxed);
+//!     }
+//!
+//!     fn on_evict(&self, _key: &&'static str, _value: &i32) {
+//!         self.evictions.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let compteurs = Arc::new(Compteurs::default());
+//! let mut cache = Cache::builder()
+//!     .capacity(1)
+//!     .hooks(compteurs.clone())
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.put("a", 1);
+//! cache.get(&"a");
+//! cache.put("b", 2);
+//! assert_eq!(compteurs.succes.load(Ordering::Relaxed), 1);
+//! assert_eq!(compteurs.evictions.load(Ordering::Relaxed), 1);
+//! ```
+
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+use crate::lru::Cache;
+use crate::lru::events::{Mutation, RemovalCause};
+
+/// Crochets appelés aux étapes du cycle de vie des entrées.
+pub trait CacheHooks<K, V>: Send + Sync {
+    /// Appelé lorsqu'une lecture trouve une valeur.
+    fn on_hit(&self, _key: &K, _value: &V) {}
+
+    /// Appelé lorsqu'une lecture ne trouve pas de valeur.
+    fn on_miss(&self, _key: &K) {}
+
+    /// Appelé lorsqu'une nouvelle clé est insérée.
+    fn on_insert(&self, _key: &K, _value: &V) {}
+
+    /// Appelé lorsque la valeur d'une clé existante est remplacée ou
+    /// fusionnée.
+    fn on_update(&self, _key: &K, _value: &V) {}
+
+    /// Appelé lorsqu'une entrée est évincée pour respecter la capacité ou le
+    /// budget de poids.
+    fn on_evict(&self, _key: &K, _value: &V) {}
+
+    /// Appelé lorsqu'une entrée quitte le cache, quelle qu'en soit la cause.
+    ///
+    /// L'implémentation par défaut appelle `on_evict` pour les évictions.
+    fn on_remove(&self, key: &K, value: &V, cause: RemovalCause) {
+        if cause.is_eviction() {
+            self.on_evict(key, value);
+        }
+    }
+}
+
+impl<K, V, H> CacheHooks<K, V> for Arc<H>
+where
+    H: CacheHooks<K, V> + ?Sized,
+{
+    fn on_hit(&self, key: &K, value: &V) {
+        (**self).on_hit(key, value)
+    }
+
+    fn on_miss(&self, key: &K) {
+        (**self).on_miss(key)
+    }
+
+    fn on_insert(&self, key: &K, value: &V) {
+        (**self).on_insert(key, value)
+    }
+
+    fn on_update(&self, key: &K, value: &V) {
+        (**self).on_update(key, value)
+    }
+
+    fn on_evict(&self, key: &K, value: &V) {
+        (**self).on_evict(key, value)
+    }
+
+    fn on_remove(&self, key: &K, value: &V, cause: RemovalCause) {
+        (**self).on_remove(key, value, cause)
+    }
+}
+
+/// Crochets enregistrés sur un cache.
+pub(crate) struct Hooks<K, V>(Vec<Arc<dyn CacheHooks<K, V>>>);
+
+impl<K, V> Hooks<K, V> {
+    pub(crate) fn push(&mut self, hooks: Arc<dyn CacheHooks<K, V>>) {
+        self.0.push(hooks);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn hit(&self, key: &K, value: &V) {
+        for hooks in &self.0 {
+            hooks.on_hit(key, value);
+        }
+    }
+
+    pub(crate) fn miss(&self, key: &K) {
+        for hooks in &self.0 {
+            hooks.on_miss(key);
+        }
+    }
+
+    /// Transmet une mutation aux crochets correspondants.
+    pub(crate) fn mutation(&self, mutation: &Mutation<'_, K, V>) {
+        for hooks in &self.0 {
+            match *mutation {
+                Mutation::Insert { key, value } => hooks.on_insert(key, value),
+                Mutation::Update { key, value } => hooks.on_update(key, value),
+                Mutation::Remove { key, value, cause } => hooks.on_remove(key, value, cause),
+            }
+        }
+    }
+}
+
+impl<K, V> Default for Hooks<K, V> {
+    fn default() -> Self {
+        Hooks(Vec::new())
+    }
+}
+
+impl<K, V> Clone for Hooks<K, V> {
+    fn clone(&self) -> Self {
+        Hooks(self.0.clone())
+    }
+}
+
+impl<K, V> fmt::Debug for Hooks<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Hooks").field(&self.0.len()).finish()
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Enregistre des crochets sur le cache.
+    pub fn add_hooks<H>(&mut self, hooks: H)
+    where
+        H: CacheHooks<K, V> + 'static,
+    {
+        self.observers.hooks.push(Arc::new(hooks));
+    }
+}
@@ -0,0 +1,221 @@
+//! Module vérifiant et réparant un fichier de persistance au chargement.
+//!
+//! `Cache::new_persistent` refuse un fichier dont une ligne est invalide.
+//! `new_persistent_repaired` (ou `CacheBuilder::build_persistent_repaired`)
+//! charge au contraire tout ce qui peut l'être et retourne un
+//! `IntegrityReport` décrivant ce qui a été réparé :
+//!
+//! * la somme de contrôle de l'en-tête est comparée aux lignes d'entrées ;
+//! * les lignes mal formées ou impossibles à analyser sont écartées ;
+//! * une clé présente plusieurs fois n'est conservée qu'à sa dernière
+//!   position, ce qui reconstruit un ordre d'utilisation cohérent ;
+//! * le nombre d'entrées annoncé par l'en-tête est comparé aux entrées lues,
+//!   et celles qui dépassent la capacité sont signalées.
+//!
+//! Un fichier d'un format non pris en charge reste une erreur : il ne peut
+//! pas être réparé.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::CacheRead;
+//!
+//! let path = std::env::temp_dir().join(format!("lru_integrity_doc_{}.txt", std::process::id()));
+//! std::fs::write(&path, "a\t1\nligne abîmée\nb\tdeux\nc\t3\n").unwrap();
+//!
+//! let (cache, report) = Cache::<String, i32>::new_persistent_repaired(10, &path).unwrap();
+//! assert_eq!(cache.len(), 2);
+//! assert_eq!(report.dropped.len(), 2);
+//! assert!(!report.is_clean());
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+use std::str::FromStr;
+use crate::error::CacheError;
+use crate::messages;
+use crate::lru::Cache;
+use crate::lru::format::{self, FileDescription};
+use crate::lru::traits::CacheTrait;
+
+/// Résultat de la vérification de la somme de contrôle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    /// La somme de contrôle correspond aux lignes d'entrées
+    Valid,
+    /// Les lignes d'entrées ne correspondent pas à la somme de contrôle
+    Mismatch {
+        /// Somme annoncée par l'en-tête
+        expected: u64,
+        /// Somme des lignes lues
+        actual: u64,
+    },
+    /// Le fichier ne porte pas de somme de contrôle
+    Missing,
+}
+
+/// Ligne écartée lors du chargement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DroppedRecord {
+    /// Numéro de la ligne (à partir de 1)
+    pub line: usize,
+    /// Raison de l'écart
+    pub reason: String,
+}
+
+/// Compte rendu de la vérification et des réparations d'un chargement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Vérification de la somme de contrôle
+    pub checksum: ChecksumStatus,
+    /// Lignes écartées
+    pub dropped: Vec<DroppedRecord>,
+    /// Occurrences surnuméraires de clés présentes plusieurs fois
+    pub duplicates: usize,
+    /// Nombre d'entrées annoncé par l'en-tête, s'il y en a un
+    pub expected_entries: Option<usize>,
+    /// Nombre d'entrées distinctes lues
+    pub loaded: usize,
+    /// Entrées les plus anciennes écartées faute de capacité
+    pub over_capacity: usize,
+}
+
+impl IntegrityReport {
+    fn new() -> Self {
+        IntegrityReport {
+            checksum: ChecksumStatus::Missing,
+            dropped: Vec::new(),
+            duplicates: 0,
+            expected_entries: None,
+            loaded: 0,
+            over_capacity: 0,
+        }
+    }
+
+    /// Vérifie qu'aucune anomalie n'a été détectée. Un fichier sans somme de
+    /// contrôle (format antérieur) peut être sain.
+    pub fn is_clean(&self) -> bool {
+        !matches!(self.checksum, ChecksumStatus::Mismatch { .. })
+            && self.dropped.is_empty()
+            && self.duplicates == 0
+            && self.expected_entries.is_none_or(|expected| expected == self.loaded + self.duplicates)
+            && self.over_capacity == 0
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone + Display + FromStr,
+    V: Display + FromStr,
+{
+    /// Crée un cache persistant en réparant son fichier au besoin, et
+    /// retourne le compte rendu des vérifications. Un fichier absent donne
+    /// un cache vide et un compte rendu sain.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la capacité est 0,
+    /// `CacheError::IoError` si le fichier ne peut pas être lu, et
+    /// `CacheError::Corrupted` si son format n'est pas pris en charge.
+    pub fn new_persistent_repaired<P: AsRef<Path>>(capacity: usize, path: P) -> Result<(Self, IntegrityReport), CacheError> {
+        let mut cache = Self::try_new(capacity)?;
+        let report = cache.load_file_repaired(path)?;
+        Ok((cache, report))
+    }
+
+    /// Charge le fichier de persistance, s'il existe, en écartant ce qui ne
+    /// peut pas l'être.
+    pub(crate) fn load_file_repaired<P: AsRef<Path>>(&mut self, path: P) -> Result<IntegrityReport, CacheError> {
+        let path = path.as_ref();
+        let mut report = IntegrityReport::new();
+        let content = match fs::read(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(report),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut lines = content.split_inclusive(|&byte| byte == b'\n').enumerate().peekable();
+        if let Some(&(_, raw)) = lines.peek() {
+            let first = String::from_utf8_lossy(raw);
+            match FileDescription::parse_header(first.trim_end_matches(['\n', '\r'])) {
+                Some(Ok(description)) => {
+                    report.expected_entries = Some(description.entries);
+                    if let Some(expected) = description.checksum {
+                        let actual = format::checksum(&content[raw.len()..]);
+                        report.checksum = if actual == expected {
+                            ChecksumStatus::Valid
+                        } else {
+                            ChecksumStatus::Mismatch { expected, actual }
+                        };
+                    }
+                    lines.next();
+                }
+                Some(Err(reason)) if reason.starts_with(messages::UNSUPPORTED_FILE_FORMAT) => {
+                    return Err(CacheError::Corrupted {
+                        path: Some(path.to_path_buf()),
+                        line: 1,
+                        reason,
+                    });
+                }
+                Some(Err(reason)) => {
+                    report.dropped.push(DroppedRecord { line: 1, reason });
+                    lines.next();
+                }
+                None => {}
+            }
+        }
+
+        let mut entries = Vec::new();
+        for (index, line) in lines {
+            let line = line.strip_suffix(b"\n").unwrap_or(line);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.is_empty() {
+                continue;
+            }
+            match parse_record::<K, V>(line) {
+                Ok(entry) => entries.push(entry),
+                Err(reason) => report.dropped.push(DroppedRecord { line: index + 1, reason }),
+            }
+        }
+
+        // Seule la dernière occurrence d'une clé compte : parcourir depuis la
+        // fin garde la position la plus récente de chaque clé
+        let mut seen = HashSet::new();
+        let mut unique = Vec::with_capacity(entries.len());
+        for (key, value) in entries.into_iter().rev() {
+            if seen.insert(key.clone()) {
+                unique.push((key, value));
+            } else {
+                report.duplicates += 1;
+            }
+        }
+        unique.reverse();
+
+        report.loaded = unique.len();
+        report.over_capacity = unique.len().saturating_sub(self.capacity);
+        for (key, value) in unique {
+            self.put(key, value);
+        }
+        self.mark_synced();
+
+        if !report.is_clean() {
+            log::warn!("{}: {}", messages::LOG_FILE_REPAIRED, path.display());
+        }
+        Ok(report)
+    }
+}
+
+fn parse_record<K: FromStr, V: FromStr>(line: &[u8]) -> Result<(K, V), String> {
+    let line = std::str::from_utf8(line).map_err(|_| messages::INVALID_LINE_FORMAT.to_string())?;
+    let Some((key, value)) = line.split_once('\t').filter(|(_, value)| !value.contains('\t')) else {
+        return Err(messages::INVALID_LINE_FORMAT.to_string());
+    };
+    let key = K::from_str(key).map_err(|_| format!("{}: {}", messages::UNPARSABLE_KEY, key))?;
+    let value = V::from_str(value).map_err(|_| format!("{}: {}", messages::UNPARSABLE_VALUE, value))?;
+    Ok((key, value))
+}
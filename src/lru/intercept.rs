@@ -0,0 +1,207 @@
+//! Module fournissant une chaîne d'intercepteurs autour d'un cache.
+//!
+//! `InterceptedCache` encapsule n'importe quelle implémentation de
+//! `CacheTrait` et fait passer chaque opération par une liste ordonnée
+//! d'`Interceptor` avant qu'elle n'atteigne le cache : un intercepteur peut
+//! réécrire les clés (normalisation, préfixe de locataire...), refuser ou
+//! transformer une insertion, ou simplement observer les opérations, par
+//! exemple pour les journaliser en masquant les valeurs sensibles.
+//!
+//! Les intercepteurs sont appliqués dans l'ordre de leur enregistrement :
+//! chacun reçoit la clé et la valeur produites par le précédent.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::intercept::{InterceptedCache, Interceptor};
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! /// Normalise les clés en minuscules.
+//! struct Minuscules;
+//!
+//! impl Interceptor<String, String> for Minuscules {
+//!     fn map_key(&self, key: &String) -> Option<String> {
+//!         Some(key.to_lowercase())
+//!     }
+//! }
+//!
+//! /// Refuse les valeurs vides.
+//! struct PasDeVide;
+//!
+//! impl Interceptor<String, String> for PasDeVide {
+//!     fn on_put(&self, _key: &String, value: String) -> Option<String> {
+//!         Some(value).filter(|value| !value.is_empty())
+//!     }
+//! }
+//!
+//! let mut cache = InterceptedCache::new(Cache::new(10))
+//!     .with(Minuscules)
+//!     .with(PasDeVide);
+//!
+//! cache.put("Paris".to_string(), "FR".to_string());
+//! cache.put("Vide".to_string(), String::new());
+//!
+//! assert_eq!(cache.get(&"PARIS".to_string()), Some(&"FR".to_string()));
+//! assert!(!cache.contains(&"vide".to_string()));
+//! assert_eq!(cache.inner().len(), 1);
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Étape de la chaîne traversée par les opérations d'un `InterceptedCache`.
+///
+/// Toutes les méthodes ont une implémentation par défaut qui laisse passer
+/// l'opération inchangée.
+pub trait Interceptor<K, V>: Send {
+    /// Retourne la clé à utiliser à la place de celle reçue, ou `None` pour
+    /// la conserver. Appelé pour toutes les opérations portant sur une clé.
+    fn map_key(&self, _key: &K) -> Option<K> {
+        None
+    }
+
+    /// Retourne la valeur à insérer, éventuellement transformée, ou `None`
+    /// pour refuser l'insertion.
+    fn on_put(&self, _key: &K, value: V) -> Option<V> {
+        Some(value)
+    }
+
+    /// Observe le résultat d'une lecture.
+    fn on_get(&self, _key: &K, _value: Option<&V>) {}
+
+    /// Observe une suppression explicite.
+    fn on_remove(&self, _key: &K, _value: Option<&V>) {}
+}
+
+/// Cache dont les opérations traversent une chaîne d'intercepteurs.
+pub struct InterceptedCache<C, K, V> {
+    inner: C,
+    interceptors: Vec<Box<dyn Interceptor<K, V>>>,
+}
+
+impl<C, K, V> InterceptedCache<C, K, V>
+where
+    K: Clone,
+{
+    /// Encapsule le cache donné, sans intercepteur.
+    pub fn new(inner: C) -> Self {
+        InterceptedCache {
+            inner,
+            interceptors: Vec::new(),
+        }
+    }
+
+    /// Ajoute un intercepteur en fin de chaîne.
+    pub fn with<I>(mut self, interceptor: I) -> Self
+    where
+        I: Interceptor<K, V> + 'static,
+    {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
+    /// Retourne le nombre d'intercepteurs de la chaîne.
+    pub fn depth(&self) -> usize {
+        self.interceptors.len()
+    }
+
+    /// Retourne une référence au cache encapsulé.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Consomme le décorateur et retourne le cache encapsulé.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Applique les réécritures de clé de toute la chaîne.
+    fn resolve<'a>(&self, key: &'a K) -> Cow<'a, K> {
+        let mut key = Cow::Borrowed(key);
+        for interceptor in &self.interceptors {
+            if let Some(rewritten) = interceptor.map_key(&key) {
+                key = Cow::Owned(rewritten);
+            }
+        }
+        key
+    }
+}
+
+impl<C, K, V> fmt::Debug for InterceptedCache<C, K, V>
+where
+    C: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InterceptedCache")
+            .field("inner", &self.inner)
+            .field("interceptors", &self.interceptors.len())
+            .finish()
+    }
+}
+
+impl<C, K, V> CacheRead<K, V> for InterceptedCache<C, K, V>
+where
+    C: CacheRead<K, V>,
+    K: Clone,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.inner.peek(&self.resolve(key))
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.inner.contains(&self.resolve(key))
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+}
+
+impl<C, K, V> CacheTrait<K, V> for InterceptedCache<C, K, V>
+where
+    C: CacheTrait<K, V>,
+    K: Clone,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let key = self.resolve(key);
+        let value = self.inner.get(&key);
+        for interceptor in &self.interceptors {
+            interceptor.on_get(&key, value);
+        }
+        value
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let key = self.resolve(&key).into_owned();
+        let mut value = Some(value);
+        for interceptor in &self.interceptors {
+            value = value.and_then(|value| interceptor.on_put(&key, value));
+        }
+        if let Some(value) = value {
+            self.inner.put(key, value);
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let key = self.resolve(key);
+        let value = self.inner.remove(&key);
+        for interceptor in &self.interceptors {
+            interceptor.on_remove(&key, value.as_ref());
+        }
+        value
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
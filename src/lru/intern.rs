@@ -0,0 +1,130 @@
+//! Module facilitant l'usage de clés `Arc<str>` et leur partage.
+//!
+//! Avec `K = Arc<str>`, cloner une clé (par exemple dans l'ordre
+//! d'utilisation) ne copie plus la chaîne. Les méthodes `put_str`,
+//! `get_str`, `peek_str` et `remove_str` acceptent directement un `&str` :
+//! une clé déjà présente réutilise la chaîne partagée existante, sans
+//! nouvelle allocation.
+//!
+//! Avec `CacheBuilder::intern_keys`, le cache garde en outre un
+//! `KeyInterner` qui survit aux évictions et à `clear` : une clé qui revient
+//! dans le cache réutilise la chaîne allouée lors de son premier passage.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::sync::Arc;
+//! use lru_cache::lru::Cache;
+//!
+//! let mut cache: Cache<Arc<str>, u32> = Cache::builder()
+//!     .capacity(1)
+//!     .intern_keys()
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.put_str("/articles/une-cle-assez-longue", 1);
+//! cache.put_str("/autre", 2); // évince la première clé
+//! cache.put_str("/articles/une-cle-assez-longue", 3);
+//!
+//! assert_eq!(cache.get_str("/articles/une-cle-assez-longue"), Some(&3));
+//! assert_eq!(cache.key_interner().unwrap().len(), 2);
+//! ```
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use crate::lru::Cache;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Registre de chaînes partagées.
+#[derive(Debug, Clone, Default)]
+pub struct KeyInterner {
+    keys: HashSet<Arc<str>>,
+}
+
+impl KeyInterner {
+    /// Crée un registre vide.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retourne la chaîne partagée égale à la clé, en l'allouant si elle
+    /// n'est pas encore enregistrée.
+    pub fn intern(&mut self, key: &str) -> Arc<str> {
+        if let Some(shared) = self.keys.get(key) {
+            return Arc::clone(shared);
+        }
+        let shared: Arc<str> = Arc::from(key);
+        self.keys.insert(Arc::clone(&shared));
+        shared
+    }
+
+    /// Oublie les chaînes qui ne sont plus utilisées qu'à travers le
+    /// registre, et retourne leur nombre.
+    pub fn purge(&mut self) -> usize {
+        let before = self.keys.len();
+        self.keys.retain(|key| Arc::strong_count(key) > 1);
+        before - self.keys.len()
+    }
+
+    /// Retourne le nombre de chaînes enregistrées.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Vérifie si le registre est vide.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl<V> Cache<Arc<str>, V> {
+    /// Insère une valeur sous la clé donnée.
+    pub fn put_str(&mut self, key: &str, value: V) {
+        let key = self.shared_key(key);
+        self.put(key, value);
+    }
+
+    /// Récupère la valeur associée à la clé, comme `get`, sans allouer.
+    pub fn get_str(&mut self, key: &str) -> Option<&V> {
+        self.get_borrowed(key)
+    }
+
+    /// Retourne la valeur associée à la clé sans modifier l'ordre
+    /// d'utilisation, comme `peek`.
+    pub fn peek_str(&self, key: &str) -> Option<&V> {
+        let (shared, _) = self.elements.get_key_value(key)?;
+        self.peek(shared)
+    }
+
+    /// Supprime l'entrée associée à la clé et retourne sa valeur.
+    pub fn remove_str(&mut self, key: &str) -> Option<V> {
+        let (shared, _) = self.elements.get_key_value(key)?;
+        let shared = Arc::clone(shared);
+        self.remove(&shared)
+    }
+
+    /// Retourne le registre des clés, si `CacheBuilder::intern_keys` l'a
+    /// activé.
+    pub fn key_interner(&self) -> Option<&KeyInterner> {
+        self.interner.as_ref()
+    }
+
+    /// Retourne la chaîne partagée à utiliser pour la clé : celle du cache
+    /// si la clé est présente, sinon celle du registre s'il est activé.
+    pub(crate) fn shared_key(&mut self, key: &str) -> Arc<str> {
+        if let Some((shared, _)) = self.elements.get_key_value(key) {
+            return Arc::clone(shared);
+        }
+        let len = self.elements.len();
+        match self.interner.as_mut() {
+            Some(interner) => {
+                // Les clés sorties du cache depuis longtemps sont oubliées
+                if interner.len() >= 2 * len.max(self.capacity) {
+                    interner.purge();
+                }
+                interner.intern(key)
+            }
+            None => Arc::from(key),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Module implémentant la validation des clés à l'insertion.
+//!
+//! Le constructeur permet de borner la taille des clés (pour les clés
+//! représentables en octets : `String`, `&str`, `Vec<u8>`...) et d'enregistrer
+//! un validateur arbitraire rejetant les clés pathologiques. Une clé refusée
+//! n'est jamais insérée : `try_put` retourne l'erreur, `put` l'ignore.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::Cache;
+//!
+//! let mut cache = Cache::builder()
+//!     .capacity(10)
+//!     .max_key_length(16)
+//!     .key_validator(|cle: &String| {
+//!         if cle.contains('\n') { Err("saut de ligne interdit".to_string()) } else { Ok(()) }
+//!     })
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.try_put("SELECT 1".to_string(), 1).unwrap();
+//! assert!(matches!(
+//!     cache.try_put("SELECT * FROM une_table_immense".to_string(), 2),
+//!     Err(CacheError::KeyTooLarge { max: 16, .. })
+//! ));
+//! assert!(matches!(
+//!     cache.try_put("a\nb".to_string(), 3),
+//!     Err(CacheError::InvalidKey { .. })
+//! ));
+//! ```
+
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+use crate::error::CacheError;
+use crate::lru::Cache;
+
+/// Nombre maximal d'octets de la clé repris dans les messages d'erreur.
+const KEY_PREVIEW_LEN: usize = 32;
+
+type CheckFn<K> = dyn Fn(&K) -> Result<(), CacheError> + Send + Sync;
+
+/// Vérification appliquée à chaque clé avant son insertion.
+pub(crate) struct KeyCheck<K>(Arc<CheckFn<K>>);
+
+impl<K> KeyCheck<K> {
+    /// Crée une vérification de la taille en octets de la clé.
+    pub(crate) fn max_length(max: usize) -> Self
+    where
+        K: AsRef<[u8]>,
+    {
+        KeyCheck(Arc::new(move |key: &K| {
+            let bytes = key.as_ref();
+            if bytes.len() > max {
+                return Err(CacheError::KeyTooLarge {
+                    key: preview(bytes),
+                    size: bytes.len(),
+                    max,
+                });
+            }
+            Ok(())
+        }))
+    }
+
+    /// Crée une vérification déléguée à un validateur fourni par l'utilisateur.
+    pub(crate) fn validator<F>(validator: F) -> Self
+    where
+        F: Fn(&K) -> Result<(), String> + Send + Sync + 'static,
+    {
+        KeyCheck(Arc::new(move |key: &K| {
+            validator(key).map_err(|reason| CacheError::InvalidKey { reason })
+        }))
+    }
+
+    pub(crate) fn check(&self, key: &K) -> Result<(), CacheError> {
+        (self.0)(key)
+    }
+}
+
+impl<K> Clone for KeyCheck<K> {
+    fn clone(&self) -> Self {
+        KeyCheck(Arc::clone(&self.0))
+    }
+}
+
+impl<K> fmt::Debug for KeyCheck<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("KeyCheck")
+    }
+}
+
+/// Construit un aperçu lisible et tronqué d'une clé.
+fn preview(bytes: &[u8]) -> String {
+    let shown = &bytes[..bytes.len().min(KEY_PREVIEW_LEN)];
+    let mut preview = String::from_utf8_lossy(shown).into_owned();
+    if bytes.len() > KEY_PREVIEW_LEN {
+        preview.push('…');
+    }
+    preview
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Vérifie que la clé respecte toutes les règles configurées.
+    pub(crate) fn check_key(&self, key: &K) -> Result<(), CacheError> {
+        self.key_checks.iter().try_for_each(|check| check.check(key))
+    }
+}
@@ -0,0 +1,296 @@
+//! Module implémentant l'hydratation paresseuse d'un cache persistant.
+//!
+//! `LazyCache::open` ne lit au démarrage que les clés du fichier de
+//! persistance et la position de chaque valeur. Une valeur n'est relue et
+//! analysée que lors du premier accès à sa clé ; elle rejoint alors le cache
+//! comme entrée la plus récemment utilisée. Les entrées jamais relues sont
+//! considérées comme les plus froides et sont abandonnées en premier lorsque
+//! la place vient à manquer.
+//!
+//! # Exemple
+//!
+//! ```no_run
+//! use lru_cache::lru::lazy::LazyCache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = LazyCache::<String, String>::open(10_000, "mon_cache.txt").unwrap();
+//! assert_eq!(cache.pending(), cache.len()); // rien n'est encore désérialisé
+//!
+//! let valeur = cache.get(&"clé".to_string()).cloned(); // lecture à la demande
+//! cache.persist("mon_cache.txt").unwrap();
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::format::FileDescription;
+use crate::lru::traits::{CacheRead, CacheTrait};
+use crate::messages;
+
+/// Position d'une valeur non encore chargée dans le fichier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    offset: u64,
+    len: usize,
+    line: usize,
+}
+
+/// Cache persistant dont les valeurs sont chargées à la demande.
+#[derive(Debug)]
+pub struct LazyCache<K, V>
+where
+    K: Hash + Eq,
+{
+    cache: Cache<K, V>,
+    index: HashMap<K, Span>,
+    cold_order: VecDeque<(K, u64)>,
+    file: Option<File>,
+    path: PathBuf,
+}
+
+impl<K, V> LazyCache<K, V>
+where
+    K: Hash + Eq + Clone + Display + FromStr,
+    V: Display + FromStr,
+{
+    /// Ouvre le fichier de persistance et indexe ses entrées sans
+    /// désérialiser les valeurs. Un fichier absent donne un cache vide.
+    ///
+    /// Seules les `capacity` dernières entrées du fichier (les plus récentes)
+    /// sont conservées.
+    ///
+    /// # Errors
+    ///
+    /// * `CacheError::CapacityError` si la capacité est 0
+    /// * `CacheError::Corrupted` si une ligne est mal formée ou si une clé ne
+    ///   peut pas être analysée
+    /// * `CacheError::IoError` si le fichier ne peut pas être lu
+    pub fn open<P: AsRef<Path>>(capacity: usize, path: P) -> Result<Self, CacheError> {
+        let path = path.as_ref().to_path_buf();
+        let mut lazy = LazyCache {
+            cache: Cache::try_new(capacity)?,
+            index: HashMap::new(),
+            cold_order: VecDeque::new(),
+            file: None,
+            path,
+        };
+        let Ok(file) = File::open(&lazy.path) else {
+            return Ok(lazy);
+        };
+
+        let mut reader = BufReader::new(file);
+        let mut line = Vec::new();
+        let mut offset = 0u64;
+        let mut number = 0;
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
+            }
+            number += 1;
+            let start = offset;
+            offset += read as u64;
+
+            let content = line.strip_suffix(b"\n").unwrap_or(&line);
+            let content = content.strip_suffix(b"\r").unwrap_or(content);
+            if content.is_empty() {
+                continue;
+            }
+            if number == 1 {
+                if let Some(header) = FileDescription::parse_header(&String::from_utf8_lossy(content)) {
+                    header.map_err(|reason| lazy.corrupted(number, reason))?;
+                    continue;
+                }
+            }
+            let Some(tab) = content.iter().position(|&b| b == b'\t') else {
+                return Err(lazy.corrupted(number, messages::INVALID_LINE_FORMAT.to_string()));
+            };
+            if content[tab + 1..].contains(&b'\t') {
+                return Err(lazy.corrupted(number, messages::INVALID_LINE_FORMAT.to_string()));
+            }
+            let raw_key = String::from_utf8_lossy(&content[..tab]);
+            let key = K::from_str(&raw_key)
+                .map_err(|_| lazy.corrupted(number, format!("{}: {}", messages::UNPARSABLE_KEY, raw_key)))?;
+
+            let span = Span {
+                offset: start + tab as u64 + 1,
+                len: content.len() - tab - 1,
+                line: number,
+            };
+            lazy.cold_order.push_back((key.clone(), span.offset));
+            lazy.index.insert(key, span);
+            if lazy.index.len() > capacity {
+                lazy.drop_coldest();
+            }
+        }
+
+        lazy.file = Some(reader.into_inner());
+        Ok(lazy)
+    }
+
+    /// Récupère la valeur associée à la clé, en la chargeant depuis le
+    /// fichier si nécessaire.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Corrupted` si la valeur ne peut pas être
+    /// analysée (l'entrée est alors abandonnée) et `CacheError::IoError` si
+    /// le fichier ne peut pas être relu.
+    pub fn try_get(&mut self, key: &K) -> Result<Option<&V>, CacheError> {
+        self.hydrate(key)?;
+        Ok(self.cache.get(key))
+    }
+
+    /// Charge toutes les valeurs encore présentes uniquement dans le fichier.
+    /// Elles restent plus froides que les entrées déjà lues.
+    ///
+    /// # Errors
+    ///
+    /// Retourne la première erreur de chargement rencontrée.
+    pub fn hydrate_all(&mut self) -> Result<(), CacheError> {
+        let already_hydrated = self.cache.usage_order.len();
+        let mut result = Ok(());
+        while let Some((key, _)) = self.cold_order.pop_front() {
+            if self.index.contains_key(&key) {
+                result = self.hydrate(&key);
+                if result.is_err() {
+                    break;
+                }
+            }
+        }
+        // Les valeurs chargées ici passent devant les entrées déjà lues
+        self.cache.usage_order.rotate_left(already_hydrated);
+        result
+    }
+
+    /// Sauvegarde l'ensemble du cache, après avoir chargé les valeurs encore
+    /// présentes uniquement dans le fichier.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `hydrate_all` et de `Cache::persist`.
+    pub fn persist<P: AsRef<Path>>(&mut self, path: P) -> Result<(), CacheError> {
+        self.hydrate_all()?;
+        self.file = None;
+        self.cache.persist(path)
+    }
+
+    /// Charge la valeur de la clé si elle n'est présente que dans le fichier.
+    fn hydrate(&mut self, key: &K) -> Result<(), CacheError> {
+        let Some(span) = self.index.remove(key) else {
+            return Ok(());
+        };
+        let Some(file) = self.file.as_mut() else {
+            return Ok(());
+        };
+        let mut raw = vec![0; span.len];
+        file.seek(SeekFrom::Start(span.offset))?;
+        file.read_exact(&mut raw)?;
+
+        let raw = String::from_utf8_lossy(&raw);
+        let value = V::from_str(&raw)
+            .map_err(|_| self.corrupted(span.line, format!("{}: {}", messages::UNPARSABLE_VALUE, raw)))?;
+        self.cache.put(key.clone(), value);
+        Ok(())
+    }
+}
+
+impl<K, V> LazyCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne le nombre d'entrées dont la valeur n'a pas encore été chargée.
+    pub fn pending(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Retourne le cache des valeurs déjà chargées.
+    pub fn hydrated(&self) -> &Cache<K, V> {
+        &self.cache
+    }
+
+    /// Abandonne l'entrée non chargée la plus froide.
+    fn drop_coldest(&mut self) {
+        while let Some((key, offset)) = self.cold_order.pop_front() {
+            if self.index.get(&key).is_some_and(|span| span.offset == offset) {
+                self.index.remove(&key);
+                return;
+            }
+        }
+    }
+
+    fn corrupted(&self, line: usize, reason: String) -> CacheError {
+        CacheError::Corrupted {
+            path: Some(self.path.clone()),
+            line,
+            reason,
+        }
+    }
+}
+
+impl<K, V> CacheRead<K, V> for LazyCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne la valeur si elle a déjà été chargée ; `peek` ne lit jamais
+    /// le fichier.
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.cache.peek(key)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.index.contains_key(key) || self.cache.contains(key)
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len() + self.index.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+}
+
+impl<K, V> CacheTrait<K, V> for LazyCache<K, V>
+where
+    K: Hash + Eq + Clone + Display + FromStr,
+    V: Display + FromStr,
+{
+    /// Récupère la valeur, en la chargeant si nécessaire. Une valeur
+    /// illisible est abandonnée et signalée dans le journal.
+    fn get(&mut self, key: &K) -> Option<&V> {
+        if let Err(err) = self.hydrate(key) {
+            log::warn!("{}: {}", messages::LOG_HYDRATION_FAILED, err);
+        }
+        self.cache.get(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let known = self.index.remove(&key).is_some() || self.cache.contains(&key);
+        // Les entrées jamais relues cèdent leur place en premier
+        if !known && !self.index.is_empty() && self.len() >= self.capacity() {
+            self.drop_coldest();
+        }
+        self.cache.put(key, value);
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        if let Err(err) = self.hydrate(key) {
+            log::warn!("{}: {}", messages::LOG_HYDRATION_FAILED, err);
+        }
+        self.cache.remove(key)
+    }
+
+    fn clear(&mut self) {
+        self.index.clear();
+        self.cold_order.clear();
+        self.cache.clear();
+    }
+}
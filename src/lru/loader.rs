@@ -0,0 +1,56 @@
+//! Module définissant les chargeurs de valeurs.
+//!
+//! Un `Loader` sait produire la valeur associée à une clé depuis la source
+//! de vérité (base de données, service distant...). Il est utilisé par les
+//! fonctionnalités qui remplissent le cache elles-mêmes, comme le
+//! rafraîchissement en arrière-plan.
+//!
+//! Toute closure `Fn(&K) -> Result<V, CacheError>` est un chargeur.
+//! `with_retry` l'enveloppe dans une politique de nouvelles tentatives et
+//! `with_timeout` borne la durée de chaque chargement.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::loader::Loader;
+//!
+//! let chargeur = |cle: &u32| -> Result<String, CacheError> { Ok(format!("valeur_{}", cle)) };
+//! assert_eq!(chargeur.load(&7).unwrap(), "valeur_7");
+//! ```
+
+use crate::error::CacheError;
+use std::time::Duration;
+use crate::lru::retry::{RetryPolicy, RetryingLoader};
+use crate::lru::timeout::TimeoutLoader;
+
+/// Source capable de produire la valeur associée à une clé.
+pub trait Loader<K, V> {
+    /// Charge la valeur associée à la clé.
+    fn load(&self, key: &K) -> Result<V, CacheError>;
+
+    /// Relance les échecs transitoires du chargeur selon la politique donnée.
+    fn with_retry(self, policy: RetryPolicy) -> RetryingLoader<Self>
+    where
+        Self: Sized,
+    {
+        RetryingLoader::new(self, policy)
+    }
+
+    /// Abandonne les chargements qui dépassent le délai donné.
+    fn with_timeout(self, timeout: Duration) -> TimeoutLoader<Self, K, V>
+    where
+        Self: Sized,
+    {
+        TimeoutLoader::new(self, timeout)
+    }
+}
+
+impl<K, V, F> Loader<K, V> for F
+where
+    F: Fn(&K) -> Result<V, CacheError>,
+{
+    fn load(&self, key: &K) -> Result<V, CacheError> {
+        self(key)
+    }
+}
@@ -0,0 +1,133 @@
+//! Module fournissant un décorateur qui journalise les opérations d'un cache.
+//!
+//! `LoggingCache` encapsule n'importe quelle implémentation de `CacheTrait`
+//! et émet une entrée de journal via la façade `log` pour chaque opération :
+//! clé concernée (via `Debug`), succès ou échec des lectures, et évictions
+//! provoquées par les insertions.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::logging::LoggingCache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = LoggingCache::new(Cache::new(2))
+//!     .with_level(log::Level::Info)
+//!     .with_name("sessions");
+//!
+//! cache.put("clé", 1);            // "[sessions] insertion: \"clé\""
+//! assert_eq!(cache.get(&"clé"), Some(&1)); // "[sessions] succès: \"clé\""
+//! ```
+
+use std::fmt::Debug;
+use log::Level;
+use crate::lru::traits::{CacheRead, CacheTrait};
+use crate::messages;
+
+/// Décorateur journalisant chaque opération du cache encapsulé.
+///
+/// Le niveau de journalisation est `Debug` par défaut.
+#[derive(Debug, Clone)]
+pub struct LoggingCache<C> {
+    inner: C,
+    level: Level,
+    name: String,
+}
+
+impl<C> LoggingCache<C> {
+    /// Encapsule le cache donné.
+    pub fn new(inner: C) -> Self {
+        LoggingCache {
+            inner,
+            level: Level::Debug,
+            name: "cache".to_string(),
+        }
+    }
+
+    /// Définit le niveau auquel les opérations sont journalisées.
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Définit le nom du cache, affiché en préfixe de chaque entrée.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Retourne une référence au cache encapsulé.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Consomme le décorateur et retourne le cache encapsulé.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<K, V, C> CacheRead<K, V> for LoggingCache<C>
+where
+    C: CacheRead<K, V>,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.inner.peek(key)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.inner.contains(key)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+}
+
+impl<K, V, C> CacheTrait<K, V> for LoggingCache<C>
+where
+    K: Debug,
+    C: CacheTrait<K, V>,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let value = self.inner.get(key);
+        let outcome = if value.is_some() { messages::LOG_HIT } else { messages::LOG_MISS };
+        log::log!(self.level, "[{}] {}: {:?}", self.name, outcome, key);
+        value
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let existed = self.inner.contains(&key);
+        let len_before = self.inner.len();
+        let operation = if existed { messages::LOG_UPDATE } else { messages::LOG_PUT };
+        log::log!(self.level, "[{}] {}: {:?}", self.name, operation, key);
+
+        self.inner.put(key, value);
+
+        if !existed && self.inner.len() <= len_before {
+            log::log!(self.level, "[{}] {}", self.name, messages::LOG_EVICTION);
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.inner.remove(key);
+        if value.is_some() {
+            log::log!(self.level, "[{}] {}: {:?}", self.name, messages::LOG_REMOVE, key);
+        }
+        value
+    }
+
+    fn clear(&mut self) {
+        log::log!(self.level, "[{}] {}", self.name, messages::LOG_CLEAR);
+        self.inner.clear();
+    }
+}
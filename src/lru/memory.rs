@@ -0,0 +1,162 @@
+//! Module estimant l'empreinte mémoire du cache.
+//!
+//! `memory_usage` détaille la mémoire occupée par la table des entrées,
+//! l'ordre d'utilisation, les clés et les valeurs. Le calcul est exact pour
+//! les types implémentant `MemSize`, qui déclarent la mémoire qu'ils
+//! possèdent sur le tas ; `estimated_memory_usage` s'applique à tous les types
+//! mais ne compte que leur taille en ligne.
+//!
+//! Les tailles sont déduites des capacités allouées et de la disposition des
+//! types ; elles ignorent les métadonnées propres à l'allocateur.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::new(100);
+//! cache.put("clé".to_string(), vec![0u8; 1000]);
+//!
+//! let rapport = cache.memory_usage();
+//! assert!(rapport.exact);
+//! assert!(rapport.value_bytes >= 1000);
+//! assert!(rapport.total() > rapport.value_bytes);
+//! ```
+
+use std::hash::Hash;
+use std::mem::size_of;
+use crate::lru::{Cache, Entry};
+
+/// Mémoire possédée par une valeur en dehors de sa taille en ligne.
+pub trait MemSize {
+    /// Retourne le nombre d'octets alloués sur le tas et possédés par la
+    /// valeur.
+    fn heap_size(&self) -> usize;
+}
+
+macro_rules! impl_mem_size_inline {
+    ($($ty:ty),*) => {
+        $(
+            impl MemSize for $ty {
+                fn heap_size(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_mem_size_inline!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, ());
+
+impl MemSize for &str {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl MemSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: MemSize> MemSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(MemSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: MemSize> MemSize for Box<[T]> {
+    fn heap_size(&self) -> usize {
+        self.len() * size_of::<T>() + self.iter().map(MemSize::heap_size).sum::<usize>()
+    }
+}
+
+impl MemSize for Box<str> {
+    fn heap_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: MemSize> MemSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, MemSize::heap_size)
+    }
+}
+
+impl<A: MemSize, B: MemSize> MemSize for (A, B) {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size()
+    }
+}
+
+/// Détail de la mémoire occupée par le cache, en octets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// Table des entrées hors clés et valeurs : emplacements libres, octets
+    /// de contrôle et métadonnées de chaque entrée
+    pub map_overhead: usize,
+    /// Ordre d'utilisation, qui conserve une copie de chaque clé
+    pub order_bytes: usize,
+    /// Clés : taille en ligne et mémoire possédée
+    pub key_bytes: usize,
+    /// Valeurs : taille en ligne et mémoire possédée
+    pub value_bytes: usize,
+    /// Vrai si la mémoire possédée par les clés et les valeurs a été comptée
+    pub exact: bool,
+}
+
+impl MemoryReport {
+    /// Retourne l'empreinte totale.
+    pub fn total(&self) -> usize {
+        self.map_overhead + self.order_bytes + self.key_bytes + self.value_bytes
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Calcule l'empreinte mémoire du cache, y compris la mémoire possédée
+    /// par les clés et les valeurs. Le coût est linéaire en la taille du
+    /// cache.
+    pub fn memory_usage(&self) -> MemoryReport
+    where
+        K: MemSize,
+        V: MemSize,
+    {
+        self.measure(MemSize::heap_size, MemSize::heap_size, true)
+    }
+
+    /// Estime l'empreinte mémoire du cache à partir de la seule taille en
+    /// ligne des clés et des valeurs, pour les types sans `MemSize`.
+    pub fn estimated_memory_usage(&self) -> MemoryReport {
+        self.measure(|_| 0, |_| 0, false)
+    }
+
+    fn measure<FK, FV>(&self, key_heap: FK, value_heap: FV, exact: bool) -> MemoryReport
+    where
+        FK: Fn(&K) -> usize,
+        FV: Fn(&V) -> usize,
+    {
+        let len = self.elements.len();
+        let slot = size_of::<K>() + size_of::<Entry<V>>();
+        // Chaque emplacement de la table est précédé d'un octet de contrôle
+        let table = self.elements.capacity() * (slot + 1);
+        let key_inline = len * size_of::<K>();
+        let value_inline = len * size_of::<V>();
+
+        let keys_owned: usize = self.elements.keys().map(&key_heap).sum();
+        let order_owned: usize = self.usage_order.iter().map(&key_heap).sum();
+        let values_owned: usize = self.elements.values().map(|entry| value_heap(&entry.value)).sum();
+
+        MemoryReport {
+            map_overhead: table - key_inline - value_inline,
+            order_bytes: self.usage_order.allocated_bytes() + order_owned,
+            key_bytes: key_inline + keys_owned,
+            value_bytes: value_inline + values_owned,
+            exact,
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Module fournissant un décorateur qui mesure l'activité d'un cache.
+//!
+//! `MeteredCache` instrumente n'importe quelle implémentation de `CacheTrait`,
+//! y compris celles fournies par l'utilisateur, sans la modifier : il compte
+//! les succès, échecs, insertions et évictions, et mesure la latence des
+//! opérations `get` et `put`.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::metered::MeteredCache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = MeteredCache::new(Cache::new(2));
+//! cache.put(1, "un");
+//! cache.get(&1);
+//! cache.get(&2);
+//!
+//! let metrics = cache.metrics();
+//! assert_eq!(metrics.hits, 1);
+//! assert_eq!(metrics.misses, 1);
+//! assert_eq!(metrics.hit_ratio(), 0.5);
+//! ```
+
+use std::time::{Duration, Instant};
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Statistiques de latence d'un type d'opération.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyMetrics {
+    /// Nombre d'opérations mesurées
+    pub count: u64,
+    /// Durée cumulée des opérations
+    pub total: Duration,
+    /// Durée de l'opération la plus lente
+    pub max: Duration,
+}
+
+impl LatencyMetrics {
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+
+    /// Retourne la durée moyenne d'une opération.
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((self.total.as_nanos() / u128::from(self.count)) as u64)
+        }
+    }
+}
+
+/// Métriques collectées par un `MeteredCache`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    /// Nombre de lectures ayant trouvé une valeur
+    pub hits: u64,
+    /// Nombre de lectures infructueuses
+    pub misses: u64,
+    /// Nombre d'insertions de nouvelles clés
+    pub inserts: u64,
+    /// Nombre de mises à jour de clés existantes
+    pub updates: u64,
+    /// Nombre de suppressions explicites
+    pub removals: u64,
+    /// Nombre d'évictions provoquées par des insertions
+    pub evictions: u64,
+    /// Latence des opérations `get`
+    pub get_latency: LatencyMetrics,
+    /// Latence des opérations `put`
+    pub put_latency: LatencyMetrics,
+}
+
+impl CacheMetrics {
+    /// Retourne la proportion de lectures ayant trouvé une valeur (entre 0 et 1).
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Décorateur collectant des métriques sur le cache encapsulé.
+#[derive(Debug, Clone)]
+pub struct MeteredCache<C> {
+    inner: C,
+    metrics: CacheMetrics,
+}
+
+impl<C> MeteredCache<C> {
+    /// Encapsule le cache donné.
+    pub fn new(inner: C) -> Self {
+        MeteredCache {
+            inner,
+            metrics: CacheMetrics::default(),
+        }
+    }
+
+    /// Retourne les métriques collectées depuis la création ou la dernière
+    /// remise à zéro.
+    pub fn metrics(&self) -> CacheMetrics {
+        self.metrics
+    }
+
+    /// Remet toutes les métriques à zéro.
+    pub fn reset_metrics(&mut self) {
+        self.metrics = CacheMetrics::default();
+    }
+
+    /// Retourne une référence au cache encapsulé.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Consomme le décorateur et retourne le cache encapsulé.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<K, V, C> CacheRead<K, V> for MeteredCache<C>
+where
+    C: CacheRead<K, V>,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.inner.peek(key)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.inner.contains(key)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+}
+
+impl<K, V, C> CacheTrait<K, V> for MeteredCache<C>
+where
+    C: CacheTrait<K, V>,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let start = Instant::now();
+        let value = self.inner.get(key);
+        self.metrics.get_latency.record(start.elapsed());
+        if value.is_some() {
+            self.metrics.hits += 1;
+        } else {
+            self.metrics.misses += 1;
+        }
+        value
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        let existed = self.inner.contains(&key);
+        let len_before = self.inner.len();
+
+        let start = Instant::now();
+        self.inner.put(key, value);
+        self.metrics.put_latency.record(start.elapsed());
+
+        if existed {
+            self.metrics.updates += 1;
+        } else {
+            self.metrics.inserts += 1;
+            if self.inner.len() <= len_before {
+                self.metrics.evictions += 1;
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.inner.remove(key);
+        if value.is_some() {
+            self.metrics.removals += 1;
+        }
+        value
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
@@ -0,0 +1,89 @@
+//! Module implémentant la migration des clés à la lecture.
+//!
+//! Un changement de format des clés (nouveau schéma, préfixe de version...)
+//! rend d'ordinaire le cache entier inutilisable : toutes les lectures sous
+//! le nouveau format échouent. `get_migrated(ancienne, nouvelle)` cherche
+//! d'abord la nouvelle clé, puis se rabat sur l'ancienne et, si elle est
+//! présente, déplace l'entrée sous la nouvelle clé. Le cache se migre ainsi
+//! au fil des lectures, sans démarrage à froid.
+//!
+//! L'entrée déplacée conserve sa valeur, sa durée de vie restante, son
+//! compteur de lectures et sa place dans l'ordre d'utilisation, avant
+//! d'être lue comme toute entrée. Les observateurs voient la suppression de
+//! l'ancienne clé puis l'insertion de la nouvelle.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = Cache::new(10);
+//! cache.put("user:42".to_string(), "Ada");
+//!
+//! let nom = cache.get_migrated(&"user:42".to_string(), &"v2:user:42".to_string());
+//! assert_eq!(nom, Some(&"Ada"));
+//! assert!(!cache.contains(&"user:42".to_string()));
+//! assert!(cache.contains(&"v2:user:42".to_string()));
+//! ```
+
+use std::hash::Hash;
+use crate::lru::Cache;
+use crate::lru::events::{Mutation, RemovalCause};
+use crate::lru::traits::CacheTrait;
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Lit la valeur de `new_key`, ou à défaut celle de `old_key`, qui est
+    /// alors déplacée sous `new_key`.
+    ///
+    /// Si la nouvelle clé est refusée par les vérifications de clés, l'entrée
+    /// reste sous l'ancienne clé et sa valeur est retournée telle quelle.
+    pub fn get_migrated(&mut self, old_key: &K, new_key: &K) -> Option<&V> {
+        if old_key != new_key && !self.live(new_key) && self.live(old_key) {
+            if self.check_key(new_key).is_err() {
+                return self.get(old_key);
+            }
+            // Une nouvelle clé expirée ne doit rien léguer à l'entrée déplacée
+            if self.elements.contains_key(new_key) {
+                self.remove_entry(new_key, RemovalCause::Expired);
+            }
+            self.rekey(old_key, new_key.clone());
+        }
+        self.get(new_key)
+    }
+
+    /// Indique si la clé est présente et non expirée.
+    fn live(&self, key: &K) -> bool {
+        self.elements.contains_key(key) && !self.is_expired(key)
+    }
+
+    /// Déplace l'entrée de l'ancienne clé sous la nouvelle, absente.
+    fn rekey(&mut self, old_key: &K, new_key: K) {
+        let Some(mut entry) = self.elements.remove(old_key) else {
+            return;
+        };
+        self.unindex_key(old_key);
+        self.index_key(&new_key);
+        self.usage_order.replace_key(old_key, new_key.clone());
+        if let Some(deadline) = self.expirations.remove(old_key) {
+            self.expirations.set(new_key.clone(), deadline);
+        }
+        if let Some(buckets) = self.time_buckets.as_mut() {
+            buckets.push_at(new_key.clone(), entry.inserted);
+        }
+        if let Some(overflow) = self.overflow.as_ref() {
+            overflow.discard(old_key);
+        }
+        self.next_version += 1;
+        entry.version = self.next_version;
+        if self.observers.is_active() {
+            let cause = RemovalCause::Explicit;
+            self.observers.notify(&Mutation::Remove { key: old_key, value: &entry.value, cause });
+            self.observers.notify(&Mutation::Insert { key: &new_key, value: &entry.value });
+        }
+        self.elements.insert(new_key, entry);
+    }
+}
@@ -38,22 +38,177 @@
 //! cache.persist("mon_cache.txt").unwrap();
 //! ```
 
-use std::collections::HashMap;
+use std::borrow::Borrow;
 use std::hash::Hash;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Write, BufReader, BufWriter};
 use std::path::Path;
-use std::fmt::Display;
+use std::fmt::{Display, Write as _};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::time::{Duration, Instant};
+use hashbrown::HashMap;
 use crate::error::CacheError;
-use crate::lru::traits::CacheTrait;
+use crate::messages;
+use crate::lru::background::BackgroundEviction;
+use crate::lru::buckets::TimeBuckets;
+use crate::lru::checkpoint::CheckpointStore;
+use crate::lru::clock::{Clock, SystemClock};
+use crate::lru::deterministic::KeyHasher;
+use crate::lru::duplicate::{DuplicatePolicy, PutOutcome, UpdateRecency};
+use crate::lru::events::{Mutation, Observers, RemovalCause};
+use crate::lru::format::FileDescription;
+use crate::lru::frequency::Decay;
+use crate::lru::intern::KeyInterner;
+use crate::lru::keys::KeyCheck;
+use crate::lru::order::UsageOrder;
+use crate::lru::overflow::Overflow;
+use crate::lru::pin::Pins;
+use crate::lru::placeholder::FailedLoads;
+use crate::lru::sampled::Sampler;
+use crate::lru::scan::HashIndex;
+use crate::lru::shrink::ShrinkPolicy;
+use crate::lru::stats::TimedOp;
+use crate::lru::traits::{CacheRead, CacheTrait};
+use crate::lru::ttl::ExpiryQueue;
+use crate::lru::weight::Weigher;
+use crate::lru::xfetch::EarlyExpiration;
 
+pub mod adaptive;
+pub mod advisor;
+pub mod age;
+#[cfg(feature = "rkyv")]
+pub mod archived;
+pub mod audit;
+pub mod background;
+#[cfg(feature = "bytes")]
+pub mod blob;
+pub mod breaker;
+pub mod buckets;
+pub mod buffered;
+pub mod builder;
+pub mod bytes;
+pub mod chain;
+pub mod checkpoint;
+pub mod clock;
+pub mod clock_pro;
+pub mod cluster;
+pub mod coalesce;
+pub mod compat;
+#[cfg(feature = "compression")]
+pub mod compressed;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod deadline;
+pub mod dedup;
+pub mod deterministic;
+// Le registre garde des références faibles de `std`, absentes de loom
+#[cfg(not(loom))]
+pub mod diagnostics;
+pub mod doubles;
+pub mod duplicate;
+pub mod duration;
+pub mod equivalent;
+#[cfg(feature = "watch")]
+pub mod file_watch;
+pub mod events;
+pub mod format;
+pub mod frequency;
+pub mod frozen;
+pub mod hashed;
+pub mod hooks;
+pub mod integrity;
+pub mod intercept;
+pub mod intern;
+pub mod keys;
+pub mod lazy;
+pub mod loader;
+pub mod logging;
+pub mod memory;
+pub mod metered;
+pub mod migrate;
+pub(crate) mod order;
+pub mod ordered;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod overflow;
+pub mod pin;
+pub mod placeholder;
+pub mod pressure;
+pub(crate) mod primitives;
+pub mod promote;
+#[cfg(feature = "read-mostly")]
+pub mod read_mostly;
+pub mod read_through;
+pub mod refresh;
+pub mod registry;
+pub mod reload;
+pub mod replication;
+pub mod reserve;
+pub mod retry;
+pub mod sampled;
+pub mod scan;
+pub mod scoped;
+pub mod secondary;
+pub mod sensitive;
+pub mod sharded;
+#[cfg(feature = "shared-memory")]
+pub mod shared;
+pub mod shrink;
+pub mod stats;
+pub mod string;
+pub mod swap;
+pub mod sync;
+#[cfg(feature = "tcp-sync")]
+pub mod tcp_sync;
+pub mod tenant;
+pub mod tiered;
+pub mod timeout;
 pub mod traits;
+pub mod transaction;
+pub mod ttl;
+pub mod version;
+pub mod warm;
+pub mod watermark;
+pub mod weight;
+pub mod xfetch;
+
+pub use builder::CacheBuilder;
+
+/// Entrée stockée dans le cache : la valeur et ses métadonnées d'usage.
+#[derive(Debug, Clone)]
+pub(crate) struct Entry<V> {
+    pub(crate) value: V,
+    pub(crate) hits: u64,
+    /// Valeur de `hits` au dernier rafraîchissement de l'entrée
+    pub(crate) refreshed_hits: u64,
+    pub(crate) weight: usize,
+    pub(crate) version: u64,
+    pub(crate) warmed: bool,
+    /// Instant d'insertion de la clé
+    pub(crate) inserted: Instant,
+    /// Instant de la dernière lecture (ou de l'insertion)
+    pub(crate) accessed: Instant,
+    /// Durée du calcul de la valeur, pour l'expiration anticipée
+    pub(crate) cost: Duration,
+}
+
+impl<V> Entry<V> {
+    pub(crate) fn new(value: V, weight: usize, version: u64, now: Instant) -> Self {
+        Entry { value, hits: 0, refreshed_hits: 0, weight, version, warmed: false, inserted: now, accessed: now, cost: Duration::ZERO }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod tests;
 
 /// Structure principale du cache LRU.
 /// 
-/// Le cache utilise une `HashMap` pour stocker les paires clé-valeur et un `Vec`
-/// pour maintenir l'ordre d'utilisation des éléments.
+/// Le cache utilise une `HashMap` pour stocker les paires clé-valeur et une
+/// liste doublement chaînée indexée pour maintenir l'ordre d'utilisation des
+/// éléments : lectures et écritures ne parcourent jamais cet ordre.
 /// 
 /// # Type Parameters
 /// 
@@ -80,8 +235,43 @@ where
     K: Hash + Eq,
 {
     pub(crate) capacity: usize,
-    pub(crate) elements: HashMap<K, V>,
-    pub(crate) usage_order: Vec<K>,
+    pub(crate) elements: HashMap<K, Entry<V>, KeyHasher>,
+    pub(crate) usage_order: UsageOrder<K>,
+    /// Empreintes triées des clés, pour le parcours paginé, si
+    /// `CacheBuilder::indexed_scan` a été demandé
+    pub(crate) scan_index: Option<HashIndex>,
+    pub(crate) expirations: ExpiryQueue<K>,
+    pub(crate) time_buckets: Option<TimeBuckets<K>>,
+    pub(crate) default_ttl: Option<Duration>,
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) weigher: Option<Weigher<V>>,
+    pub(crate) max_weight: Option<usize>,
+    pub(crate) max_value_weight: Option<usize>,
+    pub(crate) total_weight: usize,
+    pub(crate) key_checks: Vec<KeyCheck<K>>,
+    pub(crate) duplicate_policy: DuplicatePolicy<V>,
+    pub(crate) update_recency: UpdateRecency,
+    pub(crate) next_version: u64,
+    pub(crate) observers: Observers<K, V>,
+    pub(crate) overflow: Option<Overflow<K, V>>,
+    pub(crate) decay: Option<Decay>,
+    pub(crate) interner: Option<KeyInterner>,
+    pub(crate) track_access: bool,
+    pub(crate) low_watermark: Option<usize>,
+    pub(crate) background_eviction: Option<BackgroundEviction>,
+    pub(crate) sampling: Option<Sampler>,
+    /// Version des entrées au dernier chargement ou à la dernière sauvegarde
+    pub(crate) synced_version: AtomicU64,
+    pub(crate) checkpoints: Option<CheckpointStore>,
+    pub(crate) pins: Pins<K>,
+    /// Emplacements réservés pour de nouvelles clés
+    pub(crate) reserved: usize,
+    pub(crate) shrink_policy: ShrinkPolicy,
+    pub(crate) failed_loads: Option<FailedLoads<K>>,
+    pub(crate) early_expiration: Option<EarlyExpiration>,
+    /// Identité du cache, retenue par ses instantanés : `refreeze` ne reprend
+    /// que les entrées d'un instantané de ce cache
+    pub(crate) identity: Arc<()>,
 }
 
 impl<K, V> Cache<K, V> 
@@ -106,27 +296,364 @@ where
     /// let cache: Cache<String, i32> = Cache::new(3);
     /// ```
     pub fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Crée un nouveau cache avec la capacité spécifiée, sans paniquer.
+    /// 
+    /// # Errors
+    /// 
+    /// Retourne `CacheError::CapacityError` si la capacité est 0.
+    /// 
+    /// # Exemples
+    /// 
+    /// ```
+    /// use lru_cache::lru::Cache;
+    /// 
+    /// assert!(Cache::<String, i32>::try_new(3).is_ok());
+    /// assert!(Cache::<String, i32>::try_new(0).is_err());
+    /// ```
+    pub fn try_new(capacity: usize) -> Result<Self, CacheError> {
         if capacity == 0 {
-            panic!("La capacité du cache doit être supérieure à 0");
+            return Err(CacheError::CapacityError(messages::ZERO_CAPACITY.to_string()));
         }
         
-        Cache {
+        let hasher = KeyHasher::random();
+        Ok(Cache {
             capacity,
-            elements: HashMap::with_capacity(capacity),
-            usage_order: Vec::with_capacity(capacity),
+            elements: HashMap::with_capacity_and_hasher(capacity, hasher.clone()),
+            usage_order: UsageOrder::with_capacity(capacity, hasher),
+            scan_index: None,
+            expirations: ExpiryQueue::default(),
+            time_buckets: None,
+            default_ttl: None,
+            clock: Arc::new(SystemClock),
+            weigher: None,
+            max_weight: None,
+            max_value_weight: None,
+            total_weight: 0,
+            key_checks: Vec::new(),
+            duplicate_policy: DuplicatePolicy::Overwrite,
+            update_recency: UpdateRecency::Promote,
+            next_version: 0,
+            observers: Observers::default(),
+            overflow: None,
+            decay: None,
+            interner: None,
+            track_access: false,
+            low_watermark: None,
+            background_eviction: None,
+            sampling: None,
+            synced_version: AtomicU64::new(0),
+            checkpoints: None,
+            pins: Pins::new(),
+            reserved: 0,
+            shrink_policy: ShrinkPolicy::Never,
+            failed_loads: None,
+            early_expiration: None,
+            identity: Arc::new(()),
+        })
+    }
+
+    /// Retourne un constructeur permettant de configurer le cache avant sa création.
+    /// 
+    /// # Exemples
+    /// 
+    /// ```
+    /// use lru_cache::lru::Cache;
+    /// 
+    /// let cache = Cache::<String, i32>::builder().capacity(10).build().unwrap();
+    /// assert_eq!(cache.capacity(), 10);
+    /// ```
+    pub fn builder() -> CacheBuilder<K, V> {
+        CacheBuilder::new()
+    }
+
+    /// Retourne la capacité maximale du cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Modifie la capacité maximale du cache.
+    /// 
+    /// Si la nouvelle capacité est inférieure au nombre d'éléments présents,
+    /// les éléments les moins récemment utilisés sont évincés.
+    /// 
+    /// # Errors
+    /// 
+    /// Retourne `CacheError::CapacityError` si la nouvelle capacité est 0 ;
+    /// le cache n'est alors pas modifié.
+    /// 
+    /// # Exemples
+    /// 
+    /// ```
+    /// use lru_cache::lru::Cache;
+    /// use lru_cache::lru::traits::CacheTrait;
+    /// 
+    /// let mut cache = Cache::new(3);
+    /// cache.put(1, "un");
+    /// cache.put(2, "deux");
+    /// cache.put(3, "trois");
+    /// 
+    /// cache.resize(2).unwrap();
+    /// assert_eq!(cache.len(), 2);
+    /// assert_eq!(cache.get(&1), None);
+    /// ```
+    pub fn resize(&mut self, capacity: usize) -> Result<(), CacheError> {
+        if capacity == 0 {
+            return Err(CacheError::CapacityError(format!(
+                "{} ({}: {})",
+                messages::RESIZE_TO_ZERO,
+                messages::CURRENT_CAPACITY,
+                self.capacity
+            )));
         }
+
+        let excess = self.elements.len().saturating_sub(capacity);
+        self.evict_batch(excess, RemovalCause::Capacity);
+        self.capacity = capacity;
+        self.reserved = self.reserved.min(capacity.saturating_sub(self.elements.len()));
+        self.low_watermark = self.low_watermark.map(|low| low.min(capacity - 1));
+        Ok(())
+    }
+
+    /// Supprime les `n` entrées les moins récemment utilisées et les
+    /// retourne, de la plus ancienne à la plus récente.
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use lru_cache::lru::Cache;
+    /// use lru_cache::lru::traits::CacheTrait;
+    ///
+    /// let mut cache = Cache::new(3);
+    /// cache.put(1, "un");
+    /// cache.put(2, "deux");
+    /// cache.put(3, "trois");
+    ///
+    /// assert_eq!(cache.evict(2), vec![(1, "un"), (2, "deux")]);
+    /// assert_eq!(cache.len(), 1);
+    /// ```
+    pub fn evict(&mut self, n: usize) -> Vec<(K, V)> {
+        self.evict_batch(n, RemovalCause::Explicit)
+    }
+
+    /// Insère ou met à jour une entrée, en évinçant si nécessaire les
+    /// entrées expirées puis les éléments les moins récemment utilisés.
+    /// 
+    /// Une clé déjà présente est traitée selon la politique de doublons, sauf
+    /// si `overwrite` est vrai. Retourne une erreur, sans modifier le cache,
+    /// si la clé est refusée ou si la valeur dépasse les limites de poids.
+    pub(crate) fn insert_entry(&mut self, key: K, value: V, overwrite: bool) -> Result<PutOutcome<V>, CacheError> {
+        if !self.key_checks.is_empty() {
+            self.check_key(&key)?;
+        }
+        // Une entrée expirée ne compte pas comme un doublon
+        if self.is_expired(&key) {
+            self.remove_entry(&key, RemovalCause::Expired);
+        }
+        let exists = self.elements.contains_key(&key);
+        let overwrite = overwrite || matches!(self.duplicate_policy, DuplicatePolicy::Overwrite);
+        let weight = self.weigh(&value);
+        if !exists || overwrite {
+            self.check_weight(weight)?;
+        }
+
+        if !exists && !self.take_reserved_slot() && self.elements.len() >= self.capacity {
+            if self.expirations.len() > 0 || self.time_buckets.is_some() {
+                self.purge_expired();
+            }
+            if self.elements.len() >= self.capacity && !self.defer_eviction() {
+                self.evict_for_insert();
+            }
+        }
+
+        // Si la clé existe déjà, appliquer la politique de doublons
+        let outcome = if let Some(entry) = self.elements.get_mut(&key) {
+            let outcome = if overwrite {
+                PutOutcome::Replaced(std::mem::replace(&mut entry.value, value))
+            } else {
+                self.duplicate_policy.resolve(&mut entry.value, value)
+            };
+            if let PutOutcome::Kept(_) = outcome {
+                return Ok(outcome);
+            }
+            let weight = match outcome {
+                PutOutcome::Merged => self.weigher.as_ref().map_or(1, |weigher| weigher.weigh(&entry.value)),
+                _ => weight,
+            };
+            self.total_weight = self.total_weight - entry.weight + weight;
+            entry.weight = weight;
+            self.next_version += 1;
+            entry.version = self.next_version;
+            entry.warmed = false;
+            let promote = self.update_recency == UpdateRecency::Promote;
+            if self.track_access && promote {
+                entry.accessed = self.clock.now();
+            }
+            if let Err(err) = self.check_weight(weight) {
+                // La valeur fusionnée ne respecte plus les limites
+                self.remove_entry(&key, RemovalCause::Rejected);
+                return Err(err);
+            }
+            if self.observers.is_active() {
+                if let Some(entry) = self.elements.get(&key) {
+                    self.observers.notify(&Mutation::Update { key: &key, value: &entry.value });
+                }
+            }
+            if promote {
+                self.move_to_recently_used(&key);
+            }
+            outcome
+        } else {
+            // Sinon, ajouter le nouvel élément
+            self.total_weight += weight;
+            self.next_version += 1;
+            if self.observers.is_active() {
+                self.observers.notify(&Mutation::Insert { key: &key, value: &value });
+            }
+            let entry = Entry::new(value, weight, self.next_version, self.clock.now());
+            self.index_key(&key);
+            self.elements.insert(key.clone(), entry);
+            self.bucket_key(&key);
+            self.track_recency(key);
+            PutOutcome::Inserted
+        };
+
+        self.evict_overweight();
+        Ok(outcome)
+    }
+
+    /// Supprime l'entrée associée à la clé ainsi que ses métadonnées.
+    pub(crate) fn remove_entry(&mut self, key: &K, cause: RemovalCause) -> Option<V> {
+        if cause == RemovalCause::Explicit {
+            if let Some(overflow) = self.overflow.as_ref() {
+                overflow.discard(key);
+            }
+        }
+        if !self.elements.contains_key(key) {
+            return None;
+        }
+        self.usage_order.remove(key);
+        self.release_entry(key, cause)
+    }
+
+    /// Supprime l'entrée et ses métadonnées, hormis sa place dans l'ordre
+    /// d'utilisation, déjà retirée par l'appelant.
+    fn release_entry(&mut self, key: &K, cause: RemovalCause) -> Option<V> {
+        let entry = self.elements.remove(key)?;
+        self.unindex_key(key);
+        if self.observers.is_active() {
+            self.observers.notify(&Mutation::Remove { key, value: &entry.value, cause });
+        }
+        self.expirations.remove(key);
+        self.total_weight -= entry.weight;
+        if cause.is_eviction() {
+            self.record_eviction_age(entry.inserted);
+        }
+        // Une entrée évincée déborde vers le stockage secondaire
+        if let Some(overflow) = self.overflow.as_ref().filter(|_| cause.is_eviction()) {
+            overflow.spill(key.clone(), entry.value);
+            return None;
+        }
+        Some(entry.value)
+    }
+
+    /// Supprime d'un coup les `count` entrées non épinglées les moins
+    /// récemment utilisées.
+    pub(crate) fn evict_batch(&mut self, count: usize, cause: RemovalCause) -> Vec<(K, V)> {
+        if self.sampling.is_some() {
+            let mut evicted = Vec::new();
+            for _ in 0..count {
+                let Some(key) = self.sample_victim() else {
+                    break;
+                };
+                if let Some(value) = self.release_entry(&key, cause) {
+                    evicted.push((key, value));
+                }
+            }
+            self.maybe_shrink();
+            return evicted;
+        }
+        let keys: Vec<K> = if self.pins.is_empty() {
+            (0..count).map_while(|_| self.usage_order.pop_front()).collect()
+        } else {
+            // Les entrées épinglées gardent leur place
+            let pinned = self.pins.lock();
+            let keys: Vec<K> = self
+                .usage_order
+                .iter()
+                .filter(|key| !pinned.contains_key(*key))
+                .take(count)
+                .cloned()
+                .collect();
+            for key in &keys {
+                self.usage_order.remove(key);
+            }
+            keys
+        };
+        let evicted = keys
+            .into_iter()
+            .filter_map(|key| {
+                let value = self.release_entry(&key, cause)?;
+                Some((key, value))
+            })
+            .collect();
+        self.maybe_shrink();
+        evicted
+    }
+
+    /// Lecture par une forme empruntée de la clé (`&str` pour une clé
+    /// `Arc<str>`...). La clé possédée n'est construite que pour un échec de
+    /// lecture que le débordement ou les crochets doivent connaître.
+    pub(crate) fn get_borrowed<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q> + for<'a> From<&'a Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let owned = match self.elements.get_key_value(key) {
+            Some((owned, _)) => owned.clone(),
+            None if self.overflow.is_none() && !self.observers.wants_misses() => {
+                let start = self.start_timer();
+                self.record_read(false);
+                self.record_latency(TimedOp::Get, start);
+                return None;
+            }
+            None => K::from(key),
+        };
+        self.get(&owned)
+    }
+
+    /// Ajoute une nouvelle clé à la fin de l'ordre d'utilisation, sauf en
+    /// éviction échantillonnée où aucun ordre n'est tenu.
+    pub(crate) fn track_recency(&mut self, key: K) {
+        if self.sampling.is_none() {
+            self.usage_order.push_back(key);
+        }
+    }
+
+    /// Retourne les clés du moins récemment utilisé au plus récemment
+    /// utilisé, ou dans un ordre quelconque en éviction échantillonnée.
+    pub(crate) fn recency_order(&self) -> impl Iterator<Item = &K> {
+        let unordered = self.sampling.is_some().then(|| self.elements.keys());
+        self.usage_order.iter().chain(unordered.into_iter().flatten())
+    }
+
+    /// Déplace à la fin de l'ordre d'utilisation la clé d'empreinte `hash`
+    /// (voir `hash_key`) satisfaisant le prédicat.
+    pub(crate) fn move_to_recently_used_by<F: Fn(&K) -> bool>(&mut self, hash: u64, is_match: F) {
+        self.usage_order.move_to_back_by(hash, is_match);
     }
 
     /// Met à jour l'ordre d'utilisation en déplaçant la clé spécifiée
     /// à la fin de la liste (élément le plus récemment utilisé).
     fn move_to_recently_used(&mut self, key: &K) {
-        if let Some(pos) = self.usage_order.iter().position(|k| k == key) {
-            let key = self.usage_order.remove(pos);
-            self.usage_order.push(key);
-        }
+        self.usage_order.move_to_back(key);
     }
 
     /// Retourne le nombre d'éléments actuellement dans le cache.
+    /// 
+    /// Les entrées expirées mais pas encore purgées sont comptées.
     pub fn len(&self) -> usize {
         self.elements.len()
     }
@@ -138,16 +665,71 @@ where
 
     /// Vide le cache de tous ses éléments.
     pub fn clear(&mut self) {
+        if self.observers.is_active() {
+            for (key, entry) in &self.elements {
+                self.observers.notify(&Mutation::Remove { key, value: &entry.value, cause: RemovalCause::Cleared });
+            }
+        }
         self.elements.clear();
         self.usage_order.clear();
+        if let Some(index) = self.scan_index.as_mut() {
+            index.clear();
+        }
+        self.expirations.clear();
+        if let Some(buckets) = self.time_buckets.as_mut() {
+            buckets.clear();
+        }
+        self.total_weight = 0;
+        self.maybe_shrink();
     }
 
-    /// Retourne un itérateur sur les paires clé-valeur du cache.
+    /// Retourne un itérateur sur les paires clé-valeur du cache, du moins
+    /// récemment utilisé au plus récemment utilisé.
+    /// 
+    /// Les entrées expirées sont ignorées.
     pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
-        self.usage_order.iter().filter_map(|key| {
-            self.elements.get(key).map(|value| (key, value))
+        self.recency_order().filter_map(|key| {
+            if self.is_expired(key) {
+                return None;
+            }
+            self.elements.get(key).map(|entry| (key, &entry.value))
         })
     }
+
+    /// Parcourt les entrées comme `iter`, avec la garantie explicite de ne
+    /// rien modifier : ni l'ordre d'utilisation, ni les compteurs de
+    /// lectures, ni les statistiques, ni les entrées expirées, qui sont
+    /// seulement ignorées. Le parcours ne demande qu'un `&Cache` et peut
+    /// donc coexister avec d'autres lectures partagées.
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use lru_cache::lru::Cache;
+    /// use lru_cache::lru::traits::CacheTrait;
+    ///
+    /// let mut cache = Cache::builder().capacity(2).record_stats().build().unwrap();
+    /// cache.put("a", 1);
+    /// cache.put("b", 2);
+    ///
+    /// let lecture = &cache;
+    /// let total: i32 = lecture.peek_iter().map(|(_, v)| v).sum();
+    /// assert_eq!(total, 3);
+    /// assert_eq!(cache.stats().hits, 0);
+    ///
+    /// // "a" reste la moins récemment utilisée
+    /// cache.put("c", 3);
+    /// assert_eq!(cache.get(&"a"), None);
+    /// ```
+    pub fn peek_iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.iter()
+    }
+
+    /// Retourne le nombre de lectures réussies de l'entrée depuis son
+    /// insertion.
+    pub fn hit_count(&self, key: &K) -> Option<u64> {
+        self.elements.get(key).map(|entry| entry.hits)
+    }
 }
 
 impl<K, V> Cache<K, V> 
@@ -168,8 +750,10 @@ where
     /// # Errors
     /// 
     /// Retourne une erreur si :
-    /// * Le fichier existe mais ne peut pas être lu
-    /// * Le contenu du fichier ne peut pas être parsé
+    /// * La capacité est 0 (`CacheError::CapacityError`)
+    /// * Le fichier existe mais ne peut pas être lu (`CacheError::IoError`)
+    /// * Le contenu du fichier ne peut pas être parsé (`CacheError::Corrupted`,
+    ///   avec le chemin et le numéro de la ligne fautive)
     /// 
     /// # Exemples
     /// 
@@ -179,46 +763,85 @@ where
     /// let cache = Cache::<String, String>::new_persistent(3, "cache.txt").unwrap();
     /// ```
     pub fn new_persistent<P: AsRef<Path>>(capacity: usize, path: P) -> Result<Self, CacheError> {
-        let cache = match File::open(path.as_ref()) {
-            Ok(file) => {
-                let reader = BufReader::new(file);
-                Self::load_from_reader(reader, capacity)?
-            },
-            Err(_) => Self::new(capacity),
-        };
+        let mut cache = Self::try_new(capacity)?;
+        cache.load_file_if_exists(path)?;
         Ok(cache)
     }
 
-    fn load_from_reader<R: Read>(mut reader: R, capacity: usize) -> Result<Self, CacheError> {
-        let mut content = String::new();
-        reader.read_to_string(&mut content)
-            .map_err(|e| CacheError::IoError(e))?;
+    /// Charge dans le cache le contenu du fichier de persistance, s'il
+    /// existe.
+    pub(crate) fn load_file_if_exists<P: AsRef<Path>>(&mut self, path: P) -> Result<(), CacheError> {
+        let Some(entries) = Self::read_file_entries(path)? else {
+            return Ok(());
+        };
+        for (key, value) in entries {
+            self.put(key, value);
+        }
+        self.mark_synced();
+        Ok(())
+    }
+
+    /// Lit toutes les entrées du fichier de persistance, ou `None` s'il
+    /// n'existe pas. Le fichier est lu en entier avant d'être appliqué : une
+    /// ligne invalide n'en laisse rien passer.
+    pub(crate) fn read_file_entries<P: AsRef<Path>>(path: P) -> Result<Option<Vec<(K, V)>>, CacheError> {
+        let Ok(file) = File::open(path.as_ref()) else {
+            return Ok(None);
+        };
+        Self::parse_entries(BufReader::new(file)).map(Some).map_err(|err| match err {
+            CacheError::Corrupted { path: None, line, reason } => CacheError::Corrupted {
+                path: Some(path.as_ref().to_path_buf()),
+                line,
+                reason,
+            },
+            other => other,
+        })
+    }
 
-        let mut cache = Self::new(capacity);
+    fn parse_entries<R: Read>(mut reader: R) -> Result<Vec<(K, V)>, CacheError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
 
-        for line in content.lines() {
+        let mut entries = Vec::new();
+        for (index, line) in content.lines().enumerate() {
             if line.is_empty() {
                 continue;
             }
 
+            let corrupted = |reason: String| CacheError::Corrupted {
+                path: None,
+                line: index + 1,
+                reason,
+            };
+
+            if index == 0 {
+                if let Some(header) = FileDescription::parse_header(line) {
+                    header.map_err(corrupted)?;
+                    continue;
+                }
+            }
+
             let parts: Vec<&str> = line.split('\t').collect();
             if parts.len() != 2 {
-                return Err(CacheError::ParseError("Format de ligne invalide".to_string()));
+                return Err(corrupted(messages::INVALID_LINE_FORMAT.to_string()));
             }
 
             let key = K::from_str(parts[0])
-                .map_err(|_| CacheError::ParseError(format!("Impossible de parser la clé: {}", parts[0])))?;
+                .map_err(|_| corrupted(format!("{}: {}", messages::UNPARSABLE_KEY, parts[0])))?;
             let value = V::from_str(parts[1])
-                .map_err(|_| CacheError::ParseError(format!("Impossible de parser la valeur: {}", parts[1])))?;
+                .map_err(|_| corrupted(format!("{}: {}", messages::UNPARSABLE_VALUE, parts[1])))?;
 
-            cache.put(key, value);
+            entries.push((key, value));
         }
 
-        Ok(cache)
+        Ok(entries)
     }
 
     /// Sauvegarde l'état actuel du cache dans un fichier.
     /// 
+    /// Le fichier commence par un en-tête décrivant son format (voir le
+    /// module `format`), suivi d'une ligne par entrée.
+    /// 
     /// # Arguments
     /// 
     /// * `path` - Le chemin du fichier où sauvegarder le cache
@@ -238,25 +861,70 @@ where
     /// cache.persist("cache.txt").unwrap();
     /// ```
     pub fn persist<P: AsRef<Path>>(&self, path: P) -> Result<(), CacheError> {
+        let start = self.start_timer();
+        self.write_entries(path.as_ref())?;
+        self.mark_synced();
+        self.record_latency(TimedOp::Persist, start);
+        Ok(())
+    }
+
+    /// Écrit l'en-tête et toutes les entrées dans le fichier donné.
+    pub(crate) fn write_entries(&self, path: &Path) -> Result<(), CacheError> {
+        let entries: Vec<_> = self.iter().collect();
+        self.write_file(path, &entries)
+    }
+
+    /// Écrit l'en-tête, avec la somme de contrôle des lignes d'entrées, puis
+    /// les entrées données.
+    fn write_file(&self, path: &Path, entries: &[(&K, &V)]) -> Result<(), CacheError> {
         let file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(path)
-            .map_err(|e| CacheError::IoError(e))?;
+            .open(path)?;
 
         let mut writer = BufWriter::new(file);
 
-        for key in &self.usage_order {
-            if let Some(value) = self.elements.get(key) {
-                writeln!(writer, "{}\t{}", key, value)
-                    .map_err(|e| CacheError::IoError(e))?;
-            }
+        let mut body = String::new();
+        for (key, value) in entries {
+            // L'écriture dans une `String` n'échoue pas
+            let _ = writeln!(body, "{}\t{}", key, value);
         }
+        let description = FileDescription {
+            checksum: Some(format::checksum(body.as_bytes())),
+            ..FileDescription::new(entries.len(), self.capacity())
+        };
+        writeln!(writer, "{}", description.header_line())?;
+        writer.write_all(body.as_bytes())?;
 
-        writer.flush().map_err(|e| CacheError::IoError(e))?;
+        writer.flush()?;
         Ok(())
     }
+
+    /// Sauvegarde uniquement les `n` entrées les plus récemment utilisées,
+    /// dans le même format que `persist`.
+    ///
+    /// Le fichier reste ainsi petit tout en conservant la partie chaude du
+    /// cache, celle qui mérite d'être restaurée au démarrage.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les mêmes erreurs que `persist`.
+    ///
+    /// # Exemples
+    ///
+    /// ```no_run
+    /// use lru_cache::lru::Cache;
+    ///
+    /// let cache = Cache::<String, String>::new(10_000);
+    /// cache.persist_top(500, "cache.txt").unwrap();
+    /// ```
+    pub fn persist_top<P: AsRef<Path>>(&self, n: usize, path: P) -> Result<(), CacheError> {
+        // Les entrées expirées sont ignorées par `iter` : le décompte se fait
+        // sur les entrées effectivement écrites
+        let entries: Vec<_> = self.iter().collect();
+        self.write_file(path.as_ref(), &entries[entries.len().saturating_sub(n)..])
+    }
 }
 
 impl<K, V> CacheTrait<K, V> for Cache<K, V>
@@ -264,31 +932,52 @@ where
     K: Hash + Eq + Clone,
 {
     fn get(&mut self, key: &K) -> Option<&V> {
-        if self.elements.contains_key(key) {
-            self.move_to_recently_used(key);
-            self.elements.get(key)
-        } else {
-            None
-        }
+        let hash = self.hash_key(key);
+        self.get_with_hash(hash, key)
     }
 
     fn put(&mut self, key: K, value: V) {
-        if self.elements.len() >= self.capacity && !self.elements.contains_key(&key) {
-            // Supprimer l'élément le moins récemment utilisé
-            if let Some(lru_key) = self.usage_order.first().cloned() {
-                self.elements.remove(&lru_key);
-                self.usage_order.remove(0);
-            }
+        let start = self.start_timer();
+        if self.put_entry(key.clone(), value, self.default_ttl, false).is_err() {
+            // Valeur refusée : l'ancienne valeur ne doit pas rester visible
+            self.remove_entry(&key, RemovalCause::Rejected);
         }
+        self.record_latency(TimedOp::Put, start);
+    }
 
-        // Si la clé existe déjà, la mettre à jour
-        if self.elements.contains_key(&key) {
-            self.elements.insert(key.clone(), value);
-            self.move_to_recently_used(&key);
-        } else {
-            // Sinon, ajouter le nouvel élément
-            self.elements.insert(key.clone(), value);
-            self.usage_order.push(key);
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key, RemovalCause::Explicit)
+    }
+
+    fn clear(&mut self) {
+        Cache::clear(self)
+    }
+}
+
+impl<K, V> CacheRead<K, V> for Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        if self.is_expired(key) {
+            return None;
         }
+        self.elements.get(key).map(|entry| &entry.value)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.elements.contains_key(key) && !self.is_expired(key)
+    }
+
+    fn len(&self) -> usize {
+        Cache::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        Cache::is_empty(self)
+    }
+
+    fn capacity(&self) -> usize {
+        Cache::capacity(self)
     }
 }
\ No newline at end of file
@@ -0,0 +1,321 @@
+//! Module implémentant l'ordre d'utilisation du cache.
+//!
+//! L'ordre est une liste doublement chaînée dont les nœuds sont rangés dans
+//! un vecteur et reliés par leurs indices ; les emplacements libérés sont
+//! chaînés entre eux et réutilisés. Une table de hachage retrouve le nœud
+//! d'une clé : ajouter, retirer ou déplacer une clé coûte donc O(1), quelle
+//! que soit la taille du cache.
+//!
+//! La table ne stocke que les indices des nœuds et compare les clés dans les
+//! nœuds : chaque clé n'est copiée qu'une fois dans l'ordre. Elle utilise la
+//! même fonction de hachage que la table des entrées, pour qu'une empreinte
+//! calculée par l'une serve aussi à l'autre.
+
+use std::hash::{BuildHasher, Hash};
+use std::mem::size_of;
+use hashbrown::HashTable;
+use crate::lru::deterministic::KeyHasher;
+
+/// Indice marquant l'absence de nœud.
+const NIL: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct Node<K> {
+    /// Clé du nœud, absente pour un emplacement libre
+    key: Option<K>,
+    prev: usize,
+    next: usize,
+}
+
+/// Clés du moins récemment utilisé (tête) au plus récemment utilisé (queue).
+#[derive(Debug)]
+pub(crate) struct UsageOrder<K> {
+    nodes: Vec<Node<K>>,
+    index: HashTable<usize>,
+    hasher: KeyHasher,
+    head: usize,
+    tail: usize,
+    /// Premier emplacement libre, les suivants étant chaînés par `next`
+    free: usize,
+}
+
+fn key_of<K>(nodes: &[Node<K>], slot: usize) -> &K {
+    nodes[slot].key.as_ref().expect("un nœud indexé porte une clé")
+}
+
+impl<K: Hash + Eq> UsageOrder<K> {
+    /// Crée un ordre vide, sans allocation.
+    pub(crate) fn new(hasher: KeyHasher) -> Self {
+        Self::with_capacity(0, hasher)
+    }
+
+    /// Crée un ordre vide pouvant recevoir `capacity` clés sans
+    /// réallocation.
+    pub(crate) fn with_capacity(capacity: usize, hasher: KeyHasher) -> Self {
+        UsageOrder {
+            nodes: Vec::with_capacity(capacity),
+            index: HashTable::with_capacity(capacity),
+            hasher,
+            head: NIL,
+            tail: NIL,
+            free: NIL,
+        }
+    }
+
+    /// Retourne le nombre de clés.
+    pub(crate) fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    fn find(&self, hash: u64, mut is_match: impl FnMut(&K) -> bool) -> Option<usize> {
+        let nodes = &self.nodes;
+        self.index.find(hash, |&slot| is_match(key_of(nodes, slot))).copied()
+    }
+
+    fn find_key(&self, key: &K) -> Option<usize> {
+        self.find(self.hasher.hash_one(key), |candidate| candidate == key)
+    }
+
+    /// Range la clé dans un emplacement, libre ou nouveau, et l'indexe.
+    fn allocate(&mut self, key: K) -> usize {
+        let hash = self.hasher.hash_one(&key);
+        let node = Node { key: Some(key), prev: NIL, next: NIL };
+        let slot = if self.free == NIL {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        } else {
+            let slot = self.free;
+            self.free = self.nodes[slot].next;
+            self.nodes[slot] = node;
+            slot
+        };
+        let (nodes, hasher) = (&self.nodes, &self.hasher);
+        self.index.insert_unique(hash, slot, |&slot| hasher.hash_one(key_of(nodes, slot)));
+        slot
+    }
+
+    /// Retire le nœud de la liste sans le libérer.
+    fn unlink(&mut self, slot: usize) {
+        let Node { prev, next, .. } = self.nodes[slot];
+        match prev {
+            NIL => self.head = next,
+            prev => self.nodes[prev].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.nodes[next].prev = prev,
+        }
+    }
+
+    fn link_back(&mut self, slot: usize) {
+        self.nodes[slot].prev = self.tail;
+        self.nodes[slot].next = NIL;
+        match self.tail {
+            NIL => self.head = slot,
+            tail => self.nodes[tail].next = slot,
+        }
+        self.tail = slot;
+    }
+
+    fn link_front(&mut self, slot: usize) {
+        self.nodes[slot].prev = NIL;
+        self.nodes[slot].next = self.head;
+        match self.head {
+            NIL => self.tail = slot,
+            head => self.nodes[head].prev = slot,
+        }
+        self.head = slot;
+    }
+
+    /// Retire le nœud de la liste et de la table, et libère son
+    /// emplacement.
+    fn release(&mut self, slot: usize) -> K {
+        self.unlink(slot);
+        let hash = self.hasher.hash_one(key_of(&self.nodes, slot));
+        if let Ok(entry) = self.index.find_entry(hash, |&candidate| candidate == slot) {
+            entry.remove();
+        }
+        let node = &mut self.nodes[slot];
+        node.next = self.free;
+        self.free = slot;
+        node.key.take().expect("un nœud indexé porte une clé")
+    }
+
+    /// Ajoute la clé comme la plus récemment utilisée, ou l'y déplace si
+    /// elle est déjà présente.
+    pub(crate) fn push_back(&mut self, key: K) {
+        let slot = match self.find_key(&key) {
+            Some(slot) => {
+                self.unlink(slot);
+                slot
+            }
+            None => self.allocate(key),
+        };
+        self.link_back(slot);
+    }
+
+    /// Ajoute la clé comme la moins récemment utilisée, ou l'y déplace si
+    /// elle est déjà présente.
+    pub(crate) fn push_front(&mut self, key: K) {
+        let slot = match self.find_key(&key) {
+            Some(slot) => {
+                self.unlink(slot);
+                slot
+            }
+            None => self.allocate(key),
+        };
+        self.link_front(slot);
+    }
+
+    /// Retire et retourne la clé la moins récemment utilisée.
+    pub(crate) fn pop_front(&mut self) -> Option<K> {
+        (self.head != NIL).then(|| self.release(self.head))
+    }
+
+    /// Retire la clé. Retourne `false` si elle était absente.
+    pub(crate) fn remove(&mut self, key: &K) -> bool {
+        match self.find_key(key) {
+            Some(slot) => {
+                self.release(slot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Déplace la clé à la fin de l'ordre, si elle est présente.
+    pub(crate) fn move_to_back(&mut self, key: &K) {
+        if let Some(slot) = self.find_key(key) {
+            self.unlink(slot);
+            self.link_back(slot);
+        }
+    }
+
+    /// Déplace à la fin de l'ordre la clé d'empreinte `hash` qui satisfait
+    /// le prédicat.
+    pub(crate) fn move_to_back_by(&mut self, hash: u64, is_match: impl FnMut(&K) -> bool) {
+        if let Some(slot) = self.find(hash, is_match) {
+            self.unlink(slot);
+            self.link_back(slot);
+        }
+    }
+
+    /// Échange les places des deux clés, si elles sont toutes deux
+    /// présentes.
+    pub(crate) fn swap(&mut self, a: &K, b: &K) {
+        let (Some(first), Some(second)) = (self.find_key(a), self.find_key(b)) else {
+            return;
+        };
+        let (hash_a, hash_b) = (self.hasher.hash_one(a), self.hasher.hash_one(b));
+        let key = self.nodes[first].key.take();
+        self.nodes[first].key = std::mem::replace(&mut self.nodes[second].key, key);
+        // Les entrées de la table sont repérées par leur indice, les clés
+        // ayant déjà changé de nœud
+        if let Some(slot) = self.index.find_mut(hash_a, |&slot| slot == first) {
+            *slot = second;
+        }
+        if let Some(slot) = self.index.find_mut(hash_b, |&slot| slot == second) {
+            *slot = first;
+        }
+    }
+
+    /// Remplace la clé `old` par `new`, absente, à la même place.
+    pub(crate) fn replace_key(&mut self, old: &K, new: K) {
+        let Some(slot) = self.find_key(old) else {
+            return;
+        };
+        if let Ok(entry) = self.index.find_entry(self.hasher.hash_one(old), |&candidate| candidate == slot) {
+            entry.remove();
+        }
+        let hash = self.hasher.hash_one(&new);
+        self.nodes[slot].key = Some(new);
+        let (nodes, hasher) = (&self.nodes, &self.hasher);
+        self.index.insert_unique(hash, slot, |&slot| hasher.hash_one(key_of(nodes, slot)));
+    }
+
+    /// Déplace les `count` premières clés à la fin de l'ordre, dans le même
+    /// ordre.
+    pub(crate) fn rotate_left(&mut self, count: usize) {
+        for _ in 0..count.min(self.len()) {
+            let slot = self.head;
+            self.unlink(slot);
+            self.link_back(slot);
+        }
+    }
+
+    /// Retire toutes les clés, en gardant la mémoire allouée.
+    pub(crate) fn clear(&mut self) {
+        self.nodes.clear();
+        self.index.clear();
+        self.head = NIL;
+        self.tail = NIL;
+        self.free = NIL;
+    }
+
+    /// Parcourt les clés du moins récemment utilisé au plus récemment
+    /// utilisé.
+    pub(crate) fn iter(&self) -> Iter<'_, K> {
+        Iter { nodes: &self.nodes, next: self.head, remaining: self.len() }
+    }
+
+    /// Remplace la fonction de hachage, pour suivre celle de la table des
+    /// entrées.
+    pub(crate) fn set_hasher(&mut self, hasher: KeyHasher) {
+        self.hasher = hasher;
+        self.index.clear();
+        let mut slot = self.head;
+        while slot != NIL {
+            let (nodes, hasher) = (&self.nodes, &self.hasher);
+            let hash = hasher.hash_one(key_of(nodes, slot));
+            self.index.insert_unique(hash, slot, |&slot| hasher.hash_one(key_of(nodes, slot)));
+            slot = self.nodes[slot].next;
+        }
+    }
+
+    /// Retourne le nombre de clés et le nombre d'emplacements alloués.
+    pub(crate) fn occupancy(&self) -> (usize, usize) {
+        (self.len(), self.nodes.capacity())
+    }
+
+    /// Retourne la mémoire allouée par les nœuds et la table, hors mémoire
+    /// possédée par les clés.
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        self.nodes.capacity() * size_of::<Node<K>>() + self.index.allocation_size()
+    }
+
+    /// Compacte les nœuds et libère la mémoire inutilisée.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let mut compacted = UsageOrder::with_capacity(self.len(), self.hasher.clone());
+        while let Some(key) = self.pop_front() {
+            compacted.push_back(key);
+        }
+        *self = compacted;
+    }
+}
+
+/// Itérateur sur les clés d'un `UsageOrder`.
+pub(crate) struct Iter<'a, K> {
+    nodes: &'a [Node<K>],
+    next: usize,
+    remaining: usize,
+}
+
+impl<'a, K> Iterator for Iter<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        if self.next == NIL {
+            return None;
+        }
+        let node = &self.nodes[self.next];
+        self.next = node.next;
+        self.remaining -= 1;
+        node.key.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K> ExactSizeIterator for Iter<'_, K> {}
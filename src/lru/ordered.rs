@@ -0,0 +1,185 @@
+//! Module implémentant un cache à clés ordonnées.
+//!
+//! `OrderedCache` tient, à côté du cache, un index trié (`BTreeSet`) de ses
+//! clés, ce qui permet de parcourir ou de supprimer toutes les entrées d'un
+//! intervalle de clés. C'est utile pour un cache indexé par date, invalidé
+//! par fenêtre de temps. L'éviction LRU s'applique normalement : l'index est
+//! tenu à jour par un observateur des mutations du cache.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::ordered::OrderedCache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! // Mesures indexées par minute
+//! let mut cache = OrderedCache::new(Cache::new(100));
+//! for minute in 0..10u32 {
+//!     cache.put(minute, minute * 2);
+//! }
+//!
+//! let fenetre: Vec<_> = cache.range(3..6).into_iter().map(|(k, v)| (*k, *v)).collect();
+//! assert_eq!(fenetre, vec![(3, 6), (4, 8), (5, 10)]);
+//!
+//! assert_eq!(cache.remove_range(..5).len(), 5);
+//! assert_eq!(cache.range(..).len(), 5);
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::RangeBounds;
+use std::sync::{Arc, Mutex, MutexGuard};
+use crate::lru::Cache;
+use crate::lru::events::Mutation;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Index trié partagé entre le cache et son observateur.
+type SharedKeys<K> = Arc<Mutex<BTreeSet<K>>>;
+
+fn lock<K>(keys: &SharedKeys<K>) -> MutexGuard<'_, BTreeSet<K>> {
+    keys.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Cache dont les entrées peuvent être parcourues par intervalle de clés.
+pub struct OrderedCache<K, V>
+where
+    K: Hash + Eq,
+{
+    cache: Cache<K, V>,
+    keys: SharedKeys<K>,
+}
+
+impl<K, V> fmt::Debug for OrderedCache<K, V>
+where
+    K: Hash + Eq + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderedCache").field("cache", &self.cache).finish()
+    }
+}
+
+impl<K, V> OrderedCache<K, V>
+where
+    K: Hash + Ord + Clone + Send + 'static,
+    V: 'static,
+{
+    /// Encapsule le cache donné, en indexant les entrées déjà présentes.
+    pub fn new(mut cache: Cache<K, V>) -> Self {
+        let keys: SharedKeys<K> = Arc::new(Mutex::new(cache.recency_order().cloned().collect()));
+
+        let shared = Arc::clone(&keys);
+        cache.observers.listeners.push(Box::new(move |mutation: &Mutation<'_, K, V>| {
+            match *mutation {
+                Mutation::Insert { key, .. } => {
+                    lock(&shared).insert(key.clone());
+                }
+                Mutation::Update { .. } => {}
+                Mutation::Remove { key, .. } => {
+                    lock(&shared).remove(key);
+                }
+            }
+            true
+        }));
+        OrderedCache { cache, keys }
+    }
+}
+
+impl<K, V> OrderedCache<K, V>
+where
+    K: Hash + Ord + Clone,
+{
+    /// Retourne les entrées dont la clé appartient à l'intervalle, par ordre
+    /// croissant de clé, sans modifier l'ordre LRU.
+    ///
+    /// Les entrées expirées mais pas encore purgées sont ignorées.
+    pub fn range<R>(&self, range: R) -> Vec<(&K, &V)>
+    where
+        R: RangeBounds<K>,
+    {
+        let keys = lock(&self.keys);
+        keys.range(range)
+            .filter(|key| self.cache.contains(key))
+            .filter_map(|key| {
+                let (key, entry) = self.cache.elements.get_key_value(key)?;
+                Some((key, &entry.value))
+            })
+            .collect()
+    }
+
+    /// Supprime toutes les entrées dont la clé appartient à l'intervalle et
+    /// les retourne par ordre croissant de clé.
+    pub fn remove_range<R>(&mut self, range: R) -> Vec<(K, V)>
+    where
+        R: RangeBounds<K>,
+    {
+        // Le verrou est relâché avant les suppressions, qui notifient
+        // l'observateur
+        let keys: Vec<K> = lock(&self.keys).range(range).cloned().collect();
+        keys.into_iter()
+            .filter_map(|key| {
+                let value = self.cache.remove(&key)?;
+                Some((key, value))
+            })
+            .collect()
+    }
+
+    /// Retourne la plus petite et la plus grande clé présentes.
+    pub fn key_bounds(&self) -> Option<(K, K)> {
+        let keys = lock(&self.keys);
+        Some((keys.first()?.clone(), keys.last()?.clone()))
+    }
+
+    /// Retourne une référence au cache encapsulé.
+    pub fn inner(&self) -> &Cache<K, V> {
+        &self.cache
+    }
+}
+
+impl<K, V> CacheRead<K, V> for OrderedCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.cache.peek(key)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.cache.contains(key)
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+}
+
+impl<K, V> CacheTrait<K, V> for OrderedCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        self.cache.put(key, value);
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.cache.remove(key)
+    }
+
+    fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
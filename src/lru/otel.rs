@@ -0,0 +1,188 @@
+//! Module exportant les métriques du cache vers OpenTelemetry.
+//!
+//! Disponible avec la fonctionnalité `otel`. `SyncCache::register_otel_metrics`
+//! déclare sur un `Meter` des instruments observables (lectures, mutations,
+//! taille, poids) relus à chaque collecte : le cache apparaît ainsi dans le
+//! pipeline OTLP de l'application, avec la ressource configurée sur son
+//! `MeterProvider`, sans pont dédié. Les attributs de `OtelConfig`
+//! (`cache.name` et ceux ajoutés) accompagnent chaque mesure.
+//!
+//! `OtelEvents` est un `CacheHooks` optionnel qui ajoute un événement au span
+//! actif pour chaque lecture, insertion et suppression, afin de voir dans une
+//! trace quels accès au cache ont réussi.
+//!
+//! Les compteurs de lectures et de mutations reposent sur les statistiques du
+//! cache (`CacheBuilder::record_stats`) ; sans elles, ils restent à 0.
+//!
+//! # Exemple
+//!
+//! ```
+//! use opentelemetry::{global, KeyValue};
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::otel::{OtelConfig, OtelEvents};
+//! use lru_cache::lru::sync::SyncCache;
+//!
+//! let config = OtelConfig::new("sessions").with_attribute(KeyValue::new("region", "eu-west"));
+//! let cache: SyncCache<String, String> = SyncCache::from_cache(
+//!     Cache::builder()
+//!         .capacity(1000)
+//!         .record_stats()
+//!         .hooks(OtelEvents::new(&config))
+//!         .build()
+//!         .unwrap(),
+//! );
+//! let _metriques = cache.register_otel_metrics(&global::meter("lru_cache"), &config);
+//! ```
+
+use std::hash::Hash;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Meter, ObservableCounter, ObservableGauge};
+use opentelemetry::trace::get_active_span;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+use crate::lru::hooks::CacheHooks;
+use crate::lru::stats::CacheStats;
+use crate::lru::sync::SyncCache;
+use crate::messages;
+
+/// Nom, description et lecture d'un instrument.
+type Instrument<T> = (&'static str, &'static str, fn(&T) -> u64);
+
+/// Attributs joints aux métriques et événements d'un cache.
+#[derive(Debug, Clone)]
+pub struct OtelConfig {
+    attributes: Vec<KeyValue>,
+}
+
+impl OtelConfig {
+    /// Crée une configuration identifiant le cache par l'attribut
+    /// `cache.name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        OtelConfig {
+            attributes: vec![KeyValue::new("cache.name", name.into())],
+        }
+    }
+
+    /// Ajoute un attribut à chaque mesure et événement.
+    pub fn with_attribute(mut self, attribute: KeyValue) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Retourne les attributs configurés.
+    pub fn attributes(&self) -> &[KeyValue] {
+        &self.attributes
+    }
+}
+
+/// Instruments enregistrés pour un cache.
+///
+/// Les instruments restent déclarés tant que cette valeur est conservée.
+#[derive(Debug)]
+pub struct OtelMetrics {
+    _counters: Vec<ObservableCounter<u64>>,
+    _gauges: Vec<ObservableGauge<u64>>,
+}
+
+/// Crochets ajoutant au span actif un événement par accès au cache.
+#[derive(Debug, Clone)]
+pub struct OtelEvents {
+    attributes: Vec<KeyValue>,
+}
+
+impl OtelEvents {
+    /// Crée les crochets avec les attributs de la configuration.
+    pub fn new(config: &OtelConfig) -> Self {
+        OtelEvents {
+            attributes: config.attributes.clone(),
+        }
+    }
+
+    fn emit(&self, name: &'static str, extra: Option<KeyValue>) {
+        get_active_span(|span| {
+            if span.is_recording() {
+                let mut attributes = self.attributes.clone();
+                attributes.extend(extra);
+                span.add_event(name, attributes);
+            }
+        });
+    }
+}
+
+impl<K, V> CacheHooks<K, V> for OtelEvents {
+    fn on_hit(&self, _key: &K, _value: &V) {
+        self.emit("cache.hit", None);
+    }
+
+    fn on_miss(&self, _key: &K) {
+        self.emit("cache.miss", None);
+    }
+
+    fn on_insert(&self, _key: &K, _value: &V) {
+        self.emit("cache.insert", None);
+    }
+
+    fn on_remove(&self, _key: &K, _value: &V, cause: RemovalCause) {
+        self.emit("cache.remove", Some(KeyValue::new("cache.removal_cause", cause.as_str())));
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Send + 'static,
+{
+    /// Déclare sur le `Meter` les instruments observables du cache.
+    pub fn register_otel_metrics(&self, meter: &Meter, config: &OtelConfig) -> OtelMetrics {
+        let counters: [Instrument<CacheStats>; 7] = [
+            ("cache.hits", messages::METRIC_HITS, |stats| stats.hits),
+            ("cache.misses", messages::METRIC_MISSES, |stats| stats.misses),
+            ("cache.inserts", messages::METRIC_INSERTS, |stats| stats.inserts),
+            ("cache.updates", messages::METRIC_UPDATES, |stats| stats.updates),
+            ("cache.removals", messages::METRIC_REMOVALS, |stats| stats.removals),
+            ("cache.evictions", messages::METRIC_EVICTIONS, |stats| stats.evictions),
+            ("cache.expirations", messages::METRIC_EXPIRATIONS, |stats| stats.expirations),
+        ];
+        let gauges: [Instrument<Cache<K, V>>; 3] = [
+            ("cache.size", messages::METRIC_SIZE, |cache| cache.len() as u64),
+            ("cache.capacity", messages::METRIC_CAPACITY, |cache| cache.capacity() as u64),
+            ("cache.weight", messages::METRIC_WEIGHT, |cache| cache.total_weight() as u64),
+        ];
+
+        let counters = counters
+            .into_iter()
+            .map(|(name, description, read)| {
+                let (cache, attributes) = (self.clone(), config.attributes.clone());
+                meter
+                    .u64_observable_counter(name)
+                    .with_description(description)
+                    .with_callback(move |observer| {
+                        if let Ok(value) = cache.with_lock(|cache| read(&cache.stats())) {
+                            observer.observe(value, &attributes);
+                        }
+                    })
+                    .build()
+            })
+            .collect();
+        let gauges = gauges
+            .into_iter()
+            .map(|(name, description, read)| {
+                let (cache, attributes) = (self.clone(), config.attributes.clone());
+                meter
+                    .u64_observable_gauge(name)
+                    .with_description(description)
+                    .with_callback(move |observer| {
+                        if let Ok(value) = cache.with_lock(|cache| read(cache)) {
+                            observer.observe(value, &attributes);
+                        }
+                    })
+                    .build()
+            })
+            .collect();
+
+        OtelMetrics {
+            _counters: counters,
+            _gauges: gauges,
+        }
+    }
+}
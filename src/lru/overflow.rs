@@ -0,0 +1,281 @@
+//! Module implémentant le débordement des entrées évincées vers un stockage
+//! secondaire.
+//!
+//! Lorsqu'un `StorageBackend` est configuré, les entrées évincées pour
+//! respecter la capacité ou le budget de poids lui sont confiées au lieu
+//! d'être perdues. Un échec de lecture le consulte avant de retourner `None` :
+//! la valeur retrouvée est retirée du stockage et réintégrée au cache comme
+//! entrée la plus récemment utilisée. Le cache forme ainsi un premier niveau
+//! rapide devant un niveau plus lent (disque, sled, redis...), sans logique de
+//! hiérarchisation chez l'appelant.
+//!
+//! Les suppressions explicites retirent aussi l'entrée du stockage ; les
+//! entrées expirées n'y sont jamais écrites. `peek` et `contains` ne
+//! consultent que la mémoire.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::sync::Arc;
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::overflow::{MemoryStore, StorageBackend};
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let stockage = Arc::new(MemoryStore::new());
+//! let mut cache = Cache::builder()
+//!     .capacity(1)
+//!     .overflow(stockage.clone())
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.put("a", 1);
+//! cache.put("b", 2); // "a" déborde vers le stockage
+//! assert!(!cache.contains(&"a"));
+//! assert_eq!(stockage.fetch(&"a").unwrap(), Some(1));
+//!
+//! assert_eq!(cache.get(&"a"), Some(&1)); // relu depuis le stockage
+//! assert_eq!(stockage.fetch(&"b").unwrap(), Some(2));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::hash::Hash;
+use std::io::ErrorKind;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::format;
+use crate::messages;
+
+/// Stockage secondaire recevant les entrées évincées du cache.
+///
+/// Les méthodes prennent `&self` : une implémentation qui doit se modifier
+/// utilise une mutabilité intérieure, comme le font déjà la plupart des
+/// clients de bases clé-valeur.
+pub trait StorageBackend<K, V>: Send + Sync {
+    /// Enregistre une entrée évincée, en remplaçant la valeur existante.
+    fn store(&self, key: K, value: V) -> Result<(), CacheError>;
+
+    /// Retourne la valeur enregistrée pour la clé, si elle existe.
+    fn fetch(&self, key: &K) -> Result<Option<V>, CacheError>;
+
+    /// Supprime l'entrée enregistrée pour la clé, si elle existe.
+    fn delete(&self, key: &K) -> Result<(), CacheError>;
+}
+
+/// Stockage secondaire configuré sur un cache.
+pub(crate) struct Overflow<K, V>(Arc<dyn StorageBackend<K, V>>);
+
+impl<K, V> Overflow<K, V> {
+    pub(crate) fn new(backend: Arc<dyn StorageBackend<K, V>>) -> Self {
+        Overflow(backend)
+    }
+
+    /// Confie une entrée évincée au stockage ; un échec est journalisé.
+    pub(crate) fn spill(&self, key: K, value: V) {
+        if let Err(err) = self.0.store(key, value) {
+            log::warn!("{}: {}", messages::LOG_OVERFLOW_FAILED, err);
+        }
+    }
+
+    /// Retire et retourne la valeur enregistrée pour la clé.
+    pub(crate) fn take(&self, key: &K) -> Option<V> {
+        let value = self.0.fetch(key).and_then(|value| {
+            if value.is_some() {
+                self.0.delete(key)?;
+            }
+            Ok(value)
+        });
+        value.unwrap_or_else(|err| {
+            log::warn!("{}: {}", messages::LOG_OVERFLOW_FAILED, err);
+            None
+        })
+    }
+
+    /// Supprime l'entrée enregistrée pour la clé ; un échec est journalisé.
+    pub(crate) fn discard(&self, key: &K) {
+        if let Err(err) = self.0.delete(key) {
+            log::warn!("{}: {}", messages::LOG_OVERFLOW_FAILED, err);
+        }
+    }
+}
+
+impl<K, V> Clone for Overflow<K, V> {
+    fn clone(&self) -> Self {
+        Overflow(Arc::clone(&self.0))
+    }
+}
+
+impl<K, V> fmt::Debug for Overflow<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Overflow(..)")
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Réintègre au cache la valeur débordée pour la clé, si elle existe.
+    pub(crate) fn recall_overflow(&mut self, key: &K) {
+        let Some(value) = self.overflow.as_ref().and_then(|overflow| overflow.take(key)) else {
+            return;
+        };
+        // Une valeur refusée par les limites actuelles du cache est abandonnée
+        let _ = self.put_entry(key.clone(), value, self.default_ttl, true);
+    }
+}
+
+/// Stockage secondaire en mémoire, utile pour les tests ou comme niveau
+/// non borné derrière un cache borné.
+#[derive(Debug)]
+pub struct MemoryStore<K, V> {
+    entries: Mutex<HashMap<K, V>>,
+}
+
+impl<K, V> MemoryStore<K, V> {
+    /// Crée un stockage vide.
+    pub fn new() -> Self {
+        MemoryStore { entries: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl<K, V> Default for MemoryStore<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> MemoryStore<K, V>
+where
+    K: Hash + Eq,
+{
+    /// Retourne le nombre d'entrées enregistrées.
+    pub fn len(&self) -> usize {
+        self.entries.lock().map_or(0, |entries| entries.len())
+    }
+
+    /// Indique si le stockage est vide.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K, V> StorageBackend<K, V> for MemoryStore<K, V>
+where
+    K: Hash + Eq + Send,
+    V: Clone + Send,
+{
+    fn store(&self, key: K, value: V) -> Result<(), CacheError> {
+        self.entries.lock().map_err(|_| CacheError::Poisoned)?.insert(key, value);
+        Ok(())
+    }
+
+    fn fetch(&self, key: &K) -> Result<Option<V>, CacheError> {
+        Ok(self.entries.lock().map_err(|_| CacheError::Poisoned)?.get(key).cloned())
+    }
+
+    fn delete(&self, key: &K) -> Result<(), CacheError> {
+        self.entries.lock().map_err(|_| CacheError::Poisoned)?.remove(key);
+        Ok(())
+    }
+}
+
+/// Stockage secondaire sur disque : un fichier par entrée dans un
+/// répertoire, au format des fichiers de persistance (`clé\tvaleur`).
+///
+/// Le nom de chaque fichier est l'empreinte FNV-1a du texte de la clé,
+/// identique d'un processus et d'une version de Rust à l'autre : un
+/// répertoire rouvert retrouve ses entrées. En cas de collision, la dernière entrée écrite remplace la précédente : le stockage
+/// reste un cache et peut perdre des entrées, jamais en retourner une fausse.
+#[derive(Debug)]
+pub struct DirectoryStore<K, V> {
+    dir: PathBuf,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> DirectoryStore<K, V> {
+    /// Utilise le répertoire indiqué, créé s'il n'existe pas.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si le répertoire ne peut pas être créé.
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self, CacheError> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(DirectoryStore {
+            dir: dir.as_ref().to_path_buf(),
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<K, V> DirectoryStore<K, V>
+where
+    K: Display,
+{
+    fn path_of(&self, key: &K) -> PathBuf {
+        self.dir.join(format!("{:016x}", format::checksum(key.to_string().as_bytes())))
+    }
+}
+
+impl<K, V> StorageBackend<K, V> for DirectoryStore<K, V>
+where
+    K: Hash + Display,
+    V: Display + FromStr,
+{
+    fn store(&self, key: K, value: V) -> Result<(), CacheError> {
+        fs::write(self.path_of(&key), format!("{}\t{}", key, value))?;
+        Ok(())
+    }
+
+    fn fetch(&self, key: &K) -> Result<Option<V>, CacheError> {
+        let Some((path, raw)) = self.read(key)? else {
+            return Ok(None);
+        };
+        V::from_str(&raw)
+            .map(Some)
+            .map_err(|_| corrupted(path, format!("{}: {}", messages::UNPARSABLE_VALUE, raw)))
+    }
+
+    fn delete(&self, key: &K) -> Result<(), CacheError> {
+        let Some((path, _)) = self.read(key)? else {
+            return Ok(());
+        };
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<K, V> DirectoryStore<K, V>
+where
+    K: Hash + Display,
+{
+    /// Lit le fichier de la clé et retourne son chemin et la valeur brute.
+    fn read(&self, key: &K) -> Result<Option<(PathBuf, String)>, CacheError> {
+        let path = self.path_of(key);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let Some((stored_key, raw)) = content.split_once('\t') else {
+            return Err(corrupted(path, messages::INVALID_LINE_FORMAT.to_string()));
+        };
+        // Collision : le fichier appartient à une autre clé
+        if stored_key != key.to_string() {
+            return Ok(None);
+        }
+        let raw = raw.to_string();
+        Ok(Some((path, raw)))
+    }
+}
+
+fn corrupted(path: PathBuf, reason: String) -> CacheError {
+    CacheError::Corrupted { path: Some(path), line: 1, reason }
+}
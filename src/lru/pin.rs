@@ -0,0 +1,152 @@
+//! Module implémentant l'épinglage des entrées.
+//!
+//! Une entrée épinglée n'est jamais évincée : ni pour respecter la capacité
+//! ou le budget de poids, ni par `evict`, `shrink_by` ou la pression
+//! mémoire. Seules sa suppression explicite et l'expiration de sa durée de
+//! vie la retirent du cache. Si toutes les candidates sont épinglées, le
+//! cache dépasse temporairement sa capacité plutôt que d'évincer une entrée
+//! épinglée.
+//!
+//! `pin` et `unpin` épinglent et libèrent manuellement une clé. `pin_guard`
+//! retourne un `PinGuard` qui libère la clé à sa destruction : l'entrée ne
+//! peut disparaître par éviction tant que le gardien existe, même si le
+//! cache est modifié entre-temps. Les épinglages se cumulent : une clé reste
+//! épinglée tant qu'un épinglage au moins n'a pas été libéré.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = Cache::new(2);
+//! cache.put("modèle", 1);
+//! {
+//!     let _gardien = cache.pin_guard(&"modèle").unwrap();
+//!     cache.put("a", 2);
+//!     cache.put("b", 3);
+//!     // « modèle » est la moins récemment utilisée, mais reste présente
+//!     assert!(cache.contains(&"modèle"));
+//!     assert!(!cache.contains(&"a"));
+//! }
+//! assert!(!cache.is_pinned(&"modèle"));
+//! cache.put("c", 4);
+//! assert!(!cache.contains(&"modèle"));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard};
+use crate::lru::Cache;
+
+/// Nombre d'épinglages de chaque clé, partagé avec les gardiens.
+pub(crate) struct Pins<K>(Arc<Mutex<HashMap<K, usize>>>);
+
+impl<K> Pins<K> {
+    pub(crate) fn new() -> Self {
+        Pins(Arc::new(Mutex::new(HashMap::new())))
+    }
+
+    /// Verrouille les compteurs. Un compteur reste cohérent même si un
+    /// thread a paniqué en le détenant : l'empoisonnement est ignoré.
+    pub(crate) fn lock(&self) -> MutexGuard<'_, HashMap<K, usize>> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<K: Hash + Eq> Pins<K> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.lock().contains_key(key)
+    }
+
+    fn pin(&self, key: K) {
+        *self.lock().entry(key).or_insert(0) += 1;
+    }
+
+    fn unpin(&self, key: &K) -> bool {
+        let mut pins = self.lock();
+        match pins.get_mut(key) {
+            Some(count) if *count > 1 => *count -= 1,
+            Some(_) => {
+                pins.remove(key);
+            }
+            None => return false,
+        }
+        true
+    }
+}
+
+impl<K> Clone for Pins<K> {
+    fn clone(&self) -> Self {
+        Pins(Arc::clone(&self.0))
+    }
+}
+
+impl<K: fmt::Debug> fmt::Debug for Pins<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Pins").field(&*self.lock()).finish()
+    }
+}
+
+/// Gardien d'un épinglage, libéré à sa destruction.
+#[must_use = "l'entrée est libérée dès la destruction du gardien"]
+pub struct PinGuard<K: Hash + Eq> {
+    pins: Pins<K>,
+    key: K,
+}
+
+impl<K: Hash + Eq> PinGuard<K> {
+    /// Retourne la clé épinglée.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K: Hash + Eq> Drop for PinGuard<K> {
+    fn drop(&mut self) {
+        self.pins.unpin(&self.key);
+    }
+}
+
+impl<K: Hash + Eq + fmt::Debug> fmt::Debug for PinGuard<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PinGuard").field("key", &self.key).finish()
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Épingle l'entrée associée à la clé. Retourne `false` si la clé est
+    /// absente ou expirée.
+    pub fn pin(&self, key: &K) -> bool {
+        if self.is_expired(key) || !self.elements.contains_key(key) {
+            return false;
+        }
+        self.pins.pin(key.clone());
+        true
+    }
+
+    /// Libère un épinglage de la clé. Retourne `false` si elle n'était pas
+    /// épinglée.
+    pub fn unpin(&self, key: &K) -> bool {
+        self.pins.unpin(key)
+    }
+
+    /// Vérifie si la clé est épinglée.
+    pub fn is_pinned(&self, key: &K) -> bool {
+        self.pins.contains(key)
+    }
+
+    /// Épingle l'entrée associée à la clé jusqu'à la destruction du gardien
+    /// retourné. Retourne `None` si la clé est absente ou expirée.
+    pub fn pin_guard(&self, key: &K) -> Option<PinGuard<K>> {
+        self.pin(key).then(|| PinGuard { pins: self.pins.clone(), key: key.clone() })
+    }
+}
@@ -0,0 +1,180 @@
+//! Module implémentant le chargement avec mémorisation des échecs.
+//!
+//! `Cache::try_get_or_insert_with` retourne la valeur présente ou la charge
+//! avec la fonction donnée et l'insère. Lorsque le chargeur échoue (service
+//! indisponible, délai dépassé...), le relancer à chaque lecture de la même
+//! clé ne fait qu'aggraver la charge d'un système déjà en difficulté. Avec
+//! `CacheBuilder::load_failure_cooldown`, l'échec est mémorisé dans une
+//! marque d'échec : pendant le délai de carence, les appels suivants pour la
+//! même clé échouent aussitôt avec `CacheError::RecentLoadFailure`, sans
+//! appeler le chargeur.
+//!
+//! Les marques d'échec ne sont pas des entrées : elles n'occupent pas
+//! d'emplacement, n'apparaissent ni dans `iter` ni dans les fichiers de
+//! persistance, et ne se confondent pas avec la mise en cache d'une absence
+//! légitime, qui reste une valeur comme une autre (par exemple `None` dans un
+//! `Cache<K, Option<V>>`).
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::time::Duration;
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::Cache;
+//!
+//! let mut cache: Cache<u32, String> = Cache::builder()
+//!     .capacity(100)
+//!     .load_failure_cooldown(Duration::from_secs(30))
+//!     .build()
+//!     .unwrap();
+//!
+//! let mut appels = 0;
+//! let mut chargeur = |_: &u32| {
+//!     appels += 1;
+//!     Err(CacheError::ParseError("service indisponible".to_string()))
+//! };
+//! assert!(matches!(cache.try_get_or_insert_with(7, &mut chargeur), Err(CacheError::ParseError(_))));
+//! // Pendant le délai de carence, le chargeur n'est pas relancé
+//! assert!(matches!(
+//!     cache.try_get_or_insert_with(7, &mut chargeur),
+//!     Err(CacheError::RecentLoadFailure { .. })
+//! ));
+//! assert_eq!(appels, 1);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Marques d'échec de chargement : échéance et message de l'erreur.
+///
+/// Le délai de carence étant fixe, les échéances suivent l'ordre des
+/// marques : une file les retire de la plus ancienne à la plus récente. Une
+/// marque effacée ou remplacée laisse dans la file une échéance périmée,
+/// ignorée à son tour.
+#[derive(Debug)]
+pub(crate) struct FailedLoads<K> {
+    cooldown: Duration,
+    failures: HashMap<K, (Instant, String)>,
+    deadlines: VecDeque<(Instant, K)>,
+}
+
+impl<K> FailedLoads<K>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Crée un registre dont les marques durent `cooldown`.
+    pub(crate) fn new(cooldown: Duration) -> Self {
+        FailedLoads {
+            cooldown,
+            failures: HashMap::new(),
+            deadlines: VecDeque::new(),
+        }
+    }
+
+    /// Retourne l'erreur à signaler si la clé porte une marque encore
+    /// valide ; une marque échue est retirée.
+    fn check(&mut self, key: &K, now: Instant) -> Option<CacheError> {
+        let (deadline, reason) = self.failures.get(key)?;
+        if *deadline <= now {
+            self.failures.remove(key);
+            return None;
+        }
+        Some(CacheError::RecentLoadFailure {
+            reason: reason.clone(),
+            retry_in: *deadline - now,
+        })
+    }
+
+    /// Marque l'échec du chargement de la clé. Les marques échues sont
+    /// retirées, puis les plus anciennes tant que le registre compte
+    /// `limit` marques ou plus.
+    fn record(&mut self, key: K, reason: String, now: Instant, limit: usize) {
+        while self.deadlines.front().is_some_and(|(deadline, _)| *deadline <= now) {
+            self.pop_oldest();
+        }
+        while self.failures.len() >= limit.max(1) && !self.deadlines.is_empty() {
+            self.pop_oldest();
+        }
+        let deadline = now + self.cooldown;
+        self.failures.insert(key.clone(), (deadline, reason));
+        self.deadlines.push_back((deadline, key));
+        // Les échéances périmées ne s'accumulent pas au-delà des marques
+        if self.deadlines.len() > 2 * self.failures.len() {
+            let failures = &self.failures;
+            self.deadlines
+                .retain(|(deadline, key)| failures.get(key).is_some_and(|(current, _)| current == deadline));
+        }
+    }
+
+    /// Retire l'échéance la plus ancienne, et la marque qu'elle désigne si
+    /// elle n'a pas été remplacée depuis.
+    fn pop_oldest(&mut self) {
+        let Some((deadline, key)) = self.deadlines.pop_front() else {
+            return;
+        };
+        if self.failures.get(&key).is_some_and(|(current, _)| *current == deadline) {
+            self.failures.remove(&key);
+        }
+    }
+
+    fn forget(&mut self, key: &K) -> bool {
+        self.failures.remove(key).is_some()
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne la valeur associée à la clé, ou la charge avec `load` et
+    /// l'insère si elle est absente ou expirée.
+    ///
+    /// Avec `CacheBuilder::load_failure_cooldown`, un échec de `load` est
+    /// mémorisé : jusqu'à la fin du délai de carence, les appels pour la
+    /// même clé retournent `CacheError::RecentLoadFailure` sans appeler
+    /// `load`. Un chargement réussi efface la marque.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `load`, `CacheError::RecentLoadFailure`
+    /// pendant le délai de carence, et les erreurs de `Cache::try_put`.
+    pub fn try_get_or_insert_with<F>(&mut self, key: K, load: F) -> Result<Option<&V>, CacheError>
+    where
+        F: FnOnce(&K) -> Result<V, CacheError>,
+    {
+        if self.contains(&key) {
+            return Ok(self.get(&key));
+        }
+
+        let now = self.clock.now();
+        if let Some(err) = self.failed_loads.as_mut().and_then(|failures| failures.check(&key, now)) {
+            return Err(err);
+        }
+        match load(&key) {
+            Ok(value) => {
+                self.forget_load_failure(&key);
+                self.try_put(key.clone(), value)?;
+                let cache = &*self;
+                Ok(cache.peek(&key))
+            }
+            Err(err) => {
+                let limit = self.capacity;
+                if let Some(failures) = self.failed_loads.as_mut() {
+                    failures.record(key, err.to_string(), now, limit);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Efface la marque d'échec de chargement de la clé, pour que le
+    /// prochain `try_get_or_insert_with` appelle de nouveau le chargeur.
+    /// Retourne `true` si la clé en portait une.
+    pub fn forget_load_failure(&mut self, key: &K) -> bool {
+        self.failed_loads.as_mut().is_some_and(|failures| failures.forget(key))
+    }
+}
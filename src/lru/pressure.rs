@@ -0,0 +1,176 @@
+//! Module implémentant la réduction du cache sous pression mémoire.
+//!
+//! `Cache::shrink_by` évince immédiatement une fraction des entrées les moins
+//! récemment utilisées. `SyncCache::set_pressure_handler` surveille en
+//! arrière-plan un `PressureSignal` (limite mémoire du cgroup, statistiques
+//! de l'allocateur...) et réduit le cache dès que le signal le demande, afin
+//! d'éviter que le conteneur ne soit tué faute de mémoire.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = Cache::new(100);
+//! for i in 0..100 {
+//!     cache.put(i, i);
+//! }
+//!
+//! assert_eq!(cache.shrink_by(0.25), 25);
+//! assert!(!cache.contains(&24));
+//! assert!(cache.contains(&25));
+//! ```
+
+use std::fs;
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+use crate::lru::sync::SyncCache;
+
+/// Source indiquant si le processus manque de mémoire.
+pub trait PressureSignal: Send {
+    /// Retourne la fraction des entrées à évincer (entre 0 et 1) si la
+    /// mémoire manque, `None` sinon.
+    fn pressure(&mut self) -> Option<f64>;
+}
+
+impl<F> PressureSignal for F
+where
+    F: FnMut() -> Option<f64> + Send,
+{
+    fn pressure(&mut self) -> Option<f64> {
+        self()
+    }
+}
+
+/// Signal lisant la consommation et la limite mémoire d'un cgroup v2.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CgroupPressure {
+    current: PathBuf,
+    max: PathBuf,
+    threshold: f64,
+    shed: f64,
+}
+
+impl CgroupPressure {
+    /// Crée un signal demandant d'évincer la fraction `shed` des entrées dès
+    /// que la consommation atteint la fraction `threshold` de la limite du
+    /// cgroup courant.
+    pub fn new(threshold: f64, shed: f64) -> Self {
+        CgroupPressure {
+            current: PathBuf::from("/sys/fs/cgroup/memory.current"),
+            max: PathBuf::from("/sys/fs/cgroup/memory.max"),
+            threshold,
+            shed,
+        }
+    }
+
+    /// Lit la consommation et la limite dans les fichiers indiqués plutôt
+    /// que dans ceux du cgroup courant.
+    pub fn with_paths<P: Into<PathBuf>>(mut self, current: P, max: P) -> Self {
+        self.current = current.into();
+        self.max = max.into();
+        self
+    }
+
+    /// Retourne la fraction de la limite actuellement consommée, ou `None`
+    /// si le cgroup n'a pas de limite ou ne peut pas être lu.
+    pub fn usage(&self) -> Option<f64> {
+        let read = |path: &PathBuf| fs::read_to_string(path).ok()?.trim().parse::<u64>().ok();
+        // La limite vaut « max » lorsque le cgroup n'est pas borné
+        let max = read(&self.max).filter(|&max| max > 0)?;
+        Some(read(&self.current)? as f64 / max as f64)
+    }
+}
+
+impl PressureSignal for CgroupPressure {
+    fn pressure(&mut self) -> Option<f64> {
+        self.usage()
+            .filter(|&usage| usage >= self.threshold)
+            .map(|_| self.shed)
+    }
+}
+
+/// Poignée du thread de surveillance de la mémoire.
+///
+/// Le thread est arrêté lorsque la poignée est abandonnée.
+#[derive(Debug)]
+pub struct PressureHandle {
+    stop: Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PressureHandle {
+    /// Arrête le thread et attend sa terminaison.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        let _ = self.stop.send(());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for PressureHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Évince la fraction donnée (bornée entre 0 et 1) des entrées, en
+    /// commençant par les moins récemment utilisées, et retourne le nombre
+    /// d'entrées évincées.
+    pub fn shrink_by(&mut self, fraction: f64) -> usize {
+        let fraction = if fraction.is_nan() { 0.0 } else { fraction.clamp(0.0, 1.0) };
+        let count = (self.len() as f64 * fraction).ceil() as usize;
+        self.evict_batch(count, RemovalCause::Capacity);
+        count
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Send + 'static,
+{
+    /// Lance un thread qui interroge le signal à l'intervalle donné et
+    /// réduit le cache de la fraction demandée sous pression mémoire.
+    ///
+    /// Le thread s'arrête lorsque la poignée retournée est abandonnée, ou
+    /// lorsque le verrou du cache est empoisonné sans possibilité de reprise.
+    pub fn set_pressure_handler<S>(&self, mut signal: S, interval: Duration) -> PressureHandle
+    where
+        S: PressureSignal + 'static,
+    {
+        let cache = self.clone();
+        let (stop, stopped) = mpsc::channel();
+
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let Some(fraction) = signal.pressure() else {
+                    continue;
+                };
+                if cache.with_lock(|cache| cache.shrink_by(fraction)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        PressureHandle {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Module choisissant les primitives de synchronisation du cache partagé.
+//!
+//! `SyncCache` n'utilise pas directement `std::sync` : ses verrous et ses
+//! compteurs de références viennent de ce module. Compilé avec
+//! `RUSTFLAGS="--cfg loom"`, le module fournit à la place les primitives de
+//! `loom`, qui explore tous les entrelacements possibles des threads d'un
+//! test (voir `tests/loom.rs`). Sans cette option, il s'agit des types de
+//! la bibliothèque standard, sans aucun surcoût.
+
+#[cfg(loom)]
+pub(crate) use loom::sync::{Arc, Condvar, Mutex, MutexGuard};
+#[cfg(not(loom))]
+pub(crate) use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+/// Lève l'empoisonnement du verrou. Les verrous de `loom` ne sont jamais
+/// empoisonnés.
+pub(crate) fn clear_poison<T>(mutex: &Mutex<T>) {
+    #[cfg(not(loom))]
+    mutex.clear_poison();
+    #[cfg(loom)]
+    let _ = mutex;
+}
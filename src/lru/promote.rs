@@ -0,0 +1,80 @@
+//! Module implémentant les lectures groupées sans promotion.
+//!
+//! Un parcours analytique lit une grande partie du cache une seule fois :
+//! avec `get`, chaque lecture promeut l'entrée, si bien que le parcours
+//! renverse l'ordre d'utilisation et fait évincer les entrées réellement
+//! fréquentées. `get_many_no_promote` lit un lot de clés en comptant les
+//! lectures dans les statistiques, sans toucher à l'ordre d'utilisation ni
+//! aux compteurs des entrées. `promote_many` promeut ensuite explicitement
+//! les clés jugées chaudes, chacune en temps constant.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = Cache::new(3);
+//! cache.put("a", 1);
+//! cache.put("b", 2);
+//! cache.put("c", 3);
+//!
+//! let valeurs = cache.get_many_no_promote(&["a", "b", "z"]);
+//! assert_eq!(valeurs, [Some(&1), Some(&2), None]);
+//!
+//! // Seule "b" est promue : "a" reste la moins récemment utilisée
+//! assert_eq!(cache.promote_many(&["b"]), 1);
+//! cache.put("d", 4);
+//! assert!(!cache.contains(&"a"));
+//! assert!(cache.contains(&"b"));
+//! ```
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use crate::lru::Cache;
+use crate::lru::traits::CacheRead;
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Lit les valeurs d'un lot de clés, dans l'ordre des clés, sans modifier
+    /// l'ordre d'utilisation, les compteurs de lectures des entrées ni leurs
+    /// instants d'accès. Les lectures sont comptées dans les statistiques ;
+    /// une clé expirée compte comme une lecture manquée.
+    pub fn get_many_no_promote(&mut self, keys: &[K]) -> Vec<Option<&V>> {
+        for key in keys {
+            let hit = self.peek(key).is_some();
+            self.record_read(hit);
+        }
+        let cache = &*self;
+        keys.iter().map(|key| cache.peek(key)).collect()
+    }
+
+    /// Marque les clés présentes comme les plus récemment utilisées, la
+    /// dernière du lot devenant la plus récente, et retourne leur nombre. Les
+    /// clés absentes, expirées ou répétées sont ignorées.
+    pub fn promote_many(&mut self, keys: &[K]) -> usize {
+        let mut promoted = HashSet::with_capacity(keys.len());
+        let mut order = Vec::with_capacity(keys.len());
+        for key in keys {
+            if self.peek(key).is_some() && promoted.insert(key) {
+                order.push(key);
+            }
+        }
+        if self.track_access {
+            let now = self.clock.now();
+            for key in &order {
+                if let Some(entry) = self.elements.get_mut(*key) {
+                    entry.accessed = now;
+                }
+            }
+        }
+        if self.sampling.is_none() {
+            for key in &order {
+                self.usage_order.move_to_back(key);
+            }
+        }
+        order.len()
+    }
+}
@@ -0,0 +1,159 @@
+//! Module implémentant un cache optimisé pour les lectures.
+//!
+//! Disponible avec la fonctionnalité `read-mostly`. Un cache de
+//! configuration ou de métadonnées est lu en permanence par tous les threads
+//! mais modifié rarement : le verrou de `SyncCache`, pris à chaque lecture,
+//! devient alors le principal coût. `ReadMostlyCache` publie un instantané
+//! immuable de son contenu derrière un pointeur atomique (`arc-swap`) : une
+//! lecture charge ce pointeur et consulte l'instantané, sans aucun verrou ni
+//! attente, même pendant une écriture.
+//!
+//! Chaque écriture modifie un `Cache` maître, réservé aux écrivains et
+//! protégé par un verrou, puis en publie un nouvel instantané qui remplace
+//! l'ancien d'un seul coup. Le nouvel instantané partage avec l'ancien les
+//! clés et les valeurs inchangées (voir `Cache::refreeze`), mais reconstruit
+//! sa table : une écriture coûte donc un pointeur par entrée, et ce mode ne
+//! convient qu'aux caches dont les écritures sont rares.
+//! `ReadMostlyCache::update` permet de regrouper plusieurs modifications en
+//! une seule publication.
+//!
+//! Les lectures ne modifient pas l'instantané : elles ne comptent ni dans
+//! l'ordre d'utilisation ni dans les statistiques du cache maître, dont
+//! l'éviction ne suit donc que l'ordre des écritures. Un lecteur qui garde
+//! un instantané (`ReadMostlyCache::load`) continue de voir son contenu
+//! après les écritures suivantes. Les durées de vie ne s'appliquent qu'au
+//! cache maître : une entrée expirée disparaît des lectures à la
+//! publication suivante.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::thread;
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::read_mostly::ReadMostlyCache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let cache = ReadMostlyCache::new(Cache::new(100));
+//! cache.put("region", "eu-west-3".to_string());
+//!
+//! let lecteur = cache.clone();
+//! thread::spawn(move || {
+//!     assert_eq!(lecteur.get(&"region").as_deref(), Some("eu-west-3"));
+//! }).join().unwrap();
+//!
+//! let avant = cache.load();
+//! cache.update(|maitre| {
+//!     maitre.put("region", "us-east-1".to_string());
+//!     maitre.put("zone", "b".to_string());
+//! });
+//! assert_eq!(cache.get(&"region").as_deref(), Some("us-east-1"));
+//! assert_eq!(avant.peek(&"region").map(String::as_str), Some("eu-west-3"));
+//! assert_eq!(avant.len(), 1);
+//! ```
+
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use arc_swap::ArcSwap;
+use crate::lru::Cache;
+use crate::lru::frozen::{FrozenCache, Snapshot};
+use crate::lru::traits::CacheTrait;
+
+/// Cache dont les lectures consultent sans verrou un instantané publié à
+/// chaque écriture.
+///
+/// Cloner un `ReadMostlyCache` est peu coûteux : les clones partagent le
+/// même cache.
+#[derive(Debug)]
+pub struct ReadMostlyCache<K, V>
+where
+    K: Hash + Eq,
+{
+    current: Arc<ArcSwap<Snapshot<K, V>>>,
+    master: Arc<Mutex<Cache<K, V>>>,
+}
+
+impl<K, V> Clone for ReadMostlyCache<K, V>
+where
+    K: Hash + Eq,
+{
+    fn clone(&self) -> Self {
+        ReadMostlyCache {
+            current: Arc::clone(&self.current),
+            master: Arc::clone(&self.master),
+        }
+    }
+}
+
+impl<K, V> ReadMostlyCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Crée un cache dont le cache maître est `cache` (capacité, durée de
+    /// vie, poids...) et publie son contenu actuel.
+    pub fn new(cache: Cache<K, V>) -> Self {
+        ReadMostlyCache {
+            current: Arc::new(ArcSwap::from_pointee(cache.snapshot(None))),
+            master: Arc::new(Mutex::new(cache)),
+        }
+    }
+
+    /// Récupère une copie de la valeur associée à la clé dans l'instantané
+    /// publié, sans verrou.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.current.load().elements.get(key).map(|entry| V::clone(&entry.value))
+    }
+
+    /// Vérifie si la clé figure dans l'instantané publié.
+    pub fn contains(&self, key: &K) -> bool {
+        self.current.load().elements.contains_key(key)
+    }
+
+    /// Retourne le nombre d'entrées de l'instantané publié.
+    pub fn len(&self) -> usize {
+        self.current.load().elements.len()
+    }
+
+    /// Vérifie si l'instantané publié est vide.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retourne l'instantané publié, pour plusieurs lectures cohérentes
+    /// entre elles. Il n'est pas affecté par les écritures suivantes.
+    pub fn load(&self) -> FrozenCache<K, V> {
+        FrozenCache::from_snapshot(self.current.load_full())
+    }
+
+    /// Modifie le cache maître avec la closure, puis publie son nouvel
+    /// état. Les écrivains sont sérialisés ; les lecteurs voient l'ancien
+    /// instantané jusqu'à la publication, puis le nouveau.
+    pub fn update<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut Cache<K, V>) -> R,
+    {
+        // Une écriture interrompue par une panique n'a rien publié : le
+        // cache maître est repris tel quel à l'écriture suivante
+        let mut master = self.master.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let result = f(&mut master);
+        let snapshot = master.snapshot(Some(&self.current.load()));
+        self.current.store(Arc::new(snapshot));
+        result
+    }
+
+    /// Ajoute ou met à jour une paire clé-valeur et publie le nouvel état.
+    pub fn put(&self, key: K, value: V) {
+        self.update(|cache| cache.put(key, value));
+    }
+
+    /// Supprime l'entrée et publie le nouvel état. Retourne la valeur
+    /// supprimée.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.update(|cache| cache.remove(key))
+    }
+
+    /// Vide le cache et publie l'état vide.
+    pub fn clear(&self) {
+        self.update(|cache| cache.clear());
+    }
+}
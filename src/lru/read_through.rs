@@ -0,0 +1,146 @@
+//! Module implémentant un cache à lecture traversante.
+//!
+//! `ReadThroughCache` associe un `Cache` à un `Loader` : une lecture qui ne
+//! trouve pas de valeur valide appelle le chargeur et insère son résultat,
+//! si bien que l'appelant n'a plus à écrire lui-même la séquence « lire,
+//! charger, insérer ».
+//!
+//! Un `CircuitBreaker` peut protéger le chargeur : tant qu'il est ouvert,
+//! les échecs de lecture ne l'appellent plus et retournent la valeur expirée
+//! encore présente dans le cache, ou `None`. De même, avec
+//! `with_stale_on_timeout`, un chargement abandonné pour dépassement de délai
+//! (`CacheError::LoadTimeout`) sert la valeur expirée si elle existe.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::time::Duration;
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::breaker::{BreakerState, CircuitBreaker};
+//! use lru_cache::lru::read_through::ReadThroughCache;
+//!
+//! let chargeur = |cle: &u32| -> Result<u32, CacheError> {
+//!     if *cle < 100 { Ok(cle * 2) } else { Err(CacheError::ParseError("hors service".to_string())) }
+//! };
+//! let mut cache = ReadThroughCache::new(Cache::new(10), chargeur)
+//!     .with_breaker(CircuitBreaker::new(2, Duration::from_secs(30)));
+//!
+//! assert_eq!(cache.get(&21).unwrap(), Some(&42));
+//! assert!(cache.get(&100).is_err());
+//! assert!(cache.get(&101).is_err());
+//!
+//! // Le disjoncteur est ouvert : le chargeur n'est plus appelé
+//! assert_eq!(cache.breaker().unwrap().state(), BreakerState::Open);
+//! assert_eq!(cache.get(&102).unwrap(), None);
+//! ```
+
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::breaker::CircuitBreaker;
+use crate::lru::loader::Loader;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Cache chargeant lui-même les valeurs absentes.
+#[derive(Debug)]
+pub struct ReadThroughCache<K, V, L>
+where
+    K: Hash + Eq,
+{
+    cache: Cache<K, V>,
+    loader: L,
+    breaker: Option<CircuitBreaker>,
+    stale_on_timeout: bool,
+}
+
+impl<K, V, L> ReadThroughCache<K, V, L>
+where
+    K: Hash + Eq + Clone,
+    L: Loader<K, V>,
+{
+    /// Associe le cache donné au chargeur.
+    pub fn new(cache: Cache<K, V>, loader: L) -> Self {
+        ReadThroughCache {
+            cache,
+            loader,
+            breaker: None,
+            stale_on_timeout: false,
+        }
+    }
+
+    /// Protège le chargeur par le disjoncteur donné.
+    pub fn with_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// Sert la valeur expirée encore présente, si elle existe, lorsque le
+    /// chargement dépasse son délai.
+    pub fn with_stale_on_timeout(mut self) -> Self {
+        self.stale_on_timeout = true;
+        self
+    }
+
+    /// Retourne la valeur associée à la clé, en la chargeant si elle est
+    /// absente ou expirée.
+    ///
+    /// Lorsque le disjoncteur est ouvert, le chargeur n'est pas appelé et la
+    /// valeur expirée encore présente est retournée, ou `None`.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs du chargeur, sauf les dépassements de délai
+    /// couverts par une valeur expirée avec `with_stale_on_timeout`.
+    pub fn get(&mut self, key: &K) -> Result<Option<&V>, CacheError> {
+        if self.cache.contains(key) {
+            return Ok(self.cache.get(key));
+        }
+
+        let now = self.cache.clock.now();
+        if self.breaker.as_mut().is_some_and(|breaker| !breaker.allow(now)) {
+            // Valeur périmée plutôt qu'un appel voué à l'échec
+            return Ok(self.cache.elements.get(key).map(|entry| &entry.value));
+        }
+
+        match self.loader.load(key) {
+            Ok(value) => {
+                if let Some(breaker) = self.breaker.as_mut() {
+                    breaker.record_success();
+                }
+                self.cache.put(key.clone(), value);
+                Ok(self.cache.peek(key))
+            }
+            Err(err) => {
+                if let Some(breaker) = self.breaker.as_mut() {
+                    breaker.record_failure(now);
+                }
+                let timed_out = matches!(err, CacheError::LoadTimeout { .. });
+                match self.cache.elements.get(key) {
+                    Some(entry) if timed_out && self.stale_on_timeout => Ok(Some(&entry.value)),
+                    _ => Err(err),
+                }
+            }
+        }
+    }
+
+    /// Retourne le disjoncteur, s'il est configuré.
+    pub fn breaker(&self) -> Option<&CircuitBreaker> {
+        self.breaker.as_ref()
+    }
+
+    /// Retourne une référence au cache encapsulé.
+    pub fn inner(&self) -> &Cache<K, V> {
+        &self.cache
+    }
+
+    /// Retourne une référence mutable au cache encapsulé.
+    pub fn inner_mut(&mut self) -> &mut Cache<K, V> {
+        &mut self.cache
+    }
+
+    /// Consomme le cache et retourne le cache encapsulé.
+    pub fn into_inner(self) -> Cache<K, V> {
+        self.cache
+    }
+}
@@ -0,0 +1,217 @@
+//! Module implémentant le rafraîchissement des entrées populaires.
+//!
+//! Une entrée dont la durée de vie arrive à son terme et qui a été lue au
+//! moins `min_hits` fois depuis son dernier rafraîchissement est rechargée
+//! via un `Loader` avant d'expirer : les clés les plus demandées ne
+//! disparaissent ainsi jamais du cache, et une clé délaissée finit par
+//! expirer.
+//!
+//! Le rafraîchissement peut être déclenché manuellement avec
+//! `SyncCache::refresh_hot_entries`, ou confié à un thread d'arrière-plan
+//! avec `SyncCache::spawn_refresher`.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::refresh::RefreshConfig;
+//! use lru_cache::lru::sync::SyncCache;
+//! use std::time::Duration;
+//!
+//! let cache = SyncCache::new(100);
+//! let config = RefreshConfig::new(Duration::from_secs(60))
+//!     .with_window(Duration::from_secs(5))
+//!     .with_min_hits(10);
+//!
+//! let handle = cache.spawn_refresher(
+//!     |cle: &u32| -> Result<u32, CacheError> { Ok(cle * 2) },
+//!     config,
+//! );
+//! // ... le thread rafraîchit les entrées jusqu'à l'arrêt
+//! handle.stop();
+//! ```
+
+use std::hash::Hash;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::loader::Loader;
+use crate::lru::sync::SyncCache;
+
+/// Configuration du rafraîchissement des entrées populaires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshConfig {
+    /// Durée de vie attribuée aux valeurs rechargées
+    pub ttl: Duration,
+    /// Une entrée est rafraîchie si elle expire dans moins de cette durée
+    pub window: Duration,
+    /// Nombre minimal de lectures depuis le dernier rafraîchissement pour
+    /// qu'une entrée soit considérée populaire
+    pub min_hits: u64,
+    /// Intervalle entre deux passes du thread d'arrière-plan
+    pub interval: Duration,
+}
+
+impl RefreshConfig {
+    /// Crée une configuration rechargeant les entrées avec la durée de vie
+    /// donnée. Par défaut, la fenêtre vaut un dixième de cette durée, une
+    /// seule lecture suffit et une passe a lieu à chaque fenêtre.
+    pub fn new(ttl: Duration) -> Self {
+        let window = ttl / 10;
+        RefreshConfig {
+            ttl,
+            window,
+            min_hits: 1,
+            interval: window.max(Duration::from_millis(1)),
+        }
+    }
+
+    /// Définit la fenêtre précédant l'expiration pendant laquelle une entrée
+    /// est rafraîchie.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Définit le nombre minimal de lectures d'une entrée populaire.
+    pub fn with_min_hits(mut self, min_hits: u64) -> Self {
+        self.min_hits = min_hits;
+        self
+    }
+
+    /// Définit l'intervalle entre deux passes du thread d'arrière-plan.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// Poignée du thread de rafraîchissement.
+///
+/// Le thread est arrêté lorsque la poignée est abandonnée.
+#[derive(Debug)]
+pub struct RefreshHandle {
+    stop: Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RefreshHandle {
+    /// Arrête le thread et attend sa terminaison.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        let _ = self.stop.send(());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for RefreshHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne les clés populaires qui expireront dans la fenêtre donnée.
+    pub(crate) fn refresh_candidates(&self, window: Duration, min_hits: u64) -> Vec<K> {
+        let now = self.clock.now();
+        self.expirations
+            .expiring_before(now + window)
+            .filter(|(_, deadline)| *deadline > now)
+            .filter(|(key, _)| {
+                self.elements
+                    .get(*key)
+                    .is_some_and(|entry| entry.hits.saturating_sub(entry.refreshed_hits) >= min_hits)
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Recharge immédiatement les entrées populaires proches de l'expiration
+    /// et retourne le nombre d'entrées rafraîchies.
+    ///
+    /// Le chargeur est appelé sans détenir le verrou du cache ; les échecs de
+    /// chargement sont ignorés et l'entrée expire normalement.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` selon la politique d'empoisonnement.
+    pub fn refresh_hot_entries<L>(&self, loader: &L, config: &RefreshConfig) -> Result<usize, CacheError>
+    where
+        L: Loader<K, V> + ?Sized,
+    {
+        let candidates = self.with_lock(|cache| cache.refresh_candidates(config.window, config.min_hits))?;
+
+        let mut refreshed = 0;
+        for key in candidates {
+            let Ok(value) = loader.load(&key) else {
+                continue;
+            };
+            let updated = self.with_lock(|cache| {
+                // L'entrée a pu être supprimée pendant le chargement
+                if cache.elements.contains_key(&key) {
+                    // Un rafraîchissement remplace toujours la valeur
+                    if cache.put_entry(key.clone(), value, Some(config.ttl), true).is_err() {
+                        return false;
+                    }
+                    // Seules les lectures suivantes comptent pour le prochain
+                    if let Some(entry) = cache.elements.get_mut(&key) {
+                        entry.refreshed_hits = entry.hits;
+                    }
+                    true
+                } else {
+                    false
+                }
+            })?;
+            if updated {
+                refreshed += 1;
+            }
+        }
+        Ok(refreshed)
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Send + 'static,
+{
+    /// Lance un thread qui rafraîchit périodiquement les entrées populaires.
+    ///
+    /// Le thread s'arrête lorsque la poignée retournée est abandonnée, ou
+    /// lorsque le verrou du cache est empoisonné sans possibilité de reprise.
+    pub fn spawn_refresher<L>(&self, loader: L, config: RefreshConfig) -> RefreshHandle
+    where
+        L: Loader<K, V> + Send + 'static,
+    {
+        let cache = self.clone();
+        let (stop, stopped) = mpsc::channel();
+
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(config.interval) {
+                if cache.refresh_hot_entries(&loader, &config).is_err() {
+                    break;
+                }
+            }
+        });
+
+        RefreshHandle {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! Module implémentant un groupe de caches soumis à un budget commun.
+//!
+//! Un processus qui utilise plusieurs caches les dimensionne chacun comme
+//! s'il disposait seul de la mémoire. `CacheRegistry` possède des caches
+//! nommés, de types quelconques, et fait respecter un budget global exprimé
+//! en nombre d'entrées ou en octets : lorsque le budget est dépassé, les
+//! entrées les moins récemment utilisées sont évincées en commençant par le
+//! cache le moins utile, celui dont la proportion de lectures réussies est
+//! la plus faible.
+//!
+//! Le budget est vérifié à chaque ajout d'un cache et à la fin de chaque
+//! accès en écriture obtenu par `CacheRegistry::cache_mut`. La proportion de
+//! lectures réussies provient des statistiques des caches : un cache dont
+//! les statistiques ne sont pas activées est considéré comme le moins utile.
+//! Les entrées épinglées ne sont jamais évincées : si elles suffisent à
+//! dépasser le budget, celui-ci reste dépassé.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::registry::{CacheRegistry, RegistryBudget};
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut registry = CacheRegistry::new(RegistryBudget::Entries(3));
+//! registry.insert("users", Cache::<u32, String>::builder().capacity(10).record_stats().build().unwrap());
+//! registry.insert("pages", Cache::<String, Vec<u8>>::builder().capacity(10).record_stats().build().unwrap());
+//!
+//! {
+//!     let mut users = registry.cache_mut::<u32, String>("users").unwrap();
+//!     users.put(1, "alice".to_string());
+//!     users.put(2, "bob".to_string());
+//!     users.get(&1);
+//! }
+//! {
+//!     let mut pages = registry.cache_mut::<String, Vec<u8>>("pages").unwrap();
+//!     pages.put("/".to_string(), vec![0; 10]);
+//!     pages.put("/a".to_string(), vec![0; 10]);
+//!     pages.get(&"/b".to_string());
+//! }
+//!
+//! // "pages", sans lecture réussie, a cédé une entrée
+//! let stats = registry.stats();
+//! assert_eq!(stats.entries, 3);
+//! assert_eq!(registry.cache::<String, Vec<u8>>("pages").unwrap().len(), 1);
+//! assert_eq!(stats.totals.evictions, 1);
+//! ```
+
+use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+use crate::lru::memory::{MemSize, MemoryReport};
+use crate::lru::stats::CacheStats;
+
+/// Budget global d'un `CacheRegistry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryBudget {
+    /// Nombre maximal d'entrées, tous caches confondus
+    Entries(usize),
+    /// Nombre maximal d'octets occupés par les clés et les valeurs, tous
+    /// caches confondus
+    Bytes(usize),
+}
+
+/// Statistiques agrégées des caches d'un `CacheRegistry`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RegistryStats {
+    /// Nombre total d'entrées
+    pub entries: usize,
+    /// Octets occupés par les clés et les valeurs
+    pub bytes: usize,
+    /// Somme des compteurs de tous les caches ; les histogrammes et la
+    /// fenêtre glissante ne sont pas agrégés
+    pub totals: CacheStats,
+    /// Statistiques de chaque cache, par nom
+    pub caches: BTreeMap<String, CacheStats>,
+}
+
+impl RegistryStats {
+    /// Retourne la proportion de lectures réussies, tous caches confondus.
+    pub fn hit_ratio(&self) -> f64 {
+        self.totals.hit_ratio()
+    }
+}
+
+/// Cache enregistré, vu indépendamment de ses types de clés et de valeurs.
+trait Registered {
+    fn len(&self) -> usize;
+    fn bytes(&self) -> usize;
+    fn stats(&self) -> CacheStats;
+    /// Évince jusqu'à `count` entrées et retourne le nombre d'entrées
+    /// réellement évincées (les entrées épinglées restent).
+    fn evict(&mut self, count: usize) -> usize;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+/// Cache enregistré avec sa fonction de mesure de la mémoire.
+struct Managed<K: Hash + Eq, V> {
+    cache: Cache<K, V>,
+    measure: fn(&Cache<K, V>) -> MemoryReport,
+}
+
+impl<K, V> Registered for Managed<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: 'static,
+{
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn bytes(&self) -> usize {
+        let report = (self.measure)(&self.cache);
+        report.key_bytes + report.value_bytes
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    fn evict(&mut self, count: usize) -> usize {
+        self.cache.evict_batch(count, RemovalCause::Capacity).len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Groupe de caches nommés soumis à un budget global.
+pub struct CacheRegistry {
+    budget: RegistryBudget,
+    caches: BTreeMap<String, Box<dyn Registered>>,
+}
+
+impl std::fmt::Debug for CacheRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheRegistry")
+            .field("budget", &self.budget)
+            .field("caches", &self.caches.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl CacheRegistry {
+    /// Crée un groupe vide soumis au budget donné.
+    pub fn new(budget: RegistryBudget) -> Self {
+        CacheRegistry {
+            budget,
+            caches: BTreeMap::new(),
+        }
+    }
+
+    /// Ajoute un cache sous le nom donné, en remplaçant celui qui portait
+    /// déjà ce nom, puis fait respecter le budget. Retourne `true` si un
+    /// cache a été remplacé.
+    ///
+    /// La mémoire du cache est estimée par `Cache::estimated_memory_usage`,
+    /// qui ne compte que la taille en ligne des clés et des valeurs.
+    pub fn insert<K, V>(&mut self, name: &str, cache: Cache<K, V>) -> bool
+    where
+        K: Hash + Eq + Clone + 'static,
+        V: 'static,
+    {
+        self.add(name, Managed { cache, measure: Cache::estimated_memory_usage })
+    }
+
+    /// Ajoute un cache comme `insert`, sa mémoire étant mesurée exactement
+    /// par `Cache::memory_usage`.
+    pub fn insert_measured<K, V>(&mut self, name: &str, cache: Cache<K, V>) -> bool
+    where
+        K: Hash + Eq + Clone + MemSize + 'static,
+        V: MemSize + 'static,
+    {
+        self.add(name, Managed { cache, measure: Cache::memory_usage })
+    }
+
+    fn add<K, V>(&mut self, name: &str, managed: Managed<K, V>) -> bool
+    where
+        K: Hash + Eq + Clone + 'static,
+        V: 'static,
+    {
+        let replaced = self.caches.insert(name.to_string(), Box::new(managed)).is_some();
+        self.enforce();
+        replaced
+    }
+
+    /// Retire le cache du groupe et le retourne, s'il existe et a les types
+    /// de clés et de valeurs demandés.
+    pub fn remove<K, V>(&mut self, name: &str) -> Option<Cache<K, V>>
+    where
+        K: Hash + Eq + Clone + 'static,
+        V: 'static,
+    {
+        self.caches.get(name)?.as_any().downcast_ref::<Managed<K, V>>()?;
+        let managed = self.caches.remove(name)?.into_any().downcast::<Managed<K, V>>().ok()?;
+        Some(managed.cache)
+    }
+
+    /// Retourne le cache portant ce nom, s'il a les types de clés et de
+    /// valeurs demandés.
+    pub fn cache<K, V>(&self, name: &str) -> Option<&Cache<K, V>>
+    where
+        K: Hash + Eq + 'static,
+        V: 'static,
+    {
+        let managed = self.caches.get(name)?.as_any().downcast_ref::<Managed<K, V>>()?;
+        Some(&managed.cache)
+    }
+
+    /// Donne accès en écriture au cache portant ce nom ; le budget est
+    /// vérifié lorsque l'accès prend fin.
+    pub fn cache_mut<K, V>(&mut self, name: &str) -> Option<RegistryGuard<'_, K, V>>
+    where
+        K: Hash + Eq + 'static,
+        V: 'static,
+    {
+        self.caches.get(name)?.as_any().downcast_ref::<Managed<K, V>>()?;
+        Some(RegistryGuard {
+            registry: self,
+            name: name.to_string(),
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    /// Retourne les noms des caches du groupe, par ordre alphabétique.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.caches.keys().map(String::as_str)
+    }
+
+    /// Retourne le budget global.
+    pub fn budget(&self) -> RegistryBudget {
+        self.budget
+    }
+
+    /// Modifie le budget global et le fait respecter aussitôt.
+    pub fn set_budget(&mut self, budget: RegistryBudget) {
+        self.budget = budget;
+        self.enforce();
+    }
+
+    /// Retourne les statistiques de chaque cache et leur somme.
+    pub fn stats(&self) -> RegistryStats {
+        let mut stats = RegistryStats::default();
+        for (name, cache) in &self.caches {
+            let cache_stats = cache.stats();
+            stats.entries += cache.len();
+            stats.bytes += cache.bytes();
+            let totals = &mut stats.totals;
+            totals.hits += cache_stats.hits;
+            totals.misses += cache_stats.misses;
+            totals.inserts += cache_stats.inserts;
+            totals.updates += cache_stats.updates;
+            totals.removals += cache_stats.removals;
+            totals.evictions += cache_stats.evictions;
+            totals.expirations += cache_stats.expirations;
+            stats.caches.insert(name.clone(), cache_stats);
+        }
+        stats
+    }
+
+    /// Évince des entrées, en commençant par le cache le moins utile, tant
+    /// que le budget est dépassé.
+    fn enforce(&mut self) {
+        // Caches dont rien n'a pu être évincé (entrées épinglées) : ils ne
+        // sont plus choisis, pour que la boucle progresse toujours
+        let mut exhausted = BTreeSet::new();
+        loop {
+            let (used, limit): (usize, usize) = match self.budget {
+                RegistryBudget::Entries(limit) => (self.caches.values().map(|cache| cache.len()).sum(), limit),
+                RegistryBudget::Bytes(limit) => (self.caches.values().map(|cache| cache.bytes()).sum(), limit),
+            };
+            if used <= limit {
+                return;
+            }
+            let excess = used - limit;
+            let Some((name, victim)) = self
+                .caches
+                .iter_mut()
+                .filter(|(name, cache)| cache.len() > 0 && !exhausted.contains(*name))
+                .min_by(|(_, a), (_, b)| a.stats().hit_ratio().total_cmp(&b.stats().hit_ratio()))
+            else {
+                return;
+            };
+            let count = match self.budget {
+                RegistryBudget::Entries(_) => excess,
+                RegistryBudget::Bytes(_) => {
+                    // Nombre d'entrées de taille moyenne couvrant l'excédent
+                    let per_entry = (victim.bytes() / victim.len()).max(1);
+                    excess.div_ceil(per_entry)
+                }
+            };
+            if victim.evict(count.min(victim.len())) == 0 {
+                exhausted.insert(name.clone());
+            }
+        }
+    }
+}
+
+/// Accès en écriture à un cache d'un `CacheRegistry` ; le budget global est
+/// vérifié lorsque l'accès prend fin.
+pub struct RegistryGuard<'a, K, V> {
+    registry: &'a mut CacheRegistry,
+    name: String,
+    _marker: std::marker::PhantomData<(K, V)>,
+}
+
+impl<K, V> RegistryGuard<'_, K, V>
+where
+    K: Hash + Eq + 'static,
+    V: 'static,
+{
+    fn managed(&self) -> &Managed<K, V> {
+        self.registry.caches[&self.name]
+            .as_any()
+            .downcast_ref()
+            .expect("type vérifié par CacheRegistry::cache_mut")
+    }
+
+    fn managed_mut(&mut self) -> &mut Managed<K, V> {
+        self.registry
+            .caches
+            .get_mut(&self.name)
+            .and_then(|cache| cache.as_any_mut().downcast_mut())
+            .expect("type vérifié par CacheRegistry::cache_mut")
+    }
+}
+
+impl<K, V> Deref for RegistryGuard<'_, K, V>
+where
+    K: Hash + Eq + 'static,
+    V: 'static,
+{
+    type Target = Cache<K, V>;
+
+    fn deref(&self) -> &Cache<K, V> {
+        &self.managed().cache
+    }
+}
+
+impl<K, V> DerefMut for RegistryGuard<'_, K, V>
+where
+    K: Hash + Eq + 'static,
+    V: 'static,
+{
+    fn deref_mut(&mut self) -> &mut Cache<K, V> {
+        &mut self.managed_mut().cache
+    }
+}
+
+impl<K, V> Drop for RegistryGuard<'_, K, V> {
+    fn drop(&mut self) {
+        self.registry.enforce();
+    }
+}
@@ -0,0 +1,130 @@
+//! Module rechargeant le cache depuis son fichier de persistance.
+//!
+//! Lorsqu'un autre outil régénère le fichier de persistance, `reload_from_file`
+//! remplace le contenu du cache par celui du fichier. Le fichier est lu et
+//! analysé en entier avant toute modification : un fichier invalide (par
+//! exemple en cours d'écriture) laisse le cache intact.
+//!
+//! Une entrée est dite modifiée (« sale ») si elle a été écrite en mémoire
+//! depuis le dernier chargement ou la dernière sauvegarde (`persist`). La
+//! `ConflictPolicy` choisit entre ces écritures et le fichier ; les entrées
+//! conservées restent modifiées et reçoivent une nouvelle version. Les
+//! suppressions faites en mémoire ne sont pas suivies : une clé supprimée
+//! mais présente dans le fichier réapparaît.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::reload::ConflictPolicy;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let path = std::env::temp_dir().join(format!("lru_reload_doc_{}.txt", std::process::id()));
+//! let mut cache: Cache<String, i32> = Cache::new(10);
+//! cache.put("a".to_string(), 1);
+//! cache.persist(&path).unwrap();
+//!
+//! // Écriture locale non sauvegardée, puis régénération externe du fichier
+//! cache.put("b".to_string(), 2);
+//! std::fs::write(&path, "a\t10\nb\t20\nc\t30\n").unwrap();
+//!
+//! cache.reload_from_file(&path, ConflictPolicy::KeepMemory).unwrap();
+//! assert_eq!(cache.peek(&"a".to_string()), Some(&10));
+//! assert_eq!(cache.peek(&"b".to_string()), Some(&2));
+//! assert_eq!(cache.peek(&"c".to_string()), Some(&30));
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+use crate::lru::traits::CacheTrait;
+
+/// Arbitrage entre les entrées modifiées en mémoire et le fichier rechargé.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Les entrées modifiées depuis la dernière synchronisation sont
+    /// conservées, même absentes du fichier
+    #[default]
+    KeepMemory,
+    /// Le fichier remplace tout le contenu du cache
+    PreferFile,
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Marque toutes les entrées actuelles comme synchronisées.
+    pub(crate) fn mark_synced(&self) {
+        self.synced_version.store(self.next_version, Ordering::Relaxed);
+    }
+
+    /// Retourne les clés modifiées en mémoire depuis le dernier chargement
+    /// ou la dernière sauvegarde.
+    pub fn dirty_keys(&self) -> impl Iterator<Item = &K> {
+        let synced = self.synced_version.load(Ordering::Relaxed);
+        self.elements
+            .iter()
+            .filter(move |(_, entry)| entry.version > synced)
+            .map(|(key, _)| key)
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone + Display + FromStr,
+    V: Display + FromStr,
+{
+    /// Remplace le contenu du cache par celui du fichier de persistance,
+    /// selon la politique donnée pour les entrées modifiées en mémoire, et
+    /// retourne le nombre d'entrées lues. Un fichier absent est ignoré.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de lecture et d'analyse du fichier ; le cache
+    /// n'est alors pas modifié.
+    pub fn reload_from_file<P: AsRef<Path>>(&mut self, path: P, policy: ConflictPolicy) -> Result<usize, CacheError> {
+        let Some(entries) = Self::read_file_entries(path)? else {
+            return Ok(0);
+        };
+        let kept: HashSet<K> = match policy {
+            ConflictPolicy::KeepMemory => self.dirty_keys().cloned().collect(),
+            ConflictPolicy::PreferFile => HashSet::new(),
+        };
+        let in_file: HashSet<&K> = entries.iter().map(|(key, _)| key).collect();
+        let stale: Vec<K> = self
+            .elements
+            .keys()
+            .filter(|key| !kept.contains(*key) && !in_file.contains(key))
+            .cloned()
+            .collect();
+        for key in stale {
+            self.remove_entry(&key, RemovalCause::Explicit);
+        }
+
+        let count = entries.len();
+        for (key, value) in entries {
+            if !kept.contains(&key) {
+                self.put(key, value);
+            }
+        }
+        self.mark_synced();
+
+        // Les entrées conservées restent absentes du fichier : elles doivent
+        // rester modifiées après la synchronisation
+        for key in &kept {
+            if let Some(entry) = self.elements.get_mut(key) {
+                self.next_version += 1;
+                entry.version = self.next_version;
+            }
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,193 @@
+//! Module implémentant la réplication d'un cache par journal d'opérations.
+//!
+//! Une fois le journal activé, chaque mutation du cache y est enregistrée
+//! avec un numéro de séquence croissant. Une réplique récupère les opérations
+//! postérieures à la dernière qu'elle a appliquée via `export_ops_since`, puis
+//! les rejoue avec `apply_ops` : elle conserve ainsi le même ensemble
+//! d'entrées que le cache principal et peut prendre le relais à chaud.
+//!
+//! Les évictions et expirations du cache principal sont répliquées comme des
+//! suppressions. Les durées de vie ne sont pas transmises : la réplique
+//! applique sa propre durée de vie par défaut.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut principal = Cache::new(2);
+//! principal.enable_replication_log(100);
+//! let mut replique = Cache::new(2);
+//!
+//! principal.put("a", 1);
+//! principal.put("b", 2);
+//! principal.put("c", 3); // évince "a"
+//!
+//! let ops = principal.export_ops_since(0).unwrap();
+//! let derniere = replique.apply_ops(ops).unwrap();
+//! assert!(!replique.contains(&"a"));
+//! assert_eq!(replique.peek(&"c"), Some(&3));
+//!
+//! // Seules les nouvelles opérations sont transmises ensuite
+//! principal.put("b", 20);
+//! assert_eq!(principal.export_ops_since(derniere).unwrap().len(), 1);
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::events::{Mutation, RemovalCause};
+use crate::messages;
+
+/// Opération répliquable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplicatedOp<K, V> {
+    /// Ajout ou mise à jour d'une entrée
+    Put {
+        /// Clé de l'entrée
+        key: K,
+        /// Valeur de l'entrée
+        value: V,
+    },
+    /// Suppression d'une entrée, quelle qu'en soit la cause
+    Remove {
+        /// Clé de l'entrée
+        key: K,
+    },
+}
+
+/// Opération accompagnée de son numéro de séquence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequencedOp<K, V> {
+    /// Numéro de séquence, strictement croissant à partir de 1
+    pub seq: u64,
+    /// Opération à rejouer
+    pub op: ReplicatedOp<K, V>,
+}
+
+/// Journal borné des dernières opérations du cache.
+pub(crate) struct OpLog<K, V> {
+    capacity: usize,
+    next_seq: u64,
+    ops: VecDeque<SequencedOp<K, V>>,
+    convert: fn(&Mutation<'_, K, V>) -> ReplicatedOp<K, V>,
+}
+
+impl<K, V> OpLog<K, V> {
+    /// Enregistre une mutation dans le journal.
+    pub(crate) fn record(&mut self, mutation: &Mutation<'_, K, V>) {
+        self.next_seq += 1;
+        if self.ops.len() == self.capacity {
+            self.ops.pop_front();
+        }
+        self.ops.push_back(SequencedOp {
+            seq: self.next_seq,
+            op: (self.convert)(mutation),
+        });
+    }
+}
+
+impl<K, V> fmt::Debug for OpLog<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OpLog")
+            .field("capacity", &self.capacity)
+            .field("next_seq", &self.next_seq)
+            .field("ops", &self.ops.len())
+            .finish()
+    }
+}
+
+fn to_op<K: Clone, V: Clone>(mutation: &Mutation<'_, K, V>) -> ReplicatedOp<K, V> {
+    match *mutation {
+        Mutation::Insert { key, value } | Mutation::Update { key, value } => ReplicatedOp::Put {
+            key: key.clone(),
+            value: value.clone(),
+        },
+        Mutation::Remove { key, .. } => ReplicatedOp::Remove { key: key.clone() },
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Active le journal de réplication, qui conserve les `capacity`
+    /// dernières opérations.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la capacité est 0.
+    pub fn enable_replication_log(&mut self, capacity: usize) {
+        assert!(capacity > 0, "{}", messages::ZERO_CAPACITY);
+        self.observers.replication = Some(OpLog {
+            capacity,
+            next_seq: 0,
+            ops: VecDeque::with_capacity(capacity.min(1024)),
+            convert: to_op::<K, V>,
+        });
+    }
+
+    /// Retourne le numéro de séquence de la dernière opération enregistrée,
+    /// ou 0 si le journal est vide ou désactivé.
+    pub fn last_seq(&self) -> u64 {
+        self.observers.replication.as_ref().map_or(0, |log| log.next_seq)
+    }
+
+    /// Retourne les opérations dont le numéro de séquence est strictement
+    /// supérieur à `seq`, dans l'ordre.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::ReplicationGap` si le journal n'est pas activé
+    /// ou si des opérations postérieures à `seq` ont déjà quitté le journal :
+    /// la réplique doit alors être réinitialisée à partir d'un instantané.
+    pub fn export_ops_since(&self, seq: u64) -> Result<Vec<SequencedOp<K, V>>, CacheError> {
+        let Some(log) = self.observers.replication.as_ref() else {
+            return Err(CacheError::ReplicationGap { requested: seq, oldest: None });
+        };
+        let oldest = log.ops.front().map_or(log.next_seq.saturating_add(1), |op| op.seq);
+        if seq.saturating_add(1) < oldest {
+            return Err(CacheError::ReplicationGap { requested: seq, oldest: Some(oldest) });
+        }
+        Ok(log.ops.iter().filter(|op| op.seq > seq).cloned().collect())
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Rejoue des opérations exportées par un autre cache et retourne le
+    /// numéro de séquence de la dernière opération appliquée.
+    ///
+    /// Les écritures remplacent toujours la valeur existante, quelle que soit
+    /// la politique de doublons. Si aucune opération n'est fournie, retourne 0.
+    ///
+    /// # Errors
+    ///
+    /// Retourne l'erreur de la première écriture refusée par les règles de
+    /// clé ou les limites de poids ; les opérations précédentes restent
+    /// appliquées.
+    pub fn apply_ops<I>(&mut self, ops: I) -> Result<u64, CacheError>
+    where
+        I: IntoIterator<Item = SequencedOp<K, V>>,
+    {
+        let mut last = 0;
+        for SequencedOp { seq, op } in ops {
+            match op {
+                ReplicatedOp::Put { key, value } => {
+                    self.put_entry(key, value, self.default_ttl, true)?;
+                }
+                ReplicatedOp::Remove { key } => {
+                    self.remove_entry(&key, RemovalCause::Explicit);
+                }
+            }
+            last = seq;
+        }
+        Ok(last)
+    }
+}
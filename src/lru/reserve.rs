@@ -0,0 +1,103 @@
+//! Module implémentant la réservation d'emplacements avant un lot
+//! d'insertions.
+//!
+//! Un import en masse qui remplit le cache évince, à partir d'un certain
+//! point, ses propres premières insertions. `reserve_slots(n)` libère dès
+//! maintenant la place de `n` nouvelles clés, en évinçant si nécessaire les
+//! entrées les moins récemment utilisées : les `n` prochaines insertions de
+//! clés nouvelles consomment chacune un emplacement réservé sans rien
+//! évincer. Les emplacements inutilisés se rendent avec `release_slots`.
+//!
+//! Tant qu'une réservation est en cours, le nombre d'entrées et
+//! d'emplacements réservés ne dépasse pas la capacité : le préchargement
+//! (`warm`) respecte les emplacements réservés.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = Cache::new(5);
+//! for i in 0..5 {
+//!     cache.put(i, i);
+//! }
+//!
+//! cache.reserve_slots(3).unwrap();
+//! assert_eq!(cache.len(), 2);
+//! for i in 10..13 {
+//!     cache.put(i, i);
+//! }
+//! assert_eq!(cache.reserved_slots(), 0);
+//! assert!((10..13).all(|i| cache.contains(&i)));
+//! ```
+
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+use crate::messages;
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Réserve la place de `n` nouvelles clés, en évinçant si nécessaire les
+    /// entrées expirées puis les moins récemment utilisées. Les réservations
+    /// successives se cumulent.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si les emplacements réservés
+    /// dépasseraient la capacité, ou si des entrées épinglées empêchent de
+    /// libérer assez de place ; la réservation n'est alors pas prise, mais
+    /// les entrées déjà évincées le restent.
+    pub fn reserve_slots(&mut self, n: usize) -> Result<(), CacheError> {
+        let reserved = self.reserved.saturating_add(n);
+        if reserved > self.capacity {
+            return Err(self.reservation_error());
+        }
+        let target = self.capacity - reserved;
+        if self.elements.len() > target && (self.expirations.len() > 0 || self.time_buckets.is_some()) {
+            self.purge_expired();
+        }
+        let excess = self.elements.len().saturating_sub(target);
+        self.evict_batch(excess, RemovalCause::Capacity);
+        if self.elements.len() > target {
+            return Err(self.reservation_error());
+        }
+        self.reserved = reserved;
+        Ok(())
+    }
+
+    fn reservation_error(&self) -> CacheError {
+        CacheError::CapacityError(format!(
+            "{} ({}: {})",
+            messages::RESERVATION_TOO_LARGE,
+            messages::CURRENT_CAPACITY,
+            self.capacity
+        ))
+    }
+
+    /// Rend jusqu'à `n` emplacements réservés et retourne leur nombre.
+    pub fn release_slots(&mut self, n: usize) -> usize {
+        let released = n.min(self.reserved);
+        self.reserved -= released;
+        released
+    }
+
+    /// Retourne le nombre d'emplacements réservés non encore consommés.
+    pub fn reserved_slots(&self) -> usize {
+        self.reserved
+    }
+
+    /// Consomme un emplacement réservé pour une nouvelle clé. Retourne
+    /// `false` s'il n'en reste aucun.
+    pub(crate) fn take_reserved_slot(&mut self) -> bool {
+        if self.reserved == 0 {
+            return false;
+        }
+        self.reserved -= 1;
+        true
+    }
+}
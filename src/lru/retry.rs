@@ -0,0 +1,218 @@
+//! Module implémentant les nouvelles tentatives de chargement.
+//!
+//! Une `RetryPolicy` relance un chargement échoué, jusqu'à `max_retries`
+//! fois, en attendant entre deux tentatives un délai qui double à chaque
+//! essai (borné par `max_backoff`) et qu'une part aléatoire (`jitter`)
+//! désynchronise d'un client à l'autre. Seules les erreurs jugées
+//! transitoires sont relancées : par défaut, les délais de chargement
+//! dépassés et les erreurs d'entrée/sortie de type délai dépassé,
+//! interruption ou connexion perdue. `retry_if` remplace ce classement.
+//!
+//! `Loader::with_retry` applique la politique à n'importe quel chargeur, qui
+//! peut ensuite servir à la lecture traversante comme au rafraîchissement.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::cell::Cell;
+//! use std::io::{Error, ErrorKind};
+//! use std::time::Duration;
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::loader::Loader;
+//! use lru_cache::lru::retry::RetryPolicy;
+//!
+//! let essais = Cell::new(0);
+//! let chargeur = |cle: &u32| -> Result<u32, CacheError> {
+//!     essais.set(essais.get() + 1);
+//!     if essais.get() < 3 {
+//!         Err(Error::from(ErrorKind::TimedOut).into())
+//!     } else {
+//!         Ok(cle * 2)
+//!     }
+//! };
+//!
+//! let politique = RetryPolicy::new(5, Duration::from_millis(1)).with_jitter(0.0);
+//! assert_eq!(politique.backoff(2), Duration::from_millis(4));
+//!
+//! let chargeur = chargeur.with_retry(politique);
+//! assert_eq!(chargeur.load(&21).unwrap(), 42);
+//! assert_eq!(essais.get(), 3);
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::io::ErrorKind;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use crate::error::CacheError;
+use crate::lru::loader::Loader;
+
+/// Délai maximal par défaut entre deux tentatives.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Part aléatoire par défaut du délai entre deux tentatives.
+pub const DEFAULT_JITTER: f64 = 0.2;
+
+/// Classement d'une erreur : `true` si elle justifie une nouvelle tentative.
+type Classifier = Arc<dyn Fn(&CacheError) -> bool + Send + Sync>;
+
+/// Politique de nouvelles tentatives d'un chargeur.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: f64,
+    classifier: Option<Classifier>,
+}
+
+impl RetryPolicy {
+    /// Crée une politique relançant jusqu'à `max_retries` fois, après un
+    /// premier délai de `initial_backoff` qui double à chaque tentative.
+    pub fn new(max_retries: u32, initial_backoff: Duration) -> Self {
+        RetryPolicy {
+            max_retries,
+            initial_backoff,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            jitter: DEFAULT_JITTER,
+            classifier: None,
+        }
+    }
+
+    /// Borne le délai entre deux tentatives.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Définit la part aléatoire du délai (entre 0 et 1) : chaque délai est
+    /// réduit d'une fraction tirée entre 0 et `jitter`.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = if jitter.is_nan() { 0.0 } else { jitter.clamp(0.0, 1.0) };
+        self
+    }
+
+    /// Remplace le classement des erreurs : seules celles pour lesquelles le
+    /// prédicat retourne `true` sont relancées.
+    pub fn retry_if<F>(mut self, retryable: F) -> Self
+    where
+        F: Fn(&CacheError) -> bool + Send + Sync + 'static,
+    {
+        self.classifier = Some(Arc::new(retryable));
+        self
+    }
+
+    /// Retourne le nombre maximal de nouvelles tentatives.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Retourne le délai, hors part aléatoire, précédant la nouvelle
+    /// tentative de rang donné (à partir de 0).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.checked_pow(retry).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+
+    /// Indique si l'erreur justifie une nouvelle tentative.
+    pub fn is_retryable(&self, err: &CacheError) -> bool {
+        match self.classifier.as_ref() {
+            Some(classifier) => classifier(err),
+            None => is_transient(err),
+        }
+    }
+
+    /// Exécute l'opération en la relançant selon la politique, et retourne
+    /// son premier succès ou sa dernière erreur.
+    ///
+    /// # Errors
+    ///
+    /// Retourne l'erreur définitive, ou la dernière erreur transitoire une
+    /// fois les tentatives épuisées.
+    pub fn run<T, F>(&self, mut operation: F) -> Result<T, CacheError>
+    where
+        F: FnMut() -> Result<T, CacheError>,
+    {
+        let mut retry = 0;
+        loop {
+            match operation() {
+                Err(err) if retry < self.max_retries && self.is_retryable(&err) => {
+                    thread::sleep(self.jittered(self.backoff(retry)));
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn jittered(&self, backoff: Duration) -> Duration {
+        if self.jitter == 0.0 {
+            return backoff;
+        }
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        backoff.mul_f64(1.0 - self.jitter * random)
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("jitter", &self.jitter)
+            .field("classifier", &self.classifier.is_some())
+            .finish()
+    }
+}
+
+/// Classement par défaut : seules les erreurs passagères sont relancées.
+fn is_transient(err: &CacheError) -> bool {
+    match err {
+        CacheError::IoError(err) => matches!(
+            err.kind(),
+            ErrorKind::TimedOut
+                | ErrorKind::Interrupted
+                | ErrorKind::WouldBlock
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::ConnectionRefused
+                | ErrorKind::BrokenPipe
+                | ErrorKind::UnexpectedEof
+        ),
+        CacheError::LoadTimeout { .. } => true,
+        _ => false,
+    }
+}
+
+/// Chargeur relançant les échecs d'un autre chargeur selon une politique.
+#[derive(Debug, Clone)]
+pub struct RetryingLoader<L> {
+    loader: L,
+    policy: RetryPolicy,
+}
+
+impl<L> RetryingLoader<L> {
+    /// Applique la politique au chargeur donné.
+    pub fn new(loader: L, policy: RetryPolicy) -> Self {
+        RetryingLoader { loader, policy }
+    }
+
+    /// Retourne la politique appliquée.
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+}
+
+impl<K, V, L> Loader<K, V> for RetryingLoader<L>
+where
+    L: Loader<K, V>,
+{
+    fn load(&self, key: &K) -> Result<V, CacheError> {
+        self.policy.run(|| self.loader.load(key))
+    }
+}
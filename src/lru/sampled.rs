@@ -0,0 +1,119 @@
+//! Module implémentant l'éviction LRU approchée par échantillonnage.
+//!
+//! L'ordre d'utilisation exact coûte une clé par entrée et un déplacement à
+//! chaque lecture. Avec `CacheBuilder::sampled_eviction`, à la manière de
+//! Redis, le cache ne tient plus d'ordre : chaque entrée retient seulement
+//! l'instant de son dernier accès, et une éviction tire quelques entrées au
+//! hasard pour supprimer la moins récemment utilisée de l'échantillon.
+//!
+//! Les entrées sont tirées en sondant la table à des empreintes aléatoires,
+//! sans aucune structure supplémentaire. Plus l'échantillon est grand, plus
+//! l'éviction se rapproche d'un LRU exact, et plus elle coûte.
+//!
+//! Dans ce mode, `iter` parcourt les entrées dans un ordre quelconque, fixé
+//! seulement en mode déterministe (`CacheBuilder::deterministic`).
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::builder().capacity(1000).sampled_eviction(5).build().unwrap();
+//! for i in 0..5000 {
+//!     cache.put(i, i);
+//! }
+//! assert_eq!(cache.len(), 1000);
+//! assert_eq!(cache.eviction_sample_size(), Some(5));
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::time::Instant;
+use crate::lru::Cache;
+use crate::lru::deterministic::mix_seed;
+
+/// Nombre de sondages tentés par entrée voulue dans l'échantillon. Une
+/// empreinte aléatoire ne tombe pas toujours sur une entrée occupée.
+const PROBES_PER_SAMPLE: usize = 64;
+
+/// Paramètres et générateur pseudo-aléatoire de l'échantillonnage.
+#[derive(Debug, Clone)]
+pub(crate) struct Sampler {
+    size: usize,
+    state: u64,
+}
+
+impl Sampler {
+    /// Crée un échantillonneur tirant `size` entrées par éviction.
+    pub(crate) fn new(size: usize) -> Self {
+        Sampler {
+            size: size.max(1),
+            state: RandomState::new().build_hasher().finish() | 1,
+        }
+    }
+
+    /// Crée un échantillonneur dont les tirages sont dérivés de la graine.
+    pub(crate) fn seeded(size: usize, seed: u64) -> Self {
+        Sampler {
+            size: size.max(1),
+            state: mix_seed(seed) | 1,
+        }
+    }
+
+    /// Tire une empreinte pseudo-aléatoire (xorshift).
+    fn next_hash(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne la taille de l'échantillon d'éviction, si l'éviction
+    /// échantillonnée est activée.
+    pub fn eviction_sample_size(&self) -> Option<usize> {
+        self.sampling.as_ref().map(|sampler| sampler.size)
+    }
+
+    /// Choisit la victime d'une éviction échantillonnée : l'entrée non
+    /// épinglée la moins récemment utilisée parmi celles tirées au hasard.
+    pub(crate) fn sample_victim(&mut self) -> Option<K> {
+        let sampler = self.sampling.as_mut()?;
+        let pinned = self.pins.lock();
+        let mut victim: Option<(&K, Instant)> = None;
+        let mut sampled = 0;
+        for _ in 0..sampler.size * PROBES_PER_SAMPLE {
+            if sampled == sampler.size {
+                break;
+            }
+            let hash = sampler.next_hash();
+            if let Some((key, entry)) = self.elements.raw_entry().from_hash(hash, |_| true) {
+                sampled += 1;
+                if pinned.contains_key(key) {
+                    continue;
+                }
+                if victim.is_none_or(|(_, accessed)| entry.accessed < accessed) {
+                    victim = Some((key, entry.accessed));
+                }
+            }
+        }
+        if victim.is_none() {
+            // Table presque vide : les premières entrées font l'affaire
+            victim = self
+                .elements
+                .iter()
+                .filter(|(key, _)| !pinned.contains_key(*key))
+                .take(sampler.size)
+                .map(|(key, entry)| (key, entry.accessed))
+                .min_by_key(|(_, accessed)| *accessed);
+        }
+        victim.map(|(key, _)| key.clone())
+    }
+}
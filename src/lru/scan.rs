@@ -0,0 +1,211 @@
+//! Module implémentant le parcours paginé des clés, à la manière de `SCAN`.
+//!
+//! Parcourir un cache d'un million d'entrées d'un seul tenant emprunte le
+//! cache (ou détient le verrou de `SyncCache`) pendant tout le parcours.
+//! `Cache::scan` ne retourne qu'une page d'au plus `count` entrées environ,
+//! et un `Cursor` à repasser à l'appel suivant ; entre deux pages, le cache
+//! reste libre d'être lu et modifié.
+//!
+//! Les entrées sont parcourues par ordre d'empreinte (voir
+//! `Cache::hash_key`) et le curseur retient l'empreinte où reprendre. Ce
+//! repère ne dépend ni de l'ordre d'utilisation ni de la disposition de la
+//! table, qui changent à chaque modification. Une entrée présente du début à
+//! la fin du parcours est donc retournée exactement une fois ; une entrée
+//! ajoutée ou retirée pendant le parcours peut l'être ou non. Les entrées
+//! expirées sont ignorées, et le parcours ne modifie ni l'ordre
+//! d'utilisation ni les statistiques.
+//!
+//! Comme pour `SCAN`, le parcours commence à `Cursor::START` et se termine
+//! lorsque le curseur retourné est de nouveau `Cursor::START`. Un curseur
+//! n'a de sens que pour le cache qui l'a produit : chaque cache a sa propre
+//! graine de hachage.
+//!
+//! Par défaut, chaque page parcourt toute la table pour retrouver les
+//! empreintes qui suivent le curseur : son coût, et la durée de verrouillage
+//! de `SyncCache::scan`, croissent avec la taille du cache. Avec
+//! `CacheBuilder::indexed_scan`, le cache tient un index trié des empreintes
+//! de ses clés, mis à jour à chaque ajout et à chaque retrait ; une page
+//! reprend alors directement au curseur, et son coût ne dépend plus que de
+//! la taille de la page et du nombre d'entrées expirées rencontrées.
+//!
+//! Une page peut dépasser `count` entrées lorsque plusieurs clés partagent
+//! l'empreinte de la dernière : elles sont retournées ensemble, car le
+//! curseur ne saurait pas où reprendre entre elles. Avec des empreintes de
+//! 64 bits, une page ne compte ainsi qu'exceptionnellement une ou deux
+//! entrées de plus.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::scan::Cursor;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::new(100);
+//! for i in 0..10 {
+//!     cache.put(i, i * 10);
+//! }
+//!
+//! let mut vues = Vec::new();
+//! let mut curseur = Cursor::START;
+//! loop {
+//!     let (page, suivant) = cache.scan(curseur, 3);
+//!     assert!(page.len() <= 3);
+//!     vues.extend(page.into_iter().map(|(cle, _)| *cle));
+//!     if suivant.is_start() {
+//!         break;
+//!     }
+//!     curseur = suivant;
+//! }
+//! vues.sort();
+//! assert_eq!(vues, (0..10).collect::<Vec<_>>());
+//! ```
+
+use std::collections::BTreeMap;
+use std::hash::{BuildHasher, Hash};
+use crate::error::CacheError;
+use crate::lru::{Cache, Entry};
+use crate::lru::sync::SyncCache;
+
+/// Position d'un parcours paginé : l'empreinte à partir de laquelle
+/// reprendre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Cursor(pub u64);
+
+impl Cursor {
+    /// Début d'un parcours, également retourné à la fin du parcours.
+    pub const START: Cursor = Cursor(0);
+
+    /// Indique si le curseur désigne le début (ou la fin) d'un parcours.
+    pub fn is_start(self) -> bool {
+        self == Cursor::START
+    }
+}
+
+/// Empreintes des clés présentes, triées, avec le nombre de clés qui
+/// partagent chacune.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HashIndex {
+    hashes: BTreeMap<u64, usize>,
+}
+
+impl HashIndex {
+    pub(crate) fn insert(&mut self, hash: u64) {
+        *self.hashes.entry(hash).or_insert(0) += 1;
+    }
+
+    pub(crate) fn remove(&mut self, hash: u64) {
+        if let Some(keys) = self.hashes.get_mut(&hash) {
+            *keys -= 1;
+            if *keys == 0 {
+                self.hashes.remove(&hash);
+            }
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.hashes.clear();
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Construit l'index des empreintes à partir des clés présentes ; il est
+    /// ensuite tenu à jour à chaque ajout et à chaque retrait.
+    pub(crate) fn use_scan_index(&mut self) {
+        let mut index = HashIndex::default();
+        for key in self.elements.keys() {
+            index.insert(self.elements.hasher().hash_one(key));
+        }
+        self.scan_index = Some(index);
+    }
+
+    /// Ajoute la clé à l'index des empreintes, s'il est tenu.
+    pub(crate) fn index_key(&mut self, key: &K) {
+        if let Some(index) = self.scan_index.as_mut() {
+            index.insert(self.elements.hasher().hash_one(key));
+        }
+    }
+
+    /// Retire la clé de l'index des empreintes, s'il est tenu.
+    pub(crate) fn unindex_key(&mut self, key: &K) {
+        if let Some(index) = self.scan_index.as_mut() {
+            index.remove(self.elements.hasher().hash_one(key));
+        }
+    }
+
+    /// Retourne la page d'entrées valides qui suit le curseur, par ordre
+    /// d'empreinte, et le curseur de la page suivante.
+    ///
+    /// La page compte au plus `count` entrées (au moins une), sauf si
+    /// plusieurs clés partagent l'empreinte de la dernière : elles sont
+    /// alors retournées ensemble pour qu'aucune ne soit sautée. Sans
+    /// `CacheBuilder::indexed_scan`, chaque page parcourt toute la table.
+    pub fn scan<'a>(&'a self, cursor: Cursor, count: usize) -> (Vec<(&'a K, &'a V)>, Cursor) {
+        let count = count.max(1);
+        let Some(index) = self.scan_index.as_ref() else {
+            return self.scan_table(cursor, count);
+        };
+        let mut page = Vec::with_capacity(count.min(self.elements.len()));
+        for (&hash, &keys) in index.hashes.range(cursor.0..) {
+            if page.len() >= count {
+                return (page, Cursor(hash));
+            }
+            let is_match = |key: &K| self.hash_key(key) == hash;
+            let live = |(key, entry): (&'a K, &'a Entry<V>)| (!self.is_expired(key)).then_some((key, &entry.value));
+            if keys == 1 {
+                let found = self.elements.raw_entry().from_hash(hash, |key| is_match(key));
+                page.extend(found.and_then(live));
+            } else {
+                // Collision d'empreintes : la table ne retrouve qu'une clé
+                // par empreinte, il faut la parcourir
+                page.extend(self.elements.iter().filter(|(key, _)| is_match(key)).filter_map(live));
+            }
+        }
+        (page, Cursor::START)
+    }
+
+    /// Parcours sans index : trie les empreintes de toutes les entrées
+    /// valides qui suivent le curseur.
+    fn scan_table<'a>(&'a self, cursor: Cursor, count: usize) -> (Vec<(&'a K, &'a V)>, Cursor) {
+        let mut found: Vec<(u64, &'a K, &'a V)> = self
+            .elements
+            .iter()
+            .map(|(key, entry)| (self.hash_key(key), key, &entry.value))
+            .filter(|&(hash, key, _)| hash >= cursor.0 && !self.is_expired(key))
+            .collect();
+        found.sort_unstable_by_key(|&(hash, _, _)| hash);
+
+        // Les clés qui partagent l'empreinte de la dernière restent ensemble
+        let mut end = count.min(found.len());
+        while end < found.len() && found[end].0 == found[end - 1].0 {
+            end += 1;
+        }
+        let next = found.get(end).map_or(Cursor::START, |&(hash, _, _)| Cursor(hash));
+        found.truncate(end);
+        (found.into_iter().map(|(_, key, value)| (key, value)).collect(), next)
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Retourne une copie de la page d'entrées qui suit le curseur (voir
+    /// `Cache::scan`). Le verrou n'est détenu que le temps de la page.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si le verrou est empoisonné et que la
+    /// politique est `PoisonPolicy::Propagate`.
+    pub fn scan(&self, cursor: Cursor, count: usize) -> Result<(Vec<(K, V)>, Cursor), CacheError> {
+        self.with_lock(|cache| {
+            let (page, next) = cache.scan(cursor, count);
+            let page = page.into_iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+            (page, next)
+        })
+    }
+}
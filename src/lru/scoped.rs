@@ -0,0 +1,260 @@
+//! Module implémentant des quotas de capacité par portée.
+//!
+//! `ScopedCache` partage une capacité totale entre des portées nommées, par
+//! exemple « vignettes » 70 % et « métadonnées » 30 %. Chaque portée est un
+//! `Cache` indépendant, dimensionné selon sa part et doté de son propre
+//! ordre LRU : une portée très sollicitée n'évince que ses propres entrées
+//! et ne peut pas vider les autres.
+//!
+//! Les caches des portées sont créés par une fabrique recevant leur quota,
+//! `Cache::try_new` par défaut ; `with_factory` permet de les configurer
+//! (durée de vie, statistiques, etc.) avec un `CacheBuilder`.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::scoped::ScopedCache;
+//!
+//! let mut cache = ScopedCache::new(10);
+//! cache.add_scope("vignettes", 70).unwrap();
+//! cache.add_scope("métadonnées", 30).unwrap();
+//!
+//! // Les vignettes débordent sans toucher aux métadonnées
+//! cache.put(&"métadonnées", 0, "titre").unwrap();
+//! for i in 0..20 {
+//!     cache.put(&"vignettes", i, "image").unwrap();
+//! }
+//! assert_eq!(cache.scope_len(&"vignettes"), 7);
+//! assert_eq!(cache.get(&"métadonnées", &0), Some(&"titre"));
+//!
+//! // Le total des parts ne peut pas dépasser 100 %
+//! assert!(cache.add_scope("journaux", 10).is_err());
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::messages;
+use crate::lru::Cache;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Fabrique créant le cache d'une portée à partir de son quota.
+type ScopeFactory<K, V> = Box<dyn Fn(usize) -> Result<Cache<K, V>, CacheError> + Send + Sync>;
+
+/// Portée : sa part de la capacité totale, en pourcentage, et son cache.
+struct Scope<K: Hash + Eq, V> {
+    share: u32,
+    cache: Cache<K, V>,
+}
+
+/// Cache partageant sa capacité entre des portées indépendantes.
+pub struct ScopedCache<S, K: Hash + Eq, V> {
+    capacity: usize,
+    scopes: HashMap<S, Scope<K, V>>,
+    factory: ScopeFactory<K, V>,
+}
+
+impl<S, K, V> fmt::Debug for ScopedCache<S, K, V>
+where
+    S: fmt::Debug,
+    K: Hash + Eq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shares: Vec<(&S, u32)> = self.scopes.iter().map(|(scope, entry)| (scope, entry.share)).collect();
+        f.debug_struct("ScopedCache")
+            .field("capacity", &self.capacity)
+            .field("shares", &shares)
+            .finish()
+    }
+}
+
+impl<S, K, V> ScopedCache<S, K, V>
+where
+    S: Hash + Eq + Clone,
+    K: Hash + Eq + Clone + 'static,
+    V: 'static,
+{
+    /// Crée un cache sans portée, de la capacité totale indiquée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la capacité est 0.
+    pub fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Crée un cache sans portée, sans paniquer.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la capacité est 0.
+    pub fn try_new(capacity: usize) -> Result<Self, CacheError> {
+        Self::with_factory(capacity, Cache::try_new)
+    }
+
+    /// Crée un cache dont les portées sont construites par la fabrique
+    /// donnée, appelée avec le quota de chaque portée.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la capacité est 0.
+    pub fn with_factory<F>(capacity: usize, factory: F) -> Result<Self, CacheError>
+    where
+        F: Fn(usize) -> Result<Cache<K, V>, CacheError> + Send + Sync + 'static,
+    {
+        if capacity == 0 {
+            return Err(CacheError::CapacityError(messages::ZERO_CAPACITY.to_string()));
+        }
+        Ok(ScopedCache {
+            capacity,
+            scopes: HashMap::new(),
+            factory: Box::new(factory),
+        })
+    }
+}
+
+impl<S, K, V> ScopedCache<S, K, V>
+where
+    S: Hash + Eq + Clone,
+    K: Hash + Eq + Clone,
+{
+    /// Quota correspondant à une part de la capacité totale, arrondi à
+    /// l'inférieur : la somme des quotas ne dépasse jamais la capacité.
+    fn quota_for(&self, share: u32) -> usize {
+        self.capacity * share as usize / 100
+    }
+
+    /// Ajoute une portée recevant `share` pour cent de la capacité totale et
+    /// retourne son quota. Une portée existante est redimensionnée, en
+    /// conservant ses entrées les plus récentes.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la part ne réserve aucune
+    /// entrée de la capacité totale ou si le total des parts dépasserait
+    /// 100 %, ainsi que les erreurs de la fabrique.
+    pub fn add_scope(&mut self, scope: S, share: u32) -> Result<usize, CacheError> {
+        let others: u32 = self
+            .scopes
+            .iter()
+            .filter(|(name, _)| **name != scope)
+            .map(|(_, entry)| entry.share)
+            .sum();
+        let quota = self.quota_for(share);
+        if quota == 0 || others + share > 100 {
+            return Err(CacheError::CapacityError(messages::INVALID_SCOPE_SHARE.to_string()));
+        }
+
+        match self.scopes.get_mut(&scope) {
+            Some(entry) => {
+                entry.cache.resize(quota)?;
+                entry.share = share;
+            }
+            None => {
+                let cache = (self.factory)(quota)?;
+                self.scopes.insert(scope, Scope { share, cache });
+            }
+        }
+        Ok(quota)
+    }
+
+    /// Retire une portée et retourne son cache.
+    pub fn remove_scope(&mut self, scope: &S) -> Option<Cache<K, V>> {
+        self.scopes.remove(scope).map(|entry| entry.cache)
+    }
+
+    fn cache_mut(&mut self, scope: &S) -> Result<&mut Cache<K, V>, CacheError> {
+        self.scopes
+            .get_mut(scope)
+            .map(|entry| &mut entry.cache)
+            .ok_or_else(|| CacheError::InvalidKey { reason: messages::UNKNOWN_SCOPE.to_string() })
+    }
+
+    /// Ajoute ou met à jour une entrée de la portée. Seules les entrées de
+    /// cette portée peuvent être évincées.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::InvalidKey` si la portée n'existe pas.
+    pub fn put(&mut self, scope: &S, key: K, value: V) -> Result<(), CacheError> {
+        self.cache_mut(scope)?.put(key, value);
+        Ok(())
+    }
+
+    /// Retourne la valeur associée à la clé dans la portée, en la marquant
+    /// comme récemment utilisée dans cette portée.
+    pub fn get(&mut self, scope: &S, key: &K) -> Option<&V> {
+        self.scopes.get_mut(scope)?.cache.get(key)
+    }
+
+    /// Retourne la valeur associée à la clé dans la portée, sans modifier
+    /// l'ordre d'utilisation.
+    pub fn peek(&self, scope: &S, key: &K) -> Option<&V> {
+        self.scopes.get(scope)?.cache.peek(key)
+    }
+
+    /// Vérifie si la clé est présente dans la portée.
+    pub fn contains(&self, scope: &S, key: &K) -> bool {
+        self.scopes.get(scope).is_some_and(|entry| entry.cache.contains(key))
+    }
+
+    /// Supprime la clé de la portée et retourne sa valeur.
+    pub fn remove(&mut self, scope: &S, key: &K) -> Option<V> {
+        self.scopes.get_mut(scope)?.cache.remove(key)
+    }
+
+    /// Vide une portée et retourne le nombre d'entrées supprimées.
+    pub fn clear_scope(&mut self, scope: &S) -> usize {
+        self.scopes.get_mut(scope).map_or(0, |entry| {
+            let count = entry.cache.len();
+            entry.cache.clear();
+            count
+        })
+    }
+
+    /// Retourne le cache d'une portée.
+    pub fn scope(&self, scope: &S) -> Option<&Cache<K, V>> {
+        self.scopes.get(scope).map(|entry| &entry.cache)
+    }
+
+    /// Retourne le cache d'une portée, modifiable.
+    pub fn scope_mut(&mut self, scope: &S) -> Option<&mut Cache<K, V>> {
+        self.scopes.get_mut(scope).map(|entry| &mut entry.cache)
+    }
+
+    /// Retourne les portées existantes, dans un ordre quelconque.
+    pub fn scopes(&self) -> impl Iterator<Item = &S> {
+        self.scopes.keys()
+    }
+
+    /// Retourne la part de la capacité totale d'une portée, en pourcentage.
+    pub fn share(&self, scope: &S) -> Option<u32> {
+        self.scopes.get(scope).map(|entry| entry.share)
+    }
+
+    /// Retourne le quota, en entrées, d'une portée.
+    pub fn quota(&self, scope: &S) -> Option<usize> {
+        self.scopes.get(scope).map(|entry| entry.cache.capacity())
+    }
+
+    /// Retourne le nombre d'entrées d'une portée.
+    pub fn scope_len(&self, scope: &S) -> usize {
+        self.scopes.get(scope).map_or(0, |entry| entry.cache.len())
+    }
+
+    /// Retourne le nombre total d'entrées, toutes portées confondues.
+    pub fn len(&self) -> usize {
+        self.scopes.values().map(|entry| entry.cache.len()).sum()
+    }
+
+    /// Vérifie si aucune portée ne contient d'entrée.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retourne la capacité totale partagée entre les portées.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
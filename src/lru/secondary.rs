@@ -0,0 +1,228 @@
+//! Module implémentant un cache doté d'un index secondaire.
+//!
+//! `IndexedCache` retrouve les entrées par leur clé principale, comme
+//! `Cache`, mais aussi par une clé secondaire calculée à partir de la valeur
+//! (l'utilisateur d'une session, le domaine d'une URL...). Plusieurs entrées
+//! peuvent partager la même clé secondaire.
+//!
+//! L'index est tenu à jour par un observateur des mutations du cache : les
+//! évictions, expirations et remplacements de valeurs y sont répercutés au
+//! moment où ils se produisent, ce qu'une table parallèle tenue à la main ne
+//! peut pas garantir.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::secondary::IndexedCache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! // Sessions indexées par identifiant d'utilisateur
+//! let mut sessions = IndexedCache::new(Cache::new(2), |user: &u32| *user);
+//! sessions.put("s1", 7);
+//! sessions.put("s2", 8);
+//! assert_eq!(sessions.get_by_secondary(&7), Some(&7));
+//!
+//! // L'éviction de "s2" retire aussi l'utilisateur 8 de l'index
+//! sessions.put("s3", 9);
+//! assert_eq!(sessions.get_by_secondary(&8), None);
+//! assert_eq!(sessions.keys_by_secondary(&9), vec!["s3"]);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard};
+use crate::lru::Cache;
+use crate::lru::events::Mutation;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Correspondances entre clés secondaires et clés principales.
+struct Index<K, S> {
+    /// Clés principales par clé secondaire, de la plus ancienne à la plus
+    /// récemment indexée
+    keys: HashMap<S, Vec<K>>,
+    /// Clé secondaire de chaque clé principale indexée
+    secondary: HashMap<K, S>,
+}
+
+impl<K, S> Index<K, S>
+where
+    K: Hash + Eq + Clone,
+    S: Hash + Eq + Clone,
+{
+    fn insert(&mut self, key: &K, secondary: S) {
+        if self.secondary.get(key) == Some(&secondary) {
+            return;
+        }
+        self.remove(key);
+        self.keys.entry(secondary.clone()).or_default().push(key.clone());
+        self.secondary.insert(key.clone(), secondary);
+    }
+
+    fn remove(&mut self, key: &K) {
+        let Some(secondary) = self.secondary.remove(key) else {
+            return;
+        };
+        if let Some(keys) = self.keys.get_mut(&secondary) {
+            keys.retain(|k| k != key);
+            if keys.is_empty() {
+                self.keys.remove(&secondary);
+            }
+        }
+    }
+}
+
+/// Index partagé entre le cache et son observateur.
+type SharedIndex<K, S> = Arc<Mutex<Index<K, S>>>;
+
+fn lock<K, S>(index: &SharedIndex<K, S>) -> MutexGuard<'_, Index<K, S>> {
+    index.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Cache dont les entrées sont aussi retrouvées par une clé secondaire.
+pub struct IndexedCache<K, S, V>
+where
+    K: Hash + Eq,
+{
+    cache: Cache<K, V>,
+    index: SharedIndex<K, S>,
+}
+
+impl<K, S, V> fmt::Debug for IndexedCache<K, S, V>
+where
+    K: Hash + Eq + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexedCache")
+            .field("cache", &self.cache)
+            .field("secondary_keys", &lock(&self.index).keys.len())
+            .finish()
+    }
+}
+
+impl<K, S, V> IndexedCache<K, S, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    S: Hash + Eq + Clone + Send + 'static,
+    V: 'static,
+{
+    /// Encapsule le cache donné ; `secondary` calcule la clé secondaire
+    /// d'une valeur. Les entrées déjà présentes sont indexées.
+    pub fn new<F>(mut cache: Cache<K, V>, secondary: F) -> Self
+    where
+        F: Fn(&V) -> S + Send + 'static,
+    {
+        let mut index = Index {
+            keys: HashMap::new(),
+            secondary: HashMap::new(),
+        };
+        for (key, value) in cache.iter() {
+            index.insert(key, secondary(value));
+        }
+        let index = Arc::new(Mutex::new(index));
+
+        let shared = Arc::clone(&index);
+        cache.observers.listeners.push(Box::new(move |mutation: &Mutation<'_, K, V>| {
+            let mut index = lock(&shared);
+            match *mutation {
+                Mutation::Insert { key, value } | Mutation::Update { key, value } => {
+                    index.insert(key, secondary(value));
+                }
+                Mutation::Remove { key, .. } => index.remove(key),
+            }
+            true
+        }));
+        IndexedCache { cache, index }
+    }
+}
+
+impl<K, S, V> IndexedCache<K, S, V>
+where
+    K: Hash + Eq + Clone,
+    S: Hash + Eq,
+{
+    /// Retourne la valeur de l'entrée la plus récemment indexée sous la clé
+    /// secondaire, et la marque comme récemment utilisée.
+    pub fn get_by_secondary(&mut self, secondary: &S) -> Option<&V> {
+        // Le verrou est relâché avant la lecture, qui peut purger une
+        // entrée expirée et donc notifier l'observateur
+        let key = lock(&self.index).keys.get(secondary)?.last()?.clone();
+        self.cache.get(&key)
+    }
+
+    /// Retourne les clés principales indexées sous la clé secondaire, de la
+    /// plus ancienne à la plus récemment indexée, sans modifier l'ordre LRU.
+    ///
+    /// Les entrées expirées mais pas encore purgées sont ignorées.
+    pub fn keys_by_secondary(&self, secondary: &S) -> Vec<K> {
+        let index = lock(&self.index);
+        index
+            .keys
+            .get(secondary)
+            .into_iter()
+            .flatten()
+            .filter(|key| self.cache.contains(key))
+            .cloned()
+            .collect()
+    }
+
+    /// Supprime toutes les entrées indexées sous la clé secondaire et
+    /// retourne le nombre d'entrées supprimées.
+    pub fn remove_by_secondary(&mut self, secondary: &S) -> usize {
+        let keys = lock(&self.index).keys.get(secondary).cloned().unwrap_or_default();
+        keys.iter().filter(|key| self.cache.remove(key).is_some()).count()
+    }
+
+    /// Retourne une référence au cache encapsulé.
+    pub fn inner(&self) -> &Cache<K, V> {
+        &self.cache
+    }
+}
+
+impl<K, S, V> CacheRead<K, V> for IndexedCache<K, S, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        self.cache.peek(key)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.cache.contains(key)
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+}
+
+impl<K, S, V> CacheTrait<K, V> for IndexedCache<K, S, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        self.cache.put(key, value);
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.cache.remove(key)
+    }
+
+    fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
@@ -0,0 +1,88 @@
+//! Module fournissant une enveloppe masquant les valeurs sensibles.
+//!
+//! Un cache de jetons d'authentification ne doit jamais les écrire en
+//! clair : ni via `Debug` (le `Debug` du cache, des événements ou des
+//! erreurs), ni via `Display` (journaux, affichages du binaire). Une valeur
+//! enveloppée dans `Sensitive` s'affiche toujours sous la forme d'un
+//! marqueur ; seule `expose` donne accès à la valeur.
+//!
+//! `Sensitive` n'implémente ni `FromStr` ni `Serialize` : un cache de
+//! valeurs sensibles ne peut pas être sauvegardé en clair par `persist` ou
+//! par serde, l'erreur étant signalée dès la compilation.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::sensitive::Sensitive;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::new(10);
+//! cache.put("alice", Sensitive::new("jeton-secret".to_string()));
+//!
+//! let jeton = cache.get(&"alice").unwrap();
+//! assert_eq!(jeton.expose(), "jeton-secret");
+//! assert!(!jeton.to_string().contains("jeton-secret"));
+//! assert!(!format!("{:?}", cache).contains("jeton-secret"));
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use crate::messages;
+
+/// Valeur jamais affichée en clair.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sensitive<V>(V);
+
+impl<V> Sensitive<V> {
+    /// Enveloppe une valeur sensible.
+    pub fn new(value: V) -> Self {
+        Sensitive(value)
+    }
+
+    /// Retourne la valeur en clair. Chaque appel est un point où la valeur
+    /// peut fuir : le nom est volontairement explicite.
+    pub fn expose(&self) -> &V {
+        &self.0
+    }
+
+    /// Retourne la valeur en clair, modifiable.
+    pub fn expose_mut(&mut self) -> &mut V {
+        &mut self.0
+    }
+
+    /// Consomme l'enveloppe et retourne la valeur en clair.
+    pub fn into_inner(self) -> V {
+        self.0
+    }
+}
+
+impl<V: Hash> Sensitive<V> {
+    /// Retourne une empreinte de la valeur, stable au sein d'un même
+    /// binaire, permettant de corréler deux occurrences dans les journaux
+    /// sans révéler la valeur.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.0.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+}
+
+impl<V> From<V> for Sensitive<V> {
+    fn from(value: V) -> Self {
+        Sensitive(value)
+    }
+}
+
+impl<V> fmt::Debug for Sensitive<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Sensitive({})", messages::REDACTED)
+    }
+}
+
+impl<V> fmt::Display for Sensitive<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(messages::REDACTED)
+    }
+}
@@ -0,0 +1,292 @@
+//! Module implémentant un cache partagé entre threads et partitionné.
+//!
+//! `ShardedCache` répartit les clés, selon leur empreinte, entre plusieurs
+//! `Cache` protégés chacun par leur propre verrou : des threads accédant à
+//! des partitions différentes ne se bloquent pas. Chaque partition tient son
+//! propre ordre LRU, si bien que l'éviction est approchée à l'échelle du
+//! cache entier.
+//!
+//! Le nombre de partitions peut évoluer sans redémarrage :
+//! `set_shard_count` redistribue toutes les entrées sur le nouveau nombre
+//! de partitions, et `rebalance` répartit à nouveau la capacité entre les
+//! partitions existantes. Aucune entrée n'est perdue : chaque partition
+//! reçoit au moins la place de ses entrées, plus une part égale de la
+//! capacité libre. Les durées de vie restantes sont conservées.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::sharded::ShardedCache;
+//!
+//! let cache = ShardedCache::new(1000, 2);
+//! for i in 0..500 {
+//!     cache.put(i, i * 2).unwrap();
+//! }
+//!
+//! // Montée en charge : plus de partitions, sans perte
+//! cache.set_shard_count(8).unwrap();
+//! assert_eq!(cache.shard_count().unwrap(), 8);
+//! assert_eq!(cache.len().unwrap(), 500);
+//! assert_eq!(cache.get(&42).unwrap(), Some(84));
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::{Mutex, MutexGuard, RwLock};
+use std::time::Duration;
+use crate::error::CacheError;
+use crate::messages;
+use crate::lru::Cache;
+use crate::lru::traits::CacheTrait;
+
+/// Cache partitionné, partageable entre threads.
+#[derive(Debug)]
+pub struct ShardedCache<K, V>
+where
+    K: Hash + Eq,
+{
+    pub(crate) shards: RwLock<Vec<Mutex<Cache<K, V>>>>,
+    capacity: usize,
+    hasher: RandomState,
+}
+
+/// Répartit la capacité totale : chaque partition reçoit la place de ses
+/// entrées, plus une part égale de la capacité libre (au moins 1 au total).
+fn allocate(capacity: usize, lens: &[usize]) -> Vec<usize> {
+    let free = capacity.saturating_sub(lens.iter().sum());
+    let (share, remainder) = (free / lens.len(), free % lens.len());
+    lens.iter()
+        .enumerate()
+        .map(|(index, len)| (len + share + usize::from(index < remainder)).max(1))
+        .collect()
+}
+
+fn lock<K: Hash + Eq, V>(shard: &Mutex<Cache<K, V>>) -> Result<MutexGuard<'_, Cache<K, V>>, CacheError> {
+    shard.lock().map_err(|_| CacheError::Poisoned)
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retire toutes les entrées, de la moins à la plus récemment utilisée,
+    /// avec leur durée de vie restante, sans les signaler comme supprimées.
+    fn take_entries(&mut self) -> Vec<(K, V, Option<Duration>)> {
+        let order: Vec<K> = self.recency_order().cloned().collect();
+        let mut entries = Vec::with_capacity(order.len());
+        for key in order {
+            if self.is_expired(&key) {
+                continue;
+            }
+            let ttl = self.ttl(&key);
+            if let Some(entry) = self.elements.remove(&key) {
+                entries.push((key, entry.value, ttl));
+            }
+        }
+        self.clear();
+        entries
+    }
+}
+
+impl<K, V> ShardedCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Crée un cache partitionné de la capacité totale indiquée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la capacité est 0 ou si le nombre de partitions n'est pas
+    /// compris entre 1 et la capacité.
+    pub fn new(capacity: usize, shards: usize) -> Self {
+        Self::try_new(capacity, shards).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Crée un cache partitionné sans paniquer.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la capacité est 0 ou si le
+    /// nombre de partitions n'est pas compris entre 1 et la capacité.
+    pub fn try_new(capacity: usize, shards: usize) -> Result<Self, CacheError> {
+        if capacity == 0 {
+            return Err(CacheError::CapacityError(messages::ZERO_CAPACITY.to_string()));
+        }
+        Self::check_shard_count(capacity, shards)?;
+        let shards = allocate(capacity, &vec![0; shards])
+            .into_iter()
+            .map(|quota| Cache::try_new(quota).map(Mutex::new))
+            .collect::<Result<_, _>>()?;
+        Ok(ShardedCache {
+            shards: RwLock::new(shards),
+            capacity,
+            hasher: RandomState::new(),
+        })
+    }
+
+    fn check_shard_count(capacity: usize, shards: usize) -> Result<(), CacheError> {
+        if shards == 0 || shards > capacity {
+            return Err(CacheError::CapacityError(messages::INVALID_SHARD_COUNT.to_string()));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn shard_index(&self, key: &K, shards: usize) -> usize {
+        (self.hasher.hash_one(key) % shards as u64) as usize
+    }
+
+    /// Exécute une closure avec un accès exclusif à la partition de la clé.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn with_shard<R, F>(&self, key: &K, f: F) -> Result<R, CacheError>
+    where
+        F: FnOnce(&mut Cache<K, V>) -> R,
+    {
+        let shards = self.shards.read().map_err(|_| CacheError::Poisoned)?;
+        let mut shard = lock(&shards[self.shard_index(key, shards.len())])?;
+        Ok(f(&mut shard))
+    }
+
+    /// Ajoute ou met à jour une paire clé-valeur dans sa partition.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn put(&self, key: K, value: V) -> Result<(), CacheError> {
+        let index = key.clone();
+        self.with_shard(&index, |shard| shard.put(key, value))
+    }
+
+    /// Supprime la clé et retourne sa valeur.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn remove(&self, key: &K) -> Result<Option<V>, CacheError> {
+        self.with_shard(key, |shard| shard.remove(key))
+    }
+
+    /// Retourne le nombre total d'entrées.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn len(&self) -> Result<usize, CacheError> {
+        Ok(self.shard_lens()?.iter().sum())
+    }
+
+    /// Vérifie si le cache est vide.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn is_empty(&self) -> Result<bool, CacheError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Retourne la capacité totale, répartie entre les partitions.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Retourne le nombre de partitions.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn shard_count(&self) -> Result<usize, CacheError> {
+        Ok(self.shards.read().map_err(|_| CacheError::Poisoned)?.len())
+    }
+
+    /// Retourne le nombre d'entrées de chaque partition.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn shard_lens(&self) -> Result<Vec<usize>, CacheError> {
+        let shards = self.shards.read().map_err(|_| CacheError::Poisoned)?;
+        shards.iter().map(|shard| Ok(lock(shard)?.len())).collect()
+    }
+
+    /// Retourne la capacité de chaque partition.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn shard_capacities(&self) -> Result<Vec<usize>, CacheError> {
+        let shards = self.shards.read().map_err(|_| CacheError::Poisoned)?;
+        shards.iter().map(|shard| Ok(lock(shard)?.capacity())).collect()
+    }
+
+    /// Redistribue toutes les entrées sur `shards` partitions. Les accès
+    /// concurrents attendent la fin de la redistribution.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si le nombre de partitions n'est
+    /// pas compris entre 1 et la capacité, et `CacheError::Poisoned` si un
+    /// verrou est empoisonné ; le cache n'est alors pas modifié.
+    pub fn set_shard_count(&self, shards: usize) -> Result<(), CacheError> {
+        Self::check_shard_count(self.capacity, shards)?;
+        let mut current = self.shards.write().map_err(|_| CacheError::Poisoned)?;
+        // Tous les verrous sont pris avant de toucher au contenu
+        let mut guards = current.iter().map(lock).collect::<Result<Vec<_>, _>>()?;
+
+        let mut routed: Vec<Vec<_>> = (0..shards).map(|_| Vec::new()).collect();
+        for guard in guards.iter_mut() {
+            for entry in guard.take_entries() {
+                routed[self.shard_index(&entry.0, shards)].push(entry);
+            }
+        }
+        drop(guards);
+
+        let lens: Vec<usize> = routed.iter().map(Vec::len).collect();
+        let mut next = Vec::with_capacity(shards);
+        for (entries, quota) in routed.into_iter().zip(allocate(self.capacity, &lens)) {
+            let mut cache = Cache::try_new(quota)?;
+            for (key, value, ttl) in entries {
+                match ttl {
+                    Some(ttl) => cache.put_with_ttl(key, value, ttl),
+                    None => cache.put(key, value),
+                }
+            }
+            next.push(Mutex::new(cache));
+        }
+        *current = next;
+        Ok(())
+    }
+
+    /// Répartit à nouveau la capacité totale entre les partitions existantes
+    /// selon leur remplissage actuel, sans déplacer ni évincer d'entrée.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn rebalance(&self) -> Result<(), CacheError> {
+        let shards = self.shards.write().map_err(|_| CacheError::Poisoned)?;
+        let mut guards = shards.iter().map(lock).collect::<Result<Vec<_>, _>>()?;
+        let lens: Vec<usize> = guards.iter().map(|guard| guard.len()).collect();
+        for (guard, quota) in guards.iter_mut().zip(allocate(self.capacity, &lens)) {
+            guard.resize(quota)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K, V> ShardedCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Récupère une copie de la valeur associée à la clé, en la marquant
+    /// comme récemment utilisée dans sa partition.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si un verrou est empoisonné.
+    pub fn get(&self, key: &K) -> Result<Option<V>, CacheError> {
+        self.with_shard(key, |shard| shard.get(key).cloned())
+    }
+}
@@ -0,0 +1,474 @@
+//! Module implémentant un cache partagé entre processus d'une même machine.
+//!
+//! Disponible avec la fonctionnalité `shared-memory` (expérimental).
+//! `SharedCache` range ses entrées dans un fichier projeté en mémoire, par
+//! exemple sous `/dev/shm` : plusieurs processus ouvrant le même chemin
+//! partagent alors un seul cache chaud, sans passer par le réseau. C'est le
+//! cas des serveurs web « pre-fork » dont chaque processus de travail
+//! recalculerait sinon les mêmes valeurs.
+//!
+//! La région a une disposition fixe choisie à sa création (`SharedLayout`) :
+//! nombre d'entrées et tailles maximales des clés et des valeurs, en octets.
+//! Les accès sont sérialisés par un verrou tournant logé dans la région
+//! elle-même, donc visible de tous les processus. Un processus qui meurt en
+//! tenant ce verrou bloque les autres : la région doit alors être supprimée.
+//! L'entrée évincée est la moins récemment lue ou écrite, retrouvée par un
+//! parcours de la région ; ce coût linéaire réserve ce cache aux capacités
+//! modestes.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::shared::{SharedCache, SharedLayout};
+//!
+//! let path = std::env::temp_dir().join(format!("lru_shared_doc_{}", std::process::id()));
+//! let layout = SharedLayout::new(100, 32, 256);
+//! let cache = SharedCache::open(&path, layout).unwrap();
+//! cache.put(b"page:/", b"<html>...</html>").unwrap();
+//!
+//! // Un autre processus ouvrant le même chemin voit la même entrée
+//! let autre = SharedCache::open(&path, layout).unwrap();
+//! assert_eq!(autre.get(b"page:/").as_deref(), Some(&b"<html>...</html>"[..]));
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use std::fs::{self, File, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::ptr;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::thread;
+use memmap2::MmapMut;
+use crate::error::CacheError;
+use crate::messages;
+
+/// Marque d'une région initialisée (« LRU_SHM1 »).
+const MAGIC: u64 = 0x4c52_555f_5348_4d31;
+const HEADER_SIZE: usize = 64;
+const SLOT_HEADER_SIZE: usize = 32;
+
+// Champs de l'en-tête de la région
+const MAGIC_OFFSET: usize = 0;
+const LOCK_OFFSET: usize = 8;
+const CAPACITY_OFFSET: usize = 12;
+const MAX_KEY_OFFSET: usize = 16;
+const MAX_VALUE_OFFSET: usize = 20;
+const LEN_OFFSET: usize = 24;
+const CLOCK_OFFSET: usize = 32;
+
+// Champs de l'en-tête d'un emplacement
+const STATE_OFFSET: usize = 0;
+const KEY_LEN_OFFSET: usize = 4;
+const VALUE_LEN_OFFSET: usize = 8;
+const HASH_OFFSET: usize = 16;
+const LAST_USED_OFFSET: usize = 24;
+
+// États d'un emplacement
+const EMPTY: u32 = 0;
+const USED: u32 = 1;
+const REMOVED: u32 = 2;
+
+/// Nombre de tentatives d'acquisition du verrou avant de céder le processeur.
+const SPINS_BEFORE_YIELD: u32 = 64;
+
+/// Disposition d'une région partagée, fixée à sa création.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedLayout {
+    /// Nombre maximal d'entrées
+    pub capacity: usize,
+    /// Taille maximale d'une clé, en octets
+    pub max_key_len: usize,
+    /// Taille maximale d'une valeur, en octets
+    pub max_value_len: usize,
+}
+
+impl SharedLayout {
+    /// Crée une disposition de `capacity` entrées.
+    pub fn new(capacity: usize, max_key_len: usize, max_value_len: usize) -> Self {
+        SharedLayout { capacity, max_key_len, max_value_len }
+    }
+
+    /// Nombre d'emplacements, le double de la capacité pour garder des
+    /// sondages courts.
+    fn slots(&self) -> usize {
+        self.capacity * 2
+    }
+
+    fn slot_size(&self) -> usize {
+        (SLOT_HEADER_SIZE + self.max_key_len + self.max_value_len).next_multiple_of(8)
+    }
+
+    /// Taille totale de la région, en octets.
+    pub fn region_size(&self) -> usize {
+        HEADER_SIZE + self.slots() * self.slot_size()
+    }
+
+    fn validate(&self) -> Result<(), CacheError> {
+        if self.capacity == 0 {
+            return Err(CacheError::CapacityError(messages::ZERO_CAPACITY.to_string()));
+        }
+        if self.max_key_len == 0 {
+            return Err(CacheError::CapacityError(messages::ZERO_KEY_SIZE.to_string()));
+        }
+        let fits = |n: usize| u32::try_from(n).is_ok();
+        if !fits(self.slots()) || !fits(self.max_key_len) || !fits(self.max_value_len) {
+            return Err(CacheError::CapacityError(messages::SHARED_LAYOUT_MISMATCH.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Cache d'octets partagé entre processus par une région projetée en
+/// mémoire.
+#[derive(Debug)]
+pub struct SharedCache {
+    path: PathBuf,
+    layout: SharedLayout,
+    base: *mut u8,
+    _map: MmapMut,
+}
+
+// Toutes les lectures et écritures de la région passent par le verrou
+// qu'elle contient, qui exclut aussi les autres threads du processus.
+unsafe impl Send for SharedCache {}
+unsafe impl Sync for SharedCache {}
+
+/// Verrou de la région, relâché à sa libération.
+struct RegionGuard<'a> {
+    cache: &'a SharedCache,
+}
+
+impl Drop for RegionGuard<'_> {
+    fn drop(&mut self) {
+        self.cache.lock_word().store(0, Ordering::Release);
+    }
+}
+
+impl SharedCache {
+    /// Ouvre la région partagée du chemin donné, ou la crée avec la
+    /// disposition donnée si elle n'existe pas encore.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la disposition est invalide,
+    /// `CacheError::ParseError` si la région existe avec une autre
+    /// disposition, et `CacheError::IoError` si le fichier ne peut pas être
+    /// créé ou projeté.
+    pub fn open<P: AsRef<Path>>(path: P, layout: SharedLayout) -> Result<Self, CacheError> {
+        layout.validate()?;
+        let path = path.as_ref().to_path_buf();
+        loop {
+            match OpenOptions::new().read(true).write(true).open(&path) {
+                Ok(file) => return Self::attach(path, layout, &file),
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+            if let Some(cache) = Self::create(&path, layout)? {
+                return Ok(cache);
+            }
+            // Un autre processus a créé la région entre-temps : l'ouvrir
+        }
+    }
+
+    /// Initialise la région dans un fichier temporaire puis la publie sous
+    /// son chemin par un lien physique, qui échoue si un autre processus
+    /// l'a publiée avant : aucun processus ne voit de région à moitié
+    /// initialisée.
+    fn create(path: &Path, layout: SharedLayout) -> Result<Option<Self>, CacheError> {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{}.tmp", process::id()));
+        let tmp = path.with_file_name(name);
+
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&tmp)?;
+        file.set_len(layout.region_size() as u64)?;
+        let cache = Self::map(path.to_path_buf(), layout, &file)?;
+        cache.write_u32(CAPACITY_OFFSET, layout.capacity as u32);
+        cache.write_u32(MAX_KEY_OFFSET, layout.max_key_len as u32);
+        cache.write_u32(MAX_VALUE_OFFSET, layout.max_value_len as u32);
+        cache.magic().store(MAGIC, Ordering::Release);
+        cache._map.flush()?;
+
+        let published = fs::hard_link(&tmp, path);
+        fs::remove_file(&tmp)?;
+        match published {
+            Ok(()) => Ok(Some(cache)),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn attach(path: PathBuf, layout: SharedLayout, file: &File) -> Result<Self, CacheError> {
+        let mismatch = || {
+            CacheError::ParseError(format!("{}: {}", messages::SHARED_LAYOUT_MISMATCH, path.display()))
+        };
+        if file.metadata()?.len() != layout.region_size() as u64 {
+            return Err(mismatch());
+        }
+        let cache = Self::map(path.clone(), layout, file)?;
+        let matches = cache.magic().load(Ordering::Acquire) == MAGIC
+            && cache.read_u32(CAPACITY_OFFSET) as usize == layout.capacity
+            && cache.read_u32(MAX_KEY_OFFSET) as usize == layout.max_key_len
+            && cache.read_u32(MAX_VALUE_OFFSET) as usize == layout.max_value_len;
+        if !matches {
+            return Err(mismatch());
+        }
+        Ok(cache)
+    }
+
+    fn map(path: PathBuf, layout: SharedLayout, file: &File) -> Result<Self, CacheError> {
+        // La projection reste valide tant que `_map` vit ; sa taille a été
+        // vérifiée ou fixée par l'appelant.
+        let mut map = unsafe { MmapMut::map_mut(file)? };
+        let base = map.as_mut_ptr();
+        Ok(SharedCache { path, layout, base, _map: map })
+    }
+
+    /// Retourne le chemin de la région.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Retourne la disposition de la région.
+    pub fn layout(&self) -> SharedLayout {
+        self.layout
+    }
+
+    /// Retourne le nombre maximal d'entrées.
+    pub fn capacity(&self) -> usize {
+        self.layout.capacity
+    }
+
+    /// Retourne le nombre d'entrées.
+    pub fn len(&self) -> usize {
+        let _guard = self.lock();
+        self.read_u32(LEN_OFFSET) as usize
+    }
+
+    /// Indique si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retourne une copie de la valeur associée à la clé et la marque comme
+    /// récemment utilisée.
+    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let _guard = self.lock();
+        let slot = self.find(key).ok()?;
+        self.touch(slot);
+        let len = self.read_u32(self.slot_offset(slot) + VALUE_LEN_OFFSET) as usize;
+        Some(self.bytes(self.value_offset(slot), len).to_vec())
+    }
+
+    /// Indique si la clé est présente, sans modifier l'ordre d'utilisation.
+    pub fn contains(&self, key: &[u8]) -> bool {
+        let _guard = self.lock();
+        self.find(key).is_ok()
+    }
+
+    /// Ajoute ou met à jour une entrée, en évinçant la moins récemment
+    /// utilisée si le cache est plein.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::KeyTooLarge` ou `CacheError::ValueTooLarge` si
+    /// la clé ou la valeur dépasse la taille prévue par la disposition.
+    pub fn put(&self, key: &[u8], value: &[u8]) -> Result<(), CacheError> {
+        if key.len() > self.layout.max_key_len {
+            return Err(CacheError::KeyTooLarge {
+                key: String::from_utf8_lossy(key).into_owned(),
+                size: key.len(),
+                max: self.layout.max_key_len,
+            });
+        }
+        if value.len() > self.layout.max_value_len {
+            return Err(CacheError::ValueTooLarge { weight: value.len(), max: self.layout.max_value_len });
+        }
+
+        let _guard = self.lock();
+        let slot = match self.find(key) {
+            Ok(slot) => slot,
+            Err(free) => {
+                let free = if self.read_u32(LEN_OFFSET) as usize >= self.layout.capacity {
+                    self.evict_lru();
+                    self.find(key).unwrap_err()
+                } else {
+                    free
+                };
+                // Au plus la moitié des emplacements est occupée
+                let slot = free.expect("région partagée saturée");
+                let offset = self.slot_offset(slot);
+                self.write_u64(offset + HASH_OFFSET, hash(key));
+                self.write_u32(offset + KEY_LEN_OFFSET, key.len() as u32);
+                self.bytes_mut(self.key_offset(slot), key.len()).copy_from_slice(key);
+                self.write_u32(offset + STATE_OFFSET, USED);
+                self.write_u32(LEN_OFFSET, self.read_u32(LEN_OFFSET) + 1);
+                slot
+            }
+        };
+        self.write_u32(self.slot_offset(slot) + VALUE_LEN_OFFSET, value.len() as u32);
+        self.bytes_mut(self.value_offset(slot), value.len()).copy_from_slice(value);
+        self.touch(slot);
+        Ok(())
+    }
+
+    /// Supprime l'entrée associée à la clé et retourne sa valeur.
+    pub fn remove(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let _guard = self.lock();
+        let slot = self.find(key).ok()?;
+        let len = self.read_u32(self.slot_offset(slot) + VALUE_LEN_OFFSET) as usize;
+        let value = self.bytes(self.value_offset(slot), len).to_vec();
+        self.release(slot);
+        Some(value)
+    }
+
+    /// Vide le cache pour tous les processus.
+    pub fn clear(&self) {
+        let _guard = self.lock();
+        for slot in 0..self.layout.slots() {
+            self.write_u32(self.slot_offset(slot) + STATE_OFFSET, EMPTY);
+        }
+        self.write_u32(LEN_OFFSET, 0);
+    }
+
+    /// Acquiert le verrou de la région.
+    fn lock(&self) -> RegionGuard<'_> {
+        let lock = self.lock_word();
+        let mut spins = 0;
+        while lock.compare_exchange_weak(0, 1, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            spins += 1;
+            if spins < SPINS_BEFORE_YIELD {
+                std::hint::spin_loop();
+            } else {
+                thread::yield_now();
+            }
+        }
+        RegionGuard { cache: self }
+    }
+
+    /// Sonde les emplacements à partir de celui désigné par l'empreinte de
+    /// la clé. Retourne l'emplacement de la clé, ou à défaut le premier
+    /// emplacement libre rencontré.
+    fn find(&self, key: &[u8]) -> Result<usize, Option<usize>> {
+        let slots = self.layout.slots();
+        let key_hash = hash(key);
+        let start = (key_hash % slots as u64) as usize;
+        let mut free = None;
+        for probe in 0..slots {
+            let slot = (start + probe) % slots;
+            let offset = self.slot_offset(slot);
+            match self.read_u32(offset + STATE_OFFSET) {
+                EMPTY => return Err(free.or(Some(slot))),
+                REMOVED => {
+                    free = free.or(Some(slot));
+                }
+                _ => {
+                    if self.read_u64(offset + HASH_OFFSET) == key_hash
+                        && self.read_u32(offset + KEY_LEN_OFFSET) as usize == key.len()
+                        && self.bytes(self.key_offset(slot), key.len()) == key
+                    {
+                        return Ok(slot);
+                    }
+                }
+            }
+        }
+        Err(free)
+    }
+
+    /// Évince l'entrée la moins récemment utilisée.
+    fn evict_lru(&self) {
+        let victim = (0..self.layout.slots())
+            .filter(|&slot| self.read_u32(self.slot_offset(slot) + STATE_OFFSET) == USED)
+            .min_by_key(|&slot| self.read_u64(self.slot_offset(slot) + LAST_USED_OFFSET));
+        if let Some(slot) = victim {
+            self.release(slot);
+        }
+    }
+
+    /// Libère un emplacement. Lorsqu'il précède un emplacement vide, il
+    /// redevient vide lui aussi, avec les emplacements supprimés qui le
+    /// précèdent, afin que les sondages ne s'allongent pas au fil des
+    /// suppressions.
+    fn release(&self, slot: usize) {
+        let slots = self.layout.slots();
+        let next = (slot + 1) % slots;
+        if self.read_u32(self.slot_offset(next) + STATE_OFFSET) == EMPTY {
+            let mut current = slot;
+            loop {
+                self.write_u32(self.slot_offset(current) + STATE_OFFSET, EMPTY);
+                current = (current + slots - 1) % slots;
+                if self.read_u32(self.slot_offset(current) + STATE_OFFSET) != REMOVED {
+                    break;
+                }
+            }
+        } else {
+            self.write_u32(self.slot_offset(slot) + STATE_OFFSET, REMOVED);
+        }
+        self.write_u32(LEN_OFFSET, self.read_u32(LEN_OFFSET) - 1);
+    }
+
+    /// Marque l'emplacement comme utilisé le plus récemment.
+    fn touch(&self, slot: usize) {
+        let tick = self.read_u64(CLOCK_OFFSET) + 1;
+        self.write_u64(CLOCK_OFFSET, tick);
+        self.write_u64(self.slot_offset(slot) + LAST_USED_OFFSET, tick);
+    }
+
+    fn slot_offset(&self, slot: usize) -> usize {
+        HEADER_SIZE + slot * self.layout.slot_size()
+    }
+
+    fn key_offset(&self, slot: usize) -> usize {
+        self.slot_offset(slot) + SLOT_HEADER_SIZE
+    }
+
+    fn value_offset(&self, slot: usize) -> usize {
+        self.key_offset(slot) + self.layout.max_key_len
+    }
+
+    fn lock_word(&self) -> &AtomicU32 {
+        // Champ aligné de l'en-tête, partagé uniquement par accès atomiques
+        unsafe { &*(self.base.add(LOCK_OFFSET) as *const AtomicU32) }
+    }
+
+    fn magic(&self) -> &AtomicU64 {
+        unsafe { &*(self.base.add(MAGIC_OFFSET) as *const AtomicU64) }
+    }
+
+    // Les accès suivants restent dans la région, dont la taille correspond
+    // à la disposition, et n'ont lieu que sous le verrou (ou avant la
+    // publication de la région).
+
+    fn read_u32(&self, offset: usize) -> u32 {
+        unsafe { ptr::read_volatile(self.base.add(offset) as *const u32) }
+    }
+
+    fn write_u32(&self, offset: usize, value: u32) {
+        unsafe { ptr::write_volatile(self.base.add(offset) as *mut u32, value) }
+    }
+
+    fn read_u64(&self, offset: usize) -> u64 {
+        unsafe { ptr::read_volatile(self.base.add(offset) as *const u64) }
+    }
+
+    fn write_u64(&self, offset: usize, value: u64) {
+        unsafe { ptr::write_volatile(self.base.add(offset) as *mut u64, value) }
+    }
+
+    fn bytes(&self, offset: usize, len: usize) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.base.add(offset), len) }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn bytes_mut(&self, offset: usize, len: usize) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.base.add(offset), len) }
+    }
+}
+
+/// Empreinte FNV-1a des clés, identique d'un processus et d'une version du
+/// compilateur à l'autre, contrairement à `DefaultHasher`.
+fn hash(key: &[u8]) -> u64 {
+    key.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,129 @@
+//! Module implémentant la restitution de la mémoire des structures internes.
+//!
+//! Par défaut, le cache réserve dès sa création la place de `capacity`
+//! entrées et garde pour toujours la mémoire atteinte au plus fort de son
+//! remplissage, même après `clear` ou une éviction massive : les insertions
+//! suivantes n'ont jamais à réallouer. Un processus de longue durée dont le
+//! cache gonfle occasionnellement conserve ainsi indéfiniment ce pic.
+//!
+//! Avec `ShrinkPolicy::Auto`, la place n'est plus réservée d'avance, et la
+//! table des entrées, l'ordre d'utilisation et l'échéancier sont réduits à
+//! leur taille utile lorsque, après un vidage, une éviction par lot ou une
+//! purge des entrées expirées, leur taux d'occupation tombe sous le seuil
+//! indiqué. Les petites structures ne sont jamais réduites.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::shrink::ShrinkPolicy;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::with_capacity_and_shrink_policy(100_000, ShrinkPolicy::auto());
+//! for i in 0..50_000 {
+//!     cache.put(i, i);
+//! }
+//! let pic = cache.estimated_memory_usage().total();
+//!
+//! cache.clear();
+//! assert!(cache.estimated_memory_usage().total() < pic / 100);
+//! ```
+
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::messages;
+
+/// Taux d'occupation sous lequel `ShrinkPolicy::auto` réduit les structures.
+pub const DEFAULT_SHRINK_THRESHOLD: f64 = 0.25;
+
+/// Nombre d'emplacements en deçà duquel une structure n'est jamais réduite.
+const MIN_SHRINK_CAPACITY: usize = 64;
+
+/// Politique de restitution de la mémoire des structures internes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ShrinkPolicy {
+    /// La place de `capacity` entrées est réservée et jamais rendue
+    /// (comportement par défaut)
+    #[default]
+    Never,
+    /// Les structures sont réduites lorsque leur taux d'occupation tombe
+    /// sous `threshold` (entre 0 exclu et 1 inclus)
+    Auto {
+        /// Taux d'occupation déclenchant la réduction
+        threshold: f64,
+    },
+}
+
+impl ShrinkPolicy {
+    /// Réduction automatique au seuil par défaut.
+    pub fn auto() -> Self {
+        ShrinkPolicy::Auto { threshold: DEFAULT_SHRINK_THRESHOLD }
+    }
+
+    /// Vérifie que le seuil est compris entre 0 exclu et 1 inclus.
+    pub(crate) fn check(&self) -> Result<(), CacheError> {
+        match *self {
+            ShrinkPolicy::Auto { threshold } if !(threshold > 0.0 && threshold <= 1.0) => {
+                Err(CacheError::CapacityError(messages::INVALID_SHRINK_THRESHOLD.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Indique si une structure de `len` éléments pour `allocated` emplacements
+/// doit être réduite.
+fn should_shrink(len: usize, allocated: usize, threshold: f64) -> bool {
+    allocated > MIN_SHRINK_CAPACITY && (len as f64) < allocated as f64 * threshold
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Crée un cache de la capacité donnée appliquant la politique de
+    /// restitution de la mémoire indiquée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la capacité est 0 ou si le seuil de la politique n'est pas
+    /// compris entre 0 exclu et 1 inclus.
+    pub fn with_capacity_and_shrink_policy(capacity: usize, policy: ShrinkPolicy) -> Self {
+        Cache::builder()
+            .capacity(capacity)
+            .shrink_policy(policy)
+            .build()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Retourne la politique de restitution de la mémoire.
+    pub fn shrink_policy(&self) -> ShrinkPolicy {
+        self.shrink_policy
+    }
+
+    /// Applique la politique : abandonne la place réservée d'avance.
+    pub(crate) fn use_shrink_policy(&mut self, policy: ShrinkPolicy) {
+        self.shrink_policy = policy;
+        self.maybe_shrink();
+    }
+
+    /// Réduit les structures internes dont le taux d'occupation est passé
+    /// sous le seuil de la politique.
+    pub(crate) fn maybe_shrink(&mut self) {
+        let ShrinkPolicy::Auto { threshold } = self.shrink_policy else {
+            return;
+        };
+        if should_shrink(self.elements.len(), self.elements.capacity(), threshold) {
+            self.elements.shrink_to_fit();
+        }
+        let (len, allocated) = self.usage_order.occupancy();
+        if should_shrink(len, allocated, threshold) {
+            self.usage_order.shrink_to_fit();
+        }
+        let (len, allocated) = self.expirations.occupancy();
+        if should_shrink(len, allocated, threshold) {
+            self.expirations.shrink_to_fit();
+        }
+    }
+}
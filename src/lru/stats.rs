@@ -0,0 +1,493 @@
+//! Module implémentant les statistiques internes du cache.
+//!
+//! Une fois activées sur le constructeur (`record_stats`), les statistiques
+//! comptent les lectures et les mutations depuis la création du cache.
+//! Une fenêtre glissante (`stats_window`) suit en outre le taux de succès
+//! sur les dernières lectures ou les dernières secondes : la moyenne sur
+//! toute la durée de vie masque l'effondrement du taux de succès qui suit un
+//! changement de trafic.
+//!
+//! Deux histogrammes à compartiments fixes complètent les compteurs : l'âge
+//! des entrées au moment de leur éviction, et le temps écoulé depuis la
+//! lecture précédente lorsqu'une entrée est lue. Des entrées évincées jeunes
+//! signalent une capacité trop faible ; des entrées lues longtemps après leur
+//! dernier accès aident à dimensionner leur durée de vie.
+//!
+//! Enfin, `time_operations` mesure la durée de `get`, `put` et `persist`
+//! dans des histogrammes de latence, pour qu'une régression de performance
+//! apparaisse dans la télémétrie de production. Ces durées sont mesurées
+//! avec l'horloge système, quelle que soit l'horloge du cache.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::stats::StatsWindow;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::builder()
+//!     .capacity(10)
+//!     .stats_window(StatsWindow::Ops(4))
+//!     .build()
+//!     .unwrap();
+//! cache.put("a", 1);
+//! for _ in 0..6 {
+//!     cache.get(&"a");
+//! }
+//! for _ in 0..2 {
+//!     cache.get(&"b");
+//! }
+//!
+//! let stats = cache.stats();
+//! assert_eq!(stats.hit_ratio(), 0.75);
+//! assert_eq!(stats.window.unwrap().hit_ratio(), 0.5);
+//! ```
+//!
+//! Avec la fonctionnalité `serde`, `CacheStats` est sérialisable et
+//! `stats_json` produit directement le document JSON, à intégrer par exemple
+//! dans la réponse d'un point de contrôle de santé. Les durées y sont écrites
+//! sous forme lisible (`"1ms250us"`, voir `duration::HumanDuration`).
+
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+#[cfg(feature = "serde")]
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::events::{Mutation, RemovalCause};
+
+/// Nombre de tranches d'une fenêtre temporelle.
+const TIME_SLICES: u32 = 16;
+
+/// Bornes supérieures (incluses) des compartiments des histogrammes ; le
+/// dernier compartiment reçoit les durées au-delà de la dernière borne.
+pub const HISTOGRAM_BOUNDS: [Duration; 8] = [
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+    Duration::from_secs(10),
+    Duration::from_secs(60),
+    Duration::from_secs(600),
+    Duration::from_secs(3600),
+];
+
+/// Étendue de la fenêtre glissante des statistiques.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsWindow {
+    /// Les `n` dernières lectures
+    Ops(usize),
+    /// Les lectures de la durée écoulée, mesurée par tranches d'un seizième
+    Duration(Duration),
+}
+
+/// Lectures observées sur la fenêtre glissante.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WindowStats {
+    /// Lectures ayant trouvé une valeur
+    pub hits: u64,
+    /// Lectures infructueuses
+    pub misses: u64,
+}
+
+impl WindowStats {
+    /// Retourne la proportion de lectures ayant trouvé une valeur (entre 0 et 1).
+    pub fn hit_ratio(&self) -> f64 {
+        ratio(self.hits, self.misses)
+    }
+}
+
+/// Histogramme de durées à compartiments fixes (`HISTOGRAM_BOUNDS`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DurationHistogram {
+    /// Nombre de durées de chaque compartiment
+    pub counts: [u64; HISTOGRAM_BOUNDS.len() + 1],
+}
+
+impl DurationHistogram {
+    /// Ajoute une durée au compartiment correspondant.
+    pub fn record(&mut self, duration: Duration) {
+        let bucket = HISTOGRAM_BOUNDS.partition_point(|&bound| bound < duration);
+        self.counts[bucket] += 1;
+    }
+
+    /// Retourne le nombre total de durées enregistrées.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Retourne la borne supérieure de chaque compartiment (`None` pour le
+    /// dernier, non borné) et le nombre de durées qu'il contient.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        HISTOGRAM_BOUNDS
+            .iter()
+            .copied()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .zip(self.counts.iter().copied())
+    }
+
+    /// Retourne la proportion des durées inférieures ou égales à la borne
+    /// donnée, arrondie aux compartiments (entre 0 et 1).
+    pub fn fraction_within(&self, bound: Duration) -> f64 {
+        let buckets = HISTOGRAM_BOUNDS.partition_point(|&b| b <= bound);
+        let within: u64 = self.counts[..buckets].iter().sum();
+        ratio(within, self.total() - within)
+    }
+}
+
+/// Bornes supérieures (incluses) des compartiments des histogrammes de
+/// latence ; le dernier compartiment reçoit les durées au-delà.
+pub const LATENCY_BOUNDS: [Duration; 7] = [
+    Duration::from_micros(1),
+    Duration::from_micros(10),
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+];
+
+/// Histogramme des durées d'une opération (`LATENCY_BOUNDS`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LatencyHistogram {
+    /// Nombre de mesures de chaque compartiment
+    pub counts: [u64; LATENCY_BOUNDS.len() + 1],
+    /// Durée cumulée des mesures
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::lru::duration::serialize"))]
+    pub total: Duration,
+    /// Durée la plus longue mesurée
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::lru::duration::serialize"))]
+    pub max: Duration,
+}
+
+impl LatencyHistogram {
+    /// Ajoute une mesure.
+    pub fn record(&mut self, duration: Duration) {
+        let bucket = LATENCY_BOUNDS.partition_point(|&bound| bound < duration);
+        self.counts[bucket] += 1;
+        self.total += duration;
+        self.max = self.max.max(duration);
+    }
+
+    /// Retourne le nombre de mesures.
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Retourne la durée moyenne, ou zéro sans mesure.
+    pub fn mean(&self) -> Duration {
+        match self.count() {
+            0 => Duration::ZERO,
+            count => {
+                // Division en nanosecondes : le nombre de mesures peut
+                // dépasser `u32::MAX`
+                let nanos = self.total.as_nanos() / u128::from(count);
+                Duration::new((nanos / 1_000_000_000) as u64, (nanos % 1_000_000_000) as u32)
+            }
+        }
+    }
+
+    /// Retourne la borne supérieure de chaque compartiment (`None` pour le
+    /// dernier, non borné) et le nombre de mesures qu'il contient.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        LATENCY_BOUNDS
+            .iter()
+            .copied()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .zip(self.counts.iter().copied())
+    }
+}
+
+/// Latences des opérations du cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LatencyStats {
+    /// Lectures (`get`)
+    pub get: LatencyHistogram,
+    /// Insertions (`put`, `try_put`)
+    pub put: LatencyHistogram,
+    /// Sauvegardes (`persist`)
+    pub persist: LatencyHistogram,
+}
+
+/// Opération chronométrée.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TimedOp {
+    Get,
+    Put,
+    Persist,
+}
+
+/// Statistiques du cache depuis sa création.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CacheStats {
+    /// Lectures ayant trouvé une valeur
+    pub hits: u64,
+    /// Lectures infructueuses
+    pub misses: u64,
+    /// Insertions de nouvelles clés
+    pub inserts: u64,
+    /// Mises à jour de clés existantes
+    pub updates: u64,
+    /// Suppressions explicites
+    pub removals: u64,
+    /// Évictions pour respecter la capacité ou le budget de poids
+    pub evictions: u64,
+    /// Entrées supprimées à l'expiration de leur durée de vie
+    pub expirations: u64,
+    /// Lectures de la fenêtre glissante, si elle est configurée
+    pub window: Option<WindowStats>,
+    /// Âge des entrées (depuis leur insertion) au moment de leur éviction
+    pub eviction_age: DurationHistogram,
+    /// Temps écoulé depuis la lecture précédente (ou l'insertion) lors de
+    /// chaque lecture réussie
+    pub idle_at_access: DurationHistogram,
+    /// Latences des opérations, si leur mesure est activée
+    pub latency: Option<LatencyStats>,
+}
+
+impl CacheStats {
+    /// Retourne la proportion de lectures ayant trouvé une valeur (entre 0 et 1).
+    pub fn hit_ratio(&self) -> f64 {
+        ratio(self.hits, self.misses)
+    }
+}
+
+fn ratio(hits: u64, misses: u64) -> f64 {
+    let total = hits + misses;
+    if total == 0 {
+        0.0
+    } else {
+        hits as f64 / total as f64
+    }
+}
+
+/// Fenêtre glissante des lectures.
+#[derive(Debug, Clone)]
+enum Window {
+    Ops {
+        size: usize,
+        ops: VecDeque<bool>,
+        hits: u64,
+    },
+    Time {
+        span: Duration,
+        slice: Duration,
+        /// Début de chaque tranche, succès et échecs
+        slices: VecDeque<(Instant, u64, u64)>,
+    },
+}
+
+impl Window {
+    fn new(window: StatsWindow) -> Self {
+        match window {
+            StatsWindow::Ops(size) => Window::Ops {
+                size: size.max(1),
+                ops: VecDeque::new(),
+                hits: 0,
+            },
+            StatsWindow::Duration(span) => Window::Time {
+                span,
+                slice: (span / TIME_SLICES).max(Duration::from_millis(1)),
+                slices: VecDeque::new(),
+            },
+        }
+    }
+
+    fn record(&mut self, hit: bool, now: Instant) {
+        match self {
+            Window::Ops { size, ops, hits } => {
+                if ops.len() == *size && ops.pop_front() == Some(true) {
+                    *hits -= 1;
+                }
+                ops.push_back(hit);
+                *hits += u64::from(hit);
+            }
+            Window::Time { span, slice, slices } => {
+                while slices.front().is_some_and(|&(start, _, _)| start + *span <= now) {
+                    slices.pop_front();
+                }
+                match slices.back_mut() {
+                    Some((start, hits, misses)) if *start + *slice > now => {
+                        *hits += u64::from(hit);
+                        *misses += u64::from(!hit);
+                    }
+                    _ => slices.push_back((now, u64::from(hit), u64::from(!hit))),
+                }
+            }
+        }
+    }
+
+    fn snapshot(&self, now: Instant) -> WindowStats {
+        match self {
+            Window::Ops { ops, hits, .. } => WindowStats {
+                hits: *hits,
+                misses: ops.len() as u64 - hits,
+            },
+            Window::Time { span, slices, .. } => slices
+                .iter()
+                .filter(|&&(start, _, _)| start + *span > now)
+                .fold(WindowStats::default(), |total, &(_, hits, misses)| WindowStats {
+                    hits: total.hits + hits,
+                    misses: total.misses + misses,
+                }),
+        }
+    }
+}
+
+/// Compteurs alimentés par les lectures et les mutations du cache.
+///
+/// Les latences sont protégées par un verrou afin que les opérations en
+/// lecture seule, comme `persist`, puissent aussi être mesurées.
+#[derive(Debug)]
+pub(crate) struct StatsRecorder {
+    totals: CacheStats,
+    window: Option<Window>,
+    latency: Option<Mutex<LatencyStats>>,
+}
+
+impl StatsRecorder {
+    pub(crate) fn new(window: Option<StatsWindow>) -> Self {
+        StatsRecorder {
+            totals: CacheStats::default(),
+            window: window.map(Window::new),
+            latency: None,
+        }
+    }
+
+    /// Active la mesure des latences.
+    pub(crate) fn with_latency(mut self) -> Self {
+        self.latency = Some(Mutex::new(LatencyStats::default()));
+        self
+    }
+
+    /// Enregistre la durée d'une opération.
+    fn record_latency(&self, op: TimedOp, duration: Duration) {
+        let Some(latency) = self.latency.as_ref() else {
+            return;
+        };
+        let mut latency = latency.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match op {
+            TimedOp::Get => latency.get.record(duration),
+            TimedOp::Put => latency.put.record(duration),
+            TimedOp::Persist => latency.persist.record(duration),
+        }
+    }
+
+    /// Enregistre une lecture.
+    pub(crate) fn record_read(&mut self, hit: bool, now: Instant) {
+        if hit {
+            self.totals.hits += 1;
+        } else {
+            self.totals.misses += 1;
+        }
+        if let Some(window) = self.window.as_mut() {
+            window.record(hit, now);
+        }
+    }
+
+    /// Enregistre l'âge d'une entrée évincée.
+    pub(crate) fn record_eviction_age(&mut self, age: Duration) {
+        self.totals.eviction_age.record(age);
+    }
+
+    /// Enregistre le temps écoulé depuis le dernier accès à une entrée lue.
+    pub(crate) fn record_idle_time(&mut self, idle: Duration) {
+        self.totals.idle_at_access.record(idle);
+    }
+
+    /// Enregistre une mutation.
+    pub(crate) fn record<K, V>(&mut self, mutation: &Mutation<'_, K, V>) {
+        match mutation {
+            Mutation::Insert { .. } => self.totals.inserts += 1,
+            Mutation::Update { .. } => self.totals.updates += 1,
+            Mutation::Remove { cause, .. } => match cause {
+                RemovalCause::Explicit => self.totals.removals += 1,
+                RemovalCause::Capacity | RemovalCause::Weight => self.totals.evictions += 1,
+                RemovalCause::Expired => self.totals.expirations += 1,
+                RemovalCause::Rejected | RemovalCause::Cleared => {}
+            },
+        }
+    }
+
+    fn snapshot(&self, now: Instant) -> CacheStats {
+        CacheStats {
+            window: self.window.as_ref().map(|window| window.snapshot(now)),
+            latency: self
+                .latency
+                .as_ref()
+                .map(|latency| *latency.lock().unwrap_or_else(|poisoned| poisoned.into_inner())),
+            ..self.totals
+        }
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne les statistiques du cache. Elles restent à zéro si elles
+    /// n'ont pas été activées sur le constructeur.
+    pub fn stats(&self) -> CacheStats {
+        self.observers
+            .stats
+            .as_ref()
+            .map(|stats| stats.snapshot(self.clock.now()))
+            .unwrap_or_default()
+    }
+
+    /// Retourne les statistiques du cache sous forme de document JSON.
+    ///
+    /// Disponible avec la fonctionnalité `serde`.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Serialization` si la sérialisation échoue.
+    #[cfg(feature = "serde")]
+    pub fn stats_json(&self) -> Result<String, CacheError> {
+        serde_json::to_string(&self.stats()).map_err(|err| CacheError::Serialization(err.to_string()))
+    }
+
+    /// Enregistre une lecture dans les statistiques et le conseiller de
+    /// dimensionnement, s'ils sont activés.
+    pub(crate) fn record_read(&mut self, hit: bool) {
+        if let Some(stats) = self.observers.stats.as_mut() {
+            stats.record_read(hit, self.clock.now());
+        }
+        if let Some(advisor) = self.observers.advisor.as_mut() {
+            advisor.record_read(hit);
+        }
+    }
+
+    /// Démarre le chronométrage d'une opération, si la mesure des latences
+    /// est activée.
+    pub(crate) fn start_timer(&self) -> Option<Instant> {
+        self.observers
+            .stats
+            .as_ref()
+            .filter(|stats| stats.latency.is_some())
+            .map(|_| Instant::now())
+    }
+
+    /// Enregistre la durée d'une opération chronométrée par `start_timer`.
+    pub(crate) fn record_latency(&self, op: TimedOp, start: Option<Instant>) {
+        if let (Some(start), Some(stats)) = (start, self.observers.stats.as_ref()) {
+            stats.record_latency(op, start.elapsed());
+        }
+    }
+
+    /// Enregistre l'âge d'une entrée évincée, si les statistiques sont
+    /// activées.
+    pub(crate) fn record_eviction_age(&mut self, inserted: Instant) {
+        if let Some(stats) = self.observers.stats.as_mut() {
+            stats.record_eviction_age(self.clock.now().saturating_duration_since(inserted));
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Module implémentant un cache spécialisé pour les clés textuelles.
+//!
+//! Avec `Cache<String, V>`, chaque insertion copie la clé dans l'ordre
+//! d'utilisation et chaque lecture oblige l'appelant à posséder une
+//! `String`. `StringCache` stocke ses clés sous forme de `Arc<str>` : la
+//! chaîne est allouée une seule fois, à l'insertion d'une nouvelle clé, puis
+//! partagée entre la table et l'ordre d'utilisation. Toutes les opérations
+//! prennent un `&str`, si bien que ni une lecture, ni la mise à jour d'une
+//! clé existante n'allouent.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::string::StringCache;
+//!
+//! let mut cache = StringCache::new(2);
+//! cache.put("fr", "Bonjour");
+//! cache.put("en", "Hello");
+//!
+//! let langue = String::from("fr");
+//! assert_eq!(cache.get(&langue), Some(&"Bonjour"));
+//! cache.put("de", "Hallo"); // évince "en"
+//! assert!(!cache.contains("en"));
+//! ```
+
+use std::sync::Arc;
+use crate::error::CacheError;
+use crate::lru::{Cache, CacheBuilder};
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Cache LRU à clés textuelles, consulté par `&str`.
+#[derive(Debug)]
+pub struct StringCache<V> {
+    cache: Cache<Arc<str>, V>,
+}
+
+impl<V> StringCache<V> {
+    /// Crée un cache de la capacité donnée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la capacité vaut 0, comme `Cache::new`.
+    pub fn new(capacity: usize) -> Self {
+        StringCache { cache: Cache::new(capacity) }
+    }
+
+    /// Construit le cache configuré par le constructeur donné.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `CacheBuilder::build`.
+    pub fn from_builder(builder: CacheBuilder<Arc<str>, V>) -> Result<Self, CacheError> {
+        Ok(StringCache { cache: builder.build()? })
+    }
+
+    /// Insère ou met à jour une valeur.
+    pub fn put(&mut self, key: &str, value: V) {
+        self.cache.put_str(key, value);
+    }
+
+    /// Récupère la valeur associée à la clé et la marque comme la plus
+    /// récemment utilisée.
+    pub fn get(&mut self, key: &str) -> Option<&V> {
+        self.cache.get_borrowed(key)
+    }
+
+    /// Retourne la valeur associée à la clé sans modifier l'ordre
+    /// d'utilisation.
+    pub fn peek(&self, key: &str) -> Option<&V> {
+        self.cache.peek_str(key)
+    }
+
+    /// Vérifie si la clé est présente et valide.
+    pub fn contains(&self, key: &str) -> bool {
+        self.peek(key).is_some()
+    }
+
+    /// Supprime l'entrée et retourne sa valeur.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        self.cache.remove_str(key)
+    }
+
+    /// Retourne le nombre d'entrées du cache.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Vérifie si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Vide le cache.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Retourne un itérateur sur les paires clé-valeur, du moins récemment
+    /// utilisé au plus récemment utilisé.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.cache.iter().map(|(key, value)| (&**key, value))
+    }
+
+    /// Retourne une référence au cache encapsulé.
+    pub fn inner(&self) -> &Cache<Arc<str>, V> {
+        &self.cache
+    }
+}
+
+impl<V> CacheRead<Arc<str>, V> for StringCache<V> {
+    fn peek(&self, key: &Arc<str>) -> Option<&V> {
+        self.cache.peek(key)
+    }
+
+    fn contains(&self, key: &Arc<str>) -> bool {
+        self.cache.contains(key)
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        CacheRead::capacity(&self.cache)
+    }
+}
+
+impl<V> CacheTrait<Arc<str>, V> for StringCache<V> {
+    fn get(&mut self, key: &Arc<str>) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    fn put(&mut self, key: Arc<str>, value: V) {
+        self.cache.put(key, value);
+    }
+
+    fn remove(&mut self, key: &Arc<str>) -> Option<V> {
+        self.cache.remove(key)
+    }
+
+    fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
@@ -0,0 +1,90 @@
+//! Module implémentant l'échange des valeurs de deux clés.
+//!
+//! Un cache de rendu à double tampon bascule régulièrement ses entrées
+//! « courante » et « suivante ». `swap` échange leurs valeurs en une seule
+//! opération, sans cloner les valeurs ni les retirer du cache. Chaque clé
+//! conserve sa place dans l'ordre d'utilisation, sa durée de vie et son
+//! compteur d'accès ; avec `swap_with_recency`, les places dans l'ordre
+//! d'utilisation sont échangées elles aussi et suivent donc les valeurs.
+//!
+//! L'échange compte comme une mise à jour des deux clés : leurs versions
+//! avancent et les observateurs reçoivent deux notifications de mise à jour.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = Cache::new(10);
+//! cache.put("courante", vec![1, 2, 3]);
+//! cache.put("suivante", vec![4, 5, 6]);
+//!
+//! assert!(cache.swap(&"courante", &"suivante"));
+//! assert_eq!(cache.peek(&"courante"), Some(&vec![4, 5, 6]));
+//! assert_eq!(cache.peek(&"suivante"), Some(&vec![1, 2, 3]));
+//!
+//! // Une clé absente : rien n'est échangé
+//! assert!(!cache.swap(&"courante", &"absente"));
+//! ```
+
+use std::hash::Hash;
+use crate::lru::Cache;
+use crate::lru::events::Mutation;
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Échange les valeurs des deux clés, sans modifier l'ordre
+    /// d'utilisation. Retourne `false`, sans rien modifier, si l'une des clés
+    /// est absente ou expirée.
+    pub fn swap(&mut self, a: &K, b: &K) -> bool {
+        self.swap_entries(a, b, false)
+    }
+
+    /// Échange les valeurs des deux clés ainsi que leurs places dans l'ordre
+    /// d'utilisation : chaque valeur garde sa fraîcheur. Retourne `false`,
+    /// sans rien modifier, si l'une des clés est absente ou expirée.
+    ///
+    /// En éviction échantillonnée, où aucun ordre n'est tenu, seules les
+    /// valeurs sont échangées.
+    pub fn swap_with_recency(&mut self, a: &K, b: &K) -> bool {
+        self.swap_entries(a, b, true)
+    }
+
+    fn swap_entries(&mut self, a: &K, b: &K, recency: bool) -> bool {
+        if self.is_expired(a) || self.is_expired(b) {
+            return false;
+        }
+        if a == b {
+            return self.elements.contains_key(a);
+        }
+        let [Some(first), Some(second)] = self.elements.get_many_mut([a, b]) else {
+            return false;
+        };
+        std::mem::swap(&mut first.value, &mut second.value);
+        std::mem::swap(&mut first.weight, &mut second.weight);
+        let now = self.track_access.then(|| self.clock.now());
+        for entry in [first, second] {
+            self.next_version += 1;
+            entry.version = self.next_version;
+            entry.warmed = false;
+            if let Some(now) = now {
+                entry.accessed = now;
+            }
+        }
+
+        if recency {
+            self.usage_order.swap(a, b);
+        }
+        if self.observers.is_active() {
+            for key in [a, b] {
+                if let Some(entry) = self.elements.get(key) {
+                    self.observers.notify(&Mutation::Update { key, value: &entry.value });
+                }
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,283 @@
+//! Module fournissant une version thread-safe du cache LRU.
+//!
+//! `SyncCache` encapsule un `Cache` derrière un `Arc<Mutex<_>>` et peut être
+//! cloné à moindre coût pour être partagé entre plusieurs threads.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::sync::SyncCache;
+//! use std::thread;
+//!
+//! let cache = SyncCache::new(10);
+//! let partage = cache.clone();
+//!
+//! thread::spawn(move || {
+//!     partage.put("clé", 1).unwrap();
+//! }).join().unwrap();
+//!
+//! assert_eq!(cache.get(&"clé").unwrap(), Some(1));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::PoisonError;
+use std::sync::mpsc::Receiver;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::coalesce::{Flights, ScheduleHook};
+use crate::lru::events::CacheEvent;
+use crate::lru::primitives::{self, Arc, Mutex, MutexGuard};
+use crate::lru::timeout::{InFlight, DEFAULT_MAX_LOADS};
+use crate::lru::traits::CacheTrait;
+use crate::lru::transaction::Transaction;
+
+/// Comportement à adopter lorsque le verrou du cache a été empoisonné,
+/// c'est-à-dire lorsqu'un thread a paniqué en le détenant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PoisonPolicy {
+    /// Retourne `CacheError::Poisoned` à chaque opération (comportement par défaut)
+    #[default]
+    Propagate,
+    /// Vide le cache puis reprend normalement : son contenu est recalculable
+    Clear,
+    /// Ignore l'empoisonnement et continue avec le contenu existant
+    Ignore,
+}
+
+/// Cache LRU partageable entre threads.
+///
+/// Les valeurs sont retournées par clone puisqu'aucune référence ne peut
+/// survivre à la libération du verrou.
+#[derive(Debug)]
+pub struct SyncCache<K, V>
+where
+    K: Hash + Eq,
+{
+    pub(crate) inner: Arc<Mutex<Cache<K, V>>>,
+    poison_policy: PoisonPolicy,
+    pub(crate) flights: Flights<K, V>,
+    pub(crate) schedule_hook: Option<ScheduleHook>,
+    /// Chargements de `get_or_load_with_deadline` en cours, communs aux clones
+    pub(crate) deadline_loads: std::sync::Arc<InFlight<K, V>>,
+    pub(crate) max_deadline_loads: usize,
+}
+
+impl<K, V> Clone for SyncCache<K, V>
+where
+    K: Hash + Eq,
+{
+    fn clone(&self) -> Self {
+        SyncCache {
+            inner: Arc::clone(&self.inner),
+            poison_policy: self.poison_policy,
+            flights: Arc::clone(&self.flights),
+            schedule_hook: self.schedule_hook.clone(),
+            deadline_loads: std::sync::Arc::clone(&self.deadline_loads),
+            max_deadline_loads: self.max_deadline_loads,
+        }
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Crée un nouveau cache partagé avec la capacité spécifiée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la capacité est 0.
+    pub fn new(capacity: usize) -> Self {
+        Self::from_cache(Cache::new(capacity))
+    }
+
+    /// Crée un nouveau cache partagé sans paniquer.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la capacité est 0.
+    pub fn try_new(capacity: usize) -> Result<Self, CacheError> {
+        Ok(Self::from_cache(Cache::try_new(capacity)?))
+    }
+
+    /// Encapsule un cache existant pour le partager entre threads.
+    pub fn from_cache(cache: Cache<K, V>) -> Self {
+        SyncCache {
+            inner: Arc::new(Mutex::new(cache)),
+            poison_policy: PoisonPolicy::default(),
+            flights: Arc::new(Mutex::new(HashMap::new())),
+            schedule_hook: None,
+            deadline_loads: std::sync::Arc::new(InFlight::new()),
+            max_deadline_loads: DEFAULT_MAX_LOADS,
+        }
+    }
+
+    /// Définit le comportement en cas d'empoisonnement du verrou.
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use lru_cache::lru::sync::{PoisonPolicy, SyncCache};
+    ///
+    /// let cache: SyncCache<String, i32> = SyncCache::new(10)
+    ///     .with_poison_policy(PoisonPolicy::Clear);
+    /// assert_eq!(cache.poison_policy(), PoisonPolicy::Clear);
+    /// ```
+    pub fn with_poison_policy(mut self, policy: PoisonPolicy) -> Self {
+        self.poison_policy = policy;
+        self
+    }
+
+    /// Retourne la politique d'empoisonnement configurée.
+    pub fn poison_policy(&self) -> PoisonPolicy {
+        self.poison_policy
+    }
+
+    /// Acquiert le verrou en appliquant la politique d'empoisonnement.
+    fn lock(&self) -> Result<MutexGuard<'_, Cache<K, V>>, CacheError> {
+        self.inner.lock().or_else(|poisoned| self.recover(poisoned))
+    }
+
+    /// Applique la politique d'empoisonnement à un verrou empoisonné.
+    pub(crate) fn recover<'a>(
+        &'a self,
+        poisoned: PoisonError<MutexGuard<'a, Cache<K, V>>>,
+    ) -> Result<MutexGuard<'a, Cache<K, V>>, CacheError> {
+        match self.poison_policy {
+            PoisonPolicy::Propagate => Err(CacheError::Poisoned),
+            PoisonPolicy::Clear => {
+                primitives::clear_poison(&self.inner);
+                let mut guard = poisoned.into_inner();
+                guard.clear();
+                Ok(guard)
+            }
+            PoisonPolicy::Ignore => {
+                primitives::clear_poison(&self.inner);
+                Ok(poisoned.into_inner())
+            }
+        }
+    }
+
+    /// Exécute une closure avec un accès exclusif au cache sous-jacent.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si le verrou est empoisonné et que la
+    /// politique est `PoisonPolicy::Propagate`.
+    pub fn with_lock<R, F>(&self, f: F) -> Result<R, CacheError>
+    where
+        F: FnOnce(&mut Cache<K, V>) -> R,
+    {
+        let mut guard = self.lock()?;
+        Ok(f(&mut guard))
+    }
+
+    /// Parcourt les entrées avec `Cache::peek_iter` en détenant le verrou :
+    /// le parcours ne modifie ni l'ordre d'utilisation ni les statistiques.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` si le verrou est empoisonné et que la
+    /// politique est `PoisonPolicy::Propagate`.
+    ///
+    /// # Exemples
+    ///
+    /// ```
+    /// use lru_cache::lru::sync::SyncCache;
+    ///
+    /// let cache = SyncCache::new(10);
+    /// cache.put("a", 1).unwrap();
+    /// cache.put("b", 2).unwrap();
+    /// let cles = cache.peek_iter(|entries| entries.map(|(k, _)| *k).collect::<Vec<_>>()).unwrap();
+    /// assert_eq!(cles, ["a", "b"]);
+    /// ```
+    pub fn peek_iter<R, F>(&self, f: F) -> Result<R, CacheError>
+    where
+        F: FnOnce(&mut dyn Iterator<Item = (&K, &V)>) -> R,
+    {
+        let guard = self.lock()?;
+        let mut entries = guard.peek_iter();
+        let result = f(&mut entries);
+        Ok(result)
+    }
+
+    /// Exécute une transaction en détenant le verrou jusqu'à sa validation.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` selon la politique d'empoisonnement,
+    /// ou l'erreur de `Cache::transaction`.
+    pub fn transaction<R, F>(&self, f: F) -> Result<R, CacheError>
+    where
+        F: FnOnce(&mut Transaction<'_, K, V>) -> Result<R, CacheError>,
+    {
+        self.lock()?.transaction(f)
+    }
+
+    /// Ajoute ou met à jour une paire clé-valeur dans le cache.
+    pub fn put(&self, key: K, value: V) -> Result<(), CacheError> {
+        self.lock()?.put(key, value);
+        Ok(())
+    }
+
+    /// Échange les valeurs des deux clés sous un même verrou (voir
+    /// `Cache::swap`).
+    pub fn swap(&self, a: &K, b: &K) -> Result<bool, CacheError> {
+        Ok(self.lock()?.swap(a, b))
+    }
+
+    /// Retourne le nombre d'éléments actuellement dans le cache.
+    pub fn len(&self) -> Result<usize, CacheError> {
+        Ok(self.lock()?.len())
+    }
+
+    /// Vérifie si le cache est vide.
+    pub fn is_empty(&self) -> Result<bool, CacheError> {
+        Ok(self.lock()?.is_empty())
+    }
+
+    /// Vide le cache de tous ses éléments.
+    pub fn clear(&self) -> Result<(), CacheError> {
+        self.lock()?.clear();
+        Ok(())
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    /// S'abonne aux mutations du cache partagé.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` selon la politique d'empoisonnement.
+    pub fn subscribe(&self) -> Result<Receiver<CacheEvent<K, V>>, CacheError> {
+        Ok(self.lock()?.subscribe())
+    }
+
+    /// S'abonne aux mutations du cache partagé avec un tampon de `bound`
+    /// événements (voir `Cache::subscribe_bounded`).
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` selon la politique d'empoisonnement.
+    pub fn subscribe_bounded(&self, bound: usize) -> Result<Receiver<CacheEvent<K, V>>, CacheError> {
+        Ok(self.lock()?.subscribe_bounded(bound))
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Récupère une copie de la valeur associée à la clé.
+    ///
+    /// Met également à jour l'ordre d'utilisation du cache.
+    pub fn get(&self, key: &K) -> Result<Option<V>, CacheError> {
+        Ok(self.lock()?.get(key).cloned())
+    }
+}
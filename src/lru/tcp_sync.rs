@@ -0,0 +1,286 @@
+//! Module implémentant la synchronisation de caches sur TCP.
+//!
+//! Disponible avec la fonctionnalité `tcp-sync`. Un cache principal diffuse
+//! son journal de réplication (voir `replication`) aux répliques connectées.
+//! Chaque réplique reçoit d'abord un instantané complet du cache, puis les
+//! opérations suivantes ; une réplique trop en retard sur le journal reçoit un
+//! nouvel instantané. Le protocole est textuel, une ligne par message, et
+//! reprend le format des fichiers de persistance : les clés et les valeurs
+//! ne doivent donc contenir ni tabulation ni saut de ligne.
+//!
+//! ```text
+//! SNAPSHOT\t<seq>, puis PUT\t<clé>\t<valeur> pour chaque entrée, puis END
+//! OP\t<seq>\tPUT\t<clé>\t<valeur>
+//! OP\t<seq>\tDEL\t<clé>
+//! ```
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::sync::SyncCache;
+//! use std::time::Duration;
+//!
+//! let principal: SyncCache<String, i32> = SyncCache::new(100);
+//! principal.with_lock(|cache| cache.enable_replication_log(1000)).unwrap();
+//! principal.put("a".to_string(), 1).unwrap();
+//!
+//! let serveur = principal.serve_replication("127.0.0.1:0", Duration::from_millis(10)).unwrap();
+//! let replique: SyncCache<String, i32> = SyncCache::new(100);
+//! let _abonnement = replique.replicate_from(serveur.local_addr()).unwrap();
+//!
+//! while replique.get(&"a".to_string()).unwrap().is_none() {
+//!     std::thread::sleep(Duration::from_millis(5));
+//! }
+//! ```
+
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use crate::error::CacheError;
+use crate::lru::replication::{ReplicatedOp, SequencedOp};
+use crate::lru::sync::SyncCache;
+use crate::lru::traits::CacheTrait;
+use crate::messages;
+
+/// Délai après lequel une lecture bloquante vérifie la demande d'arrêt.
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Poignée d'un thread de synchronisation, arrêté à sa libération.
+#[derive(Debug)]
+pub struct SyncHandle {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SyncHandle {
+    fn spawn<F>(addr: SocketAddr, f: F) -> Self
+    where
+        F: FnOnce(Arc<AtomicBool>) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        SyncHandle {
+            addr,
+            stop,
+            thread: Some(thread::spawn(move || f(flag))),
+        }
+    }
+
+    /// Retourne l'adresse du serveur : adresse d'écoute côté principal,
+    /// adresse du principal côté réplique.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Arrête le thread et attend sa fin.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for SyncHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone + Display + FromStr + Send + 'static,
+    V: Clone + Display + FromStr + Send + 'static,
+{
+    /// Diffuse les mutations de ce cache aux répliques qui se connectent à
+    /// l'adresse donnée, en vérifiant le journal à l'intervalle indiqué.
+    ///
+    /// Le journal de réplication doit être activé
+    /// (`Cache::enable_replication_log`) ; à défaut, un instantané complet
+    /// est renvoyé à chaque intervalle.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si l'adresse ne peut pas être écoutée.
+    pub fn serve_replication<A: ToSocketAddrs>(&self, addr: A, interval: Duration) -> Result<SyncHandle, CacheError> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let cache = self.clone();
+
+        Ok(SyncHandle::spawn(addr, move |stop| {
+            let mut connections = Vec::new();
+            while !stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let cache = cache.clone();
+                        let stop = Arc::clone(&stop);
+                        connections.push(thread::spawn(move || {
+                            if let Err(err) = stream_ops(&cache, stream, interval, &stop) {
+                                log::warn!("{}: {}", messages::LOG_SYNC_FAILED, err);
+                            }
+                        }));
+                    }
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => thread::sleep(STOP_CHECK_INTERVAL.min(interval)),
+                    Err(err) => log::warn!("{}: {}", messages::LOG_SYNC_FAILED, err),
+                }
+            }
+            for connection in connections {
+                let _ = connection.join();
+            }
+        }))
+    }
+
+    /// Se connecte à un cache principal et applique ses mutations à ce cache
+    /// jusqu'à l'arrêt de la poignée ou la fermeture de la connexion.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::IoError` si la connexion échoue.
+    pub fn replicate_from<A: ToSocketAddrs>(&self, addr: A) -> Result<SyncHandle, CacheError> {
+        let stream = TcpStream::connect(addr)?;
+        let addr = stream.peer_addr()?;
+        stream.set_read_timeout(Some(STOP_CHECK_INTERVAL))?;
+        let cache = self.clone();
+
+        Ok(SyncHandle::spawn(addr, move |stop| {
+            if let Err(err) = apply_stream(&cache, stream, &stop) {
+                log::warn!("{}: {}", messages::LOG_SYNC_FAILED, err);
+            }
+        }))
+    }
+}
+
+/// Côté principal : envoie l'instantané si nécessaire puis les opérations.
+fn stream_ops<K, V>(cache: &SyncCache<K, V>, stream: TcpStream, interval: Duration, stop: &AtomicBool) -> Result<(), CacheError>
+where
+    K: Hash + Eq + Clone + Display,
+    V: Clone + Display,
+{
+    stream.set_nonblocking(false)?;
+    let mut writer = BufWriter::new(stream);
+
+    // Aucune opération n'est connue de la réplique : l'instantané initial
+    // est forcé par un numéro de séquence hors du journal.
+    let mut seq = None;
+    while !stop.load(Ordering::Relaxed) {
+        let batch = cache.with_lock(|cache| match seq.map(|seq| cache.export_ops_since(seq)) {
+            Some(Ok(ops)) => Ok(ops),
+            _ => {
+                let entries: Vec<_> = cache.iter().map(|(k, v)| format!("PUT\t{}\t{}", k, v)).collect();
+                Err((cache.last_seq(), entries))
+            }
+        })?;
+        match batch {
+            Ok(ops) => {
+                for SequencedOp { seq: op_seq, op } in ops {
+                    match op {
+                        ReplicatedOp::Put { key, value } => writeln!(writer, "OP\t{}\tPUT\t{}\t{}", op_seq, key, value)?,
+                        ReplicatedOp::Remove { key } => writeln!(writer, "OP\t{}\tDEL\t{}", op_seq, key)?,
+                    }
+                    seq = Some(op_seq);
+                }
+            }
+            Err((snapshot_seq, entries)) => {
+                writeln!(writer, "SNAPSHOT\t{}", snapshot_seq)?;
+                for entry in entries {
+                    writeln!(writer, "{}", entry)?;
+                }
+                writeln!(writer, "END")?;
+                seq = Some(snapshot_seq);
+            }
+        }
+        writer.flush()?;
+        thread::sleep(interval);
+    }
+    Ok(())
+}
+
+/// Côté réplique : applique les messages reçus au cache local.
+fn apply_stream<K, V>(cache: &SyncCache<K, V>, stream: TcpStream, stop: &AtomicBool) -> Result<(), CacheError>
+where
+    K: Hash + Eq + Clone + FromStr,
+    V: Clone + FromStr,
+{
+    let mut reader = BufReader::new(stream);
+    let mut snapshot: Option<Vec<(K, V)>> = None;
+    while let Some(line) = read_line(&mut reader, stop)? {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            ["SNAPSHOT", _] => snapshot = Some(Vec::new()),
+            ["PUT", key, value] => {
+                let entries = snapshot.as_mut().ok_or_else(|| protocol_error(&line))?;
+                entries.push((parse(key, &line)?, parse(value, &line)?));
+            }
+            ["END"] => {
+                let entries = snapshot.take().ok_or_else(|| protocol_error(&line))?;
+                cache.with_lock(|cache| {
+                    cache.clear();
+                    for (key, value) in entries {
+                        cache.put(key, value);
+                    }
+                })?;
+            }
+            ["OP", seq, "PUT", key, value] => {
+                let op = ReplicatedOp::Put { key: parse(key, &line)?, value: parse(value, &line)? };
+                apply(cache, parse(seq, &line)?, op)?;
+            }
+            ["OP", seq, "DEL", key] => {
+                let op = ReplicatedOp::Remove { key: parse(key, &line)? };
+                apply(cache, parse(seq, &line)?, op)?;
+            }
+            _ => return Err(protocol_error(&line)),
+        }
+    }
+    Ok(())
+}
+
+fn apply<K, V>(cache: &SyncCache<K, V>, seq: u64, op: ReplicatedOp<K, V>) -> Result<(), CacheError>
+where
+    K: Hash + Eq + Clone,
+{
+    cache.with_lock(|cache| cache.apply_ops([SequencedOp { seq, op }]))??;
+    Ok(())
+}
+
+/// Lit une ligne complète, en vérifiant régulièrement la demande d'arrêt.
+/// Retourne `None` à la fermeture de la connexion ou à l'arrêt.
+fn read_line<R: BufRead>(reader: &mut R, stop: &AtomicBool) -> io::Result<Option<String>> {
+    let mut buffer = Vec::new();
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        match reader.read_until(b'\n', &mut buffer) {
+            Ok(0) => return Ok(None),
+            Ok(_) if buffer.ends_with(b"\n") => {
+                buffer.pop();
+                return String::from_utf8(buffer)
+                    .map(Some)
+                    .map_err(|err| io::Error::new(ErrorKind::InvalidData, err));
+            }
+            Ok(_) => {}
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn parse<T: FromStr>(field: &str, line: &str) -> Result<T, CacheError> {
+    field.parse().map_err(|_| protocol_error(line))
+}
+
+fn protocol_error(line: &str) -> CacheError {
+    CacheError::ParseError(format!("{}: {}", messages::INVALID_SYNC_MESSAGE, line))
+}
@@ -0,0 +1,235 @@
+//! Module fournissant un cache multi-locataire.
+//!
+//! `TenantCache` adresse les entrées par couple (locataire, clé) et s'appuie
+//! sur `ScopedCache` : chaque locataire reçoit une part de la capacité
+//! totale et son propre ordre LRU, si bien qu'un locataire très actif ne
+//! peut évincer que ses propres entrées. Les lectures réussies et manquées
+//! sont comptées par locataire, pour la facturation ou la supervision.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::tenant::TenantCache;
+//!
+//! let mut cache = TenantCache::new(100);
+//! cache.add_tenant("acme", 50).unwrap();
+//! cache.add_tenant("globex", 20).unwrap();
+//!
+//! cache.put(&"acme", "page:1", "contenu").unwrap();
+//! assert_eq!(cache.get(&"acme", &"page:1"), Some(&"contenu"));
+//! assert_eq!(cache.get(&"globex", &"page:1"), None);
+//!
+//! let stats = cache.stats(&"acme").unwrap();
+//! assert_eq!((stats.hits, stats.misses, stats.quota), (1, 0, 50));
+//!
+//! assert_eq!(cache.evict_tenant(&"acme"), 1);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::scoped::ScopedCache;
+
+/// Compteurs de lectures d'un locataire.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Counters {
+    hits: u64,
+    misses: u64,
+}
+
+/// Statistiques d'un locataire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TenantStats {
+    /// Lectures ayant trouvé une entrée
+    pub hits: u64,
+    /// Lectures n'ayant pas trouvé d'entrée
+    pub misses: u64,
+    /// Nombre d'entrées actuelles
+    pub entries: usize,
+    /// Nombre maximal d'entrées du locataire
+    pub quota: usize,
+    /// Part de la capacité totale, en pourcentage
+    pub share: u32,
+}
+
+impl TenantStats {
+    /// Retourne la proportion de lectures réussies, ou 0 sans lecture.
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Cache partagé entre locataires, isolés les uns des autres.
+pub struct TenantCache<T, K: Hash + Eq, V> {
+    scopes: ScopedCache<T, K, V>,
+    counters: HashMap<T, Counters>,
+}
+
+impl<T, K, V> fmt::Debug for TenantCache<T, K, V>
+where
+    T: fmt::Debug,
+    K: Hash + Eq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantCache").field("scopes", &self.scopes).finish()
+    }
+}
+
+impl<T, K, V> TenantCache<T, K, V>
+where
+    T: Hash + Eq + Clone,
+    K: Hash + Eq + Clone + 'static,
+    V: 'static,
+{
+    /// Crée un cache sans locataire, de la capacité totale indiquée.
+    ///
+    /// # Panics
+    ///
+    /// Panique si la capacité est 0.
+    pub fn new(capacity: usize) -> Self {
+        Self::from_scoped(ScopedCache::new(capacity))
+    }
+
+    /// Crée un cache dont les caches des locataires sont construits par la
+    /// fabrique donnée (voir `ScopedCache::with_factory`).
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::CapacityError` si la capacité est 0.
+    pub fn with_factory<F>(capacity: usize, factory: F) -> Result<Self, CacheError>
+    where
+        F: Fn(usize) -> Result<Cache<K, V>, CacheError> + Send + Sync + 'static,
+    {
+        ScopedCache::with_factory(capacity, factory).map(Self::from_scoped)
+    }
+}
+
+impl<T, K, V> TenantCache<T, K, V>
+where
+    T: Hash + Eq + Clone,
+    K: Hash + Eq + Clone,
+{
+    /// Crée un cache multi-locataire à partir de portées existantes, chacune
+    /// devenant un locataire.
+    pub fn from_scoped(scopes: ScopedCache<T, K, V>) -> Self {
+        let counters = scopes.scopes().map(|tenant| (tenant.clone(), Counters::default())).collect();
+        TenantCache { scopes, counters }
+    }
+
+    /// Ajoute un locataire recevant `share` pour cent de la capacité totale,
+    /// ou modifie sa part, et retourne son quota.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `ScopedCache::add_scope`.
+    pub fn add_tenant(&mut self, tenant: T, share: u32) -> Result<usize, CacheError> {
+        let quota = self.scopes.add_scope(tenant.clone(), share)?;
+        self.counters.entry(tenant).or_default();
+        Ok(quota)
+    }
+
+    /// Retire un locataire, ses entrées et ses statistiques ; sa part de la
+    /// capacité redevient disponible.
+    pub fn remove_tenant(&mut self, tenant: &T) -> bool {
+        self.counters.remove(tenant);
+        self.scopes.remove_scope(tenant).is_some()
+    }
+
+    /// Ajoute ou met à jour une entrée du locataire.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::InvalidKey` si le locataire n'existe pas.
+    pub fn put(&mut self, tenant: &T, key: K, value: V) -> Result<(), CacheError> {
+        self.scopes.put(tenant, key, value)
+    }
+
+    /// Retourne la valeur associée à la clé du locataire et compte la
+    /// lecture dans ses statistiques.
+    pub fn get(&mut self, tenant: &T, key: &K) -> Option<&V> {
+        let counters = self.counters.get_mut(tenant)?;
+        let value = self.scopes.get(tenant, key);
+        match value {
+            Some(_) => counters.hits += 1,
+            None => counters.misses += 1,
+        }
+        value
+    }
+
+    /// Retourne la valeur associée à la clé du locataire, sans modifier
+    /// l'ordre d'utilisation ni les statistiques.
+    pub fn peek(&self, tenant: &T, key: &K) -> Option<&V> {
+        self.scopes.peek(tenant, key)
+    }
+
+    /// Supprime la clé du locataire et retourne sa valeur.
+    pub fn remove(&mut self, tenant: &T, key: &K) -> Option<V> {
+        self.scopes.remove(tenant, key)
+    }
+
+    /// Supprime toutes les entrées du locataire et retourne leur nombre. Le
+    /// locataire conserve sa part et ses statistiques.
+    pub fn evict_tenant(&mut self, tenant: &T) -> usize {
+        self.scopes.clear_scope(tenant)
+    }
+
+    /// Retourne les statistiques du locataire.
+    pub fn stats(&self, tenant: &T) -> Option<TenantStats> {
+        let counters = self.counters.get(tenant)?;
+        Some(TenantStats {
+            hits: counters.hits,
+            misses: counters.misses,
+            entries: self.scopes.scope_len(tenant),
+            quota: self.scopes.quota(tenant)?,
+            share: self.scopes.share(tenant)?,
+        })
+    }
+
+    /// Retourne les statistiques de tous les locataires, dans un ordre
+    /// quelconque.
+    pub fn report(&self) -> Vec<(T, TenantStats)> {
+        self.counters
+            .keys()
+            .filter_map(|tenant| Some((tenant.clone(), self.stats(tenant)?)))
+            .collect()
+    }
+
+    /// Remet à zéro les compteurs de lectures du locataire.
+    pub fn reset_stats(&mut self, tenant: &T) {
+        if let Some(counters) = self.counters.get_mut(tenant) {
+            *counters = Counters::default();
+        }
+    }
+
+    /// Retourne les locataires existants, dans un ordre quelconque.
+    pub fn tenants(&self) -> impl Iterator<Item = &T> {
+        self.scopes.scopes()
+    }
+
+    /// Retourne le cache d'un locataire.
+    pub fn tenant(&self, tenant: &T) -> Option<&Cache<K, V>> {
+        self.scopes.scope(tenant)
+    }
+
+    /// Retourne le nombre total d'entrées, tous locataires confondus.
+    pub fn len(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Vérifie si aucun locataire ne détient d'entrée.
+    pub fn is_empty(&self) -> bool {
+        self.scopes.is_empty()
+    }
+
+    /// Retourne la capacité totale partagée entre les locataires.
+    pub fn capacity(&self) -> usize {
+        self.scopes.capacity()
+    }
+}
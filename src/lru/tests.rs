@@ -54,4 +54,87 @@ mod tests {
         assert_eq!(cache.get(&"A"), None);
         assert_eq!(cache.get(&"B"), Some(&String::from("value_b")));
     }
-}
\ No newline at end of file
+
+    ///////////////////////////////////////////////////////////////////////////
+    // Tests de la file d'expiration
+    ///////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn test_expiry_queue_pops_only_expired() {
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let mut queue = ExpiryQueue::default();
+        queue.set("A", start + Duration::from_secs(1));
+        queue.set("B", start + Duration::from_secs(3));
+        queue.set("C", start + Duration::from_secs(2));
+        queue.set("A", start + Duration::from_secs(4));
+
+        assert_eq!(queue.pop_expired(start + Duration::from_secs(2)), vec!["C"]);
+        assert_eq!(queue.len(), 2);
+
+        queue.remove(&"B");
+        assert_eq!(queue.pop_expired(start + Duration::from_secs(10)), vec!["A"]);
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[test]
+    fn test_expiry_queue_keys_sharing_a_deadline() {
+        use std::time::{Duration, Instant};
+
+        let deadline = Instant::now() + Duration::from_secs(1);
+        let mut queue = ExpiryQueue::default();
+        for key in 0..10_000u32 {
+            queue.set(key, deadline);
+        }
+        for key in (0..10_000).filter(|key| key % 100 != 0) {
+            assert_eq!(queue.remove(&key), Some(deadline));
+        }
+        assert_eq!(queue.remove(&1), None);
+
+        // Les clés restantes sortent dans leur ordre d'ajout
+        assert_eq!(queue.expiring_before(deadline).count(), 100);
+        let expired = queue.pop_expired(deadline);
+        assert_eq!(expired, (0..10_000).step_by(100).collect::<Vec<_>>());
+        assert_eq!(queue.len(), 0);
+    }
+
+    ///////////////////////////////////////////////////////////////////////////
+    // Tests de l'ordre d'utilisation
+    ///////////////////////////////////////////////////////////////////////////
+
+    #[test]
+    fn test_usage_order_links_and_reuses_slots() {
+        use std::hash::BuildHasher;
+
+        let mut order = UsageOrder::new(KeyHasher::seeded(1));
+        for key in ["A", "B", "C", "D"] {
+            order.push_back(key);
+        }
+        order.move_to_back(&"A");
+        assert!(order.remove(&"C"));
+        assert!(!order.remove(&"C"));
+
+        // L'emplacement libéré par "C" est réutilisé
+        let (_, allocated) = order.occupancy();
+        order.push_front("E");
+        assert_eq!(order.occupancy(), (4, allocated));
+        assert_eq!(order.iter().copied().collect::<Vec<_>>(), ["E", "B", "D", "A"]);
+        order.push_back("F");
+
+        order.swap(&"E", &"A");
+        order.replace_key(&"B", "G");
+        assert_eq!(order.iter().copied().collect::<Vec<_>>(), ["A", "G", "D", "E", "F"]);
+        order.move_to_back_by(KeyHasher::seeded(1).hash_one("G"), |key| *key == "G");
+        order.rotate_left(2);
+        assert_eq!(order.pop_front(), Some("E"));
+        assert_eq!(order.iter().copied().collect::<Vec<_>>(), ["F", "G", "A", "D"]);
+
+        order.set_hasher(KeyHasher::seeded(2));
+        order.shrink_to_fit();
+        order.move_to_back(&"F");
+        assert_eq!(order.iter().copied().collect::<Vec<_>>(), ["G", "A", "D", "F"]);
+        order.clear();
+        assert_eq!((order.len(), order.pop_front()), (0, None));
+    }
+}
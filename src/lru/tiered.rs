@@ -0,0 +1,234 @@
+//! Module implémentant le stockage des valeurs par taille.
+//!
+//! Un `Cache<K, Vec<u8>>` alloue chaque valeur sur le tas, même quelques
+//! octets : chaque lecture suit un pointeur vers une allocation distincte.
+//! `TieredCache` stocke les valeurs binaires d'au plus `INLINE` octets
+//! directement dans l'entrée, et ne place sur le tas que les plus grandes.
+//! Lorsque la plupart des valeurs sont petites (compteurs, drapeaux,
+//! identifiants), les lectures restent dans la table des entrées et le
+//! nombre d'allocations chute.
+//!
+//! Le seuil est le paramètre constant `INLINE` (32 octets par défaut) : il
+//! fixe la place réservée dans chaque entrée, qu'elle serve ou non. Un seuil
+//! trop grand gaspille de la mémoire pour les valeurs déportées ; `stats`
+//! indique combien de valeurs tiennent dans l'entrée pour l'ajuster.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::tiered::TieredCache;
+//!
+//! let mut cache: TieredCache<&str, 16> = TieredCache::new(Cache::builder().capacity(100)).unwrap();
+//! cache.put("drapeau", b"on").unwrap();
+//! cache.put("page", &[b'x'; 1024]).unwrap();
+//!
+//! assert_eq!(cache.get(&"drapeau"), Some(&b"on"[..]));
+//! assert_eq!(cache.get(&"page").map(<[u8]>::len), Some(1024));
+//!
+//! let stats = cache.stats();
+//! assert_eq!((stats.inline_entries, stats.spilled_entries), (1, 1));
+//! assert_eq!(cache.threshold(), 16);
+//! ```
+
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Deref;
+use crate::error::CacheError;
+use crate::lru::{Cache, CacheBuilder};
+use crate::lru::memory::MemSize;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Seuil par défaut, en octets, sous lequel une valeur est stockée dans
+/// l'entrée.
+pub const DEFAULT_INLINE: usize = 32;
+
+/// Valeur binaire stockée dans l'entrée si elle compte au plus `N` octets,
+/// sur le tas sinon.
+#[derive(Clone)]
+pub struct TieredValue<const N: usize>(Repr<N>);
+
+#[derive(Clone)]
+enum Repr<const N: usize> {
+    Inline { len: usize, bytes: [u8; N] },
+    Spilled(Box<[u8]>),
+}
+
+impl<const N: usize> TieredValue<N> {
+    /// Indique si la valeur est stockée dans l'entrée.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    /// Retourne les octets de la valeur.
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.0 {
+            Repr::Inline { len, bytes } => &bytes[..*len],
+            Repr::Spilled(bytes) => bytes,
+        }
+    }
+}
+
+impl<const N: usize> From<&[u8]> for TieredValue<N> {
+    fn from(value: &[u8]) -> Self {
+        if value.len() > N {
+            return TieredValue(Repr::Spilled(value.into()));
+        }
+        let mut bytes = [0; N];
+        bytes[..value.len()].copy_from_slice(value);
+        TieredValue(Repr::Inline { len: value.len(), bytes })
+    }
+}
+
+impl<const N: usize> Deref for TieredValue<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for TieredValue<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> PartialEq for TieredValue<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const N: usize> Eq for TieredValue<N> {}
+
+impl<const N: usize> fmt::Debug for TieredValue<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TieredValue").field(&self.as_slice()).finish()
+    }
+}
+
+impl<const N: usize> MemSize for TieredValue<N> {
+    fn heap_size(&self) -> usize {
+        match &self.0 {
+            Repr::Inline { .. } => 0,
+            Repr::Spilled(bytes) => bytes.len(),
+        }
+    }
+}
+
+/// Répartition des entrées présentes entre stockage dans l'entrée et sur le
+/// tas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TieredStats {
+    /// Nombre d'entrées
+    pub entries: usize,
+    /// Nombre de valeurs stockées dans l'entrée
+    pub inline_entries: usize,
+    /// Nombre de valeurs déportées sur le tas
+    pub spilled_entries: usize,
+    /// Taille totale des valeurs stockées dans l'entrée, en octets
+    pub inline_bytes: usize,
+    /// Taille totale des valeurs déportées sur le tas, en octets
+    pub spilled_bytes: usize,
+}
+
+impl TieredStats {
+    /// Retourne la part des valeurs stockées dans l'entrée, ou 1 si le
+    /// cache est vide.
+    pub fn inline_ratio(&self) -> f64 {
+        if self.entries == 0 {
+            return 1.0;
+        }
+        self.inline_entries as f64 / self.entries as f64
+    }
+}
+
+/// Cache de valeurs binaires stockant les petites valeurs dans l'entrée.
+#[derive(Debug)]
+pub struct TieredCache<K, const INLINE: usize = DEFAULT_INLINE>
+where
+    K: Hash + Eq,
+{
+    cache: Cache<K, TieredValue<INLINE>>,
+}
+
+impl<K, const INLINE: usize> TieredCache<K, INLINE>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Construit le cache configuré par le constructeur donné (capacité,
+    /// budget de poids, durée de vie...). Son peseur est remplacé par la
+    /// taille des valeurs.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `CacheBuilder::build`.
+    pub fn new(builder: CacheBuilder<K, TieredValue<INLINE>>) -> Result<Self, CacheError> {
+        Ok(TieredCache {
+            cache: builder.weigher(|value| value.len()).build()?,
+        })
+    }
+
+    /// Retourne la taille maximale, en octets, d'une valeur stockée dans
+    /// l'entrée.
+    pub fn threshold(&self) -> usize {
+        INLINE
+    }
+
+    /// Insère une copie de la valeur, dans l'entrée ou sur le tas selon sa
+    /// taille.
+    ///
+    /// # Errors
+    ///
+    /// Retourne les erreurs de `Cache::try_put`.
+    pub fn put(&mut self, key: K, value: &[u8]) -> Result<(), CacheError> {
+        self.cache.try_put(key, TieredValue::from(value))
+    }
+
+    /// Retourne les octets associés à la clé, sans copie, et marque l'entrée
+    /// comme la plus récemment utilisée.
+    pub fn get(&mut self, key: &K) -> Option<&[u8]> {
+        self.cache.get(key).map(TieredValue::as_slice)
+    }
+
+    /// Supprime l'entrée et indique si elle existait.
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.cache.remove(key).is_some()
+    }
+
+    /// Vérifie si la clé est présente, sans modifier l'ordre d'utilisation.
+    pub fn contains(&self, key: &K) -> bool {
+        self.cache.contains(key)
+    }
+
+    /// Retourne le nombre d'entrées du cache.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Vérifie si le cache est vide.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Calcule la répartition des entrées présentes.
+    pub fn stats(&self) -> TieredStats {
+        self.cache.iter().fold(TieredStats::default(), |mut stats, (_, value)| {
+            stats.entries += 1;
+            if value.is_inline() {
+                stats.inline_entries += 1;
+                stats.inline_bytes += value.len();
+            } else {
+                stats.spilled_entries += 1;
+                stats.spilled_bytes += value.len();
+            }
+            stats
+        })
+    }
+
+    /// Retourne le cache sous-jacent.
+    pub fn inner(&self) -> &Cache<K, TieredValue<INLINE>> {
+        &self.cache
+    }
+}
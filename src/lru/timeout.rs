@@ -0,0 +1,268 @@
+//! Module implémentant le délai maximal des chargements.
+//!
+//! Le cache n'a pas de chemin de chargement asynchrone : `TimeoutLoader`
+//! exécute donc chaque chargement sur un thread dédié et cesse de l'attendre
+//! une fois le délai écoulé, en retournant `CacheError::LoadTimeout`. Le
+//! chargement abandonné ne peut pas être interrompu : il se termine en
+//! arrière-plan et son résultat est ignoré. Un appel lent à la source ne
+//! bloque ainsi jamais l'appelant au-delà du délai.
+//!
+//! Les threads de chargement sont bornés : une clé n'est chargée que par un
+//! thread à la fois, les appelants suivants attendant son résultat, et au
+//! plus `max_loads` chargements (`DEFAULT_MAX_LOADS` par défaut) sont en
+//! cours. Au-delà, un appel attend qu'un chargement se termine, toujours
+//! dans la limite du délai. Une source bloquée n'accumule donc pas de
+//! threads à chaque appel.
+//!
+//! Un appelant qui rejoint un chargement en cours reçoit une copie de sa
+//! valeur ; en cas d'erreur, seul le premier à la lire la reçoit telle
+//! quelle, les autres recevant `CacheError::LoadFailed` qui la décrit. Une
+//! panique du chargeur est aussi rapportée par `CacheError::LoadFailed`.
+//!
+//! Combiné à `ReadThroughCache::with_stale_on_timeout`, un délai dépassé
+//! sert la valeur expirée encore présente plutôt qu'une erreur.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::thread;
+//! use std::time::Duration;
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::loader::Loader;
+//!
+//! let lent = |cle: &u32| -> Result<u32, CacheError> {
+//!     if *cle > 10 {
+//!         thread::sleep(Duration::from_millis(200));
+//!     }
+//!     Ok(*cle)
+//! };
+//! let chargeur = lent.with_timeout(Duration::from_millis(50));
+//!
+//! assert_eq!(chargeur.load(&1).unwrap(), 1);
+//! assert!(matches!(chargeur.load(&11), Err(CacheError::LoadTimeout { .. })));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::error::CacheError;
+use crate::lru::loader::Loader;
+use crate::messages;
+
+/// Nombre maximal par défaut de chargements simultanés d'un `TimeoutLoader`.
+pub const DEFAULT_MAX_LOADS: usize = 16;
+
+/// Chargeur abandonnant les chargements d'un autre chargeur qui dépassent un
+/// délai.
+///
+/// Les clones d'un `TimeoutLoader` partagent ses chargements en cours et
+/// leur borne.
+#[derive(Debug)]
+pub struct TimeoutLoader<L, K, V> {
+    loader: Arc<L>,
+    timeout: Duration,
+    max_loads: usize,
+    in_flight: Arc<InFlight<K, V>>,
+}
+
+/// Chargements en cours, par clé.
+///
+/// Partagée par les clones d'un `TimeoutLoader` et par ceux d'un
+/// `SyncCache` pour ses chargements bornés par une échéance.
+#[derive(Debug)]
+pub(crate) struct InFlight<K, V> {
+    loads: Mutex<HashMap<K, Arc<Pending<V>>>>,
+    /// Signalée à la fin de chaque chargement, pour les appels en attente
+    /// d'une place
+    finished: Condvar,
+}
+
+/// Résultat d'un chargement en cours, attendu par un ou plusieurs appelants.
+#[derive(Debug)]
+struct Pending<V> {
+    result: Mutex<Option<Result<V, CacheError>>>,
+    done: Condvar,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // Aucune panique ne survient verrou détenu : l'état reste cohérent
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl<L, K, V> TimeoutLoader<L, K, V> {
+    /// Borne la durée des chargements du chargeur donné.
+    pub fn new(loader: L, timeout: Duration) -> Self {
+        TimeoutLoader {
+            loader: Arc::new(loader),
+            timeout,
+            max_loads: DEFAULT_MAX_LOADS,
+            in_flight: Arc::new(InFlight::new()),
+        }
+    }
+
+    /// Fixe le nombre maximal de chargements simultanés (au moins un).
+    pub fn with_max_loads(mut self, max_loads: usize) -> Self {
+        self.max_loads = max_loads.max(1);
+        self
+    }
+
+    /// Retourne le délai maximal d'un chargement.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Retourne le nombre de chargements en cours, abandonnés ou non.
+    pub fn loads_in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+}
+
+impl<L, K, V> Clone for TimeoutLoader<L, K, V> {
+    fn clone(&self) -> Self {
+        TimeoutLoader {
+            loader: Arc::clone(&self.loader),
+            timeout: self.timeout,
+            max_loads: self.max_loads,
+            in_flight: Arc::clone(&self.in_flight),
+        }
+    }
+}
+
+impl<L, K, V> TimeoutLoader<L, K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+    L: Loader<K, V> + Send + Sync + 'static,
+{
+    /// Charge la clé en attendant le résultat au plus jusqu'à l'échéance.
+    fn load_until(&self, key: &K, deadline: Instant) -> Option<Result<V, CacheError>> {
+        let loader = Arc::clone(&self.loader);
+        self.in_flight.load_until(key, deadline, self.max_loads, move |key: &K| loader.load(key))
+    }
+}
+
+impl<K, V> InFlight<K, V> {
+    /// Crée une table vide de chargements en cours.
+    pub(crate) fn new() -> Self {
+        InFlight { loads: Mutex::new(HashMap::new()), finished: Condvar::new() }
+    }
+
+    /// Retourne le nombre de chargements en cours, abandonnés ou non.
+    pub(crate) fn len(&self) -> usize {
+        lock(&self.loads).len()
+    }
+}
+
+impl<K, V> InFlight<K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Send + 'static,
+{
+    /// Retourne le chargement en cours de la clé, ou lance `load` sur un
+    /// thread dédié dès que l'une des `max_loads` places se libère avant
+    /// l'échéance.
+    fn start<F>(self: &Arc<Self>, key: &K, deadline: Instant, max_loads: usize, load: F) -> Option<Arc<Pending<V>>>
+    where
+        F: FnOnce(&K) -> Result<V, CacheError> + Send + 'static,
+    {
+        let mut loads = lock(&self.loads);
+        loop {
+            if let Some(pending) = loads.get(key) {
+                return Some(Arc::clone(pending));
+            }
+            if loads.len() < max_loads {
+                break;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            loads = self
+                .finished
+                .wait_timeout(loads, deadline - now)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+
+        let pending = Arc::new(Pending { result: Mutex::new(None), done: Condvar::new() });
+        loads.insert(key.clone(), Arc::clone(&pending));
+        drop(loads);
+
+        let in_flight = Arc::clone(self);
+        let shared = Arc::clone(&pending);
+        let key = key.clone();
+        thread::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| load(&key))).unwrap_or_else(|payload| {
+                let reason = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+                    (Some(detail), _) => format!("{}: {}", messages::LOADER_PANICKED, detail),
+                    (_, Some(detail)) => format!("{}: {}", messages::LOADER_PANICKED, detail),
+                    _ => messages::LOADER_PANICKED.to_string(),
+                };
+                Err(CacheError::LoadFailed { reason })
+            });
+            *lock(&shared.result) = Some(result);
+            shared.done.notify_all();
+            lock(&in_flight.loads).remove(&key);
+            in_flight.finished.notify_all();
+        });
+        Some(pending)
+    }
+}
+
+impl<K, V> InFlight<K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    /// Rejoint ou lance le chargement de la clé et attend son résultat
+    /// jusqu'à l'échéance ; retourne `None` si l'échéance passe avant.
+    pub(crate) fn load_until<F>(
+        self: &Arc<Self>,
+        key: &K,
+        deadline: Instant,
+        max_loads: usize,
+        load: F,
+    ) -> Option<Result<V, CacheError>>
+    where
+        F: FnOnce(&K) -> Result<V, CacheError> + Send + 'static,
+    {
+        self.start(key, deadline, max_loads, load)?.wait(deadline)
+    }
+}
+
+impl<V: Clone> Pending<V> {
+    /// Attend le résultat jusqu'à l'échéance.
+    fn wait(&self, deadline: Instant) -> Option<Result<V, CacheError>> {
+        let result = lock(&self.result);
+        let (mut result, _) = self
+            .done
+            .wait_timeout_while(result, deadline.saturating_duration_since(Instant::now()), |result| {
+                result.is_none()
+            })
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Some(match result.as_mut()? {
+            Ok(value) => Ok(value.clone()),
+            Err(CacheError::LoadFailed { reason }) => Err(CacheError::LoadFailed { reason: reason.clone() }),
+            Err(err) => {
+                let reason = err.to_string();
+                Err(std::mem::replace(err, CacheError::LoadFailed { reason }))
+            }
+        })
+    }
+}
+
+impl<L, K, V> Loader<K, V> for TimeoutLoader<L, K, V>
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+    L: Loader<K, V> + Send + Sync + 'static,
+{
+    fn load(&self, key: &K) -> Result<V, CacheError> {
+        let deadline = Instant::now() + self.timeout;
+        self.load_until(key, deadline)
+            .unwrap_or(Err(CacheError::LoadTimeout { timeout: self.timeout }))
+    }
+}
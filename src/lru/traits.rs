@@ -1,10 +1,67 @@
 //! Module définissant les traits pour le cache LRU.
 
+/// Trait définissant les opérations de lecture d'un cache.
+/// 
+/// Aucune de ces méthodes ne modifie le cache, ni même son ordre
+/// d'utilisation : elles ne nécessitent donc qu'un emprunt partagé. Les
+/// fonctions qui se contentent de consulter un cache peuvent ainsi accepter
+/// un `&impl CacheRead` et coexister avec d'autres emprunts partagés.
+/// 
+/// # Exemples
+/// 
+/// ```
+/// use lru_cache::lru::traits::{CacheRead, CacheTrait};
+/// use lru_cache::lru::Cache;
+/// 
+/// fn taux_remplissage<C: CacheRead<String, i32>>(cache: &C) -> f64 {
+///     cache.len() as f64 / cache.capacity() as f64
+/// }
+/// 
+/// let mut cache = Cache::new(4);
+/// cache.put("un".to_string(), 1);
+/// assert_eq!(taux_remplissage(&cache), 0.25);
+/// ```
+pub trait CacheRead<K, V> {
+    /// Récupère une référence à la valeur associée à la clé sans modifier
+    /// l'ordre d'utilisation du cache.
+    fn peek(&self, key: &K) -> Option<&V>;
+
+    /// Vérifie si la clé est présente, sans modifier l'ordre d'utilisation.
+    fn contains(&self, key: &K) -> bool {
+        self.peek(key).is_some()
+    }
+
+    /// Retourne le nombre d'éléments actuellement dans le cache.
+    fn len(&self) -> usize;
+
+    /// Vérifie si le cache est vide.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retourne la capacité maximale du cache.
+    fn capacity(&self) -> usize;
+}
+
 /// Trait définissant les opérations de base d'un cache.
 /// 
-/// Ce trait fournit les méthodes essentielles pour interagir avec un cache :
-/// - Récupérer une valeur (`get`)
+/// Ce trait étend `CacheRead` avec les méthodes qui modifient le cache :
+/// - Récupérer une valeur en mettant à jour l'ordre d'utilisation (`get`)
 /// - Ajouter ou mettre à jour une valeur (`put`)
+/// - Supprimer une valeur (`remove`) ou vider le cache (`clear`)
+/// 
+/// Les deux traits sont utilisables comme objets de trait : toute méthode
+/// générique ajoutée à l'avenir devra être bornée par `where Self: Sized`.
+/// Il est ainsi possible de choisir l'implémentation à l'exécution :
+/// 
+/// ```
+/// use lru_cache::lru::traits::CacheTrait;
+/// use lru_cache::lru::Cache;
+/// 
+/// let mut cache: Box<dyn CacheTrait<String, Vec<u8>>> = Box::new(Cache::new(8));
+/// cache.put("octets".to_string(), vec![1, 2, 3]);
+/// assert_eq!(cache.get(&"octets".to_string()), Some(&vec![1, 2, 3]));
+/// ```
 /// 
 /// # Type Parameters
 /// 
@@ -20,12 +77,15 @@
 /// fn utiliser_cache<C: CacheTrait<String, i32>>(cache: &mut C) {
 ///     cache.put("un".to_string(), 1);
 ///     assert_eq!(cache.get(&"un".to_string()), Some(&1));
+///     assert!(cache.contains(&"un".to_string()));
+///     assert_eq!(cache.remove(&"un".to_string()), Some(1));
+///     assert!(cache.is_empty());
 /// }
 /// 
 /// let mut cache = Cache::new(2);
 /// utiliser_cache(&mut cache);
 /// ```
-pub trait CacheTrait<K, V> {
+pub trait CacheTrait<K, V>: CacheRead<K, V> {
     /// Récupère une référence à la valeur associée à la clé.
     /// 
     /// Met également à jour l'ordre d'utilisation du cache.
@@ -35,4 +95,132 @@ pub trait CacheTrait<K, V> {
     /// 
     /// Si le cache est plein, l'élément le moins récemment utilisé est supprimé.
     fn put(&mut self, key: K, value: V);
-}
\ No newline at end of file
+
+    /// Supprime l'entrée associée à la clé et retourne sa valeur.
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    /// Vide le cache de tous ses éléments.
+    fn clear(&mut self);
+}
+
+impl<K, V, C> CacheRead<K, V> for &C
+where
+    C: CacheRead<K, V> + ?Sized,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        (**self).peek(key)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        (**self).contains(key)
+    }
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn is_empty(&self) -> bool {
+        (**self).is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+}
+
+impl<K, V, C> CacheRead<K, V> for &mut C
+where
+    C: CacheRead<K, V> + ?Sized,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        (**self).peek(key)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        (**self).contains(key)
+    }
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn is_empty(&self) -> bool {
+        (**self).is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+}
+
+impl<K, V, C> CacheRead<K, V> for Box<C>
+where
+    C: CacheRead<K, V> + ?Sized,
+{
+    fn peek(&self, key: &K) -> Option<&V> {
+        (**self).peek(key)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        (**self).contains(key)
+    }
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn is_empty(&self) -> bool {
+        (**self).is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+}
+
+impl<K, V, C> CacheTrait<K, V> for &mut C
+where
+    C: CacheTrait<K, V> + ?Sized,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        (**self).get(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        (**self).put(key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        (**self).remove(key)
+    }
+
+    fn clear(&mut self) {
+        (**self).clear()
+    }
+}
+
+impl<K, V, C> CacheTrait<K, V> for Box<C>
+where
+    C: CacheTrait<K, V> + ?Sized,
+{
+    fn get(&mut self, key: &K) -> Option<&V> {
+        (**self).get(key)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        (**self).put(key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        (**self).remove(key)
+    }
+
+    fn clear(&mut self) {
+        (**self).clear()
+    }
+}
+
+/// Vérification à la compilation que les traits restent utilisables comme
+/// objets de trait.
+const _: Option<&dyn CacheRead<String, Vec<u8>>> = None;
+const _: Option<&dyn CacheTrait<String, Vec<u8>>> = None;
@@ -0,0 +1,203 @@
+//! Module implémentant les transactions sur le cache.
+//!
+//! Une transaction accumule des écritures et des suppressions sans toucher au
+//! cache. À la validation, toutes les écritures sont vérifiées (règles de clé
+//! et limites de poids) avant d'être appliquées : soit toutes les opérations
+//! sont appliquées, soit aucune. Les évictions nécessaires ne sont effectuées
+//! qu'une seule fois, après l'application de toutes les opérations.
+//!
+//! Les écritures d'une transaction remplacent toujours la valeur existante,
+//! quelle que soit la politique de doublons du cache.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::CacheRead;
+//!
+//! let mut cache = Cache::new(10);
+//! cache.transaction(|tx| {
+//!     tx.put("solde:alice", 50);
+//!     tx.put("solde:bob", 150);
+//!     Ok(())
+//! }).unwrap();
+//!
+//! // Une erreur annule toutes les opérations en attente
+//! let resultat: Result<(), CacheError> = cache.transaction(|tx| {
+//!     tx.remove(&"solde:alice");
+//!     Err(CacheError::ParseError("montant invalide".to_string()))
+//! });
+//! assert!(resultat.is_err());
+//! assert_eq!(cache.peek(&"solde:alice"), Some(&50));
+//! ```
+
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::{Cache, Entry};
+use crate::lru::duplicate::UpdateRecency;
+use crate::lru::events::{Mutation, RemovalCause};
+use crate::lru::traits::CacheRead;
+
+/// Opération en attente dans une transaction.
+#[derive(Debug)]
+enum Operation<K, V> {
+    Put(K, V),
+    Remove(K),
+}
+
+/// Ensemble d'opérations en attente, appliquées atomiquement à la validation.
+///
+/// Les lectures via `get` voient les opérations déjà enregistrées dans la
+/// transaction.
+#[derive(Debug)]
+pub struct Transaction<'a, K, V>
+where
+    K: Hash + Eq,
+{
+    cache: &'a Cache<K, V>,
+    operations: Vec<Operation<K, V>>,
+}
+
+impl<K, V> Transaction<'_, K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Enregistre l'ajout ou la mise à jour d'une paire clé-valeur.
+    pub fn put(&mut self, key: K, value: V) {
+        self.operations.push(Operation::Put(key, value));
+    }
+
+    /// Enregistre la suppression d'une clé.
+    pub fn remove(&mut self, key: &K) {
+        self.operations.push(Operation::Remove(key.clone()));
+    }
+
+    /// Retourne la valeur qu'aura la clé si la transaction est validée.
+    ///
+    /// Ne modifie pas l'ordre d'utilisation du cache.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        for operation in self.operations.iter().rev() {
+            match operation {
+                Operation::Put(k, value) if k == key => return Some(value),
+                Operation::Remove(k) if k == key => return None,
+                _ => {}
+            }
+        }
+        self.cache.peek(key)
+    }
+
+    /// Retourne le nombre d'opérations en attente.
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Indique si aucune opération n'est en attente.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Exécute la closure dans une transaction puis applique atomiquement
+    /// les opérations qu'elle a enregistrées.
+    ///
+    /// # Errors
+    ///
+    /// Retourne l'erreur de la closure, ou l'erreur de la première écriture
+    /// refusée par les règles de clé ou les limites de poids. Dans les deux
+    /// cas, le cache n'est pas modifié.
+    pub fn transaction<R, F>(&mut self, f: F) -> Result<R, CacheError>
+    where
+        F: FnOnce(&mut Transaction<'_, K, V>) -> Result<R, CacheError>,
+    {
+        let mut transaction = Transaction {
+            cache: self,
+            operations: Vec::new(),
+        };
+        let result = f(&mut transaction)?;
+        let operations = transaction.operations;
+
+        // Toutes les écritures sont vérifiées avant d'appliquer quoi que ce soit
+        let mut weights = Vec::with_capacity(operations.len());
+        for operation in &operations {
+            if let Operation::Put(key, value) = operation {
+                self.check_key(key)?;
+                let weight = self.weigh(value);
+                self.check_weight(weight)?;
+                weights.push(weight);
+            }
+        }
+
+        let mut weights = weights.into_iter();
+        for operation in operations {
+            match operation {
+                Operation::Put(key, value) => {
+                    let weight = weights.next().unwrap_or(1);
+                    self.store_entry(key, value, weight);
+                }
+                Operation::Remove(key) => {
+                    self.remove_entry(&key, RemovalCause::Explicit);
+                }
+            }
+        }
+
+        // Réconciliation unique avec la capacité et le budget de poids
+        if self.elements.len() + self.reserved > self.capacity && (self.expirations.len() > 0 || self.time_buckets.is_some()) {
+            self.purge_expired();
+        }
+        let excess = (self.elements.len() + self.reserved).saturating_sub(self.capacity);
+        self.evict_batch(excess, RemovalCause::Capacity);
+        self.evict_overweight();
+        Ok(result)
+    }
+
+    /// Insère ou remplace une entrée déjà validée, sans éviction.
+    fn store_entry(&mut self, key: K, value: V, weight: usize) {
+        // Une entrée expirée est remplacée par une nouvelle, comme dans
+        // `insert_entry`
+        if self.is_expired(&key) {
+            self.remove_entry(&key, RemovalCause::Expired);
+        }
+        self.next_version += 1;
+        match self.elements.get_mut(&key) {
+            Some(entry) => {
+                self.total_weight = self.total_weight - entry.weight + weight;
+                entry.value = value;
+                entry.weight = weight;
+                entry.version = self.next_version;
+                entry.warmed = false;
+                if self.observers.is_active() {
+                    self.observers.notify(&Mutation::Update { key: &key, value: &entry.value });
+                }
+                if self.update_recency == UpdateRecency::Promote {
+                    self.move_to_recently_used(&key);
+                }
+            }
+            None => {
+                self.take_reserved_slot();
+                self.total_weight += weight;
+                if self.observers.is_active() {
+                    self.observers.notify(&Mutation::Insert { key: &key, value: &value });
+                }
+                let entry = Entry::new(value, weight, self.next_version, self.clock.now());
+                self.index_key(&key);
+                self.elements.insert(key.clone(), entry);
+                self.bucket_key(&key);
+                self.track_recency(key.clone());
+            }
+        }
+        match self.default_ttl {
+            Some(ttl) => {
+                let deadline = self.clock.now() + ttl;
+                self.expirations.set(key, deadline);
+            }
+            None => {
+                self.expirations.remove(&key);
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Module implémentant la durée de vie (TTL) des entrées du cache.
+//!
+//! Les échéances sont conservées dans une file ordonnée par instant
+//! d'expiration : retrouver les entrées expirées ne nécessite donc que de
+//! parcourir le début de la file, en O(expirées) plutôt qu'en O(total).
+//! Les entrées expirées sont supprimées paresseusement lors des accès, ou
+//! explicitement via `purge_expired`.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::clock::ManualClock;
+//! use lru_cache::lru::traits::CacheTrait;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let clock = ManualClock::new();
+//! let mut cache = Cache::builder()
+//!     .capacity(10)
+//!     .clock(Arc::new(clock.clone()))
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.put_with_ttl("session", 42, Duration::from_secs(60));
+//! assert_eq!(cache.get(&"session"), Some(&42));
+//!
+//! clock.advance(Duration::from_secs(61));
+//! assert_eq!(cache.get(&"session"), None);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+
+/// File des échéances d'expiration, ordonnée par instant.
+///
+/// Chaque clé y est repérée par son échéance et un numéro d'ordre unique :
+/// les clés qui partagent une échéance, nombreuses avec une horloge
+/// grossière ou manuelle, se retirent en O(log n) sans parcourir leurs
+/// voisines.
+#[derive(Debug)]
+pub(crate) struct ExpiryQueue<K> {
+    deadlines: HashMap<K, (Instant, u64)>,
+    queue: BTreeMap<(Instant, u64), K>,
+    next_seq: u64,
+}
+
+impl<K> Default for ExpiryQueue<K> {
+    fn default() -> Self {
+        ExpiryQueue {
+            deadlines: HashMap::new(),
+            queue: BTreeMap::new(),
+            next_seq: 0,
+        }
+    }
+}
+
+impl<K> ExpiryQueue<K>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Définit (ou remplace) l'échéance de la clé.
+    pub(crate) fn set(&mut self, key: K, deadline: Instant) {
+        self.remove(&key);
+        let slot = (deadline, self.next_seq);
+        self.next_seq += 1;
+        self.queue.insert(slot, key.clone());
+        self.deadlines.insert(key, slot);
+    }
+
+    /// Retire l'échéance de la clé et la retourne.
+    pub(crate) fn remove(&mut self, key: &K) -> Option<Instant> {
+        let slot = self.deadlines.remove(key)?;
+        self.queue.remove(&slot);
+        Some(slot.0)
+    }
+
+    /// Retourne l'échéance de la clé, si elle en a une.
+    pub(crate) fn deadline(&self, key: &K) -> Option<Instant> {
+        self.deadlines.get(key).map(|&(deadline, _)| deadline)
+    }
+
+    /// Indique si la clé a une échéance dépassée à l'instant donné.
+    pub(crate) fn is_expired(&self, key: &K, now: Instant) -> bool {
+        self.deadline(key).is_some_and(|deadline| deadline <= now)
+    }
+
+    /// Retire et retourne toutes les clés dont l'échéance est dépassée.
+    pub(crate) fn pop_expired(&mut self, now: Instant) -> Vec<K> {
+        let mut expired = Vec::new();
+        while let Some(entry) = self.queue.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            let key = entry.remove();
+            self.deadlines.remove(&key);
+            expired.push(key);
+        }
+        expired
+    }
+
+    /// Retourne le nombre de clés ayant une échéance et la place allouée
+    /// pour elles.
+    pub(crate) fn occupancy(&self) -> (usize, usize) {
+        (self.deadlines.len(), self.deadlines.capacity())
+    }
+
+    /// Rend la place allouée inutilisée.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.deadlines.shrink_to_fit();
+    }
+
+    /// Retourne les clés dont l'échéance est antérieure ou égale à l'instant
+    /// donné, dans l'ordre des échéances.
+    pub(crate) fn expiring_before(&self, limit: Instant) -> impl Iterator<Item = (&K, Instant)> {
+        self.queue
+            .range(..=(limit, u64::MAX))
+            .map(|(&(deadline, _), key)| (key, deadline))
+    }
+
+    /// Retourne le nombre de clés ayant une échéance.
+    pub(crate) fn len(&self) -> usize {
+        self.deadlines.len()
+    }
+
+    /// Supprime toutes les échéances.
+    pub(crate) fn clear(&mut self) {
+        self.deadlines.clear();
+        self.queue.clear();
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Ajoute ou met à jour une entrée qui expirera après la durée donnée.
+    ///
+    /// Une entrée expirée n'est plus jamais retournée ; elle est supprimée
+    /// lors de l'accès suivant ou par `purge_expired`. Comme avec `put`, une
+    /// valeur refusée par les limites de poids supprime l'ancienne valeur.
+    pub fn put_with_ttl(&mut self, key: K, value: V, ttl: Duration) {
+        if self.put_entry(key.clone(), value, Some(ttl), false).is_err() {
+            self.remove_entry(&key, RemovalCause::Rejected);
+        }
+    }
+
+    /// Retourne la durée de vie restante de l'entrée, si elle en a une et
+    /// qu'elle n'est pas encore expirée.
+    pub fn ttl(&self, key: &K) -> Option<Duration> {
+        let deadline = self.expirations.deadline(key).or_else(|| {
+            let inserted = self.elements.get(key)?.inserted;
+            Some(self.time_buckets.as_ref()?.deadline_of(inserted))
+        })?;
+        deadline.checked_duration_since(self.clock.now()).filter(|d| !d.is_zero())
+    }
+
+    /// Retourne la durée de vie appliquée par défaut aux entrées insérées
+    /// avec `put`.
+    pub fn default_ttl(&self) -> Option<Duration> {
+        self.default_ttl
+    }
+
+    /// Remplace la durée de vie appliquée par défaut aux entrées insérées
+    /// avec `put`. Les échéances des entrées déjà présentes sont conservées.
+    pub fn set_default_ttl(&mut self, ttl: Option<Duration>) {
+        self.default_ttl = ttl;
+    }
+
+    /// Supprime toutes les entrées expirées et retourne leur nombre.
+    ///
+    /// Seules les entrées expirées sont parcourues. Avec
+    /// `CacheBuilder::time_buckets`, les tranches expirées sont retirées
+    /// d'un bloc.
+    pub fn purge_expired(&mut self) -> usize {
+        let expired = self.expirations.pop_expired(self.clock.now());
+        let mut count = expired.len();
+        for key in expired {
+            self.remove_entry(&key, RemovalCause::Expired);
+        }
+        if self.time_buckets.is_some() {
+            count += self.drop_expired_buckets();
+        }
+        if count > 0 {
+            self.maybe_shrink();
+        }
+        count
+    }
+
+    /// Indique si l'entrée existe mais que sa durée de vie est dépassée.
+    pub(crate) fn is_expired(&self, key: &K) -> bool {
+        if self.expirations.len() > 0 && self.expirations.is_expired(key, self.clock.now()) {
+            return true;
+        }
+        match (&self.time_buckets, self.elements.get(key)) {
+            (Some(buckets), Some(entry)) => buckets.is_expired(entry.inserted, self.clock.now()),
+            _ => false,
+        }
+    }
+}
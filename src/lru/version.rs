@@ -0,0 +1,104 @@
+//! Module implémentant les lectures optimistes par numéro de version.
+//!
+//! Chaque écriture d'une entrée lui attribue un nouveau numéro de version,
+//! strictement croissant à l'échelle du cache : une entrée supprimée puis
+//! réinsérée ne retrouve jamais une ancienne version. Un `SyncCache` peut
+//! ainsi lire une valeur avec sa version, calculer la nouvelle valeur sans
+//! détenir le verrou, puis ne l'écrire que si l'entrée n'a pas changé entre
+//! temps.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::sync::SyncCache;
+//!
+//! let cache = SyncCache::new(10);
+//! cache.put("compteur", 0).unwrap();
+//!
+//! // Boucle lecture-modification-écriture sans verrou pendant le calcul
+//! loop {
+//!     let (valeur, version) = cache.get_with_version(&"compteur").unwrap().unwrap();
+//!     match cache.put_if_version("compteur", valeur + 1, Some(version)) {
+//!         Ok(_) => break,
+//!         Err(CacheError::VersionConflict { .. }) => continue,
+//!         Err(err) => panic!("{}", err),
+//!     }
+//! }
+//! assert_eq!(cache.get(&"compteur").unwrap(), Some(1));
+//! ```
+
+use std::hash::Hash;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::sync::SyncCache;
+use crate::lru::traits::CacheTrait;
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne la version courante de l'entrée, si elle est présente et
+    /// non expirée.
+    pub fn version(&self, key: &K) -> Option<u64> {
+        if self.is_expired(key) {
+            return None;
+        }
+        self.elements.get(key).map(|entry| entry.version)
+    }
+
+    /// Écrit la valeur si la version courante de l'entrée est celle attendue
+    /// et retourne la nouvelle version.
+    ///
+    /// `expected` vaut `None` pour exiger que la clé soit absente. L'écriture
+    /// remplace toujours la valeur, quelle que soit la politique de doublons.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::VersionConflict` si l'entrée a changé, ou les
+    /// mêmes erreurs que `try_put`.
+    pub fn put_if_version(&mut self, key: K, value: V, expected: Option<u64>) -> Result<u64, CacheError> {
+        let actual = self.version(&key);
+        if actual != expected {
+            return Err(CacheError::VersionConflict { expected, actual });
+        }
+        self.put_entry(key.clone(), value, self.default_ttl, true)?;
+        Ok(self.elements.get(&key).map_or(self.next_version, |entry| entry.version))
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Récupère une copie de la valeur associée à la clé avec sa version.
+    ///
+    /// Met également à jour l'ordre d'utilisation du cache.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` selon la politique d'empoisonnement.
+    pub fn get_with_version(&self, key: &K) -> Result<Option<(V, u64)>, CacheError> {
+        self.with_lock(|cache| {
+            let value = cache.get(key).cloned()?;
+            cache.version(key).map(|version| (value, version))
+        })
+    }
+}
+
+impl<K, V> SyncCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Écrit la valeur si la version de l'entrée n'a pas changé depuis sa
+    /// lecture et retourne la nouvelle version.
+    ///
+    /// # Errors
+    ///
+    /// Retourne `CacheError::Poisoned` selon la politique d'empoisonnement,
+    /// ou les erreurs de `Cache::put_if_version`.
+    pub fn put_if_version(&self, key: K, value: V, expected: Option<u64>) -> Result<u64, CacheError> {
+        self.with_lock(|cache| cache.put_if_version(key, value, expected))?
+    }
+}
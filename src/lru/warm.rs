@@ -0,0 +1,135 @@
+//! Module implémentant le préchargement (« warm-up ») du cache.
+//!
+//! `warm` charge en une seule passe un lot d'entrées marquées comme
+//! préchargées. Elles sont placées en queue de l'ordre d'utilisation, derrière
+//! les entrées déjà présentes : le trafic réel l'emporte toujours, et une
+//! entrée préchargée jamais lue sera la première évincée. Le préchargement
+//! n'évince aucune entrée existante et ne remplace aucune valeur : il s'arrête
+//! dès que la capacité ou le budget de poids est atteint.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::CacheTrait;
+//!
+//! let mut cache = Cache::new(3);
+//! cache.put("réel", 0);
+//!
+//! let rapport = cache.warm_with_priority(vec![
+//!     ("froid", 1, 10),
+//!     ("chaud", 2, 90),
+//!     ("tiède", 3, 50),
+//! ]);
+//! assert_eq!((rapport.loaded, rapport.not_fitting), (2, 1));
+//! assert!(cache.is_prewarmed(&"chaud"));
+//!
+//! // Les entrées préchargées sont évincées avant le trafic réel
+//! cache.put("nouveau", 4);
+//! assert_eq!(cache.get(&"tiède"), None);
+//! assert_eq!(cache.get(&"réel"), Some(&0));
+//! ```
+
+use std::hash::Hash;
+use crate::lru::{Cache, Entry};
+use crate::lru::events::Mutation;
+
+/// Bilan d'un préchargement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WarmReport {
+    /// Entrées chargées
+    pub loaded: usize,
+    /// Entrées ignorées car leur clé était déjà présente
+    pub already_present: usize,
+    /// Entrées refusées par les règles de clé ou les limites de poids
+    pub rejected: usize,
+    /// Entrées ignorées faute de place
+    pub not_fitting: usize,
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Précharge les entrées données, de la plus importante à la moins
+    /// importante, tant qu'il reste de la place.
+    ///
+    /// Parmi les entrées préchargées, les plus importantes sont les plus
+    /// récentes dans l'ordre d'utilisation. Le coût est linéaire en la taille
+    /// du lot.
+    pub fn warm<I>(&mut self, entries: I) -> WarmReport
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut report = WarmReport::default();
+        let mut warmed = Vec::new();
+        if self.expirations.len() > 0 {
+            self.purge_expired();
+        }
+
+        for (key, value) in entries {
+            if self.elements.contains_key(&key) {
+                report.already_present += 1;
+                continue;
+            }
+            let weight = self.weigh(&value);
+            if self.check_key(&key).is_err() || self.check_weight(weight).is_err() {
+                report.rejected += 1;
+                continue;
+            }
+            let over_budget = self.max_weight.is_some_and(|max| self.total_weight + weight > max);
+            if self.elements.len() + self.reserved >= self.capacity || over_budget {
+                report.not_fitting += 1;
+                continue;
+            }
+
+            self.next_version += 1;
+            if self.observers.is_active() {
+                self.observers.notify(&Mutation::Insert { key: &key, value: &value });
+            }
+            let mut entry = Entry::new(value, weight, self.next_version, self.clock.now());
+            entry.warmed = true;
+            self.total_weight += weight;
+            self.index_key(&key);
+            self.elements.insert(key.clone(), entry);
+            self.bucket_key(&key);
+            if let Some(ttl) = self.default_ttl {
+                let deadline = self.clock.now() + ttl;
+                self.expirations.set(key.clone(), deadline);
+            }
+            warmed.push(key);
+            report.loaded += 1;
+        }
+
+        // Les entrées préchargées passent derrière les entrées existantes,
+        // la plus importante en dernier
+        if self.sampling.is_none() {
+            for key in warmed {
+                self.usage_order.push_front(key);
+            }
+        }
+        report
+    }
+
+    /// Précharge les entrées données par ordre de priorité décroissante,
+    /// tant qu'il reste de la place.
+    ///
+    /// À priorité égale, l'ordre du lot est conservé.
+    pub fn warm_with_priority<I, P>(&mut self, entries: I) -> WarmReport
+    where
+        I: IntoIterator<Item = (K, V, P)>,
+        P: Ord,
+    {
+        let mut entries: Vec<_> = entries.into_iter().collect();
+        entries.sort_by(|a, b| b.2.cmp(&a.2));
+        self.warm(entries.into_iter().map(|(key, value, _)| (key, value)))
+    }
+
+    /// Indique si l'entrée a été préchargée et n'a encore été ni lue ni
+    /// réécrite.
+    pub fn is_prewarmed(&self, key: &K) -> bool {
+        self.elements
+            .get(key)
+            .is_some_and(|entry| entry.warmed)
+    }
+}
@@ -0,0 +1,48 @@
+//! Module implémentant l'éviction par lots entre deux seuils.
+//!
+//! Par défaut, une fois le cache plein, chaque nouvelle clé évince une
+//! entrée. Avec `CacheBuilder::low_watermark`, la capacité devient un seuil
+//! haut : l'insertion qui l'atteint évince d'un coup les entrées les moins
+//! récemment utilisées jusqu'au seuil bas, puis les insertions suivantes
+//! n'évincent plus rien jusqu'à ce que le seuil haut soit de nouveau
+//! atteint. Lors d'une rafale d'insertions, le coût des évictions est ainsi
+//! regroupé au lieu d'être payé à chaque écriture.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::traits::{CacheRead, CacheTrait};
+//!
+//! let mut cache = Cache::builder().capacity(4).low_watermark(2).build().unwrap();
+//! for i in 0..4 {
+//!     cache.put(i, i);
+//! }
+//!
+//! // Le seuil haut est atteint : seules les 2 entrées les plus récentes restent
+//! cache.put(4, 4);
+//! assert_eq!(cache.len(), 3);
+//! assert!(!cache.contains(&1) && cache.contains(&2));
+//! ```
+
+use std::hash::Hash;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Retourne le seuil bas d'éviction, s'il est configuré.
+    pub fn low_watermark(&self) -> Option<usize> {
+        self.low_watermark
+    }
+
+    /// Fait de la place pour une nouvelle clé dans un cache plein : une
+    /// seule entrée, ou toutes celles au-delà du seuil bas.
+    pub(crate) fn evict_for_insert(&mut self) {
+        let target = self.low_watermark.unwrap_or(self.capacity - 1);
+        let excess = self.elements.len().saturating_sub(target);
+        self.evict_batch(excess, RemovalCause::Capacity);
+    }
+}
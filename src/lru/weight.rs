@@ -0,0 +1,181 @@
+//! Module implémentant la limitation du cache par poids.
+//!
+//! Un « peseur » (`weigher`) attribue un poids à chaque valeur, par exemple
+//! sa taille en octets. Le cache peut alors être borné par un budget de poids
+//! total (`max_weight`), en plus de sa capacité en nombre d'entrées, et
+//! refuser les valeurs individuellement trop lourdes (`max_value_weight`).
+//!
+//! Sans peseur, chaque valeur pèse 1.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::error::CacheError;
+//! use lru_cache::lru::Cache;
+//!
+//! let mut cache = Cache::builder()
+//!     .capacity(100)
+//!     .weigher(|valeur: &String| valeur.len())
+//!     .max_weight(10)
+//!     .max_value_weight(4)
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.try_put(1, "abc".to_string()).unwrap();
+//! assert!(matches!(
+//!     cache.try_put(2, "beaucoup trop long".to_string()),
+//!     Err(CacheError::ValueTooLarge { .. })
+//! ));
+//! assert_eq!(cache.total_weight(), 3);
+//! ```
+
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::events::RemovalCause;
+use crate::lru::stats::TimedOp;
+use crate::messages;
+
+/// Fonction calculant le poids d'une valeur.
+pub(crate) struct Weigher<V>(Arc<dyn Fn(&V) -> usize + Send + Sync>);
+
+impl<V> Weigher<V> {
+    pub(crate) fn new<F>(weigher: F) -> Self
+    where
+        F: Fn(&V) -> usize + Send + Sync + 'static,
+    {
+        Weigher(Arc::new(weigher))
+    }
+
+    pub(crate) fn weigh(&self, value: &V) -> usize {
+        (self.0)(value)
+    }
+}
+
+impl<V> Clone for Weigher<V> {
+    fn clone(&self) -> Self {
+        Weigher(Arc::clone(&self.0))
+    }
+}
+
+impl<V> fmt::Debug for Weigher<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Weigher")
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Ajoute ou met à jour une paire clé-valeur en signalant les refus.
+    ///
+    /// Contrairement à `put`, qui abandonne silencieusement une valeur trop
+    /// lourde, cette méthode laisse le cache inchangé et retourne l'erreur.
+    ///
+    /// # Errors
+    ///
+    /// * `CacheError::KeyTooLarge` ou `CacheError::InvalidKey` si la clé est
+    ///   refusée par les règles configurées
+    /// * `CacheError::ValueTooLarge` si le poids de la valeur dépasse
+    ///   `max_value_weight`
+    /// * `CacheError::CapacityError` si le poids de la valeur dépasse à lui
+    ///   seul le budget total `max_weight`
+    pub fn try_put(&mut self, key: K, value: V) -> Result<(), CacheError> {
+        let start = self.start_timer();
+        let result = self.put_entry(key, value, self.default_ttl, false).map(|_| ());
+        self.record_latency(TimedOp::Put, start);
+        result
+    }
+
+    /// Retourne le poids cumulé des entrées du cache.
+    pub fn total_weight(&self) -> usize {
+        self.total_weight
+    }
+
+    /// Retourne le budget de poids total, s'il est défini.
+    pub fn max_weight(&self) -> Option<usize> {
+        self.max_weight
+    }
+
+    /// Calcule le poids d'une valeur.
+    pub(crate) fn weigh(&self, value: &V) -> usize {
+        self.weigher.as_ref().map_or(1, |weigher| weigher.weigh(value))
+    }
+
+    /// Vérifie qu'une valeur du poids donné peut être admise.
+    pub(crate) fn check_weight(&self, weight: usize) -> Result<(), CacheError> {
+        if let Some(max) = self.max_value_weight {
+            if weight > max {
+                return Err(CacheError::ValueTooLarge { weight, max });
+            }
+        }
+        if let Some(max) = self.max_weight {
+            if weight > max {
+                return Err(CacheError::CapacityError(format!(
+                    "{} ({} > {})",
+                    messages::VALUE_OVER_BUDGET,
+                    weight,
+                    max
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Recalcule le poids de toutes les entrées, supprime celles qui ne
+    /// respectent plus les limites de poids ou de clé et rétablit le budget
+    /// total.
+    pub(crate) fn enforce_limits(&mut self) {
+        let mut rejected = Vec::new();
+        let mut total = 0;
+        for (key, entry) in self.elements.iter_mut() {
+            entry.weight = self.weigher.as_ref().map_or(1, |weigher| weigher.weigh(&entry.value));
+            total += entry.weight;
+            let key_rejected = self.key_checks.iter().any(|check| check.check(key).is_err());
+            if key_rejected || self.max_value_weight.is_some_and(|max| entry.weight > max) {
+                rejected.push(key.clone());
+            }
+        }
+        self.total_weight = total;
+        for key in rejected {
+            self.remove_entry(&key, RemovalCause::Rejected);
+        }
+        self.evict_overweight();
+    }
+
+    /// Évince, en un seul lot, les éléments les moins récemment utilisés
+    /// nécessaires pour revenir dans le budget de poids. L'élément le plus
+    /// récent n'est jamais évincé.
+    pub(crate) fn evict_overweight(&mut self) {
+        let Some(max) = self.max_weight else {
+            return;
+        };
+        if self.sampling.is_some() {
+            while self.total_weight > max && self.elements.len() > 1 {
+                if self.evict_batch(1, RemovalCause::Weight).is_empty() {
+                    break;
+                }
+            }
+            return;
+        }
+        let mut excess = self.total_weight.saturating_sub(max);
+        let mut count = 0;
+        let pinned = self.pins.lock();
+        for key in self.usage_order.iter().take(self.usage_order.len().saturating_sub(1)) {
+            if excess == 0 {
+                break;
+            }
+            if pinned.contains_key(key) {
+                continue;
+            }
+            let weight = self.elements.get(key).map_or(0, |entry| entry.weight);
+            excess = excess.saturating_sub(weight);
+            count += 1;
+        }
+        drop(pinned);
+        self.evict_batch(count, RemovalCause::Weight);
+    }
+}
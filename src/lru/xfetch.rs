@@ -0,0 +1,167 @@
+//! Module implémentant l'expiration anticipée probabiliste (X-Fetch).
+//!
+//! Des entrées insérées ensemble avec la même durée de vie expirent
+//! ensemble : toutes les lectures qui suivent manquent au même moment et
+//! recalculent leurs valeurs d'un seul coup. Avec
+//! `CacheBuilder::early_expiration`, `Cache::get_fresh` peut considérer une
+//! entrée comme expirée un peu avant son échéance, avec une probabilité qui
+//! croît à l'approche de l'échéance et avec le coût du calcul de la valeur.
+//! Les recalculs s'étalent ainsi dans le temps, et ce sont les valeurs les
+//! plus chères qui sont rafraîchies le plus tôt.
+//!
+//! Une lecture est traitée comme un échec lorsque
+//! `coût × beta × -ln(u) >= durée de vie restante`, `u` étant tiré
+//! uniformément dans ]0, 1]. `beta` vaut habituellement 1 ; une valeur plus
+//! grande anticipe davantage. L'entrée elle-même reste en place : seul
+//! l'appelant qui a tiré l'échec la recalcule, les autres lectures
+//! continuent de la servir.
+//!
+//! Le coût d'une entrée est donné par `Cache::put_with_cost`, ou mesuré par
+//! `Cache::get_or_recompute_with`. Une entrée sans coût ni durée de vie
+//! n'est jamais anticipée.
+//!
+//! # Exemple
+//!
+//! ```
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use lru_cache::lru::Cache;
+//! use lru_cache::lru::clock::ManualClock;
+//!
+//! let clock = ManualClock::new();
+//! let mut cache = Cache::builder()
+//!     .capacity(10)
+//!     .early_expiration(1.0)
+//!     .deterministic(7)
+//!     .clock(Arc::new(clock.clone()))
+//!     .build()
+//!     .unwrap();
+//!
+//! cache.put_with_cost("rapport", 1, Duration::from_secs(60), Duration::from_secs(5));
+//! // Loin de l'échéance, l'entrée est servie presque toujours
+//! assert_eq!(cache.get_fresh(&"rapport"), Some(&1));
+//!
+//! // Une seconde avant l'échéance, un calcul de 5 s est presque toujours relancé
+//! clock.advance(Duration::from_secs(59));
+//! let relances = (0..100).filter(|_| cache.get_fresh(&"rapport").is_none()).count();
+//! assert!(relances > 70);
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::time::{Duration, Instant};
+use crate::lru::Cache;
+use crate::lru::deterministic::mix_seed;
+use crate::lru::events::RemovalCause;
+use crate::lru::traits::{CacheRead, CacheTrait};
+
+/// Paramètre et générateur pseudo-aléatoire de l'expiration anticipée.
+#[derive(Debug, Clone)]
+pub(crate) struct EarlyExpiration {
+    beta: f64,
+    state: u64,
+}
+
+impl EarlyExpiration {
+    pub(crate) fn new(beta: f64) -> Self {
+        EarlyExpiration {
+            beta,
+            state: RandomState::new().build_hasher().finish() | 1,
+        }
+    }
+
+    /// Crée un tirage dont les résultats sont dérivés de la graine.
+    pub(crate) fn seeded(beta: f64, seed: u64) -> Self {
+        EarlyExpiration {
+            beta,
+            state: mix_seed(seed) | 1,
+        }
+    }
+
+    /// Indique si `beta` est utilisable : fini et strictement positif.
+    pub(crate) fn is_valid_beta(beta: f64) -> bool {
+        beta.is_finite() && beta > 0.0
+    }
+
+    /// Tire un nombre uniforme dans ]0, 1] (xorshift).
+    fn next_unit(&mut self) -> f64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        1.0 - (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Décide si une entrée au calcul de durée `cost` doit être recalculée
+    /// alors qu'il lui reste `remaining` à vivre.
+    fn is_due(&mut self, cost: Duration, remaining: Duration) -> bool {
+        let gap = cost.as_secs_f64() * self.beta * -self.next_unit().ln();
+        gap >= remaining.as_secs_f64()
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Ajoute ou met à jour une entrée qui expirera après `ttl`, en
+    /// retenant la durée `cost` du calcul de sa valeur pour l'expiration
+    /// anticipée.
+    pub fn put_with_cost(&mut self, key: K, value: V, ttl: Duration, cost: Duration) {
+        if self.put_entry(key.clone(), value, Some(ttl), false).is_err() {
+            self.remove_entry(&key, RemovalCause::Rejected);
+            return;
+        }
+        if let Some(entry) = self.elements.get_mut(&key) {
+            entry.cost = cost;
+        }
+    }
+
+    /// Lit l'entrée comme `get`, mais la traite comme absente, sans la
+    /// supprimer, si l'expiration anticipée décide qu'il est temps de la
+    /// recalculer. Sans `CacheBuilder::early_expiration`, équivaut à `get`.
+    pub fn get_fresh(&mut self, key: &K) -> Option<&V> {
+        if self.is_due_early(key) {
+            self.record_read(false);
+            return None;
+        }
+        self.get(key)
+    }
+
+    /// Retourne la valeur associée à la clé, ou la calcule avec `compute`
+    /// si elle est absente, expirée ou tirée pour un recalcul anticipé. La
+    /// valeur calculée est insérée avec la durée de vie `ttl` et la durée
+    /// mesurée du calcul comme coût.
+    pub fn get_or_recompute_with<F>(&mut self, key: K, ttl: Duration, compute: F) -> Option<&V>
+    where
+        F: FnOnce(&K) -> V,
+    {
+        if !self.is_due_early(&key) && self.contains(&key) {
+            return self.get(&key);
+        }
+        let start = Instant::now();
+        let value = compute(&key);
+        self.put_with_cost(key.clone(), value, ttl, start.elapsed());
+        self.elements.get(&key).map(|entry| &entry.value)
+    }
+
+    /// Retourne le paramètre `beta` de l'expiration anticipée, si elle est
+    /// activée.
+    pub fn early_expiration_beta(&self) -> Option<f64> {
+        self.early_expiration.as_ref().map(|early| early.beta)
+    }
+
+    fn is_due_early(&mut self, key: &K) -> bool {
+        if self.early_expiration.is_none() {
+            return false;
+        }
+        let Some(cost) = self.elements.get(key).map(|entry| entry.cost).filter(|cost| !cost.is_zero()) else {
+            return false;
+        };
+        let Some(remaining) = self.ttl(key) else {
+            return false;
+        };
+        self.early_expiration.as_mut().is_some_and(|early| early.is_due(cost, remaining))
+    }
+}
@@ -0,0 +1,425 @@
+//! Module centralisant les messages affichés et journalisés par la bibliothèque.
+//!
+//! Les messages existent en français (par défaut) et en anglais. La langue
+//! active est choisie à la compilation : activer la feature `english-errors`
+//! remplace les messages français par leur équivalent anglais, ce qui permet
+//! d'aligner la sortie `Display` des erreurs sur la langue des journaux de
+//! l'application.
+//!
+//! Les constantes de la langue active sont réexportées à la racine du module ;
+//! les modules `fr` et `en` restent accessibles quelle que soit la feature.
+//!
+//! # Exemple
+//!
+//! ```
+//! use lru_cache::error::CacheError;
+//! use lru_cache::messages;
+//!
+//! let err = CacheError::Poisoned;
+//! assert_eq!(err.to_string(), messages::POISONED);
+//! ```
+
+/// Messages en français.
+pub mod fr {
+    /// Préfixe des erreurs de capacité
+    pub const CAPACITY_ERROR: &str = "Erreur de capacité";
+    /// Préfixe des erreurs d'entrée/sortie
+    pub const IO_ERROR: &str = "Erreur I/O";
+    /// Préfixe des erreurs de parsing
+    pub const PARSE_ERROR: &str = "Erreur de parsing";
+    /// Fichier de persistance corrompu
+    pub const CORRUPTED_FILE: &str = "Fichier corrompu";
+    /// Données corrompues sans fichier associé
+    pub const CORRUPTED_DATA: &str = "Données corrompues";
+    /// Mot désignant une ligne de fichier
+    pub const LINE: &str = "ligne";
+    /// Clé dépassant la taille maximale
+    pub const KEY_TOO_LARGE: &str = "Clé trop grande";
+    /// Clé refusée par le validateur
+    pub const INVALID_KEY: &str = "Clé refusée";
+    /// Clé ou valeur textuelle contenant une tabulation ou un saut de ligne
+    pub const INVALID_TEXT_ENTRY: &str = "Les clés et valeurs ne doivent contenir ni tabulation ni saut de ligne";
+    /// Valeur dépassant le poids maximal par entrée
+    pub const VALUE_TOO_LARGE: &str = "Valeur trop lourde";
+    /// Valeur dépassant à elle seule le budget de poids total
+    pub const VALUE_OVER_BUDGET: &str = "Le poids de la valeur dépasse le budget total du cache";
+    /// Préfixe des erreurs de sérialisation
+    pub const SERIALIZATION: &str = "Erreur de sérialisation";
+    /// Verrou empoisonné
+    pub const POISONED: &str = "Verrou du cache empoisonné";
+    /// Écriture conditionnelle refusée
+    pub const VERSION_CONFLICT: &str = "Conflit de version";
+    /// Entrée absente lors d'une comparaison de versions
+    pub const ABSENT: &str = "absente";
+    /// Opérations sorties du journal de réplication
+    pub const REPLICATION_GAP: &str = "Opérations absentes du journal de réplication";
+    /// Journal de réplication désactivé
+    pub const REPLICATION_DISABLED: &str = "Le journal de réplication n'est pas activé";
+    /// Chargement trop long abandonné
+    pub const LOAD_TIMEOUT: &str = "Délai de chargement dépassé";
+    /// Chargement en échec
+    pub const LOAD_FAILED: &str = "Échec du chargement";
+    /// Panique du chargeur
+    pub const LOADER_PANICKED: &str = "Le chargeur a paniqué";
+    /// Opération abandonnée à l'échéance fixée par l'appelant
+    pub const DEADLINE_EXCEEDED: &str = "Échéance dépassée";
+    /// Chargement non relancé après un échec récent
+    pub const RECENT_LOAD_FAILURE: &str = "Échec récent du chargement, nouvel essai possible dans";
+    /// Délai avant un nouvel essai de chargement nul
+    pub const ZERO_FAILURE_COOLDOWN: &str = "Le délai avant un nouvel essai de chargement doit être supérieur à 0";
+    /// Paramètre d'expiration anticipée invalide
+    pub const INVALID_EARLY_EXPIRATION: &str = "Le paramètre beta de l'expiration anticipée doit être fini et supérieur à 0";
+    /// Message de synchronisation invalide
+    pub const INVALID_SYNC_MESSAGE: &str = "Message de synchronisation invalide";
+    /// Capacité nulle refusée
+    pub const ZERO_CAPACITY: &str = "La capacité du cache doit être supérieure à 0";
+    /// Redimensionnement à une capacité nulle refusé
+    pub const RESIZE_TO_ZERO: &str = "Impossible de redimensionner le cache à 0";
+    /// Mot désignant la capacité actuelle
+    pub const CURRENT_CAPACITY: &str = "capacité actuelle";
+    /// Budget de poids nul refusé
+    pub const ZERO_WEIGHT: &str = "Le budget de poids du cache doit être supérieur à 0";
+    /// Seuil bas d'éviction incompatible avec la capacité
+    pub const INVALID_WATERMARK: &str = "Le seuil bas d'éviction doit être inférieur à la capacité";
+    /// Fichier de configuration invalide
+    pub const INVALID_CONFIG: &str = "Configuration invalide";
+    /// Extension de fichier de configuration non reconnue
+    pub const UNSUPPORTED_CONFIG_FORMAT: &str = "Format de configuration non reconnu (attendu : .toml, .yaml ou .yml)";
+    /// Région partagée créée avec une autre disposition
+    pub const SHARED_LAYOUT_MISMATCH: &str = "La région partagée existe avec une disposition différente";
+    /// Taille maximale de clé nulle refusée
+    pub const ZERO_KEY_SIZE: &str = "La taille maximale des clés doit être supérieure à 0";
+    /// Durée lisible mal formée
+    pub const INVALID_DURATION: &str = "Durée invalide (attendu par exemple : 500ms, 90s, 2h30m)";
+    /// Capacité absente du constructeur
+    pub const MISSING_CAPACITY: &str = "Aucune capacité n'a été définie sur le constructeur";
+    /// Ligne du fichier de persistance mal formée
+    pub const INVALID_LINE_FORMAT: &str = "Format de ligne invalide";
+    /// En-tête de fichier de persistance mal formé
+    pub const INVALID_FILE_HEADER: &str = "En-tête de fichier invalide";
+    /// Version de format ou codec de fichier non pris en charge
+    pub const UNSUPPORTED_FILE_FORMAT: &str = "Format de fichier non pris en charge";
+    /// Entrée binaire coupée par la fin du fichier
+    pub const TRUNCATED_ENTRY: &str = "Entrée tronquée par la fin du fichier";
+    /// Somme de contrôle des entrées différente de celle de l'en-tête
+    pub const CHECKSUM_MISMATCH: &str = "Somme de contrôle des entrées incorrecte";
+    /// Fichier de compatibilité dont le contenu diffère de la fixture
+    pub const FIXTURE_MISMATCH: &str = "Contenu différent de la fixture, entrée attendue";
+    /// Points de reprise non configurés
+    pub const NO_CHECKPOINTS: &str = "Aucun dossier de points de reprise n'est configuré";
+    /// Point de reprise absent du dossier
+    pub const UNKNOWN_CHECKPOINT: &str = "Point de reprise introuvable";
+    /// Nombre de points de reprise conservés nul
+    pub const ZERO_CHECKPOINTS: &str = "Le nombre de points de reprise conservés doit être supérieur à 0";
+    /// Largeur de tranche de temps nulle
+    pub const ZERO_BUCKET_WIDTH: &str = "La largeur des tranches de temps doit être supérieure à 0";
+    /// Part de portée trop petite, ou total des parts supérieur à 100 %
+    pub const INVALID_SCOPE_SHARE: &str = "La part d'une portée doit lui réserver au moins une entrée et le total des parts ne pas dépasser 100 %";
+    /// Portée absente du cache
+    pub const UNKNOWN_SCOPE: &str = "Portée inconnue";
+    /// Marqueur affiché à la place d'une valeur sensible
+    pub const REDACTED: &str = "«masqué»";
+    /// Nombre de partitions nul ou supérieur à la capacité
+    pub const INVALID_SHARD_COUNT: &str = "Le nombre de partitions doit être compris entre 1 et la capacité";
+    /// Réservation d'emplacements impossible
+    pub const RESERVATION_TOO_LARGE: &str = "Impossible de réserver autant d'emplacements";
+    /// Conseil de dimensionnement : taux de succès pour une capacité
+    pub const HIT_RATIO_AT_CAPACITY: &str = "Taux de succès avec une capacité de";
+    /// Seuil de réduction de la mémoire invalide
+    pub const INVALID_SHRINK_THRESHOLD: &str = "Le seuil de réduction de la mémoire doit être compris entre 0 exclu et 1 inclus";
+    /// Clé impossible à parser
+    pub const UNPARSABLE_KEY: &str = "Impossible de parser la clé";
+    /// Valeur impossible à parser
+    pub const UNPARSABLE_VALUE: &str = "Impossible de parser la valeur";
+    /// Journal : fichier de persistance réparé au chargement
+    pub const LOG_FILE_REPAIRED: &str = "Fichier de persistance réparé au chargement";
+    /// Journal : lecture réussie
+    pub const LOG_HIT: &str = "succès";
+    /// Journal : lecture infructueuse
+    pub const LOG_MISS: &str = "échec";
+    /// Journal : insertion
+    pub const LOG_PUT: &str = "insertion";
+    /// Journal : mise à jour d'une clé existante
+    pub const LOG_UPDATE: &str = "mise à jour";
+    /// Journal : éviction provoquée par une insertion
+    pub const LOG_EVICTION: &str = "éviction";
+    /// Journal : suppression
+    pub const LOG_REMOVE: &str = "suppression";
+    /// Journal : vidage du cache
+    pub const LOG_CLEAR: &str = "vidage";
+    /// Journal : échec d'écriture dans le fichier d'audit
+    pub const LOG_AUDIT_SINK_FAILED: &str = "Échec d'écriture du journal d'audit";
+    /// Journal : échec de la synchronisation réseau
+    pub const LOG_SYNC_FAILED: &str = "Échec de la synchronisation";
+    /// Journal : rechargement du fichier de persistance impossible
+    pub const LOG_RELOAD_FAILED: &str = "Échec du rechargement du fichier de persistance";
+    /// Journal : échec du chargement différé d'une valeur persistée
+    pub const LOG_HYDRATION_FAILED: &str = "Échec du chargement de la valeur persistée";
+    /// Journal : échec d'accès au stockage secondaire
+    pub const LOG_OVERFLOW_FAILED: &str = "Échec d'accès au stockage secondaire";
+    /// Métrique : lectures réussies
+    pub const METRIC_HITS: &str = "Lectures réussies";
+    /// Métrique : lectures infructueuses
+    pub const METRIC_MISSES: &str = "Lectures infructueuses";
+    /// Métrique : nouvelles entrées
+    pub const METRIC_INSERTS: &str = "Nouvelles entrées";
+    /// Métrique : entrées mises à jour
+    pub const METRIC_UPDATES: &str = "Entrées mises à jour";
+    /// Métrique : suppressions explicites
+    pub const METRIC_REMOVALS: &str = "Suppressions explicites";
+    /// Métrique : évictions
+    pub const METRIC_EVICTIONS: &str = "Évictions";
+    /// Métrique : entrées expirées
+    pub const METRIC_EXPIRATIONS: &str = "Entrées expirées";
+    /// Métrique : nombre d'entrées
+    pub const METRIC_SIZE: &str = "Nombre d'entrées";
+    /// Métrique : capacité
+    pub const METRIC_CAPACITY: &str = "Capacité";
+    /// Métrique : poids total des entrées
+    pub const METRIC_WEIGHT: &str = "Poids total des entrées";
+    /// Documentation du format : titre, suivi du numéro de version
+    pub const FORMAT_DOC_TITLE: &str = "Format des fichiers de persistance (version";
+    /// Documentation du format : présentation de l'en-tête
+    pub const FORMAT_DOC_HEADER: &str = "Fichier texte UTF-8. La première ligne est l'en-tête :";
+    /// Documentation du format : présentation des champs de l'en-tête
+    pub const FORMAT_DOC_FIELDS: &str = "Ses champs, séparés par une espace, sont de la forme `nom=valeur` :";
+    /// Documentation du format : colonnes du tableau des champs
+    pub const FORMAT_DOC_COLUMNS: &str = "| Champ | Signification |";
+    /// Champ d'en-tête `version`
+    pub const FORMAT_FIELD_VERSION: &str = "version du format (entier)";
+    /// Champ d'en-tête `codec`
+    pub const FORMAT_FIELD_CODEC: &str = "encodage des entrées (`text` ou `bytes`)";
+    /// Champ d'en-tête `entries`
+    pub const FORMAT_FIELD_ENTRIES: &str = "nombre d'entrées écrites";
+    /// Champ d'en-tête `capacity`
+    pub const FORMAT_FIELD_CAPACITY: &str = "capacité du cache sauvegardé";
+    /// Champ d'en-tête `created`
+    pub const FORMAT_FIELD_CREATED: &str = "date d'écriture, en secondes depuis l'époque Unix";
+    /// Champ d'en-tête `checksum`
+    pub const FORMAT_FIELD_CHECKSUM: &str =
+        "facultatif : FNV-1a 64 bits, en hexadécimal, des lignes d'entrées (sauts de ligne compris)";
+    /// Documentation du format : entrées du codec texte (`{codec}`)
+    pub const FORMAT_DOC_TEXT_ENTRIES: &str = "Avec le codec `{codec}`, chaque ligne suivante est une entrée \
+        `clé<TAB>valeur`, de la moins à la plus récemment utilisée. Clés et valeurs ne contiennent ni \
+        tabulation ni saut de ligne ; les lignes vides sont ignorées.";
+    /// Documentation du format : entrées du codec binaire (`{codec}`)
+    pub const FORMAT_DOC_BYTES_ENTRIES: &str = "Avec le codec `{codec}`, chaque entrée est une ligne \
+        `clé<TAB>longueur`, suivie des `longueur` octets bruts de la valeur puis d'un saut de ligne ; la \
+        somme de contrôle porte sur ces octets, lignes de clés comprises.";
+    /// Documentation du format : compatibilité (`{version}` : version courante)
+    pub const FORMAT_DOC_COMPATIBILITY: &str = "L'en-tête ne contient aucune tabulation. Un fichier dont la \
+        première ligne n'est pas un en-tête est lu comme une suite d'entrées (format antérieur à la \
+        version 1). Les champs d'en-tête inconnus sont ignorés ; une version supérieure à {version} ou un \
+        autre codec est refusé.";
+    /// Documentation du format : fichiers archivés (`{codec}`, `{length}`
+    /// octets d'en-tête, signature `{magic}`)
+    pub const FORMAT_DOC_ARCHIVE: &str = "Un fichier archivé (codec `{codec}`) commence par un en-tête \
+        binaire de {length} octets : la signature `{magic}`, la version (u32), 4 octets réservés, puis le \
+        nombre d'entrées, la capacité et la date d'écriture (u64), tous petit-boutistes, et 8 octets \
+        réservés. L'archive rkyv des entrées suit.";
+}
+
+/// Messages en anglais.
+pub mod en {
+    /// Capacity error prefix
+    pub const CAPACITY_ERROR: &str = "Capacity error";
+    /// I/O error prefix
+    pub const IO_ERROR: &str = "I/O error";
+    /// Parse error prefix
+    pub const PARSE_ERROR: &str = "Parse error";
+    /// Corrupted persistence file
+    pub const CORRUPTED_FILE: &str = "Corrupted file";
+    /// Corrupted data without an associated file
+    pub const CORRUPTED_DATA: &str = "Corrupted data";
+    /// Word for a file line
+    pub const LINE: &str = "line";
+    /// Key over the maximum size
+    pub const KEY_TOO_LARGE: &str = "Key too large";
+    /// Key rejected by the validator
+    pub const INVALID_KEY: &str = "Key rejected";
+    /// Clé ou valeur textuelle contenant une tabulation ou un saut de ligne
+    pub const INVALID_TEXT_ENTRY: &str = "Keys and values must contain neither tabs nor line breaks";
+    /// Value over the per-entry weight limit
+    pub const VALUE_TOO_LARGE: &str = "Value too large";
+    /// Value alone over the total weight budget
+    pub const VALUE_OVER_BUDGET: &str = "Value weight exceeds the total cache budget";
+    /// Serialization error prefix
+    pub const SERIALIZATION: &str = "Serialization error";
+    /// Poisoned lock
+    pub const POISONED: &str = "Cache lock poisoned";
+    /// Conditional write rejected
+    pub const VERSION_CONFLICT: &str = "Version conflict";
+    /// Missing entry in a version comparison
+    pub const ABSENT: &str = "absent";
+    /// Operations no longer in the replication log
+    pub const REPLICATION_GAP: &str = "Operations missing from the replication log";
+    /// Replication log disabled
+    pub const REPLICATION_DISABLED: &str = "The replication log is not enabled";
+    /// Load abandoned for taking too long
+    pub const LOAD_TIMEOUT: &str = "Load timed out";
+    /// Failed load
+    pub const LOAD_FAILED: &str = "Load failed";
+    /// Loader panic
+    pub const LOADER_PANICKED: &str = "The loader panicked";
+    /// Operation abandoned at the caller's deadline
+    pub const DEADLINE_EXCEEDED: &str = "Deadline exceeded";
+    /// Load not retried after a recent failure
+    pub const RECENT_LOAD_FAILURE: &str = "Recent load failure, next attempt possible in";
+    /// Zero delay before a new load attempt
+    pub const ZERO_FAILURE_COOLDOWN: &str = "The delay before a new load attempt must be greater than 0";
+    /// Invalid early expiration parameter
+    pub const INVALID_EARLY_EXPIRATION: &str = "The early expiration beta must be finite and greater than 0";
+    /// Invalid synchronization message
+    pub const INVALID_SYNC_MESSAGE: &str = "Invalid synchronization message";
+    /// Zero capacity rejected
+    pub const ZERO_CAPACITY: &str = "Cache capacity must be greater than 0";
+    /// Resize to zero rejected
+    pub const RESIZE_TO_ZERO: &str = "Cannot resize the cache to 0";
+    /// Word for the current capacity
+    pub const CURRENT_CAPACITY: &str = "current capacity";
+    /// Zero weight budget rejected
+    pub const ZERO_WEIGHT: &str = "Cache weight budget must be greater than 0";
+    /// Low eviction watermark incompatible with the capacity
+    pub const INVALID_WATERMARK: &str = "Low eviction watermark must be below the capacity";
+    /// Invalid configuration file
+    pub const INVALID_CONFIG: &str = "Invalid configuration";
+    /// Unrecognized configuration file extension
+    pub const UNSUPPORTED_CONFIG_FORMAT: &str = "Unrecognized configuration format (expected .toml, .yaml or .yml)";
+    /// Shared region created with another layout
+    pub const SHARED_LAYOUT_MISMATCH: &str = "The shared region exists with a different layout";
+    /// Zero maximum key size rejected
+    pub const ZERO_KEY_SIZE: &str = "Maximum key size must be greater than 0";
+    /// Malformed human-readable duration
+    pub const INVALID_DURATION: &str = "Invalid duration (expected e.g. 500ms, 90s, 2h30m)";
+    /// Capacity missing from the builder
+    pub const MISSING_CAPACITY: &str = "No capacity was set on the builder";
+    /// Malformed persistence file line
+    pub const INVALID_LINE_FORMAT: &str = "Invalid line format";
+    /// Malformed persistence file header
+    pub const INVALID_FILE_HEADER: &str = "Invalid file header";
+    /// Unsupported file format version or codec
+    pub const UNSUPPORTED_FILE_FORMAT: &str = "Unsupported file format";
+    /// Binary entry cut off by the end of the file
+    pub const TRUNCATED_ENTRY: &str = "Entry truncated by the end of the file";
+    /// Entry checksum different from the header's
+    pub const CHECKSUM_MISMATCH: &str = "Incorrect entry checksum";
+    /// Compatibility file whose content differs from the fixture
+    pub const FIXTURE_MISMATCH: &str = "Content differs from the fixture, expected entry";
+    /// Checkpoints not configured
+    pub const NO_CHECKPOINTS: &str = "No checkpoint directory is configured";
+    /// Checkpoint missing from the directory
+    pub const UNKNOWN_CHECKPOINT: &str = "Checkpoint not found";
+    /// Zero number of kept checkpoints
+    pub const ZERO_CHECKPOINTS: &str = "The number of kept checkpoints must be greater than 0";
+    /// Zero time bucket width
+    pub const ZERO_BUCKET_WIDTH: &str = "The width of time buckets must be greater than 0";
+    /// Scope share too small, or shares above 100% in total
+    pub const INVALID_SCOPE_SHARE: &str = "A scope share must reserve at least one entry and the shares must not exceed 100% in total";
+    /// Scope missing from the cache
+    pub const UNKNOWN_SCOPE: &str = "Unknown scope";
+    /// Marker displayed instead of a sensitive value
+    pub const REDACTED: &str = "<redacted>";
+    /// Shard count of zero or above the capacity
+    pub const INVALID_SHARD_COUNT: &str = "The shard count must be between 1 and the capacity";
+    /// Slot reservation impossible
+    pub const RESERVATION_TOO_LARGE: &str = "Cannot reserve that many slots";
+    /// Sizing advice: hit ratio for a capacity
+    pub const HIT_RATIO_AT_CAPACITY: &str = "Hit ratio with a capacity of";
+    /// Invalid memory shrink threshold
+    pub const INVALID_SHRINK_THRESHOLD: &str = "The memory shrink threshold must be greater than 0 and at most 1";
+    /// Key that cannot be parsed
+    pub const UNPARSABLE_KEY: &str = "Cannot parse key";
+    /// Value that cannot be parsed
+    pub const UNPARSABLE_VALUE: &str = "Cannot parse value";
+    /// Log: persistence file repaired on load
+    pub const LOG_FILE_REPAIRED: &str = "Persistence file repaired on load";
+    /// Log: successful read
+    pub const LOG_HIT: &str = "hit";
+    /// Log: unsuccessful read
+    pub const LOG_MISS: &str = "miss";
+    /// Log: insertion
+    pub const LOG_PUT: &str = "insert";
+    /// Log: update of an existing key
+    pub const LOG_UPDATE: &str = "update";
+    /// Log: eviction caused by an insertion
+    pub const LOG_EVICTION: &str = "eviction";
+    /// Log: removal
+    pub const LOG_REMOVE: &str = "remove";
+    /// Log: cache cleared
+    pub const LOG_CLEAR: &str = "clear";
+    /// Log: audit file write failure
+    pub const LOG_AUDIT_SINK_FAILED: &str = "Failed to write the audit log";
+    /// Log: network synchronization failure
+    pub const LOG_SYNC_FAILED: &str = "Synchronization failed";
+    /// Log: persistence file reload failed
+    pub const LOG_RELOAD_FAILED: &str = "Persistence file reload failed";
+    /// Log: deferred loading of a persisted value failed
+    pub const LOG_HYDRATION_FAILED: &str = "Failed to load the persisted value";
+    /// Log: secondary store access failure
+    pub const LOG_OVERFLOW_FAILED: &str = "Failed to access the secondary store";
+    /// Metric: successful reads
+    pub const METRIC_HITS: &str = "Successful reads";
+    /// Metric: unsuccessful reads
+    pub const METRIC_MISSES: &str = "Unsuccessful reads";
+    /// Metric: new entries
+    pub const METRIC_INSERTS: &str = "New entries";
+    /// Metric: updated entries
+    pub const METRIC_UPDATES: &str = "Updated entries";
+    /// Metric: explicit removals
+    pub const METRIC_REMOVALS: &str = "Explicit removals";
+    /// Metric: evictions
+    pub const METRIC_EVICTIONS: &str = "Evictions";
+    /// Metric: expired entries
+    pub const METRIC_EXPIRATIONS: &str = "Expired entries";
+    /// Metric: number of entries
+    pub const METRIC_SIZE: &str = "Number of entries";
+    /// Metric: capacity
+    pub const METRIC_CAPACITY: &str = "Capacity";
+    /// Metric: total weight of the entries
+    pub const METRIC_WEIGHT: &str = "Total weight of the entries";
+    /// Format documentation: title, followed by the version number
+    pub const FORMAT_DOC_TITLE: &str = "Persistence file format (version";
+    /// Format documentation: header introduction
+    pub const FORMAT_DOC_HEADER: &str = "UTF-8 text file. The first line is the header:";
+    /// Format documentation: header fields introduction
+    pub const FORMAT_DOC_FIELDS: &str = "Its fields, separated by a space, have the form `name=value`:";
+    /// Format documentation: columns of the field table
+    pub const FORMAT_DOC_COLUMNS: &str = "| Field | Meaning |";
+    /// `version` header field
+    pub const FORMAT_FIELD_VERSION: &str = "format version (integer)";
+    /// `codec` header field
+    pub const FORMAT_FIELD_CODEC: &str = "entry encoding (`text` or `bytes`)";
+    /// `entries` header field
+    pub const FORMAT_FIELD_ENTRIES: &str = "number of entries written";
+    /// `capacity` header field
+    pub const FORMAT_FIELD_CAPACITY: &str = "capacity of the saved cache";
+    /// `created` header field
+    pub const FORMAT_FIELD_CREATED: &str = "write time, in seconds since the Unix epoch";
+    /// `checksum` header field
+    pub const FORMAT_FIELD_CHECKSUM: &str =
+        "optional: 64-bit FNV-1a, in hexadecimal, of the entry lines (newlines included)";
+    /// Format documentation: text codec entries (`{codec}`)
+    pub const FORMAT_DOC_TEXT_ENTRIES: &str = "With the `{codec}` codec, each following line is a \
+        `key<TAB>value` entry, from least to most recently used. Keys and values contain neither tabs \
+        nor newlines; empty lines are ignored.";
+    /// Format documentation: binary codec entries (`{codec}`)
+    pub const FORMAT_DOC_BYTES_ENTRIES: &str = "With the `{codec}` codec, each entry is a `key<TAB>length` \
+        line, followed by the `length` raw bytes of the value and a newline; the checksum covers these \
+        bytes, key lines included.";
+    /// Format documentation: compatibility (`{version}`: current version)
+    pub const FORMAT_DOC_COMPATIBILITY: &str = "The header contains no tab. A file whose first line is \
+        not a header is read as a sequence of entries (format predating version 1). Unknown header \
+        fields are ignored; a version above {version} or another codec is rejected.";
+    /// Format documentation: archived files (`{codec}`, `{length}` header
+    /// bytes, `{magic}` signature)
+    pub const FORMAT_DOC_ARCHIVE: &str = "An archived file (`{codec}` codec) starts with a {length}-byte \
+        binary header: the `{magic}` signature, the version (u32), 4 reserved bytes, then the number of \
+        entries, the capacity and the write time (u64), all little-endian, and 8 reserved bytes. The \
+        rkyv archive of the entries follows.";
+}
+
+#[cfg(not(feature = "english-errors"))]
+pub use fr::*;
+
+#[cfg(feature = "english-errors")]
+pub use en::*;
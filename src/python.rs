@@ -0,0 +1,179 @@
+//! Module Python de la bibliothèque.
+//!
+//! Disponible avec la fonctionnalité `python`. Le module `lru_cache` expose
+//! la classe `LruCache`, un `SyncCache<String, String>` utilisable comme un
+//! dictionnaire, avec durée de vie optionnelle et persistance au format des
+//! fichiers écrits par les services Rust. Il se construit comme extension
+//! Python avec `maturin build --features python`.
+//!
+//! ```python
+//! from lru_cache import LruCache
+//!
+//! cache = LruCache.open("cache/cache_data.txt", 1000)
+//! cache["session"] = "abc"
+//! cache.put("jeton", "xyz", ttl=30.0)
+//! print(cache.get("absente", "défaut"), len(cache))
+//! cache.persist("cache/cache_data.txt")
+//! ```
+
+use std::time::Duration;
+use pyo3::exceptions::{PyIOError, PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use crate::error::CacheError;
+use crate::lru::Cache;
+use crate::lru::sync::SyncCache;
+use crate::lru::traits::{CacheRead, CacheTrait};
+use crate::messages;
+
+fn to_py_err(err: CacheError) -> PyErr {
+    match err {
+        CacheError::IoError(err) => PyIOError::new_err(err.to_string()),
+        CacheError::Poisoned => PyRuntimeError::new_err(err.to_string()),
+        other => PyValueError::new_err(other.to_string()),
+    }
+}
+
+fn ttl_from_secs(ttl: f64) -> PyResult<Duration> {
+    Duration::try_from_secs_f64(ttl).map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+/// Refuse les textes que le format de persistance ne peut pas écrire.
+fn check_text(text: &str) -> PyResult<()> {
+    if text.contains(['\t', '\n', '\r']) {
+        return Err(PyValueError::new_err(messages::INVALID_TEXT_ENTRY));
+    }
+    Ok(())
+}
+
+/// Cache LRU thread-safe à clés et valeurs textuelles.
+#[pyclass(name = "LruCache", module = "lru_cache")]
+pub struct PyLruCache {
+    inner: SyncCache<String, String>,
+}
+
+#[pymethods]
+impl PyLruCache {
+    /// Crée un cache vide ; `ttl` est la durée de vie par défaut des entrées,
+    /// en secondes.
+    #[new]
+    #[pyo3(signature = (capacity, ttl=None))]
+    fn new(capacity: usize, ttl: Option<f64>) -> PyResult<Self> {
+        let mut cache = Cache::try_new(capacity).map_err(to_py_err)?;
+        cache.set_default_ttl(ttl.map(ttl_from_secs).transpose()?);
+        Ok(PyLruCache { inner: SyncCache::from_cache(cache) })
+    }
+
+    /// Crée un cache chargé depuis un fichier de persistance, s'il existe.
+    #[staticmethod]
+    #[pyo3(signature = (path, capacity, ttl=None))]
+    fn open(path: &str, capacity: usize, ttl: Option<f64>) -> PyResult<Self> {
+        let mut cache = Cache::new_persistent(capacity, path).map_err(to_py_err)?;
+        cache.set_default_ttl(ttl.map(ttl_from_secs).transpose()?);
+        Ok(PyLruCache { inner: SyncCache::from_cache(cache) })
+    }
+
+    /// Capacité maximale du cache.
+    #[getter]
+    fn capacity(&self) -> PyResult<usize> {
+        self.inner.with_lock(|cache| cache.capacity()).map_err(to_py_err)
+    }
+
+    /// Ajoute ou met à jour une entrée, avec une durée de vie propre
+    /// optionnelle, en secondes. Clés et valeurs ne doivent contenir ni
+    /// tabulation ni saut de ligne.
+    #[pyo3(signature = (key, value, ttl=None))]
+    fn put(&self, key: String, value: String, ttl: Option<f64>) -> PyResult<()> {
+        check_text(&key)?;
+        check_text(&value)?;
+        let ttl = ttl.map(ttl_from_secs).transpose()?;
+        self.inner
+            .with_lock(|cache| match ttl {
+                Some(ttl) => cache.put_with_ttl(key, value, ttl),
+                None => cache.put(key, value),
+            })
+            .map_err(to_py_err)
+    }
+
+    /// Retourne la valeur associée à la clé, ou `default`.
+    #[pyo3(signature = (key, default=None))]
+    fn get(&self, key: String, default: Option<String>) -> PyResult<Option<String>> {
+        Ok(self.inner.get(&key).map_err(to_py_err)?.or(default))
+    }
+
+    /// Durée de vie restante de l'entrée, en secondes.
+    fn ttl(&self, key: String) -> PyResult<Option<f64>> {
+        self.inner
+            .with_lock(|cache| cache.ttl(&key).map(|ttl| ttl.as_secs_f64()))
+            .map_err(to_py_err)
+    }
+
+    /// Supprime l'entrée et retourne sa valeur, ou `default`.
+    #[pyo3(signature = (key, default=None))]
+    fn pop(&self, key: String, default: Option<String>) -> PyResult<Option<String>> {
+        Ok(self.inner.with_lock(|cache| cache.remove(&key)).map_err(to_py_err)?.or(default))
+    }
+
+    /// Clés du cache, de la moins à la plus récemment utilisée.
+    fn keys(&self) -> PyResult<Vec<String>> {
+        self.inner
+            .with_lock(|cache| cache.iter().map(|(key, _)| key.clone()).collect())
+            .map_err(to_py_err)
+    }
+
+    /// Paires (clé, valeur), de la moins à la plus récemment utilisée.
+    fn items(&self) -> PyResult<Vec<(String, String)>> {
+        self.inner
+            .with_lock(|cache| cache.iter().map(|(key, value)| (key.clone(), value.clone())).collect())
+            .map_err(to_py_err)
+    }
+
+    /// Vide le cache.
+    fn clear(&self) -> PyResult<()> {
+        self.inner.clear().map_err(to_py_err)
+    }
+
+    /// Sauvegarde le cache dans un fichier de persistance.
+    fn persist(&self, path: &str) -> PyResult<()> {
+        self.inner.with_lock(|cache| cache.persist(path)).map_err(to_py_err)?.map_err(to_py_err)
+    }
+
+    fn __getitem__(&self, key: String) -> PyResult<String> {
+        match self.inner.get(&key).map_err(to_py_err)? {
+            Some(value) => Ok(value),
+            None => Err(PyKeyError::new_err(key)),
+        }
+    }
+
+    fn __setitem__(&self, key: String, value: String) -> PyResult<()> {
+        check_text(&key)?;
+        check_text(&value)?;
+        self.inner.put(key, value).map_err(to_py_err)
+    }
+
+    fn __delitem__(&self, key: String) -> PyResult<()> {
+        match self.inner.with_lock(|cache| cache.remove(&key)).map_err(to_py_err)? {
+            Some(_) => Ok(()),
+            None => Err(PyKeyError::new_err(key)),
+        }
+    }
+
+    fn __contains__(&self, key: String) -> PyResult<bool> {
+        self.inner.with_lock(|cache| cache.contains(&key)).map_err(to_py_err)
+    }
+
+    fn __len__(&self) -> PyResult<usize> {
+        self.inner.len().map_err(to_py_err)
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        self.inner
+            .with_lock(|cache| format!("LruCache(len={}, capacity={})", cache.len(), cache.capacity()))
+            .map_err(to_py_err)
+    }
+}
+
+/// Module Python `lru_cache`.
+#[pymodule]
+fn lru_cache(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyLruCache>()
+}
@@ -72,4 +72,4184 @@ fn test_persistent_cache() -> Result<(), Box<dyn std::error::Error>> {
     // Nettoyage
     fs::remove_file(cache_path)?;
     Ok(())
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_persistent_cache_corrupted_file() {
+    use lru_cache::error::CacheError;
+    use std::fs;
+    let cache_path = "test_cache_corrupted.txt";
+
+    fs::write(cache_path, "1\t100\nligne_invalide\n").unwrap();
+
+    let result = Cache::<i32, i32>::new_persistent(3, cache_path);
+    fs::remove_file(cache_path).unwrap();
+
+    match result {
+        Err(CacheError::Corrupted { path, line, .. }) => {
+            assert_eq!(line, 2);
+            assert_eq!(path.as_deref(), Some(std::path::Path::new(cache_path)));
+        }
+        other => panic!("Erreur inattendue: {:?}", other),
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Tests des erreurs de capacité
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_capacity_errors() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::CacheBuilder;
+
+    assert!(matches!(Cache::<i32, i32>::try_new(0), Err(CacheError::CapacityError(_))));
+    assert!(matches!(
+        Cache::<i32, i32>::new_persistent(0, "test_cache_capacity_absent.txt"),
+        Err(CacheError::CapacityError(_))
+    ));
+    assert!(matches!(
+        CacheBuilder::<i32, i32>::new().build(),
+        Err(CacheError::CapacityError(_))
+    ));
+
+    let mut cache = Cache::new(3);
+    cache.put(1, "one");
+    cache.put(2, "two");
+    cache.put(3, "three");
+    assert!(matches!(cache.resize(0), Err(CacheError::CapacityError(_))));
+    assert_eq!(cache.capacity(), 3);
+
+    cache.resize(1).unwrap();
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&3), Some(&"three"));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Tests du cache thread-safe
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_sync_cache_poison_policies() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::sync::{PoisonPolicy, SyncCache};
+    use std::thread;
+
+    fn poison(cache: &SyncCache<i32, i32>) {
+        let shared = cache.clone();
+        let _ = thread::spawn(move || {
+            shared.with_lock(|_| panic!("panique volontaire")).unwrap();
+        })
+        .join();
+    }
+
+    let propagate = SyncCache::new(2);
+    propagate.put(1, 10).unwrap();
+    poison(&propagate);
+    assert!(matches!(propagate.get(&1), Err(CacheError::Poisoned)));
+
+    let clear = SyncCache::new(2).with_poison_policy(PoisonPolicy::Clear);
+    clear.put(1, 10).unwrap();
+    poison(&clear);
+    assert_eq!(clear.get(&1).unwrap(), None);
+    clear.put(2, 20).unwrap();
+    assert_eq!(clear.get(&2).unwrap(), Some(20));
+
+    let ignore = SyncCache::new(2).with_poison_policy(PoisonPolicy::Ignore);
+    ignore.put(1, 10).unwrap();
+    poison(&ignore);
+    assert_eq!(ignore.get(&1).unwrap(), Some(10));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Tests des messages localisés
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_error_messages_follow_locale() {
+    use lru_cache::messages;
+
+    let err = Cache::<i32, i32>::try_new(0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("{}: {}", messages::CAPACITY_ERROR, messages::ZERO_CAPACITY)
+    );
+
+    #[cfg(feature = "english-errors")]
+    assert_eq!(err.to_string(), "Capacity error: Cache capacity must be greater than 0");
+    #[cfg(not(feature = "english-errors"))]
+    assert_eq!(err.to_string(), "Erreur de capacité: La capacité du cache doit être supérieure à 0");
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Tests des opérations génériques du trait
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_trait_core_operations() {
+    fn exercise<C: CacheTrait<i32, &'static str>>(cache: &mut C) {
+        assert!(cache.is_empty());
+        assert_eq!(cache.capacity(), 2);
+
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.len(), 2);
+
+        // peek ne modifie pas l'ordre : 1 reste le moins récemment utilisé
+        assert_eq!(cache.peek(&1), Some(&"one"));
+        cache.put(3, "three");
+        assert!(!cache.contains(&1));
+
+        assert_eq!(cache.remove(&2), Some("two"));
+        assert_eq!(cache.remove(&2), None);
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    let mut cache = Cache::new(2);
+    exercise(&mut cache);
+}
+
+#[test]
+fn test_read_only_trait() {
+    use lru_cache::lru::traits::CacheRead;
+
+    fn describe<C: CacheRead<i32, &'static str>>(cache: &C, key: i32) -> Option<String> {
+        cache.peek(&key).map(|value| format!("{} -> {}", key, value))
+    }
+
+    let mut cache = Cache::new(2);
+    cache.put(1, "one");
+
+    // Plusieurs emprunts partagés simultanés sont possibles
+    let first = &cache;
+    let second = &cache;
+    assert_eq!(describe(first, 1), Some("1 -> one".to_string()));
+    assert_eq!(describe(&second, 2), None);
+    assert_eq!(CacheRead::len(&first), 1);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Tests d'utilisation des traits comme objets
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_trait_objects() {
+    fn from_config(capacity: usize) -> Box<dyn CacheTrait<String, Vec<u8>>> {
+        Box::new(Cache::new(capacity))
+    }
+
+    fn fill<C: CacheTrait<String, Vec<u8>>>(mut cache: C) {
+        cache.put("a".to_string(), vec![1]);
+        cache.put("b".to_string(), vec![2]);
+    }
+
+    let mut boxed = from_config(1);
+    fill(&mut boxed);
+    assert_eq!(boxed.len(), 1);
+    assert_eq!(boxed.get(&"b".to_string()), Some(&vec![2]));
+
+    let mut concrete = Cache::new(2);
+    fill(&mut concrete);
+    assert_eq!(concrete.len(), 2);
+
+    fill(boxed);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Tests des doublures de cache
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_cache_doubles() {
+    use lru_cache::lru::doubles::{NullCache, UnboundedMapCache};
+
+    fn hits<C: CacheTrait<i32, i32>>(mut cache: C) -> usize {
+        for i in 0..10 {
+            cache.put(i, i);
+        }
+        (0..10).filter(|i| cache.get(i).is_some()).count()
+    }
+
+    assert_eq!(hits(NullCache::new()), 0);
+    assert_eq!(hits(UnboundedMapCache::new()), 10);
+    assert_eq!(hits(Cache::new(4)), 4);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Tests des décorateurs
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_metered_cache_on_custom_impl() {
+    use lru_cache::lru::doubles::UnboundedMapCache;
+    use lru_cache::lru::metered::MeteredCache;
+
+    let mut lru = MeteredCache::new(Cache::new(1));
+    lru.put(1, 10);
+    lru.put(1, 11);
+    lru.put(2, 20);
+    lru.get(&1);
+    lru.get(&2);
+    assert_eq!(lru.remove(&2), Some(20));
+
+    let metrics = lru.metrics();
+    assert_eq!((metrics.inserts, metrics.updates, metrics.evictions), (2, 1, 1));
+    assert_eq!((metrics.hits, metrics.misses, metrics.removals), (1, 1, 1));
+    assert_eq!(metrics.get_latency.count, 2);
+    assert_eq!(metrics.put_latency.count, 3);
+
+    let mut map = MeteredCache::new(UnboundedMapCache::new());
+    map.put("a", 1);
+    map.get(&"a");
+    assert_eq!(map.metrics().hit_ratio(), 1.0);
+    map.reset_metrics();
+    assert_eq!(map.metrics().hits, 0);
+}
+
+#[test]
+fn test_chain_cache_promotes_and_writes_through() {
+    use lru_cache::lru::chain::ChainCache;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut chain: ChainCache<i32, String> = ChainCache::new(vec![
+        Box::new(Cache::new(1)),
+        Box::new(Cache::new(2)),
+        Box::new(Cache::new(3)),
+    ]);
+
+    chain.put(1, "one".to_string());
+    chain.put(2, "two".to_string());
+    chain.put(3, "three".to_string());
+
+    // 1 n'existe plus que dans le dernier niveau
+    assert_eq!(chain.level(0).unwrap().peek(&1), None);
+    assert_eq!(chain.level(1).unwrap().peek(&1), None);
+    assert_eq!(chain.get(&1), Some(&"one".to_string()));
+    assert_eq!(chain.level(0).unwrap().peek(&1), Some(&"one".to_string()));
+    assert_eq!(chain.level(1).unwrap().peek(&1), Some(&"one".to_string()));
+
+    assert_eq!(chain.remove(&1), Some("one".to_string()));
+    assert!(!chain.contains(&1));
+    assert_eq!(chain.get(&42), None);
+}
+
+#[test]
+fn test_frozen_cache_is_shareable_snapshot() {
+    use lru_cache::lru::traits::CacheRead;
+    use std::thread;
+
+    let mut cache = Cache::new(3);
+    cache.put(1, "one".to_string());
+    cache.put(2, "two".to_string());
+    let frozen = cache.freeze();
+
+    cache.put(3, "three".to_string());
+    cache.remove(&1);
+
+    let shared = frozen.clone();
+    let keys = thread::spawn(move || shared.iter().map(|(k, _)| *k).collect::<Vec<_>>())
+        .join()
+        .unwrap();
+    assert_eq!(keys, vec![1, 2]);
+    assert_eq!(frozen.peek(&1), Some(&"one".to_string()));
+    assert!(!frozen.contains(&3));
+    assert_eq!(frozen.capacity(), 3);
+
+    // Un nouveau gel ne copie que les entrées écrites depuis le précédent
+    cache.put(2, "deux".to_string());
+    let refrozen = cache.refreeze(&frozen);
+    let next = cache.refreeze(&refrozen);
+    assert_eq!(refrozen.peek(&2), Some(&"deux".to_string()));
+    assert!(!std::ptr::eq(frozen.peek(&2).unwrap(), refrozen.peek(&2).unwrap()));
+    assert!(refrozen.iter().all(|(key, value)| std::ptr::eq(value, next.peek(key).unwrap())));
+    assert_eq!(next.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![3, 2]);
+}
+
+#[test]
+fn test_refreeze_ignores_reinserted_keys() {
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache = Cache::new(3);
+    cache.put("a", 1);
+    let before = cache.freeze();
+
+    // Une clé retirée puis réinsérée n'est plus celle de l'instantané
+    cache.remove(&"a");
+    cache.put("a", 2);
+    assert_eq!(cache.refreeze(&before).peek(&"a"), Some(&2));
+
+    // Un cache remplacé recommence ses versions : l'instantané de l'ancien
+    // n'est pas repris, même pour une clé de même version
+    cache = Cache::new(3);
+    cache.put("a", 3);
+    let after = cache.refreeze(&before);
+    assert_eq!(after.peek(&"a"), Some(&3));
+    assert_eq!(cache.refreeze(&after).peek(&"a"), Some(&3));
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Tests de la durée de vie des entrées
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_ttl_expiry_and_purge() {
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::traits::CacheRead;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder()
+        .capacity(3)
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+
+    cache.put_with_ttl(1, "one", Duration::from_secs(10));
+    cache.put_with_ttl(2, "two", Duration::from_secs(20));
+    cache.put(3, "three");
+    assert_eq!(cache.ttl(&1), Some(Duration::from_secs(10)));
+    assert_eq!(cache.ttl(&3), None);
+
+    clock.advance(Duration::from_secs(15));
+    assert!(!cache.contains(&1));
+    assert_eq!(cache.purge_expired(), 1);
+    assert_eq!(cache.len(), 2);
+
+    // Une insertion dans un cache plein évince d'abord les entrées expirées
+    cache.put(4, "four");
+    clock.advance(Duration::from_secs(10));
+    cache.put(5, "five");
+    assert_eq!(cache.get(&2), None);
+    assert_eq!(cache.get(&3), Some(&"three"));
+
+    // Une insertion sans durée de vie remplace l'ancienne échéance
+    cache.put_with_ttl(3, "three", Duration::from_secs(1));
+    cache.put(3, "three bis");
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(cache.get(&3), Some(&"three bis"));
+}
+
+#[test]
+fn test_default_ttl_from_builder() {
+    use lru_cache::lru::clock::ManualClock;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder()
+        .capacity(2)
+        .time_to_live(Duration::from_secs(5))
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+
+    cache.put("a", 1);
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(cache.get(&"a"), None);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_refresh_hot_entries() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::refresh::RefreshConfig;
+    use lru_cache::lru::sync::SyncCache;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let cache = SyncCache::from_cache(
+        Cache::builder().capacity(10).clock(Arc::new(clock.clone())).build().unwrap(),
+    );
+    let ttl = Duration::from_secs(60);
+    cache.with_lock(|c| {
+        c.put_with_ttl(1, 10, ttl);
+        c.put_with_ttl(2, 20, ttl);
+    })
+    .unwrap();
+
+    // Seule la clé 1 est populaire
+    for _ in 0..3 {
+        cache.get(&1).unwrap();
+    }
+    cache.get(&2).unwrap();
+
+    let loader = |key: &i32| -> Result<i32, CacheError> { Ok(key * 100) };
+    let config = RefreshConfig::new(ttl).with_window(Duration::from_secs(10)).with_min_hits(3);
+
+    // Trop tôt : aucune entrée n'est dans la fenêtre
+    assert_eq!(cache.refresh_hot_entries(&loader, &config).unwrap(), 0);
+
+    clock.advance(Duration::from_secs(55));
+    assert_eq!(cache.refresh_hot_entries(&loader, &config).unwrap(), 1);
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.get(&1).unwrap(), Some(100));
+    assert_eq!(cache.get(&2).unwrap(), None);
+}
+
+#[test]
+fn test_refresh_skips_entries_idle_since_last_refresh() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::refresh::RefreshConfig;
+    use lru_cache::lru::sync::SyncCache;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let cache = SyncCache::from_cache(
+        Cache::builder().capacity(10).clock(Arc::new(clock.clone())).build().unwrap(),
+    );
+    let ttl = Duration::from_secs(60);
+    cache.with_lock(|c| c.put_with_ttl(1, 10, ttl)).unwrap();
+    for _ in 0..3 {
+        cache.get(&1).unwrap();
+    }
+
+    let loader = |key: &i32| -> Result<i32, CacheError> { Ok(key * 100) };
+    let config = RefreshConfig::new(ttl).with_window(Duration::from_secs(10)).with_min_hits(3);
+
+    clock.advance(Duration::from_secs(55));
+    assert_eq!(cache.refresh_hot_entries(&loader, &config).unwrap(), 1);
+
+    // Les lectures passées ne suffisent plus : la clé délaissée expire
+    clock.advance(Duration::from_secs(55));
+    assert_eq!(cache.refresh_hot_entries(&loader, &config).unwrap(), 0);
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.get(&1).unwrap(), None);
+}
+
+#[test]
+fn test_background_refresher_thread() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::refresh::RefreshConfig;
+    use lru_cache::lru::sync::SyncCache;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let cache = SyncCache::new(10);
+    cache.with_lock(|c| c.put_with_ttl("hot", 1, Duration::from_secs(3600))).unwrap();
+    cache.get(&"hot").unwrap();
+
+    // La fenêtre couvre toute la durée de vie : l'entrée est rafraîchie à chaque passe
+    let config = RefreshConfig::new(Duration::from_secs(3600))
+        .with_window(Duration::from_secs(7200))
+        .with_interval(Duration::from_millis(5));
+    let handle = cache.spawn_refresher(|_: &&str| -> Result<i32, CacheError> { Ok(2) }, config);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while cache.with_lock(|c| c.iter().map(|(_, v)| *v).next()).unwrap() != Some(2) {
+        assert!(Instant::now() < deadline, "l'entrée n'a pas été rafraîchie");
+        thread::sleep(Duration::from_millis(5));
+    }
+    handle.stop();
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Tests de la limitation par poids
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_value_weight_limits() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache = Cache::builder()
+        .capacity(10)
+        .weigher(|value: &Vec<u8>| value.len())
+        .max_weight(10)
+        .max_value_weight(6)
+        .build()
+        .unwrap();
+
+    cache.put(1, vec![0; 4]);
+    cache.put(2, vec![0; 4]);
+    assert_eq!(cache.total_weight(), 8);
+
+    // Une valeur trop lourde est refusée sans toucher au reste du cache
+    let err = cache.try_put(3, vec![0; 7]).unwrap_err();
+    assert!(matches!(err, CacheError::ValueTooLarge { weight: 7, max: 6 }));
+    assert_eq!(cache.len(), 2);
+
+    // Une valeur admissible évince selon le budget total
+    cache.try_put(3, vec![0; 5]).unwrap();
+    assert!(!cache.contains(&1));
+    assert_eq!(cache.total_weight(), 9);
+
+    // Avec put, la mise à jour refusée supprime l'ancienne valeur
+    cache.put(2, vec![0; 8]);
+    assert!(!cache.contains(&2));
+    assert_eq!(cache.total_weight(), 5);
+
+    assert!(matches!(
+        Cache::<i32, Vec<u8>>::builder().capacity(1).max_weight(0).build(),
+        Err(CacheError::CapacityError(_))
+    ));
+}
+
+#[test]
+fn test_key_validation() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache = Cache::builder()
+        .capacity(4)
+        .max_key_length(8)
+        .key_validator(|key: &Vec<u8>| {
+            if key.is_empty() { Err("clé vide".to_string()) } else { Ok(()) }
+        })
+        .build()
+        .unwrap();
+
+    cache.put(b"court".to_vec(), 1);
+    cache.put(vec![b'x'; 1024], 2);
+    assert_eq!(cache.len(), 1);
+
+    match cache.try_put(vec![b'y'; 100], 3) {
+        Err(CacheError::KeyTooLarge { key, size, max }) => {
+            assert_eq!((size, max), (100, 8));
+            assert!(key.len() < 100);
+        }
+        other => panic!("Erreur inattendue: {:?}", other),
+    }
+    assert!(matches!(cache.try_put(Vec::new(), 4), Err(CacheError::InvalidKey { .. })));
+    assert!(cache.contains(&b"court".to_vec()));
+}
+
+#[test]
+fn test_duplicate_policies() {
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::duplicate::{DuplicatePolicy, PutOutcome};
+    use lru_cache::lru::traits::{CacheRead, CacheTrait};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let mut cache = Cache::builder().capacity(2).build().unwrap();
+    assert_eq!(cache.put_with_outcome("a", 1).unwrap(), PutOutcome::Inserted);
+    assert_eq!(cache.put_with_outcome("a", 2).unwrap(), PutOutcome::Replaced(1));
+
+    // La première écriture gagne, sans toucher à l'ordre d'utilisation
+    let clock = ManualClock::new();
+    let mut first_wins = Cache::builder()
+        .capacity(2)
+        .clock(Arc::new(clock.clone()))
+        .on_duplicate(DuplicatePolicy::KeepExisting)
+        .build()
+        .unwrap();
+    first_wins.put_with_ttl("a", 1, Duration::from_secs(10));
+    first_wins.put("b", 2);
+    assert_eq!(first_wins.put_with_outcome("a", 3).unwrap(), PutOutcome::Kept(3));
+    first_wins.put("c", 4);
+    assert!(!first_wins.contains(&"a"));
+    assert_eq!(first_wins.peek(&"b"), Some(&2));
+
+    // Une entrée expirée n'est pas un doublon
+    first_wins.put_with_ttl("d", 5, Duration::from_secs(1));
+    clock.advance(Duration::from_secs(2));
+    assert_eq!(first_wins.put_with_outcome("d", 6).unwrap(), PutOutcome::Inserted);
+    assert_eq!(first_wins.peek(&"d"), Some(&6));
+
+    let mut merged = Cache::builder()
+        .capacity(2)
+        .weigher(|v: &Vec<u8>| v.len())
+        .max_value_weight(4)
+        .on_duplicate(DuplicatePolicy::merge(|current: &mut Vec<u8>, extra| current.extend(extra)))
+        .build()
+        .unwrap();
+    merged.put("k", vec![1, 2]);
+    assert_eq!(merged.put_with_outcome("k", vec![3]).unwrap(), PutOutcome::Merged);
+    assert_eq!(merged.peek(&"k"), Some(&vec![1, 2, 3]));
+    assert_eq!(merged.total_weight(), 3);
+
+    // Une fusion trop lourde supprime l'entrée
+    assert!(merged.put_with_outcome("k", vec![4, 5]).is_err());
+    assert!(!merged.contains(&"k"));
+    assert_eq!(merged.total_weight(), 0);
+}
+
+#[test]
+fn test_transaction_all_or_nothing() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::sync::SyncCache;
+    use lru_cache::lru::traits::{CacheRead, CacheTrait};
+
+    let mut cache = Cache::builder()
+        .capacity(3)
+        .weigher(|v: &u32| *v as usize)
+        .max_value_weight(100)
+        .build()
+        .unwrap();
+    cache.put("a", 1);
+    cache.put("b", 2);
+
+    // Une écriture refusée annule toute la transaction
+    let err = cache
+        .transaction(|tx| {
+            tx.remove(&"a");
+            tx.put("c", 3);
+            tx.put("d", 1000);
+            Ok(())
+        })
+        .unwrap_err();
+    assert!(matches!(err, CacheError::ValueTooLarge { .. }));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.peek(&"a"), Some(&1));
+
+    // Les lectures voient les opérations en attente ; l'éviction a lieu une fois
+    let total = cache
+        .transaction(|tx| {
+            tx.put("c", 3);
+            tx.put("d", 4);
+            tx.remove(&"b");
+            assert_eq!(tx.get(&"b"), None);
+            Ok(tx.get(&"a").copied().unwrap_or(0) + tx.get(&"d").copied().unwrap_or(0))
+        })
+        .unwrap();
+    assert_eq!(total, 5);
+    assert_eq!(cache.len(), 3);
+    assert!(!cache.contains(&"b"));
+    assert_eq!(cache.total_weight(), 1 + 3 + 4);
+
+    let shared = SyncCache::new(2);
+    shared.transaction(|tx| {
+        tx.put(1, "un");
+        tx.put(2, "deux");
+        Ok(())
+    }).unwrap();
+    assert_eq!(shared.len().unwrap(), 2);
+}
+
+#[test]
+fn test_transaction_replaces_expired_entries() {
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::events::{CacheEvent, RemovalCause};
+    use lru_cache::lru::traits::CacheRead;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder()
+        .capacity(4)
+        .clock(Arc::new(clock.clone()))
+        .time_to_live(Duration::from_secs(10))
+        .build()
+        .unwrap();
+    cache.put("a", 1);
+    clock.advance(Duration::from_secs(8));
+    let events = cache.subscribe();
+    clock.advance(Duration::from_secs(4));
+
+    // L'écriture remplace l'entrée expirée au lieu de la mettre à jour
+    cache.transaction(|tx| {
+        tx.put("a", 2);
+        Ok(())
+    })
+    .unwrap();
+    assert!(matches!(
+        events.try_recv().unwrap(),
+        CacheEvent::Removed { key: "a", value: 1, cause: RemovalCause::Expired }
+    ));
+    assert!(matches!(events.try_recv().unwrap(), CacheEvent::Inserted { key: "a", value: 2 }));
+
+    // La nouvelle entrée a sa propre durée de vie
+    clock.advance(Duration::from_secs(8));
+    assert_eq!(cache.peek(&"a"), Some(&2));
+    assert_eq!(cache.iter_older_than(Duration::from_secs(9)).count(), 0);
+}
+
+#[test]
+fn test_optimistic_versions() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::sync::SyncCache;
+    use lru_cache::lru::traits::CacheTrait;
+    use std::thread;
+
+    let cache = SyncCache::new(4);
+    let v1 = cache.put_if_version("k", 1, None).unwrap();
+    assert!(matches!(
+        cache.put_if_version("k", 2, None),
+        Err(CacheError::VersionConflict { expected: None, actual: Some(v) }) if v == v1
+    ));
+
+    // Une suppression puis réinsertion ne réutilise pas la version
+    cache.with_lock(|c| c.remove(&"k")).unwrap();
+    cache.put("k", 1).unwrap();
+    let (_, v2) = cache.get_with_version(&"k").unwrap().unwrap();
+    assert!(v2 > v1);
+    assert!(cache.put_if_version("k", 5, Some(v1)).is_err());
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let cache = cache.clone();
+            thread::spawn(move || {
+                for _ in 0..50 {
+                    loop {
+                        let (value, version) = cache.get_with_version(&"k").unwrap().unwrap();
+                        if cache.put_if_version("k", value + 1, Some(version)).is_ok() {
+                            break;
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(cache.get(&"k").unwrap(), Some(201));
+}
+
+#[test]
+fn test_audit_log_with_file_sink() {
+    use lru_cache::lru::audit::AuditOp;
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::events::RemovalCause;
+    use std::fs;
+    use std::sync::Arc;
+    use std::time::Duration;
+    let audit_path = "test_audit.log";
+    let _ = fs::remove_file(audit_path);
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder()
+        .capacity(2)
+        .clock(Arc::new(clock.clone()))
+        .audit_log(4)
+        .audit_file(audit_path)
+        .build()
+        .unwrap();
+
+    cache.put_with_ttl("session", 1, Duration::from_secs(5));
+    cache.put("session", 2);
+    cache.remove(&"session");
+    cache.put_with_ttl("jeton", 3, Duration::from_secs(5));
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.get(&"jeton"), None);
+
+    // Le tampon ne conserve que les quatre derniers enregistrements
+    let records: Vec<_> = cache.audit_log().map(|r| (r.op, r.key.as_str(), r.cause)).collect();
+    assert_eq!(records, vec![
+        (AuditOp::Update, "\"session\"", None),
+        (AuditOp::Remove, "\"session\"", Some(RemovalCause::Explicit)),
+        (AuditOp::Insert, "\"jeton\"", None),
+        (AuditOp::Remove, "\"jeton\"", Some(RemovalCause::Expired)),
+    ]);
+
+    let lines = fs::read_to_string(audit_path).unwrap();
+    fs::remove_file(audit_path).unwrap();
+    let lines: Vec<_> = lines.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].ends_with("\tinsert\t\"session\"\t-"));
+    assert!(lines[4].ends_with("\tremove\t\"jeton\"\texpired"));
+}
+
+#[test]
+fn test_change_data_capture() {
+    use lru_cache::lru::events::{CacheEvent, RemovalCause};
+    use lru_cache::lru::sync::SyncCache;
+    use std::collections::HashMap;
+    use std::thread;
+
+    let cache = SyncCache::new(2);
+    let events = cache.subscribe().unwrap();
+
+    // Un miroir reconstruit l'état du cache à partir du flux
+    let mirror = thread::spawn(move || {
+        let mut mirror = HashMap::new();
+        for event in events {
+            match event {
+                CacheEvent::Inserted { key, value } | CacheEvent::Updated { key, value } => {
+                    mirror.insert(key, value);
+                }
+                CacheEvent::Removed { key, .. } | CacheEvent::Evicted { key, .. } => {
+                    mirror.remove(&key);
+                }
+            }
+        }
+        mirror
+    });
+
+    let dropped = cache.subscribe().unwrap();
+    drop(dropped);
+
+    cache.put("a", 1).unwrap();
+    cache.put("b", 2).unwrap();
+    cache.put("a", 10).unwrap();
+    cache.put("c", 3).unwrap();
+    cache.with_lock(|c| c.remove(&"a")).unwrap();
+    let removed = cache.with_lock(|c| {
+        let events = c.subscribe();
+        c.clear();
+        events.try_iter().collect::<Vec<_>>()
+    }).unwrap();
+    assert_eq!(removed, vec![CacheEvent::Removed { key: "c", value: 3, cause: RemovalCause::Cleared }]);
+    cache.put("d", 4).unwrap();
+
+    // Le cache abandonné ferme le flux et termine le miroir
+    let expected = cache.with_lock(|c| c.iter().map(|(k, v)| (*k, *v)).collect::<HashMap<_, _>>()).unwrap();
+    drop(cache);
+    assert_eq!(mirror.join().unwrap(), expected);
+}
+
+#[test]
+fn test_slow_subscribers_lose_events_instead_of_buffering() {
+    use lru_cache::lru::events::CacheEvent;
+    use lru_cache::lru::traits::CacheTrait;
+
+    let mut cache = Cache::new(10);
+    let slow = cache.subscribe_bounded(2);
+    for key in 0..5 {
+        cache.put(key, key);
+    }
+    assert_eq!(cache.dropped_events(), 3);
+    let received: Vec<_> = slow.try_iter().collect();
+    assert_eq!(received, vec![CacheEvent::Inserted { key: 0, value: 0 }, CacheEvent::Inserted { key: 1, value: 1 }]);
+
+    // Un tampon vidé reçoit de nouveau les événements
+    cache.put(5, 5);
+    assert_eq!(slow.try_recv(), Ok(CacheEvent::Inserted { key: 5, value: 5 }));
+
+    // Un abonné abandonné est retiré sans compter de perte
+    drop(slow);
+    for key in 6..10 {
+        cache.put(key, key);
+    }
+    assert_eq!(cache.dropped_events(), 3);
+}
+
+#[test]
+fn test_replication_log() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut primary = Cache::new(3);
+    assert!(matches!(
+        primary.export_ops_since(0),
+        Err(CacheError::ReplicationGap { oldest: None, .. })
+    ));
+    primary.enable_replication_log(4);
+
+    let mut standby = Cache::new(3);
+    let mut applied = 0;
+    for round in 0..5 {
+        primary.put(round, round * 10);
+        primary.put(round + 100, round);
+        primary.remove(&(round + 100));
+        applied = standby.apply_ops(primary.export_ops_since(applied).unwrap()).unwrap();
+    }
+    assert_eq!(applied, primary.last_seq());
+    let mut expected: Vec<_> = primary.iter().map(|(k, v)| (*k, *v)).collect();
+    let mut actual: Vec<_> = standby.iter().map(|(k, v)| (*k, *v)).collect();
+    expected.sort();
+    actual.sort();
+    assert_eq!(actual, expected);
+
+    // Un retard supérieur à la taille du journal impose une resynchronisation
+    for key in 0..10 {
+        primary.put(key, key);
+    }
+    assert!(matches!(
+        primary.export_ops_since(applied),
+        Err(CacheError::ReplicationGap { oldest: Some(_), .. })
+    ));
+    assert_eq!(standby.peek(&4), Some(&40));
+
+    // Une séquence hors bornes ne déborde pas
+    assert!(primary.export_ops_since(u64::MAX).unwrap().is_empty());
+    let mut empty: Cache<u32, u32> = Cache::new(1);
+    empty.enable_replication_log(1);
+    assert!(empty.export_ops_since(u64::MAX).unwrap().is_empty());
+}
+
+#[cfg(feature = "tcp-sync")]
+#[test]
+fn test_tcp_sync_bootstrap_and_stream() {
+    use lru_cache::lru::sync::SyncCache;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let primary: SyncCache<String, u32> = SyncCache::new(10);
+    primary.with_lock(|c| c.enable_replication_log(100)).unwrap();
+    primary.put("avant".to_string(), 1).unwrap();
+    let server = primary.serve_replication("127.0.0.1:0", Duration::from_millis(5)).unwrap();
+
+    let replicas: Vec<SyncCache<String, u32>> = (0..2).map(|_| SyncCache::new(10)).collect();
+    let handles: Vec<_> = replicas.iter().map(|r| r.replicate_from(server.local_addr()).unwrap()).collect();
+
+    primary.put("apres".to_string(), 2).unwrap();
+    primary.with_lock(|c| lru_cache::lru::traits::CacheTrait::remove(c, &"avant".to_string())).unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    for replica in &replicas {
+        while replica.with_lock(|c| c.len()).unwrap() != 1 || replica.get(&"apres".to_string()).unwrap().is_none() {
+            assert!(Instant::now() < deadline, "la réplique n'a pas convergé");
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+    drop(handles);
+    server.stop();
+}
+
+#[cfg(all(unix, feature = "signals", feature = "tcp-sync"))]
+#[test]
+fn test_daemon_flushes_and_stops_on_sigterm() {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpStream;
+    use std::process::{Command, Stdio};
+
+    let path = std::env::temp_dir().join(format!("lru_shutdown_{}.txt", std::process::id()));
+    std::fs::write(&path, "a:1\nb:2\n").unwrap();
+    let mut daemon = Command::new(env!("CARGO_BIN_EXE_persistent_cache"))
+        .args(["--daemon", "--flush-secs", "1h", "--listen", "127.0.0.1:0", "--file"])
+        .arg(&path)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Le démon annonce son adresse une fois le cache chargé
+    let mut lines = BufReader::new(daemon.stdout.take().unwrap()).lines();
+    let addr = lines
+        .by_ref()
+        .map(Result::unwrap)
+        .find_map(|line| line.strip_prefix("Réplication servie sur ").map(str::to_string))
+        .unwrap();
+    // Seule la sauvegarde finale peut réécrire le fichier
+    std::fs::remove_file(&path).unwrap();
+
+    let status = Command::new("kill").args(["-TERM", &daemon.id().to_string()]).status().unwrap();
+    assert!(status.success());
+    assert!(daemon.wait().unwrap().success());
+    assert!(lines.map(Result::unwrap).any(|line| line.starts_with("Arrêt")));
+
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(saved.lines().collect::<Vec<_>>(), ["a:1", "b:2"]);
+    // Après l'arrêt, plus aucune réplique n'est servie
+    assert!(TcpStream::connect(&addr).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_cluster_client_consistent_hashing() {
+    use lru_cache::lru::cluster::ClusterClient;
+    use lru_cache::lru::doubles::UnboundedMapCache;
+    use lru_cache::lru::traits::CacheRead;
+
+    fn build(names: &[&str]) -> ClusterClient<u32, u32> {
+        names.iter().fold(ClusterClient::new(), |cluster, name| {
+            cluster.add_node(*name, Box::new(UnboundedMapCache::new()))
+        })
+    }
+
+    let before = build(&["a", "b", "c", "d"]);
+    let after = build(&["a", "b", "c", "d", "e"]);
+
+    // L'ajout d'un nœud ne déplace que les clés qui lui reviennent
+    let moved: Vec<u32> = (0..1000).filter(|k| before.owners(k) != after.owners(k)).collect();
+    assert!(moved.iter().all(|k| after.owners(k) == vec!["e"]));
+    assert!(moved.len() < 400, "{} clés déplacées", moved.len());
+
+    let mut cluster = build(&["a", "b", "c"]).with_replication(2);
+    for key in 0..100 {
+        cluster.put(key, key * 2);
+    }
+    assert_eq!(cluster.len(), 200);
+    let node_a_keys = cluster.node("a").unwrap().len();
+    assert!(node_a_keys > 0 && node_a_keys < 100);
+
+    cluster.remove_node("a");
+    assert!((0..100).all(|key| cluster.get(&key) == Some(&(key * 2))));
+    assert_eq!(cluster.remove(&7), Some(14));
+    assert!(!cluster.contains(&7));
+}
+
+#[test]
+fn test_warm_preserves_real_traffic() {
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache = Cache::builder()
+        .capacity(1000)
+        .weigher(|v: &Vec<u8>| v.len())
+        .max_weight(50)
+        .build()
+        .unwrap();
+    cache.put(0, vec![0; 10]);
+
+    let report = cache.warm((0..10).map(|k| (k, vec![k as u8; 10])));
+    assert_eq!(report.already_present, 1);
+    assert_eq!(report.loaded, 4);
+    assert_eq!(report.not_fitting, 5);
+    assert_eq!(cache.total_weight(), 50);
+    assert_eq!(cache.peek(&0), Some(&vec![0; 10]));
+
+    // Du plus froid au plus chaud : préchargées (la plus importante en
+    // dernier), puis le trafic réel
+    let order: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(order, vec![4, 3, 2, 1, 0]);
+
+    assert!(cache.is_prewarmed(&1));
+    cache.get(&1);
+    assert!(!cache.is_prewarmed(&1));
+    assert!(!cache.is_prewarmed(&0));
+
+    // Un gros lot reste linéaire
+    let mut big = Cache::new(100_000);
+    let report = big.warm((0..100_000).map(|k| (k, k)));
+    assert_eq!(report.loaded, 100_000);
+}
+
+#[test]
+fn test_lazy_hydration_from_persisted_file() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::lazy::LazyCache;
+    use lru_cache::lru::traits::CacheRead;
+    use std::fs;
+
+    let path = "test_lazy.txt";
+    let mut cache: Cache<String, i32> = Cache::new(10);
+    for k in 0..5 {
+        cache.put(format!("k{}", k), k);
+    }
+    cache.persist(path).unwrap();
+
+    // Seules les 4 entrées les plus récentes tiennent dans le cache
+    let mut lazy: LazyCache<String, i32> = LazyCache::open(4, path).unwrap();
+    assert_eq!(lazy.len(), 4);
+    assert_eq!(lazy.pending(), 4);
+    assert!(!lazy.contains(&"k0".to_string()));
+    assert!(lazy.contains(&"k1".to_string()));
+    assert_eq!(lazy.peek(&"k3".to_string()), None);
+
+    assert_eq!(lazy.get(&"k3".to_string()), Some(&3));
+    assert_eq!(lazy.pending(), 3);
+    assert_eq!(lazy.peek(&"k3".to_string()), Some(&3));
+
+    // Les entrées jamais relues cèdent leur place en premier
+    lazy.put("nouveau".to_string(), 100);
+    assert_eq!(lazy.len(), 4);
+    assert!(!lazy.contains(&"k1".to_string()));
+    assert!(lazy.contains(&"k3".to_string()));
+
+    lazy.persist(path).unwrap();
+    let reloaded: Cache<String, i32> = Cache::new_persistent(4, path).unwrap();
+    let order: Vec<_> = reloaded.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    assert_eq!(order, vec![
+        ("k2".to_string(), 2),
+        ("k4".to_string(), 4),
+        ("k3".to_string(), 3),
+        ("nouveau".to_string(), 100),
+    ]);
+
+    // Une valeur illisible n'est détectée qu'à la lecture
+    fs::write(path, "a\t1\nb\tpas_un_nombre\n").unwrap();
+    let mut lazy: LazyCache<String, i32> = LazyCache::open(4, path).unwrap();
+    assert!(matches!(
+        lazy.try_get(&"b".to_string()),
+        Err(CacheError::Corrupted { line: 2, .. })
+    ));
+    assert!(!lazy.contains(&"b".to_string()));
+    assert_eq!(lazy.try_get(&"a".to_string()).unwrap(), Some(&1));
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_persist_top_keeps_working_set() -> Result<(), Box<dyn std::error::Error>> {
+    use lru_cache::lru::traits::CacheRead;
+    use std::fs;
+    let cache_path = "test_cache_top.txt";
+
+    let mut cache = Cache::new(10);
+    for k in 0..6 {
+        cache.put(k, k * 10);
+    }
+    cache.get(&0);
+    cache.persist_top(3, cache_path)?;
+
+    let restored: Cache<i32, i32> = Cache::new_persistent(10, cache_path)?;
+    let order: Vec<_> = restored.iter().map(|(k, _)| *k).collect();
+    assert_eq!(order, vec![4, 5, 0]);
+    assert_eq!(restored.peek(&0), Some(&0));
+
+    cache.persist_top(100, cache_path)?;
+    let restored: Cache<i32, i32> = Cache::new_persistent(10, cache_path)?;
+    assert_eq!(restored.len(), 6);
+
+    fs::remove_file(cache_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_overflow_to_directory_store() {
+    use lru_cache::lru::overflow::{DirectoryStore, StorageBackend};
+    use lru_cache::lru::traits::CacheRead;
+    use std::fs;
+    use std::sync::Arc;
+
+    let dir = "test_overflow_store";
+    let _ = fs::remove_dir_all(dir);
+    let store: Arc<DirectoryStore<String, i32>> = Arc::new(DirectoryStore::open(dir).unwrap());
+    let mut cache = Cache::builder()
+        .capacity(2)
+        .overflow(store.clone())
+        .build()
+        .unwrap();
+
+    for k in 0..4 {
+        cache.put(format!("k{}", k), k);
+    }
+    assert_eq!(cache.len(), 2);
+    assert_eq!(store.fetch(&"k0".to_string()).unwrap(), Some(0));
+    assert_eq!(store.fetch(&"k1".to_string()).unwrap(), Some(1));
+
+    // Le nom du fichier ne dépend que du texte de la clé
+    store.store("alpha".to_string(), 7).unwrap();
+    assert_eq!(fs::read_to_string(format!("{}/8ac625bb85ed202b", dir)).unwrap(), "alpha\t7");
+    store.delete(&"alpha".to_string()).unwrap();
+
+    // Un échec en mémoire consulte le stockage et promeut la valeur
+    assert_eq!(cache.get(&"k0".to_string()), Some(&0));
+    assert_eq!(store.fetch(&"k0".to_string()).unwrap(), None);
+    assert_eq!(store.fetch(&"k2".to_string()).unwrap(), Some(2));
+
+    // Une suppression explicite vaut aussi pour le stockage
+    cache.remove(&"k1".to_string());
+    assert_eq!(cache.get(&"k1".to_string()), None);
+    assert_eq!(cache.get(&"absente".to_string()), None);
+
+    // Un redimensionnement fait aussi déborder les entrées
+    cache.resize(1).unwrap();
+    assert_eq!(cache.peek(&"k3".to_string()), None);
+    assert_eq!(cache.get(&"k3".to_string()), Some(&3));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn test_compressed_cache_fits_more_values() {
+    use lru_cache::lru::compressed::{CompressedCache, Compression};
+
+    let document = |i: usize| format!("{{\"id\": {}, \"tags\": [\"a\", \"b\", \"c\"]}}", i).repeat(40);
+    for compression in [Compression::Lz4, Compression::Zstd(3)] {
+        let builder = Cache::builder().capacity(1000).max_weight(20_000);
+        let mut cache = CompressedCache::new(builder, compression).unwrap();
+        for i in 0..20 {
+            cache.put(i, document(i).as_bytes()).unwrap();
+        }
+        // Non compressées, ces valeurs dépasseraient le budget deux fois
+        assert_eq!(cache.len(), 20);
+        assert_eq!(cache.get_string(&7).unwrap(), Some(document(7)));
+
+        cache.put(100, b"court").unwrap();
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.compressed_entries), (21, 20));
+        assert!(stats.ratio() > 3.0, "{:?}", stats);
+        assert_eq!(cache.get(&100).unwrap(), Some(b"court".to_vec()));
+    }
+}
+
+#[test]
+fn test_bytes_cache_with_custom_codec() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::bytes::{BytesCache, Codec};
+
+    // Codec binaire : coordonnées sur 8 octets
+    struct PointCodec;
+    impl Codec<(i32, i32)> for PointCodec {
+        fn encode(&self, value: &(i32, i32)) -> Result<Vec<u8>, CacheError> {
+            Ok([value.0.to_le_bytes(), value.1.to_le_bytes()].concat())
+        }
+        fn decode(&self, bytes: &[u8]) -> Result<(i32, i32), CacheError> {
+            let bytes: [u8; 8] = bytes.try_into().map_err(|_| CacheError::Serialization("taille".into()))?;
+            let (x, y) = bytes.split_at(4);
+            Ok((i32::from_le_bytes(x.try_into().unwrap()), i32::from_le_bytes(y.try_into().unwrap())))
+        }
+    }
+
+    let builder = Cache::builder().capacity(100).max_weight(20);
+    let mut cache = BytesCache::new(builder, PointCodec).unwrap();
+    cache.put("a", &(1, -2)).unwrap();
+    cache.put("b", &(3, 4)).unwrap();
+    assert_eq!(cache.total_bytes(), 16);
+
+    // Le budget est exact en octets : une troisième valeur évince la première
+    cache.put("c", &(5, 6)).unwrap();
+    assert!(!cache.contains(&"a"));
+    assert_eq!(cache.get::<(i32, i32)>(&"b").unwrap(), Some((3, 4)));
+    assert_eq!(cache.get_bytes(&"c").map(|bytes| bytes.len()), Some(8));
+
+    cache.put_bytes("d", vec![1, 2, 3]).unwrap();
+    assert!(matches!(cache.get::<(i32, i32)>(&"d"), Err(CacheError::Serialization(_))));
+}
+
+#[test]
+fn test_memory_usage_report() {
+    use std::mem::size_of;
+
+    let mut cache: Cache<String, Vec<u64>> = Cache::new(16);
+    let empty = cache.memory_usage();
+    assert_eq!((empty.key_bytes, empty.value_bytes), (0, 0));
+    assert!(empty.map_overhead > 0);
+
+    for k in 0..10 {
+        cache.put(format!("clé{}", k), Vec::with_capacity(100));
+    }
+    let report = cache.memory_usage();
+    assert!(report.exact);
+    assert_eq!(report.value_bytes, 10 * (size_of::<Vec<u64>>() + 800));
+    assert!(report.key_bytes >= 10 * size_of::<String>() + 40);
+    // L'ordre d'utilisation conserve sa propre copie des clés
+    assert!(report.order_bytes >= report.key_bytes - 10 * size_of::<String>());
+
+    let estimate = cache.estimated_memory_usage();
+    assert!(!estimate.exact);
+    assert_eq!(estimate.value_bytes, 10 * size_of::<Vec<u64>>());
+    assert_eq!(estimate.map_overhead, report.map_overhead);
+    assert!(estimate.total() < report.total());
+}
+
+#[test]
+fn test_memory_pressure_shrinks_cache() {
+    use lru_cache::lru::pressure::{CgroupPressure, PressureSignal};
+    use lru_cache::lru::sync::SyncCache;
+    use std::fs;
+    use std::time::Duration;
+
+    let (current, max) = ("test_memory.current", "test_memory.max");
+    fs::write(max, "1000\n").unwrap();
+    fs::write(current, "500\n").unwrap();
+    let mut signal = CgroupPressure::new(0.9, 0.5).with_paths(current, max);
+    assert_eq!(signal.usage(), Some(0.5));
+    assert_eq!(signal.pressure(), None);
+    fs::write(current, "950\n").unwrap();
+    assert_eq!(signal.pressure(), Some(0.5));
+    fs::write(max, "max\n").unwrap();
+    assert_eq!(signal.pressure(), None);
+    fs::remove_file(current).unwrap();
+    fs::remove_file(max).unwrap();
+
+    let cache = SyncCache::new(100);
+    for i in 0..100 {
+        cache.put(i, i).unwrap();
+    }
+    let mut alerts = 1;
+    let handle = cache.set_pressure_handler(
+        move || {
+            alerts -= 1;
+            (alerts == 0).then_some(0.3)
+        },
+        Duration::from_millis(5),
+    );
+    while cache.len().unwrap() == 100 {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    handle.stop();
+    assert_eq!(cache.len().unwrap(), 70);
+    assert_eq!(cache.get(&29).unwrap(), None);
+    assert_eq!(cache.get(&30).unwrap(), Some(30));
+}
+
+#[test]
+fn test_frequency_decay_over_time() {
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::frequency::FrequencyDecay;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder()
+        .capacity(10)
+        .clock(Arc::new(clock.clone()))
+        .frequency_decay(FrequencyDecay::Every(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+    cache.put("autrefois", 0);
+    cache.put("désormais", 0);
+
+    for _ in 0..64 {
+        cache.get(&"autrefois");
+    }
+    // La clé n'est plus lue : son compteur fond à chaque période
+    for _ in 0..6 {
+        clock.advance(Duration::from_secs(60));
+        for _ in 0..3 {
+            cache.get(&"désormais");
+        }
+    }
+    assert_eq!(cache.hit_count(&"autrefois"), Some(1));
+    assert!(cache.hit_count(&"désormais") > cache.hit_count(&"autrefois"));
+
+    cache.decay_frequencies();
+    assert_eq!(cache.hit_count(&"autrefois"), Some(0));
+}
+
+#[test]
+fn test_sliding_window_stats_reveal_traffic_shift() {
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::stats::StatsWindow;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder()
+        .capacity(100)
+        .clock(Arc::new(clock.clone()))
+        .stats_window(StatsWindow::Duration(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+    for k in 0..10 {
+        cache.put(k, k);
+    }
+
+    // Une heure de trafic sur les clés présentes
+    for _ in 0..60 {
+        clock.advance(Duration::from_secs(60));
+        for k in 0..10 {
+            cache.get(&k);
+        }
+    }
+    // Puis le trafic se déplace vers des clés absentes
+    clock.advance(Duration::from_secs(60));
+    for k in 100..150 {
+        cache.get(&k);
+    }
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (600, 50));
+    assert!(stats.hit_ratio() > 0.9);
+    let window = stats.window.unwrap();
+    assert_eq!((window.hits, window.misses), (0, 50));
+    assert_eq!(window.hit_ratio(), 0.0);
+
+    cache.put(0, 1);
+    cache.remove(&1);
+    assert_eq!((cache.stats().inserts, cache.stats().updates, cache.stats().removals), (10, 1, 1));
+
+    // Sans activation, les statistiques restent à zéro
+    let mut plain = Cache::new(1);
+    plain.put(1, 1);
+    plain.get(&1);
+    assert_eq!(plain.stats(), Default::default());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_stats_json_snapshot() {
+    use lru_cache::lru::stats::StatsWindow;
+
+    let mut cache = Cache::builder()
+        .capacity(2)
+        .stats_window(StatsWindow::Ops(10))
+        .build()
+        .unwrap();
+    cache.put("a", 1);
+    cache.get(&"a");
+    cache.get(&"b");
+
+    let json = cache.stats_json().unwrap();
+    assert!(json.starts_with("{\"hits\":1,\"misses\":1,\"inserts\":1,"), "{}", json);
+    assert!(json.contains("\"window\":{\"hits\":1,\"misses\":1}"), "{}", json);
+
+    let plain: Cache<i32, i32> = Cache::new(1);
+    assert!(plain.stats_json().unwrap().contains("\"window\":null"));
+}
+
+#[test]
+fn test_age_and_idle_histograms() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use lru_cache::lru::clock::ManualClock;
+
+    let clock = Arc::new(ManualClock::new());
+    let mut cache = Cache::builder()
+        .capacity(2)
+        .clock(clock.clone())
+        .record_stats()
+        .build()
+        .unwrap();
+
+    cache.put("a", 1);
+    cache.put("b", 2);
+    clock.advance(Duration::from_millis(50));
+    cache.get(&"a");
+    clock.advance(Duration::from_secs(5));
+    cache.get(&"a");
+    cache.put("c", 3); // évince « b », inséré il y a un peu plus de 5 s
+
+    let stats = cache.stats();
+    assert_eq!(stats.idle_at_access.total(), 2);
+    assert_eq!(stats.idle_at_access.fraction_within(Duration::from_millis(100)), 0.5);
+    assert_eq!(stats.eviction_age.total(), 1);
+    assert_eq!(stats.eviction_age.fraction_within(Duration::from_secs(1)), 0.0);
+    assert_eq!(stats.eviction_age.fraction_within(Duration::from_secs(10)), 1.0);
+    let (bound, count) = stats.eviction_age.buckets().find(|&(_, count)| count > 0).unwrap();
+    assert_eq!((bound, count), (Some(Duration::from_secs(10)), 1));
+}
+
+#[test]
+fn test_operation_latency_histograms() {
+    use std::time::Duration;
+    use lru_cache::lru::stats::LatencyHistogram;
+
+    let mut cache = Cache::builder().capacity(10).time_operations().build().unwrap();
+    for i in 0..5 {
+        cache.put(i, i);
+    }
+    cache.try_put(5, 5).unwrap();
+    for i in 0..8 {
+        cache.get(&i);
+    }
+    cache.persist("test_latency.txt").unwrap();
+    std::fs::remove_file("test_latency.txt").unwrap();
+
+    let latency = cache.stats().latency.unwrap();
+    assert_eq!(latency.put.count(), 6);
+    assert_eq!(latency.get.count(), 8);
+    assert_eq!(latency.persist.count(), 1);
+    assert!(latency.persist.max >= latency.persist.mean());
+    assert_eq!(latency.get.buckets().map(|(_, count)| count).sum::<u64>(), 8);
+
+    // Sans mesure des latences, les statistiques n'en contiennent pas
+    let stats_only: Cache<i32, i32> = Cache::builder().capacity(1).record_stats().build().unwrap();
+    assert!(stats_only.stats().latency.is_none());
+
+    // La moyenne reste calculable au-delà de `u32::MAX` mesures
+    let mut histogram = LatencyHistogram::default();
+    histogram.counts[0] = 1 << 32;
+    histogram.total = Duration::from_secs(1 << 32);
+    assert_eq!(histogram.mean(), Duration::from_secs(1));
+}
+
+#[test]
+fn test_lifecycle_hooks() {
+    use std::sync::{Arc, Mutex};
+    use lru_cache::lru::events::RemovalCause;
+    use lru_cache::lru::hooks::CacheHooks;
+
+    #[derive(Default)]
+    struct Journal(Mutex<Vec<String>>);
+
+    impl CacheHooks<&'static str, i32> for Journal {
+        fn on_hit(&self, key: &&'static str, value: &i32) {
+            self.0.lock().unwrap().push(format!("hit {}={}", key, value));
+        }
+
+        fn on_miss(&self, key: &&'static str) {
+            self.0.lock().unwrap().push(format!("miss {}", key));
+        }
+
+        fn on_insert(&self, key: &&'static str, _value: &i32) {
+            self.0.lock().unwrap().push(format!("insert {}", key));
+        }
+
+        fn on_update(&self, key: &&'static str, _value: &i32) {
+            self.0.lock().unwrap().push(format!("update {}", key));
+        }
+
+        fn on_evict(&self, key: &&'static str, _value: &i32) {
+            self.0.lock().unwrap().push(format!("evict {}", key));
+        }
+    }
+
+    struct Removals(Mutex<Vec<RemovalCause>>);
+
+    impl CacheHooks<&'static str, i32> for Removals {
+        fn on_remove(&self, _key: &&'static str, _value: &i32, cause: RemovalCause) {
+            self.0.lock().unwrap().push(cause);
+        }
+    }
+
+    let journal = Arc::new(Journal::default());
+    let removals = Arc::new(Removals(Mutex::new(Vec::new())));
+    let mut cache = Cache::builder().capacity(1).hooks(journal.clone()).build().unwrap();
+    cache.add_hooks(removals.clone());
+
+    cache.put("a", 1);
+    cache.get(&"a");
+    cache.put("a", 2);
+    cache.put("b", 3);
+    cache.get(&"a");
+    cache.remove(&"b");
+
+    assert_eq!(
+        *journal.0.lock().unwrap(),
+        vec!["insert a", "hit a=1", "update a", "evict a", "insert b", "miss a"]
+    );
+    assert_eq!(*removals.0.lock().unwrap(), vec![RemovalCause::Capacity, RemovalCause::Explicit]);
+}
+
+#[test]
+fn test_interceptor_chain() {
+    use std::sync::{Arc, Mutex};
+    use lru_cache::lru::intercept::{InterceptedCache, Interceptor};
+    use lru_cache::lru::traits::CacheRead;
+
+    struct Tenant(&'static str);
+
+    impl Interceptor<String, String> for Tenant {
+        fn map_key(&self, key: &String) -> Option<String> {
+            Some(format!("{}:{}", self.0, key))
+        }
+    }
+
+    struct Veto;
+
+    impl Interceptor<String, String> for Veto {
+        fn on_put(&self, key: &String, value: String) -> Option<String> {
+            if key.ends_with(":interdit") { None } else { Some(value) }
+        }
+    }
+
+    struct Redacted(Arc<Mutex<Vec<String>>>);
+
+    impl Interceptor<String, String> for Redacted {
+        fn on_get(&self, key: &String, value: Option<&String>) {
+            let shown = value.map(|value| "*".repeat(value.len()));
+            self.0.lock().unwrap().push(format!("{} -> {:?}", key, shown));
+        }
+    }
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut cache = InterceptedCache::new(Cache::new(10))
+        .with(Tenant("acme"))
+        .with(Veto)
+        .with(Redacted(log.clone()));
+    assert_eq!(cache.depth(), 3);
+
+    cache.put("jeton".to_string(), "secret".to_string());
+    cache.put("interdit".to_string(), "x".to_string());
+    assert_eq!(cache.get(&"jeton".to_string()), Some(&"secret".to_string()));
+    assert_eq!(cache.get(&"interdit".to_string()), None);
+    assert!(cache.inner().contains(&"acme:jeton".to_string()));
+    assert_eq!(cache.remove(&"jeton".to_string()), Some("secret".to_string()));
+
+    assert_eq!(*log.lock().unwrap(), vec![
+        "acme:jeton -> Some(\"******\")".to_string(),
+        "acme:interdit -> None".to_string(),
+    ]);
+}
+
+#[test]
+fn test_read_through_circuit_breaker() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::breaker::{BreakerState, CircuitBreaker};
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::read_through::ReadThroughCache;
+
+    let clock = Arc::new(ManualClock::new());
+    let up = Rc::new(Cell::new(true));
+    let calls = Rc::new(Cell::new(0));
+    let loader = {
+        let (up, calls) = (up.clone(), calls.clone());
+        move |key: &u32| -> Result<u32, CacheError> {
+            calls.set(calls.get() + 1);
+            if up.get() { Ok(key * 10) } else { Err(CacheError::ParseError("panne".to_string())) }
+        }
+    };
+    let inner = Cache::builder()
+        .capacity(10)
+        .time_to_live(Duration::from_secs(5))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    let mut cache = ReadThroughCache::new(inner, loader)
+        .with_breaker(CircuitBreaker::new(2, Duration::from_secs(30)));
+
+    assert_eq!(cache.get(&1).unwrap(), Some(&10));
+    assert_eq!(cache.get(&1).unwrap(), Some(&10));
+    assert_eq!(calls.get(), 1);
+
+    // La source tombe et l'entrée expire
+    up.set(false);
+    clock.advance(Duration::from_secs(10));
+    assert!(cache.get(&1).is_err());
+    assert!(cache.get(&2).is_err());
+    assert_eq!(cache.breaker().unwrap().state(), BreakerState::Open);
+
+    // Circuit ouvert : valeur périmée ou None, sans appel au chargeur
+    assert_eq!(cache.get(&1).unwrap(), Some(&10));
+    assert_eq!(cache.get(&3).unwrap(), None);
+    assert_eq!(calls.get(), 3);
+
+    // L'appel d'essai échoue : le disjoncteur se rouvre
+    clock.advance(Duration::from_secs(30));
+    assert!(cache.get(&3).is_err());
+    assert_eq!(cache.breaker().unwrap().state(), BreakerState::Open);
+
+    // La source revient : l'appel d'essai referme le disjoncteur
+    up.set(true);
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(cache.get(&3).unwrap(), Some(&30));
+    assert_eq!(cache.breaker().unwrap().state(), BreakerState::Closed);
+    assert_eq!(calls.get(), 5);
+}
+
+#[test]
+fn test_retry_policy_in_read_through() {
+    use std::cell::Cell;
+    use std::io::{Error, ErrorKind};
+    use std::rc::Rc;
+    use std::time::Duration;
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::loader::Loader;
+    use lru_cache::lru::read_through::ReadThroughCache;
+    use lru_cache::lru::retry::RetryPolicy;
+
+    let policy = RetryPolicy::new(3, Duration::from_millis(1))
+        .with_max_backoff(Duration::from_millis(3))
+        .with_jitter(0.5);
+    assert_eq!(policy.backoff(0), Duration::from_millis(1));
+    assert_eq!(policy.backoff(1), Duration::from_millis(2));
+    assert_eq!(policy.backoff(5), Duration::from_millis(3));
+    assert!(policy.is_retryable(&Error::from(ErrorKind::ConnectionReset).into()));
+    assert!(!policy.is_retryable(&CacheError::ParseError("définitif".to_string())));
+
+    let calls = Rc::new(Cell::new(0));
+    let loader = {
+        let calls = calls.clone();
+        move |key: &u32| -> Result<u32, CacheError> {
+            calls.set(calls.get() + 1);
+            match (*key, calls.get()) {
+                (1, 1..=2) => Err(Error::from(ErrorKind::TimedOut).into()),
+                (1, _) => Ok(100),
+                (2, _) => Err(CacheError::ParseError("définitif".to_string())),
+                _ => Err(Error::from(ErrorKind::TimedOut).into()),
+            }
+        }
+    };
+    let mut cache = ReadThroughCache::new(Cache::new(10), loader.with_retry(policy));
+
+    // Deux échecs transitoires puis un succès
+    assert_eq!(cache.get(&1).unwrap(), Some(&100));
+    assert_eq!(calls.get(), 3);
+
+    // Une erreur définitive n'est pas relancée
+    assert!(matches!(cache.get(&2), Err(CacheError::ParseError(_))));
+    assert_eq!(calls.get(), 4);
+
+    // Tentatives épuisées : la dernière erreur est retournée
+    assert!(matches!(cache.get(&3), Err(CacheError::IoError(_))));
+    assert_eq!(calls.get(), 8);
+
+    // Classement personnalisé
+    let strict = RetryPolicy::new(2, Duration::ZERO).retry_if(|err| matches!(err, CacheError::ParseError(_)));
+    assert!(strict.is_retryable(&CacheError::ParseError("x".to_string())));
+    assert!(!strict.is_retryable(&Error::from(ErrorKind::TimedOut).into()));
+}
+
+#[test]
+fn test_load_timeout_serves_stale() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::loader::Loader;
+    use lru_cache::lru::read_through::ReadThroughCache;
+
+    let slow = Arc::new(AtomicBool::new(false));
+    let loader = {
+        let slow = slow.clone();
+        move |key: &u32| -> Result<u32, CacheError> {
+            if slow.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(300));
+            }
+            Ok(key + 1)
+        }
+    };
+    let clock = Arc::new(ManualClock::new());
+    let inner = Cache::builder()
+        .capacity(10)
+        .time_to_live(Duration::from_secs(1))
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+    let mut cache = ReadThroughCache::new(inner, loader.with_timeout(Duration::from_millis(50)))
+        .with_stale_on_timeout();
+
+    assert_eq!(cache.get(&1).unwrap(), Some(&2));
+
+    slow.store(true, Ordering::SeqCst);
+    clock.advance(Duration::from_secs(2));
+    // Valeur expirée servie plutôt que l'erreur
+    assert_eq!(cache.get(&1).unwrap(), Some(&2));
+    // Sans valeur expirée, l'erreur est retournée
+    let err = cache.get(&5).unwrap_err();
+    assert!(matches!(err, CacheError::LoadTimeout { timeout } if timeout == Duration::from_millis(50)));
+}
+
+
+#[test]
+fn test_timeout_loader_bounds_loader_threads() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::loader::Loader;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let loader = {
+        let calls = calls.clone();
+        move |key: &u32| -> Result<u32, CacheError> {
+            calls.fetch_add(1, Ordering::SeqCst);
+            match key {
+                0 => panic!("source indisponible"),
+                1 => Err(CacheError::ParseError("valeur illisible".to_string())),
+                _ => {
+                    thread::sleep(Duration::from_millis(400));
+                    Ok(*key)
+                }
+            }
+        }
+    };
+    let loader = loader.with_timeout(Duration::from_millis(50)).with_max_loads(1);
+
+    // Une panique du chargeur a son erreur propre
+    assert!(matches!(loader.load(&0), Err(CacheError::LoadFailed { .. })));
+    assert!(matches!(loader.load(&1), Err(CacheError::ParseError(_))));
+
+    // Les appels répétés sur une clé lente rejoignent le même chargement,
+    // et aucune place ne se libère pour une autre clé avant le délai
+    for _ in 0..3 {
+        assert!(matches!(loader.load(&2), Err(CacheError::LoadTimeout { .. })));
+    }
+    assert!(matches!(loader.load(&3), Err(CacheError::LoadTimeout { .. })));
+    assert_eq!(loader.loads_in_flight(), 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    thread::sleep(Duration::from_millis(400));
+    assert_eq!(loader.loads_in_flight(), 0);
+    let patient = loader.clone();
+    assert!(matches!(patient.load(&3), Err(CacheError::LoadTimeout { .. })));
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+#[test]
+fn test_dedup_cache_shares_identical_values() {
+    use std::sync::Arc;
+    use lru_cache::lru::dedup::DedupCache;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache = DedupCache::new(Cache::new(4));
+    for i in 0..4 {
+        cache.put(i, format!("fragment-{}", i % 2));
+    }
+    assert_eq!(cache.distinct_values(), 2);
+    assert!(Arc::ptr_eq(&cache.get_shared(&0).unwrap(), &cache.get_shared(&2).unwrap()));
+    assert_eq!(cache.peek(&3), Some(&"fragment-1".to_string()));
+
+    // Une suppression rend une copie tant que la valeur reste partagée
+    assert_eq!(cache.remove(&1), Some("fragment-1".to_string()));
+    assert_eq!(cache.distinct_values(), 2);
+    assert_eq!(cache.remove(&3), Some("fragment-1".to_string()));
+    assert_eq!(cache.distinct_values(), 1);
+
+    // Les valeurs évincées finissent par quitter le registre
+    for i in 10..20 {
+        cache.put(i, format!("unique-{}", i));
+    }
+    assert_eq!(cache.len(), 4);
+    assert_eq!(cache.distinct_values(), 4);
+}
+
+#[test]
+fn test_interned_string_keys() {
+    use std::sync::Arc;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache: Cache<Arc<str>, u32> = Cache::builder().capacity(2).intern_keys().build().unwrap();
+    cache.put_str("session:alpha", 1);
+    let first = cache.iter().next().map(|(key, _)| Arc::clone(key)).unwrap();
+
+    cache.put_str("session:beta", 2);
+    cache.put_str("session:gamma", 3); // évince alpha
+    assert!(!cache.contains(&Arc::from("session:alpha")));
+    cache.put_str("session:alpha", 4);
+
+    // La clé revenue réutilise la chaîne de son premier passage
+    let again = cache.iter().last().map(|(key, _)| Arc::clone(key)).unwrap();
+    assert!(Arc::ptr_eq(&first, &again));
+
+    assert_eq!(cache.get_str("session:alpha"), Some(&4));
+    assert_eq!(cache.peek_str("session:gamma"), Some(&3));
+    assert_eq!(cache.get_str("absente"), None);
+    assert_eq!(cache.remove_str("session:gamma"), Some(3));
+    assert_eq!(cache.len(), 1);
+
+    // Sans registre, les helpers restent disponibles
+    let mut plain: Cache<Arc<str>, u32> = Cache::new(2);
+    plain.put_str("a", 1);
+    assert_eq!(plain.get_str("a"), Some(&1));
+    assert!(plain.key_interner().is_none());
+}
+
+#[test]
+fn test_string_cache_str_lookups() {
+    use std::sync::Arc;
+    use lru_cache::lru::string::StringCache;
+
+    let mut cache = StringCache::from_builder(Cache::builder().capacity(2).record_stats()).unwrap();
+    cache.put("alpha", 1);
+    cache.put("beta", 2);
+    cache.put("alpha", 10);
+
+    let key = String::from("alpha");
+    assert_eq!(cache.get(&key), Some(&10));
+    assert_eq!(cache.get("gamma"), None);
+    cache.put("gamma", 3); // évince beta
+    assert!(!cache.contains("beta"));
+    assert_eq!(cache.iter().collect::<Vec<_>>(), vec![("alpha", &10), ("gamma", &3)]);
+
+    // La table et l'ordre d'utilisation partagent la même chaîne
+    let shared = cache.inner().iter().next().map(|(key, _)| Arc::clone(key)).unwrap();
+    assert_eq!(Arc::strong_count(&shared), 3);
+
+    let stats = cache.inner().stats();
+    assert_eq!((stats.hits, stats.misses, stats.updates), (1, 1, 1));
+    assert_eq!(cache.remove("alpha"), Some(10));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_lookups_with_precomputed_hash() {
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache = Cache::builder().capacity(2).record_stats().build().unwrap();
+    cache.put(("eu".to_string(), 1u32), "a");
+    cache.put(("us".to_string(), 2u32), "b");
+
+    let key = ("eu".to_string(), 1u32);
+    let hash = cache.hash_key(&key);
+    assert_eq!(cache.get_with_hash(hash, &key), Some(&"a"));
+
+    // La lecture par empreinte promeut l'entrée comme `get`
+    cache.put(("ap".to_string(), 3u32), "c");
+    assert!(cache.contains(&key));
+    assert!(!cache.contains(&("us".to_string(), 2u32)));
+
+    // Recherche composite sans construire la clé
+    let region = "ap";
+    let hash = cache.hash_key(&(region.to_string(), 3u32));
+    let (found, value) = cache.find_with_hash(hash, |(r, id)| r == region && *id == 3).unwrap();
+    assert_eq!((found.0.as_str(), *value), ("ap", "c"));
+    assert!(cache.find_with_hash(hash, |(r, _)| r == "eu").is_none());
+
+    let missing = ("xx".to_string(), 0u32);
+    assert_eq!(cache.get_with_hash(cache.hash_key(&missing), &missing), None);
+    assert_eq!((cache.stats().hits, cache.stats().misses), (1, 1));
+}
+
+#[test]
+fn test_composite_key_lookups_by_reference() {
+    let mut cache = Cache::builder().capacity(2).record_stats().build().unwrap();
+    cache.put(("eu".to_string(), 1u32), "a");
+    cache.put(("us".to_string(), 2u32), "b");
+
+    let region = String::from("eu");
+    assert_eq!(cache.get_by(&(region.as_str(), &1)), Some(&"a"));
+    assert_eq!(cache.get_by(&("eu", &2)), None);
+
+    // La lecture empruntée promeut l'entrée comme `get`
+    cache.put(("ap".to_string(), 3u32), "c");
+    assert!(cache.contains_by(&("eu", &1)));
+    assert!(!cache.contains_by(&("us", &2)));
+    assert_eq!(cache.peek_by(&("ap", &3)), Some(&"c"));
+
+    assert_eq!(cache.remove_by(&("ap", &3)), Some("c"));
+    assert_eq!(cache.len(), 1);
+    assert_eq!((cache.stats().hits, cache.stats().misses), (1, 1));
+
+    let mut triples = Cache::new(4);
+    triples.put(("fr".to_string(), "paris".to_string(), 75u8), 1);
+    assert_eq!(triples.get_by(&("fr", "paris", &75)), Some(&1));
+}
+
+#[test]
+fn test_iter_by_age_and_idle_time() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use lru_cache::lru::clock::{Clock, ManualClock};
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder()
+        .capacity(10)
+        .clock(Arc::new(clock.clone()))
+        .track_access_times()
+        .build()
+        .unwrap();
+
+    cache.put("a", 1);
+    cache.put("b", 2);
+    clock.advance(Duration::from_secs(120));
+    cache.put("c", 3);
+    let checkpoint = clock.now();
+    clock.advance(Duration::from_secs(10));
+    cache.get(&"a");
+    cache.put("c", 30);
+
+    let old: Vec<_> = cache.iter_older_than(Duration::from_secs(60)).map(|(k, _)| *k).collect();
+    assert_eq!(old, vec!["b", "a"]);
+    let idle: Vec<_> = cache.iter_idle_since(checkpoint).map(|(k, _)| *k).collect();
+    assert_eq!(idle, vec!["b"]);
+
+    // Les entrées expirées ne sont jamais retournées
+    cache.put_with_ttl("d", 4, Duration::from_secs(1));
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(cache.iter_older_than(Duration::ZERO).count(), 3);
+}
+
+#[test]
+fn test_low_watermark_batch_eviction() {
+    use lru_cache::error::CacheError;
+
+    let mut cache = Cache::builder().capacity(5).low_watermark(2).record_stats().build().unwrap();
+    for i in 0..5 {
+        cache.put(i, i);
+    }
+    assert_eq!(cache.len(), 5);
+
+    cache.put(5, 5);
+    assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![3, 4, 5]);
+    assert_eq!(cache.stats().evictions, 3);
+
+    // Pas d'éviction avant d'atteindre de nouveau le seuil haut
+    cache.put(6, 6);
+    cache.put(7, 7);
+    assert_eq!((cache.len(), cache.stats().evictions), (5, 3));
+
+    cache.resize(2).unwrap();
+    assert_eq!(cache.low_watermark(), Some(1));
+
+    let invalid = Cache::<u32, u32>::builder().capacity(3).low_watermark(3).build();
+    assert!(matches!(invalid, Err(CacheError::CapacityError(_))));
+}
+
+#[test]
+fn test_evict_batch_api_and_weight_eviction() {
+    let mut cache = Cache::builder().capacity(10).record_stats().build().unwrap();
+    for i in 0..5 {
+        cache.put(i, i * 10);
+    }
+    cache.get(&0);
+    assert_eq!(cache.evict(2), vec![(1, 10), (2, 20)]);
+    assert_eq!(cache.evict(10), vec![(3, 30), (4, 40), (0, 0)]);
+    assert!(cache.is_empty());
+
+    // Une grosse insertion évince en un seul lot les entrées nécessaires
+    let mut weighted = Cache::builder()
+        .capacity(10)
+        .weigher(|value: &Vec<u8>| value.len())
+        .max_weight(10)
+        .build()
+        .unwrap();
+    for i in 0..5 {
+        weighted.put(i, vec![0; 2]);
+    }
+    weighted.put(5, vec![0; 7]);
+    assert_eq!(weighted.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![4, 5]);
+    assert_eq!(weighted.total_weight(), 9);
+}
+
+#[test]
+fn test_background_eviction_worker() {
+    use std::time::Duration;
+    use lru_cache::lru::sync::SyncCache;
+
+    let cache = SyncCache::from_cache(Cache::builder().capacity(10).low_watermark(5).build().unwrap());
+    let worker = cache.spawn_eviction_worker(20, Duration::from_secs(3600));
+
+    let writers: Vec<_> = (0..4)
+        .map(|t| {
+            let cache = cache.clone();
+            std::thread::spawn(move || {
+                for i in 0..100 {
+                    cache.put(t * 1000 + i, i).unwrap();
+                    assert!(cache.len().unwrap() <= 30);
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    // À l'arrêt, le cache est ramené sous sa capacité puis évince de nouveau
+    // lui-même
+    worker.stop();
+    assert!(cache.len().unwrap() <= 10);
+    for i in 0..50 {
+        cache.put(10_000 + i, i).unwrap();
+    }
+    assert!(cache.len().unwrap() <= 10);
+}
+
+#[test]
+fn test_sampled_eviction() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::traits::CacheRead;
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder()
+        .capacity(200)
+        .clock(Arc::new(clock.clone()))
+        .sampled_eviction(16)
+        .build()
+        .unwrap();
+    for i in 0..200 {
+        cache.put(i, i);
+        clock.advance(Duration::from_millis(1));
+    }
+    // Les 20 premières clés deviennent les plus récemment utilisées
+    for i in 0..20 {
+        cache.get(&i);
+        clock.advance(Duration::from_millis(1));
+    }
+
+    for i in 200..300 {
+        cache.put(i, i);
+        clock.advance(Duration::from_millis(1));
+    }
+    assert_eq!(cache.len(), 200);
+    assert_eq!(cache.iter().count(), 200);
+    assert_eq!(cache.estimated_memory_usage().order_bytes, 0);
+
+    // L'échantillonnage évince surtout des entrées froides
+    let hot = (0..20).filter(|i| cache.contains(i)).count();
+    assert!(hot >= 15, "seulement {hot} entrées chaudes conservées");
+    assert_eq!(cache.evict(10).len(), 10);
+    assert_eq!(cache.len(), 190);
+}
+
+#[test]
+fn test_clock_pro_resists_scans() {
+    use lru_cache::lru::clock_pro::ClockProCache;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache = ClockProCache::new(100);
+    let mut lru = Cache::new(100);
+    let (mut clock_pro_hits, mut lru_hits) = (0, 0);
+    let mut scan = 1_000_000u64;
+    for round in 0..200u64 {
+        // Ensemble de travail plus petit que le cache...
+        for key in (0..60).map(|i| (i * 7 + round) % 60) {
+            if cache.get(&key).is_some() {
+                clock_pro_hits += 1;
+            } else {
+                cache.put(key, key);
+            }
+            if lru.get(&key).is_some() {
+                lru_hits += 1;
+            } else {
+                lru.put(key, key);
+            }
+        }
+        // ... entrecoupé de balayages qui ne reviennent jamais
+        for _ in 0..80 {
+            scan += 1;
+            cache.put(scan, scan);
+            lru.put(scan, scan);
+        }
+        assert!(cache.len() <= 100);
+        assert!(cache.test_count() <= 100);
+    }
+    assert!(clock_pro_hits > lru_hits * 2, "clock-pro {clock_pro_hits}, lru {lru_hits}");
+
+    assert_eq!(cache.remove(&scan), Some(scan));
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.test_count(), 0);
+}
+
+#[test]
+fn test_clock_pro_single_page_ring() {
+    use lru_cache::lru::clock_pro::{ClockProCache, PageStatus};
+    use lru_cache::lru::traits::CacheRead;
+
+    // Avec une seule page, les trois aiguilles la désignent à la fois
+    let mut cache = ClockProCache::new(1);
+    cache.put(1, 1);
+    cache.put(1, 1);
+    cache.put(0, 0);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.peek(&0), Some(&0));
+    assert_eq!(cache.status(&1), Some(PageStatus::Test));
+
+    for key in 0..1000 {
+        cache.put(key % 3, key);
+        cache.get(&(key % 3));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.test_count() <= 1);
+    }
+}
+
+#[test]
+fn test_adaptive_cache_shifts_bias() {
+    use lru_cache::lru::adaptive::AdaptiveCache;
+    use lru_cache::lru::traits::CacheRead;
+
+    // Charge de récence : des clés revenant peu après leur éviction
+    let mut cache = AdaptiveCache::new(10);
+    for round in 0..20u32 {
+        for key in 0..12 {
+            if cache.get(&(round * 3 + key)).is_none() {
+                cache.put(round * 3 + key, key);
+            }
+        }
+    }
+    let recency = cache.stats();
+    assert!(recency.recency_ghost_hits > 0);
+    assert!(recency.recency_bias > 0.0, "{recency:?}");
+    assert!(cache.len() <= 10);
+
+    // Charge de fréquence : un noyau relu sans cesse, noyé dans un balayage
+    let mut cache = AdaptiveCache::new(10);
+    let mut scan = 1000u32;
+    for key in 0..3 {
+        cache.put(key, key);
+        cache.get(&key);
+    }
+    for _ in 0..200 {
+        for key in 0..3 {
+            if cache.get(&key).is_none() {
+                cache.put(key, key);
+            }
+        }
+        for _ in 0..20 {
+            scan += 1;
+            cache.put(scan, scan);
+        }
+    }
+    let frequency = cache.stats();
+    assert!(frequency.recency_bias < recency.recency_bias, "{frequency:?}");
+    assert!(frequency.hits > 500, "{frequency:?}");
+    assert!(cache.len() <= 10);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[cfg(feature = "otel")]
+#[test]
+fn test_otel_metrics_registration() {
+    use opentelemetry::{global, KeyValue};
+    use lru_cache::lru::otel::{OtelConfig, OtelEvents};
+    use lru_cache::lru::sync::SyncCache;
+
+    let config = OtelConfig::new("test").with_attribute(KeyValue::new("shard", 1));
+    assert_eq!(config.attributes().len(), 2);
+
+    let cache = SyncCache::from_cache(
+        Cache::builder()
+            .capacity(2)
+            .record_stats()
+            .hooks(OtelEvents::new(&config))
+            .build()
+            .unwrap(),
+    );
+    let _metrics = cache.register_otel_metrics(&global::meter("lru_cache_test"), &config);
+
+    // Sans span actif ni fournisseur configuré, l'export est sans effet
+    cache.put(1, "un").unwrap();
+    cache.put(2, "deux").unwrap();
+    cache.put(3, "trois").unwrap();
+    assert_eq!(cache.get(&3).unwrap(), Some("trois"));
+    assert_eq!(cache.with_lock(|cache| cache.stats().evictions).unwrap(), 1);
+}
+
+#[cfg(feature = "config")]
+#[test]
+fn test_cache_from_config_file() {
+    use std::time::Duration;
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::config::{CacheConfig, PolicyKind};
+
+    let dir = std::env::temp_dir().join(format!("lru_config_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let data = dir.join("data.txt");
+    std::fs::write(&data, "a\t1\nb\t2\n").unwrap();
+
+    let toml = dir.join("cache.toml");
+    std::fs::write(
+        &toml,
+        format!("capacity = 3\nttl = 60\nrecord_stats = true\npersistence_path = {:?}\nflush_interval = 5\n", data),
+    )
+    .unwrap();
+    let config = CacheConfig::from_file(&toml).unwrap();
+    assert_eq!(config.flush_interval, Some(Duration::from_secs(5)));
+    let mut cache: Cache<String, u32> = Cache::from_config(&config).unwrap();
+    assert_eq!(cache.get(&"b".to_string()), Some(&2));
+    assert_eq!(cache.stats().hits, 1);
+
+    let yaml = dir.join("cache.yml");
+    std::fs::write(&yaml, "capacity: 50\npolicy: sampled\nsample_size: 4\n").unwrap();
+    let config = CacheConfig::from_file(&yaml).unwrap();
+    assert_eq!((config.policy, config.sample_size), (PolicyKind::Sampled, Some(4)));
+    let cache: Cache<u32, u32> = config.builder().build().unwrap();
+    assert_eq!(cache.eviction_sample_size(), Some(4));
+
+    let config = CacheConfig::from_toml("capacity = 1\nttl = \"2h30m\"\nflush_interval = \"500ms\"").unwrap();
+    assert_eq!(config.ttl, Some(Duration::from_secs(9000)));
+    assert_eq!(config.flush_interval, Some(Duration::from_millis(500)));
+    assert!(matches!(CacheConfig::from_toml("capacity = 1\nttl = \"bientôt\""), Err(CacheError::ParseError(_))));
+    assert!(matches!(CacheConfig::from_toml("capacity = 1\nunknown = 2"), Err(CacheError::ParseError(_))));
+    assert!(matches!(CacheConfig::from_file(dir.join("cache.ini")), Err(CacheError::ParseError(_))));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_change_default_ttl_at_runtime() {
+    use lru_cache::lru::clock::ManualClock;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder().capacity(10).clock(Arc::new(clock.clone())).build().unwrap();
+    cache.put("a", 1);
+    assert_eq!(cache.default_ttl(), None);
+
+    cache.set_default_ttl(Some(Duration::from_secs(10)));
+    cache.put("b", 2);
+    clock.advance(Duration::from_secs(11));
+    assert_eq!(cache.get(&"a"), Some(&1));
+    assert_eq!(cache.get(&"b"), None);
+}
+
+#[cfg(feature = "shared-memory")]
+#[test]
+fn test_shared_memory_cache_across_mappings() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::shared::{SharedCache, SharedLayout};
+
+    let path = std::env::temp_dir().join(format!("lru_shared_{}", std::process::id()));
+    let layout = SharedLayout::new(3, 8, 16);
+    let first = SharedCache::open(&path, layout).unwrap();
+    let second = SharedCache::open(&path, layout).unwrap();
+
+    first.put(b"a", b"1").unwrap();
+    first.put(b"b", b"2").unwrap();
+    first.put(b"c", b"3").unwrap();
+    assert_eq!(second.get(b"a").as_deref(), Some(&b"1"[..]));
+
+    // "b" est la moins récemment utilisée depuis la lecture de "a"
+    second.put(b"d", b"4").unwrap();
+    assert!(!first.contains(b"b"));
+    assert_eq!(first.len(), 3);
+    assert_eq!(first.remove(b"c").as_deref(), Some(&b"3"[..]));
+    assert!(matches!(first.put(b"trop longue", b"x"), Err(CacheError::KeyTooLarge { .. })));
+    assert!(matches!(
+        SharedCache::open(&path, SharedLayout::new(4, 8, 16)),
+        Err(CacheError::ParseError(_))
+    ));
+
+    let threads: Vec<_> = (0..4u8)
+        .map(|t| {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                let cache = SharedCache::open(&path, layout).unwrap();
+                for i in 0..200u8 {
+                    cache.put(&[t, i], &[i]).unwrap();
+                    cache.get(&[t, i.wrapping_sub(1)]);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(first.len(), 3);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_reload_from_file_conflict_policies() {
+    use lru_cache::lru::reload::ConflictPolicy;
+    use lru_cache::lru::traits::CacheRead;
+
+    let path = std::env::temp_dir().join(format!("lru_reload_{}.txt", std::process::id()));
+    let mut cache: Cache<String, i32> = Cache::new(10);
+    cache.put("a".to_string(), 1);
+    cache.put("gone".to_string(), 0);
+    cache.persist(&path).unwrap();
+    assert_eq!(cache.dirty_keys().count(), 0);
+
+    cache.put("local".to_string(), 5);
+    std::fs::write(&path, "a\t10\nnew\t20\n").unwrap();
+    assert_eq!(cache.reload_from_file(&path, ConflictPolicy::KeepMemory).unwrap(), 2);
+    assert_eq!(cache.peek(&"a".to_string()), Some(&10));
+    assert_eq!(cache.peek(&"new".to_string()), Some(&20));
+    assert_eq!(cache.peek(&"local".to_string()), Some(&5));
+    assert!(!cache.contains(&"gone".to_string()));
+    // L'entrée conservée n'est toujours pas dans le fichier
+    assert_eq!(cache.dirty_keys().collect::<Vec<_>>(), vec!["local"]);
+
+    // Un fichier invalide laisse le cache intact
+    std::fs::write(&path, "a\t10\nbroken line\n").unwrap();
+    assert!(cache.reload_from_file(&path, ConflictPolicy::PreferFile).is_err());
+    assert_eq!(cache.len(), 3);
+
+    std::fs::write(&path, "a\t11\n").unwrap();
+    cache.reload_from_file(&path, ConflictPolicy::PreferFile).unwrap();
+    assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&"a".to_string(), &11)]);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn test_watch_persisted_file_reloads_on_change() {
+    use lru_cache::lru::reload::ConflictPolicy;
+    use lru_cache::lru::sync::SyncCache;
+    use std::time::{Duration, Instant};
+
+    let dir = std::env::temp_dir().join(format!("lru_watch_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("cache.txt");
+    std::fs::write(&path, "a\t1\n").unwrap();
+
+    let cache: SyncCache<String, i32> = SyncCache::new(10);
+    let watcher = cache.watch_persisted_file(&path, ConflictPolicy::PreferFile).unwrap();
+
+    // Régénération par renommage, comme le ferait un outil externe
+    let tmp = dir.join("cache.txt.tmp");
+    std::fs::write(&tmp, "a\t2\nb\t3\n").unwrap();
+    std::fs::rename(&tmp, &path).unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while cache.get(&"b".to_string()).unwrap() != Some(3) {
+        assert!(Instant::now() < deadline, "fichier non rechargé");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(cache.get(&"a".to_string()).unwrap(), Some(2));
+    watcher.stop();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn test_ffi_round_trip_and_persisted_file() {
+    use lru_cache::ffi::*;
+    use lru_cache::lru::traits::CacheRead;
+    use std::ffi::CString;
+
+    let path = std::env::temp_dir().join(format!("lru_ffi_{}.txt", std::process::id()));
+    let c_path = CString::new(path.to_str().unwrap()).unwrap();
+    unsafe {
+        let cache = lru_cache_new(2);
+        assert_eq!(lru_cache_put(cache, b"a".as_ptr(), 1, b"un".as_ptr(), 2), LRU_CACHE_OK);
+        assert_eq!(lru_cache_put(cache, b"k\t".as_ptr(), 2, b"v".as_ptr(), 1), LRU_CACHE_INVALID_ARGUMENT);
+
+        let (mut value, mut len) = (std::ptr::null_mut(), 0);
+        assert_eq!(lru_cache_get(cache, b"a".as_ptr(), 1, &mut value, &mut len), LRU_CACHE_OK);
+        assert_eq!(std::slice::from_raw_parts(value, len), b"un");
+        lru_cache_free_value(value, len);
+        assert_eq!(lru_cache_get(cache, b"z".as_ptr(), 1, &mut value, &mut len), LRU_CACHE_NOT_FOUND);
+
+        assert_eq!(lru_cache_persist(cache, c_path.as_ptr()), LRU_CACHE_OK);
+        assert_eq!(lru_cache_remove(cache, b"a".as_ptr(), 1), LRU_CACHE_OK);
+        assert_eq!(lru_cache_len(cache), 0);
+        lru_cache_free(cache);
+
+        // Le fichier est celui d'un cache Rust
+        let rust: Cache<String, String> = Cache::new_persistent(2, &path).unwrap();
+        assert_eq!(rust.peek(&"a".to_string()).map(String::as_str), Some("un"));
+        let reopened = lru_cache_open(c_path.as_ptr(), 2);
+        assert_eq!(lru_cache_len(reopened), 1);
+        lru_cache_free(reopened);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_python_rejects_tabs_and_line_breaks() {
+    use lru_cache::python::PyLruCache;
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+
+    Python::initialize();
+    Python::attach(|py| {
+        let globals = PyDict::new(py);
+        globals.set_item("LruCache", py.get_type::<PyLruCache>()).unwrap();
+        py.run(
+            c"
+cache = LruCache(4)
+for key, value in [('a\\tb', 'v'), ('k', 'v\\n'), ('k\\r', 'v')]:
+    for store in (cache.put, cache.__setitem__):
+        try:
+            store(key, value)
+        except ValueError:
+            pass
+        else:
+            raise AssertionError(repr((key, value)))
+cache['k'] = 'v'
+assert cache.items() == [('k', 'v')]
+",
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_python_bindings_dict_api() {
+    use lru_cache::python::PyLruCache;
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+
+    Python::initialize();
+    Python::attach(|py| {
+        let locals = PyDict::new(py);
+        locals.set_item("LruCache", py.get_type::<PyLruCache>()).unwrap();
+        py.run(
+            c"
+cache = LruCache(2)
+cache['a'] = '1'
+cache['b'] = '2'
+assert cache['a'] == '1'
+cache['c'] = '3'
+assert 'b' not in cache and len(cache) == 2
+assert cache.get('b', 'absente') == 'absente'
+cache.put('t', 'v', ttl=60.0)
+assert 0 < cache.ttl('t') <= 60
+del cache['t']
+try:
+    cache['t']
+    raise AssertionError('KeyError attendue')
+except KeyError:
+    pass
+assert cache.items() == [('c', '3')]
+",
+            None,
+            Some(&locals),
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_human_readable_durations() {
+    use std::time::Duration;
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::duration::{parse_duration, HumanDuration};
+
+    assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+    assert_eq!(parse_duration("2h30m").unwrap(), Duration::from_secs(9000));
+    assert_eq!(parse_duration("1m 30s").unwrap(), Duration::from_secs(90));
+    assert_eq!(parse_duration("45").unwrap(), Duration::from_secs(45));
+    assert_eq!(parse_duration("250µs").unwrap(), Duration::from_micros(250));
+    for invalid in ["", "ms", "10x", "1.5s", "-3s"] {
+        assert!(matches!(parse_duration(invalid), Err(CacheError::ParseError(_))), "{}", invalid);
+    }
+
+    for duration in [Duration::ZERO, Duration::from_secs(9000), Duration::new(86_401, 1_500)] {
+        let text = HumanDuration(duration).to_string();
+        assert_eq!(parse_duration(&text).unwrap(), duration, "{}", text);
+    }
+    assert_eq!(HumanDuration(Duration::from_secs(9000)).to_string(), "2h30m");
+    assert_eq!(HumanDuration(Duration::from_micros(1500)).to_string(), "1ms500us");
+
+    let cache: Cache<&str, i32> = Cache::builder().capacity(2).time_to_live_str("2h30m").build().unwrap();
+    assert_eq!(cache.default_ttl(), Some(Duration::from_secs(9000)));
+    let invalid = Cache::<&str, i32>::builder().capacity(2).time_to_live_str("bientôt").build();
+    assert!(matches!(invalid, Err(CacheError::ParseError(_))));
+}
+
+#[test]
+fn test_describe_persisted_file_header() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::format::{describe, format_documentation, FORMAT_VERSION, TEXT_CODEC};
+    use lru_cache::lru::lazy::LazyCache;
+
+    let path = std::env::temp_dir().join(format!("lru_describe_{}.txt", std::process::id()));
+    let mut cache: Cache<String, i32> = Cache::new(50);
+    for k in 0..3 {
+        cache.put(format!("k{}", k), k);
+    }
+    cache.persist(&path).unwrap();
+
+    let description = describe(&path).unwrap().unwrap();
+    assert_eq!(description.format_version, FORMAT_VERSION);
+    assert_eq!(description.codec, TEXT_CODEC);
+    assert_eq!((description.entries, description.capacity), (3, 50));
+    assert!(description.created <= std::time::SystemTime::now());
+
+    // L'en-tête est ignoré au chargement, paresseux ou non
+    let reloaded: Cache<String, i32> = Cache::new_persistent(50, &path).unwrap();
+    assert_eq!(reloaded.len(), 3);
+    let mut lazy: LazyCache<String, i32> = LazyCache::open(50, &path).unwrap();
+    assert_eq!(lazy.get(&"k1".to_string()), Some(&1));
+
+    cache.persist_top(2, &path).unwrap();
+    assert_eq!(describe(&path).unwrap().unwrap().entries, 2);
+
+    // Fichier écrit avant l'introduction de l'en-tête
+    std::fs::write(&path, "a\t1\n").unwrap();
+    assert_eq!(describe(&path).unwrap(), None);
+    assert_eq!(Cache::<String, i32>::new_persistent(5, &path).unwrap().len(), 1);
+
+    // Version future : refusée par describe comme par le chargement
+    std::fs::write(&path, "#lru_cache version=99 codec=text entries=0 capacity=1 created=0\n").unwrap();
+    assert!(matches!(describe(&path), Err(CacheError::Corrupted { line: 1, .. })));
+    assert!(matches!(Cache::<String, i32>::new_persistent(5, &path), Err(CacheError::Corrupted { line: 1, .. })));
+
+    assert!(format_documentation().contains(&format!("version {}", FORMAT_VERSION)));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn test_archived_persistence_zero_copy_reads() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::archived::ArchivedCacheFile;
+    use lru_cache::lru::format::{describe, ARCHIVE_CODEC};
+    use lru_cache::lru::traits::CacheRead;
+
+    #[derive(Debug, Clone, PartialEq, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+    struct Profil {
+        nom: String,
+        scores: Vec<u32>,
+    }
+
+    let path = std::env::temp_dir().join(format!("lru_archived_{}.rkyv", std::process::id()));
+    let mut cache: Cache<String, Profil> = Cache::new(3);
+    for (nom, score) in [("ada", 1), ("bob", 2), ("eve", 3)] {
+        cache.put(nom.to_string(), Profil { nom: nom.to_string(), scores: vec![score; 4] });
+    }
+    cache.get(&"ada".to_string());
+    cache.persist_archived(&path).unwrap();
+
+    let archive = ArchivedCacheFile::<String, Profil>::open(&path).unwrap();
+    assert_eq!((archive.len(), archive.capacity()), (3, 3));
+    let bob = archive.get("bob").unwrap();
+    assert_eq!(bob.nom, "bob");
+    assert_eq!(bob.scores.iter().map(|s| s.to_native()).sum::<u32>(), 8);
+    assert!(!archive.contains("zoe"));
+    let order: Vec<&str> = archive.iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(order, ["bob", "eve", "ada"]);
+
+    // Une nouvelle sauvegarde ne perturbe pas la projection ouverte
+    cache.put("zoe".to_string(), Profil { nom: "zoe".to_string(), scores: Vec::new() });
+    cache.persist_archived(&path).unwrap();
+    assert_eq!(archive.get("bob").unwrap().nom, "bob");
+
+    let reloaded = ArchivedCacheFile::<String, Profil>::open(&path).unwrap().to_cache().unwrap();
+    assert_eq!(reloaded.peek(&"zoe".to_string()).map(|p| p.scores.len()), Some(0));
+    assert_eq!(describe(&path).unwrap().unwrap().codec, ARCHIVE_CODEC);
+
+    // Un fichier texte n'est pas une archive
+    std::fs::write(&path, "ada\t1\n").unwrap();
+    assert!(matches!(ArchivedCacheFile::<String, Profil>::open(&path), Err(CacheError::Corrupted { .. })));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_checkpoint_rollback_and_pruning() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::traits::CacheRead;
+
+    let dir = std::env::temp_dir().join(format!("lru_checkpoints_{}", std::process::id()));
+    let mut cache: Cache<String, i32> = Cache::builder().capacity(5).checkpoints(&dir, 2).build().unwrap();
+    cache.put("a".to_string(), 1);
+    cache.put("b".to_string(), 2);
+    let first = cache.checkpoint().unwrap();
+    cache.put("c".to_string(), 3);
+    let second = cache.checkpoint().unwrap();
+    assert!(second > first);
+
+    // Déploiement fautif : valeurs empoisonnées et nouvelle clé
+    cache.put("a".to_string(), -1);
+    cache.put("poison".to_string(), -1);
+    assert_eq!(cache.rollback_to(second).unwrap(), 3);
+    let restored: Vec<_> = cache.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    assert_eq!(restored, [("a".to_string(), 1), ("b".to_string(), 2), ("c".to_string(), 3)]);
+    assert_eq!(cache.dirty_keys().count(), 3);
+
+    // Seuls les deux points de reprise les plus récents sont conservés
+    let third = cache.checkpoint().unwrap();
+    let ids: Vec<u64> = cache.checkpoints().unwrap().iter().map(|c| c.id).collect();
+    assert_eq!(ids, [second, third]);
+    assert_eq!(cache.checkpoints().unwrap()[1].entries, 3);
+    assert!(matches!(cache.rollback_to(first), Err(CacheError::IoError(_))));
+    assert_eq!(cache.peek(&"c".to_string()), Some(&3));
+
+    let mut plain: Cache<String, i32> = Cache::new(2);
+    assert!(matches!(plain.checkpoint(), Err(CacheError::IoError(_))));
+    assert!(matches!(plain.rollback_to(1), Err(CacheError::IoError(_))));
+    assert!(Cache::<String, i32>::builder().capacity(2).checkpoints(&dir, 0).build().is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_integrity_check_repairs_persisted_file() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::integrity::ChecksumStatus;
+
+    let path = std::env::temp_dir().join(format!("lru_integrity_{}.txt", std::process::id()));
+    let mut cache: Cache<String, i32> = Cache::new(10);
+    for k in 0..4 {
+        cache.put(format!("k{}", k), k);
+    }
+    cache.persist(&path).unwrap();
+
+    let (clean, report) = Cache::<String, i32>::new_persistent_repaired(10, &path).unwrap();
+    assert!(report.is_clean(), "{:?}", report);
+    assert_eq!(report.checksum, ChecksumStatus::Valid);
+    assert_eq!((report.expected_entries, report.loaded, clean.len()), (Some(4), 4, 4));
+
+    // Corruption : une valeur altérée, une ligne tronquée et un doublon
+    let content = std::fs::read_to_string(&path).unwrap();
+    let damaged = content.replace("k1\t1", "k1\tx").replace("k2\t2\n", "k2\n") + "\nk0\t9\n";
+    std::fs::write(&path, damaged).unwrap();
+    assert!(matches!(Cache::<String, i32>::new_persistent(10, &path), Err(CacheError::Corrupted { .. })));
+
+    let (repaired, report) = Cache::<String, i32>::builder()
+        .capacity(2)
+        .build_persistent_repaired(&path)
+        .unwrap();
+    assert!(matches!(report.checksum, ChecksumStatus::Mismatch { .. }));
+    let lines: Vec<usize> = report.dropped.iter().map(|record| record.line).collect();
+    assert_eq!(lines, [3, 4]);
+    assert_eq!((report.duplicates, report.loaded, report.over_capacity), (1, 2, 0));
+    let order: Vec<_> = repaired.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    assert_eq!(order, [("k3".to_string(), 3), ("k0".to_string(), 9)]);
+    assert!(!report.is_clean());
+
+    std::fs::write(&path, "#lru_cache version=99 codec=text entries=0 capacity=1 created=0\n").unwrap();
+    assert!(Cache::<String, i32>::new_persistent_repaired(10, &path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_builder_seed_prepopulates_in_order() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use lru_cache::lru::hooks::CacheHooks;
+    use lru_cache::lru::sync::SyncCache;
+    use lru_cache::lru::traits::CacheRead;
+
+    struct Inserts(Arc<AtomicUsize>);
+    impl CacheHooks<String, i32> for Inserts {
+        fn on_insert(&self, _key: &String, _value: &i32) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let inserts = Arc::new(AtomicUsize::new(0));
+    let mut cache: Cache<String, i32> = Cache::builder()
+        .capacity(3)
+        .hooks(Inserts(inserts.clone()))
+        .seed((0..4).map(|k| (format!("k{}", k), k)))
+        .seed([("k1".to_string(), 10)])
+        .build()
+        .unwrap();
+    let order: Vec<_> = cache.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    assert_eq!(order, [("k2".to_string(), 2), ("k3".to_string(), 3), ("k1".to_string(), 10)]);
+    assert_eq!(inserts.load(Ordering::SeqCst), 0);
+    cache.put("k4".to_string(), 4);
+    assert_eq!(inserts.load(Ordering::SeqCst), 1);
+    assert!(!cache.contains(&"k2".to_string()));
+
+    let rejected = Cache::<String, i32>::builder()
+        .capacity(3)
+        .max_key_length(2)
+        .seed([("trop longue".to_string(), 1)])
+        .build();
+    assert!(rejected.is_err());
+
+    let shared = SyncCache::from_cache(
+        Cache::builder().capacity(10).seed([("a".to_string(), 1), ("b".to_string(), 2)]).build().unwrap(),
+    );
+    assert_eq!(shared.len().unwrap(), 2);
+    assert_eq!(shared.get(&"a".to_string()).unwrap(), Some(1));
+}
+
+#[test]
+fn test_time_buckets_expire_whole_buckets() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::traits::CacheRead;
+
+    let clock = ManualClock::new();
+    let mut cache: Cache<u32, u32> = Cache::builder()
+        .capacity(100)
+        .clock(Arc::new(clock.clone()))
+        .time_buckets(Duration::from_secs(60), Duration::from_secs(10))
+        .build()
+        .unwrap();
+
+    for k in 0..5 {
+        cache.put(k, k);
+    }
+    clock.advance(Duration::from_secs(15));
+    for k in 5..8 {
+        cache.put(k, k);
+    }
+    // Une mise à jour laisse la clé dans sa tranche d'origine
+    cache.put(0, 100);
+    assert_eq!(cache.time_bucket_count(), 2);
+    assert_eq!(cache.ttl(&0), Some(Duration::from_secs(55)));
+
+    // Supprimée puis réinsérée, la clé 1 change de tranche
+    cache.remove(&1);
+    cache.put(1, 1);
+
+    clock.advance(Duration::from_secs(55));
+    assert!(!cache.contains(&0));
+    assert!(cache.contains(&1) && cache.contains(&5));
+    // La tranche expirée est retirée d'un bloc par l'insertion suivante
+    cache.put(8, 8);
+    assert_eq!(cache.len(), 5);
+    assert_eq!(cache.time_bucket_count(), 2);
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(cache.purge_expired(), 4);
+    assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), [8]);
+
+    let zero = Cache::<u32, u32>::builder().capacity(1).time_buckets(Duration::ZERO, Duration::ZERO).build();
+    assert!(zero.is_err());
+}
+
+#[test]
+fn test_scoped_cache_quotas_isolate_scopes() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::scoped::ScopedCache;
+
+    let mut cache: ScopedCache<&str, u32, u32> = ScopedCache::with_factory(20, |quota| {
+        Cache::builder().capacity(quota).record_stats().build()
+    })
+    .unwrap();
+    assert_eq!(cache.add_scope("bruyant", 70).unwrap(), 14);
+    assert_eq!(cache.add_scope("calme", 30).unwrap(), 6);
+    assert!(cache.add_scope("autre", 1).is_err());
+    assert!(cache.add_scope("calme", 0).is_err());
+
+    for k in 0..5 {
+        cache.put(&"calme", k, k).unwrap();
+    }
+    for k in 0..100 {
+        cache.put(&"bruyant", k, k).unwrap();
+    }
+    assert_eq!(cache.scope_len(&"calme"), 5);
+    assert_eq!(cache.scope_len(&"bruyant"), 14);
+    assert_eq!(cache.len(), 19);
+    // Les mêmes clés dans deux portées sont distinctes
+    assert_eq!(cache.get(&"calme", &0), Some(&0));
+    assert_eq!(cache.get(&"bruyant", &0), None);
+    assert_eq!(cache.scope(&"calme").unwrap().stats().hits, 1);
+
+    // Réduire une portée libère de la place pour une autre
+    assert_eq!(cache.add_scope("bruyant", 50).unwrap(), 10);
+    assert_eq!(cache.scope_len(&"bruyant"), 10);
+    assert_eq!(cache.peek(&"bruyant", &99), Some(&99));
+    assert_eq!(cache.add_scope("autre", 20).unwrap(), 4);
+
+    assert!(matches!(cache.put(&"inconnue", 1, 1), Err(CacheError::InvalidKey { .. })));
+    assert_eq!(cache.clear_scope(&"calme"), 5);
+    assert!(cache.remove_scope(&"autre").is_some());
+    assert_eq!(cache.scopes().count(), 2);
+
+    // Une part trop petite pour réserver une entrée est refusée : la somme
+    // des quotas ne dépasse jamais la capacité
+    assert!(cache.add_scope("minuscule", 4).is_err());
+    let quotas: usize = cache.scopes().map(|scope| cache.quota(scope).unwrap()).sum();
+    assert!(quotas <= cache.capacity());
+}
+
+#[test]
+fn test_tenant_cache_stats_and_isolation() {
+    use lru_cache::lru::tenant::TenantCache;
+
+    let mut cache: TenantCache<u32, String, u32> = TenantCache::new(10);
+    assert_eq!(cache.add_tenant(1, 60).unwrap(), 6);
+    assert_eq!(cache.add_tenant(2, 40).unwrap(), 4);
+    assert!(cache.put(&3, "k".to_string(), 0).is_err());
+
+    cache.put(&2, "partagée".to_string(), 2).unwrap();
+    for k in 0..50 {
+        cache.put(&1, format!("k{}", k), k).unwrap();
+    }
+    assert_eq!(cache.get(&2, &"partagée".to_string()), Some(&2));
+    assert_eq!(cache.get(&1, &"partagée".to_string()), None);
+    assert_eq!(cache.get(&1, &"k49".to_string()), Some(&49));
+    assert_eq!(cache.get(&3, &"k49".to_string()), None);
+
+    let stats = cache.stats(&1).unwrap();
+    assert_eq!((stats.hits, stats.misses, stats.entries, stats.quota, stats.share), (1, 1, 6, 6, 60));
+    assert_eq!(stats.hit_ratio(), 0.5);
+    assert_eq!(cache.stats(&2).unwrap().hits, 1);
+    assert!(cache.stats(&3).is_none());
+    assert_eq!(cache.report().len(), 2);
+
+    assert_eq!(cache.evict_tenant(&1), 6);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.stats(&1).unwrap().hits, 1);
+    cache.reset_stats(&1);
+    assert_eq!(cache.stats(&1).unwrap().hits, 0);
+
+    assert!(cache.remove_tenant(&1));
+    assert_eq!(cache.add_tenant(3, 60).unwrap(), 6);
+    assert_eq!(cache.tenants().count(), 2);
+}
+
+#[test]
+fn test_sensitive_values_are_never_printed() {
+    use lru_cache::lru::events::CacheEvent;
+    use lru_cache::lru::sensitive::Sensitive;
+
+    let mut cache: Cache<String, Sensitive<String>> = Cache::new(2);
+    let events = cache.subscribe();
+    cache.put("alice".to_string(), Sensitive::new("jeton-alice".to_string()));
+    cache.put("bob".to_string(), "jeton-bob".to_string().into());
+
+    let token = cache.get(&"alice".to_string()).unwrap();
+    assert_eq!(token.expose(), "jeton-alice");
+    assert_eq!(token.fingerprint(), Sensitive::new("jeton-alice".to_string()).fingerprint());
+    assert_ne!(token.fingerprint(), cache.get(&"bob".to_string()).unwrap().fingerprint());
+
+    let dump = format!("{:?} {:?}", cache, cache.iter().collect::<Vec<_>>());
+    assert!(!dump.contains("jeton"), "{}", dump);
+    let event: CacheEvent<String, Sensitive<String>> = events.try_recv().unwrap();
+    assert!(!format!("{:?}", event).contains("jeton"));
+    assert!(!Sensitive::new("jeton").to_string().contains("jeton"));
+    assert_eq!(Sensitive::new(3).into_inner(), 3);
+}
+
+#[test]
+fn test_deterministic_mode_is_reproducible() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use lru_cache::lru::clock::ManualClock;
+
+    let run = |seed: u64| {
+        let clock = ManualClock::new();
+        let mut cache: Cache<String, u32> = Cache::builder()
+            .capacity(20)
+            .clock(Arc::new(clock.clone()))
+            .sampled_eviction(4)
+            .deterministic(seed)
+            .seed((0..10).map(|k| (format!("s{}", k), k)))
+            .build()
+            .unwrap();
+        assert_eq!(cache.deterministic_seed(), Some(seed));
+        for i in 0..300u32 {
+            clock.advance(Duration::from_millis(u64::from(i % 2)));
+            cache.put(format!("k{}", i * 13 % 97), i);
+            if i % 5 == 0 {
+                cache.get(&format!("k{}", i % 97));
+            }
+        }
+        let hash = cache.hash_key("k1");
+        let entries: Vec<(String, u32)> = cache.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        (hash, entries)
+    };
+
+    assert_eq!(run(7), run(7));
+    assert_ne!(run(7).0, run(8).0);
+    assert_eq!(Cache::<String, u32>::new(2).deterministic_seed(), None);
+
+    // Deux caches persistants de même graine donnent le même ordre de table
+    let path = std::env::temp_dir().join(format!("lru_deterministic_{}.txt", std::process::id()));
+    let mut source: Cache<String, u32> = Cache::new(10);
+    for k in 0..10 {
+        source.put(format!("k{}", k), k);
+    }
+    source.persist(&path).unwrap();
+    let load = || {
+        let cache: Cache<String, u32> = Cache::builder()
+            .capacity(10)
+            .deterministic(1)
+            .sampled_eviction(2)
+            .build_persistent(&path)
+            .unwrap();
+        cache.iter().map(|(k, _)| k.clone()).collect::<Vec<_>>()
+    };
+    assert_eq!(load(), load());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_sharded_cache_rebalancing_keeps_entries() {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+    use lru_cache::lru::sharded::ShardedCache;
+
+    let cache = Arc::new(ShardedCache::new(400, 2));
+    let writers: Vec<_> = (0..4u32)
+        .map(|t| {
+            let cache = Arc::clone(&cache);
+            thread::spawn(move || {
+                for i in 0..50 {
+                    cache.put(t * 1000 + i, i).unwrap();
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    cache.with_shard(&7, |shard| shard.put_with_ttl(7, 7, Duration::from_secs(3600))).unwrap();
+    let before = cache.len().unwrap();
+
+    cache.set_shard_count(5).unwrap();
+    assert_eq!(cache.shard_count().unwrap(), 5);
+    assert_eq!(cache.len().unwrap(), before);
+    assert_eq!(cache.shard_capacities().unwrap().iter().sum::<usize>(), 400);
+    assert_eq!(cache.get(&3049).unwrap(), Some(49));
+    assert!(cache.with_shard(&7, |shard| shard.ttl(&7)).unwrap().is_some());
+
+    // Une partition pleine reçoit de la place prise sur les autres
+    cache.set_shard_count(1).unwrap();
+    cache.set_shard_count(4).unwrap();
+    let lens = cache.shard_lens().unwrap();
+    cache.rebalance().unwrap();
+    let capacities = cache.shard_capacities().unwrap();
+    assert_eq!(cache.shard_lens().unwrap(), lens);
+    assert!(capacities.iter().zip(&lens).all(|(capacity, len)| capacity >= len));
+    assert_eq!(capacities.iter().sum::<usize>(), 400);
+
+    assert!(cache.set_shard_count(0).is_err());
+    assert!(cache.set_shard_count(401).is_err());
+    assert_eq!(cache.len().unwrap(), before);
+}
+
+#[test]
+fn test_peek_iter_never_mutates() {
+    use lru_cache::lru::sync::SyncCache;
+
+    let mut cache: Cache<u32, u32> = Cache::builder().capacity(3).record_stats().track_access_times().build().unwrap();
+    for k in 0..3 {
+        cache.put(k, k * 10);
+    }
+    let stats = cache.stats();
+    let shared = &cache;
+    let (first, second): (Vec<_>, Vec<_>) = (shared.peek_iter().collect(), shared.peek_iter().collect());
+    assert_eq!(first, second);
+    assert_eq!(first, [(&0, &0), (&1, &10), (&2, &20)]);
+    assert_eq!(cache.stats(), stats);
+    assert_eq!(cache.hit_count(&0), Some(0));
+
+    let frozen = cache.freeze();
+    cache.put(3, 30);
+    assert_eq!(frozen.peek_iter().count(), 3);
+    assert_eq!(cache.peek_iter().map(|(k, _)| *k).collect::<Vec<_>>(), [1, 2, 3]);
+
+    let sync = SyncCache::from_cache(cache);
+    let sum = sync.peek_iter(|entries| entries.map(|(_, v)| *v).sum::<u32>()).unwrap();
+    assert_eq!(sum, 60);
+}
+
+#[test]
+fn test_swap_exchanges_values_and_optionally_recency() {
+    use lru_cache::lru::sync::SyncCache;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache: Cache<&str, String> = Cache::builder().capacity(3).record_stats().build().unwrap();
+    cache.put("courante", "A".to_string());
+    cache.put("suivante", "B".to_string());
+    cache.put("autre", "C".to_string());
+    let version = cache.version(&"courante").unwrap();
+
+    // Valeurs seules : « courante » reste la moins récemment utilisée
+    assert!(cache.swap(&"courante", &"suivante"));
+    assert_eq!(cache.peek(&"courante").map(String::as_str), Some("B"));
+    assert_eq!(cache.peek(&"suivante").map(String::as_str), Some("A"));
+    assert!(cache.version(&"courante").unwrap() > version);
+    assert_eq!(cache.stats().updates, 2);
+    assert_eq!(cache.peek_iter().map(|(k, _)| *k).collect::<Vec<_>>(), ["courante", "suivante", "autre"]);
+
+    // Avec l'ordre d'utilisation : chaque valeur garde sa fraîcheur
+    assert!(cache.swap_with_recency(&"courante", &"autre"));
+    assert_eq!(cache.peek_iter().map(|(k, v)| (*k, v.as_str())).collect::<Vec<_>>(), [
+        ("autre", "B"),
+        ("suivante", "A"),
+        ("courante", "C"),
+    ]);
+
+    assert!(!cache.swap(&"courante", &"absente"));
+    assert!(cache.swap(&"courante", &"courante"));
+    assert_eq!(cache.peek(&"courante").map(String::as_str), Some("C"));
+
+    let sync = SyncCache::from_cache(cache);
+    assert!(sync.swap(&"courante", &"suivante").unwrap());
+    assert_eq!(sync.get(&"suivante").unwrap().as_deref(), Some("C"));
+}
+
+#[test]
+fn test_pinned_entries_survive_eviction() {
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache: Cache<u32, u32> = Cache::new(3);
+    for k in 0..3 {
+        cache.put(k, k);
+    }
+    assert!(cache.pin(&0));
+    assert!(!cache.pin(&9));
+    let guard = cache.pin_guard(&1).unwrap();
+    assert_eq!(guard.key(), &1);
+
+    // Seule l'entrée non épinglée est évincée
+    cache.put(3, 3);
+    assert_eq!(cache.peek_iter().map(|(k, _)| *k).collect::<Vec<_>>(), [0, 1, 3]);
+    assert!(cache.evict(3).iter().all(|(k, _)| *k == 3));
+    cache.put(4, 4);
+    cache.put(5, 5);
+    cache.put(6, 6);
+    assert!(cache.contains(&0) && cache.contains(&1));
+
+    // Toutes épinglées : le cache dépasse sa capacité plutôt que d'évincer
+    let _six = cache.pin_guard(&6).unwrap();
+    cache.put(7, 7);
+    assert!(cache.pin(&7));
+    cache.put(8, 8);
+    assert_eq!(cache.len(), 5);
+
+    // Les épinglages se cumulent et se libèrent un à un
+    assert!(cache.pin(&0));
+    assert!(cache.unpin(&0));
+    assert!(cache.is_pinned(&0));
+    assert!(cache.unpin(&0));
+    assert!(!cache.unpin(&0));
+    drop(guard);
+    assert!(!cache.is_pinned(&1));
+    cache.put(9, 9);
+    assert!(!cache.contains(&0) && !cache.contains(&1));
+    assert_eq!(cache.peek_iter().map(|(k, _)| *k).collect::<Vec<_>>(), [6, 7, 9]);
+
+    let mut sampled: Cache<u32, u32> = Cache::builder().capacity(4).sampled_eviction(3).build().unwrap();
+    sampled.put(0, 0);
+    let _guard = sampled.pin_guard(&0).unwrap();
+    for k in 1..50 {
+        sampled.put(k, k);
+    }
+    assert!(sampled.contains(&0));
+}
+
+#[test]
+fn test_reserve_slots_protects_batch_inserts() {
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache: Cache<u32, u32> = Cache::new(6);
+    for k in 0..6 {
+        cache.put(k, k);
+    }
+    cache.reserve_slots(4).unwrap();
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.reserved_slots(), 4);
+
+    // Le lot entier tient sans évincer ses propres insertions
+    cache.put(100, 0);
+    cache.transaction(|tx| {
+        tx.put(101, 1);
+        tx.put(102, 2);
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(cache.warm(vec![(200, 0), (201, 1)]).not_fitting, 2);
+    cache.put(103, 3);
+    assert_eq!(cache.reserved_slots(), 0);
+    assert_eq!(cache.len(), 6);
+    assert!((100..104).all(|k| cache.contains(&k)));
+
+    // Au-delà de la réservation, l'éviction reprend normalement
+    cache.put(104, 4);
+    assert_eq!(cache.len(), 6);
+
+    assert!(cache.reserve_slots(7).is_err());
+    assert_eq!(cache.reserved_slots(), 0);
+    cache.reserve_slots(2).unwrap();
+    assert_eq!(cache.release_slots(5), 2);
+    assert_eq!(cache.len(), 4);
+
+    // Des entrées épinglées empêchent de libérer la place demandée
+    assert!(cache.pin(&104));
+    assert!(cache.reserve_slots(6).is_err());
+    assert_eq!(cache.reserved_slots(), 0);
+    assert!(cache.contains(&104));
+}
+
+#[test]
+fn test_capacity_advice_estimates_larger_caches() {
+    let mut cache: Cache<u32, u32> = Cache::builder().capacity(10).capacity_advisor().build().unwrap();
+    // Un ensemble de travail de 14 clés parcouru en boucle
+    for _ in 0..20 {
+        for k in 0..14 {
+            if cache.get(&k).is_none() {
+                cache.put(k, k);
+            }
+        }
+    }
+    let advice = cache.capacity_advice().unwrap();
+    assert_eq!((advice.hits, advice.misses), (0, 280));
+    assert_eq!(advice.estimates.iter().map(|e| e.capacity).collect::<Vec<_>>(), [15, 20]);
+    // 1,5x suffit à contenir l'ensemble de travail : seules les lectures
+    // à froid échouent encore
+    let expected = (280.0 - 14.0) / 280.0;
+    assert!(advice.estimates.iter().all(|e| (e.hit_ratio - expected).abs() < 1e-9));
+    assert_eq!(advice.to_string().lines().count(), 3);
+
+    // Sans le conseiller, aucun conseil
+    let plain: Cache<u32, u32> = Cache::new(10);
+    assert!(plain.capacity_advice().is_none());
+}
+
+#[test]
+fn test_get_migrated_rekeys_entries() {
+    use lru_cache::lru::traits::CacheRead;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use lru_cache::lru::clock::ManualClock;
+
+    let clock = ManualClock::new();
+    let mut cache: Cache<String, u32> = Cache::builder()
+        .capacity(3)
+        .clock(Arc::new(clock.clone()))
+        .max_key_length(8)
+        .build()
+        .unwrap();
+    let key = |s: &str| s.to_string();
+    cache.put_with_ttl(key("a"), 1, Duration::from_secs(10));
+    cache.put(key("b"), 2);
+    cache.put(key("c"), 3);
+    let version = cache.version(&key("a")).unwrap();
+
+    // L'entrée garde sa durée de vie, puis est lue comme toute entrée
+    clock.advance(Duration::from_secs(4));
+    assert_eq!(cache.get_migrated(&key("a"), &key("v2:a")), Some(&1));
+    assert!(!cache.contains(&key("a")));
+    assert_eq!(cache.ttl(&key("v2:a")), Some(Duration::from_secs(6)));
+    assert!(cache.version(&key("v2:a")).unwrap() > version);
+    assert_eq!(cache.peek_iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>(), ["b", "c", "v2:a"]);
+
+    // La nouvelle clé l'emporte lorsqu'elle existe déjà
+    cache.put(key("v2:b"), 20);
+    assert_eq!(cache.get_migrated(&key("b"), &key("v2:b")), Some(&20));
+    assert_eq!(cache.get_migrated(&key("x"), &key("v2:x")), None);
+
+    // Une nouvelle clé refusée laisse l'entrée en place
+    assert_eq!(cache.get_migrated(&key("c"), &key("version3:c")), Some(&3));
+    assert!(cache.contains(&key("c")));
+
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(cache.get_migrated(&key("v2:a"), &key("v3:a")), None);
+}
+
+#[test]
+fn test_get_migrated_replaces_expired_new_key() {
+    use lru_cache::lru::clock::ManualClock;
+    use lru_cache::lru::traits::CacheRead;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let mut cache: Cache<&str, u32> = Cache::builder().capacity(3).clock(Arc::new(clock.clone())).build().unwrap();
+    cache.put("old", 1);
+    cache.put_with_ttl("new", 2, Duration::from_secs(1));
+    clock.advance(Duration::from_secs(2));
+
+    // L'entrée déplacée ne reprend pas l'échéance dépassée de la nouvelle clé
+    assert_eq!(cache.get_migrated(&"old", &"new"), Some(&1));
+    assert_eq!(cache.ttl(&"new"), None);
+    assert_eq!(cache.len(), 1);
+    assert!(!cache.contains(&"old"));
+}
+
+#[test]
+fn test_scan_reads_without_promotion() {
+    use lru_cache::lru::clock::{Clock, ManualClock};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let mut cache: Cache<u32, u32> = Cache::builder().capacity(5).record_stats().build().unwrap();
+    for k in 0..5 {
+        cache.put(k, k * 10);
+    }
+    let scan: Vec<u32> = (0..8).collect();
+    let values = cache.get_many_no_promote(&scan);
+    assert_eq!(values.iter().flatten().count(), 5);
+    assert_eq!(values[7], None);
+    assert_eq!((cache.stats().hits, cache.stats().misses), (5, 3));
+    assert_eq!(cache.hit_count(&0), Some(0));
+    assert_eq!(cache.peek_iter().map(|(k, _)| *k).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+
+    // La dernière clé du lot devient la plus récente ; absentes et doublons ignorés
+    assert_eq!(cache.promote_many(&[3, 9, 1, 3]), 2);
+    assert_eq!(cache.peek_iter().map(|(k, _)| *k).collect::<Vec<_>>(), [0, 2, 4, 3, 1]);
+
+    // En éviction échantillonnée, la promotion date l'accès des entrées
+    let clock = ManualClock::new();
+    let mut sampled: Cache<u32, u32> = Cache::builder()
+        .capacity(3)
+        .clock(Arc::new(clock.clone()))
+        .sampled_eviction(3)
+        .build()
+        .unwrap();
+    for k in 0..3 {
+        sampled.put(k, k);
+    }
+    clock.advance(Duration::from_secs(1));
+    let since = clock.now() - Duration::from_millis(1);
+    assert_eq!(sampled.promote_many(&[0, 1]), 2);
+    assert_eq!(sampled.iter_idle_since(since).map(|(k, _)| *k).collect::<Vec<_>>(), [2]);
+}
+
+#[test]
+fn test_update_recency_keep_leaves_order_untouched() {
+    use lru_cache::lru::duplicate::UpdateRecency;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache: Cache<u32, u32> = Cache::builder()
+        .capacity(3)
+        .update_recency(UpdateRecency::Keep)
+        .build()
+        .unwrap();
+    assert_eq!(cache.update_recency(), UpdateRecency::Keep);
+    for k in 0..3 {
+        cache.put(k, k);
+    }
+    // L'écrivain met à jour 0 sans la sauver de l'éviction
+    cache.put(0, 100);
+    cache.transaction(|tx| {
+        tx.put(0, 200);
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(cache.peek_iter().map(|(k, _)| *k).collect::<Vec<_>>(), [0, 1, 2]);
+    cache.get(&1);
+    cache.put(3, 3);
+    assert!(!cache.contains(&0));
+
+    // Comportement par défaut : la mise à jour promeut la clé
+    let mut strict: Cache<u32, u32> = Cache::new(2);
+    assert_eq!(strict.update_recency(), UpdateRecency::Promote);
+    strict.put(0, 0);
+    strict.put(1, 1);
+    strict.put(0, 10);
+    strict.put(2, 2);
+    assert!(strict.contains(&0) && !strict.contains(&1));
+}
+
+#[test]
+fn test_shrink_policy_releases_memory() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::shrink::ShrinkPolicy;
+
+    let fill = |cache: &mut Cache<u64, u64>| {
+        for i in 0..20_000 {
+            cache.put(i, i);
+        }
+    };
+
+    // Par défaut, la place reste réservée après un vidage
+    let mut kept: Cache<u64, u64> = Cache::new(20_000);
+    fill(&mut kept);
+    let peak = kept.estimated_memory_usage().total();
+    kept.clear();
+    assert_eq!(kept.estimated_memory_usage().total(), peak);
+
+    let mut shrinking = Cache::with_capacity_and_shrink_policy(20_000, ShrinkPolicy::auto());
+    assert_eq!(shrinking.shrink_policy(), ShrinkPolicy::auto());
+    assert!(shrinking.estimated_memory_usage().total() < peak / 100);
+    fill(&mut shrinking);
+    // Une éviction massive ramène la mémoire à la taille utile
+    shrinking.resize(100).unwrap();
+    assert!(shrinking.estimated_memory_usage().total() < peak / 50);
+    assert_eq!(shrinking.len(), 100);
+
+    let invalid = Cache::<u64, u64>::builder()
+        .capacity(10)
+        .shrink_policy(ShrinkPolicy::Auto { threshold: 0.0 })
+        .build();
+    assert!(matches!(invalid, Err(CacheError::CapacityError(_))));
+}
+
+#[test]
+fn test_bench_workloads_measure_hit_ratio() {
+    use lru_cache::bench::{self, Access, Workload};
+    use lru_cache::lru::doubles::{NullCache, UnboundedMapCache};
+
+    let accesses: Vec<Access> = Workload::Replacement.accesses().take(5).collect();
+    assert_eq!(accesses, [Access::Put(0), Access::Put(1), Access::Put(2), Access::Get(1), Access::Put(3)]);
+
+    // Clés et valeurs propres à l'application
+    let workload = Workload::Realistic { hot: 200, cold: 2000 };
+    let key = |k: u64| format!("key:{}", k);
+    let value = |k: u64| vec![k as u8; 16];
+
+    let lru = bench::run(&mut Cache::new(1000), workload.accesses().take(20_000), key, value);
+    let null = bench::run(&mut NullCache::new(), workload.accesses().take(20_000), key, value);
+    let map = bench::run(&mut UnboundedMapCache::new(), workload.accesses().take(20_000), key, value);
+
+    assert_eq!(lru.operations, 20_000);
+    assert_eq!(lru.gets + lru.puts, lru.operations);
+    assert_eq!(null.hits, 0);
+    assert!(lru.hit_ratio() > 0.5);
+    assert!(map.hit_ratio() >= lru.hit_ratio());
+    assert!(lru.throughput() > 0.0);
+}
+
+#[test]
+fn test_compat_fixtures_detect_format_changes() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::compat::{self, FIXTURE_VERSIONS};
+    use lru_cache::lru::format::describe;
+    use std::path::Path;
+
+    // Fichiers de référence écrits une fois et conservés dans le dépôt : un
+    // changement du format écrit ou lu les rend illisibles
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    assert_eq!(compat::verify_fixtures(&fixtures).unwrap(), FIXTURE_VERSIONS.len());
+    // Le format sans en-tête n'a pas de description
+    assert!(describe(fixtures.join(compat::fixture_file_name(0))).unwrap().is_none());
+    assert!(describe(fixtures.join(compat::fixture_file_name(1))).unwrap().is_some());
+
+    let dir = std::env::temp_dir().join(format!("lru_compat_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    assert_eq!(compat::write_fixtures(&dir).unwrap().len(), FIXTURE_VERSIONS.len());
+    assert!(matches!(compat::write_fixture(99, dir.join("v99.lru")), Err(CacheError::ParseError(_))));
+
+    // Une entrée modifiée est signalée à sa ligne
+    let altered_path = dir.join(compat::fixture_file_name(0));
+    let altered = std::fs::read_to_string(fixtures.join(compat::fixture_file_name(0))).unwrap().replace("x=y", "x:y");
+    std::fs::write(&altered_path, altered).unwrap();
+    match compat::verify_fixture(&altered_path) {
+        Err(CacheError::Corrupted { line, .. }) => assert_eq!(line, 4),
+        other => panic!("{:?}", other),
+    }
+    assert!(compat::verify_fixture(dir.join("absent.lru")).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_bytes_values_round_trip_without_text_codec() {
+    use bytes::Bytes;
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::format::{describe, BYTES_CODEC};
+
+    let path = std::env::temp_dir().join(format!("lru_blob_test_{}.bin", std::process::id()));
+    let blob: Vec<u8> = (0..=255).collect();
+    let mut cache: Cache<u32, Bytes> = Cache::new(10);
+    cache.put_from_reader(1, &blob[..], blob.len()).unwrap();
+    cache.put(2, Bytes::new());
+    // Un flux trop court n'insère rien
+    assert!(matches!(cache.put_from_reader(3, &blob[..10], 20), Err(CacheError::IoError(_))));
+    assert_eq!(cache.len(), 2);
+
+    let shared = cache.get_shared(&1).unwrap();
+    assert_eq!(cache.get_slice(&1).unwrap().as_ptr(), shared.as_ptr());
+
+    cache.persist_bytes(&path).unwrap();
+    assert_eq!(describe(&path).unwrap().unwrap().codec, BYTES_CODEC);
+    // Le chargeur texte refuse le codec binaire
+    assert!(Cache::<u32, String>::new_persistent(10, &path).is_err());
+
+    let mut restored = Cache::<u32, Bytes>::new_persistent_bytes(10, &path).unwrap();
+    assert_eq!(restored.get_slice(&1), Some(&blob[..]));
+    assert_eq!(restored.get_slice(&2), Some(&[][..]));
+
+    let mut content = std::fs::read(&path).unwrap();
+    content.truncate(content.len() - 3);
+    std::fs::write(&path, &content).unwrap();
+    assert!(matches!(
+        Cache::<u32, Bytes>::new_persistent_bytes(10, &path),
+        Err(CacheError::Corrupted { line: 1, .. })
+    ));
+
+    // Sans somme de contrôle, l'erreur désigne la ligne de l'entrée fautive,
+    // en comptant les sauts de ligne des valeurs précédentes
+    let content = b"#lru_cache version=1 codec=bytes entries=2 capacity=10 created=0\n1\t3\na\nb\nx\t1\nz\n";
+    std::fs::write(&path, content).unwrap();
+    assert!(matches!(
+        Cache::<u32, Bytes>::new_persistent_bytes(10, &path),
+        Err(CacheError::Corrupted { line: 5, .. })
+    ));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_failed_loads_are_not_retried_during_cooldown() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::clock::ManualClock;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let mut cache: Cache<&str, Option<u32>> = Cache::builder()
+        .capacity(10)
+        .load_failure_cooldown(Duration::from_secs(10))
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+
+    let calls = std::cell::Cell::new(0);
+    let failing = |_: &&str| {
+        calls.set(calls.get() + 1);
+        Err(CacheError::ParseError("indisponible".to_string()))
+    };
+    assert!(matches!(cache.try_get_or_insert_with("a", failing), Err(CacheError::ParseError(_))));
+    clock.advance(Duration::from_secs(4));
+    match cache.try_get_or_insert_with("a", failing) {
+        Err(CacheError::RecentLoadFailure { retry_in, reason }) => {
+            assert_eq!(retry_in, Duration::from_secs(6));
+            assert!(reason.contains("indisponible"));
+        }
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(calls.get(), 1);
+    // L'absence légitime reste une valeur mise en cache
+    assert_eq!(cache.try_get_or_insert_with("b", |_| Ok(None)).unwrap(), Some(&None));
+    assert_eq!(cache.len(), 1);
+
+    // Après le délai de carence, le chargeur est de nouveau appelé
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(cache.try_get_or_insert_with("a", |_| Ok(Some(1))).unwrap(), Some(&Some(1)));
+    assert_eq!(cache.try_get_or_insert_with("a", failing).unwrap(), Some(&Some(1)));
+    assert_eq!(calls.get(), 1);
+
+    assert!(cache.try_get_or_insert_with("c", failing).is_err());
+    assert!(cache.forget_load_failure(&"c"));
+    assert!(cache.try_get_or_insert_with("c", failing).is_err());
+    assert_eq!(calls.get(), 3);
+
+    // Le registre des marques est borné par la capacité : la plus ancienne
+    // marque cède sa place
+    let mut small: Cache<&str, Option<u32>> = Cache::builder()
+        .capacity(2)
+        .load_failure_cooldown(Duration::from_secs(10))
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+    for key in ["x", "y", "z"] {
+        assert!(matches!(small.try_get_or_insert_with(key, failing), Err(CacheError::ParseError(_))));
+    }
+    assert!(matches!(small.try_get_or_insert_with("x", failing), Err(CacheError::ParseError(_))));
+    assert!(matches!(small.try_get_or_insert_with("z", failing), Err(CacheError::RecentLoadFailure { .. })));
+    assert!(!small.forget_load_failure(&"y"));
+    assert_eq!(calls.get(), 7);
+
+    let zero = Cache::<u32, u32>::builder().capacity(1).load_failure_cooldown(Duration::ZERO).build();
+    assert!(matches!(zero, Err(CacheError::CapacityError(_))));
+}
+
+#[test]
+fn test_secondary_index_follows_evictions_and_updates() {
+    use lru_cache::lru::secondary::IndexedCache;
+    use lru_cache::lru::traits::CacheRead;
+
+    // Sessions (identifiant, utilisateur) indexées par utilisateur
+    let mut sessions = IndexedCache::new(Cache::new(3), |session: &(u32, &str)| session.1);
+    sessions.put(1, (1, "alice"));
+    sessions.put(2, (2, "bob"));
+    sessions.put(3, (3, "alice"));
+    assert_eq!(sessions.keys_by_secondary(&"alice"), vec![1, 3]);
+    assert_eq!(sessions.get_by_secondary(&"alice"), Some(&(3, "alice")));
+
+    // La session 1 est évincée : l'index ne la retourne plus
+    sessions.put(4, (4, "carol"));
+    assert_eq!(sessions.keys_by_secondary(&"alice"), vec![3]);
+
+    // Un remplacement de valeur déplace la clé dans l'index
+    sessions.put(3, (3, "bob"));
+    assert_eq!(sessions.get_by_secondary(&"alice"), None);
+    assert_eq!(sessions.keys_by_secondary(&"bob"), vec![2, 3]);
+
+    assert_eq!(sessions.remove_by_secondary(&"bob"), 2);
+    assert_eq!(sessions.len(), 1);
+    sessions.clear();
+    assert_eq!(sessions.get_by_secondary(&"carol"), None);
+
+    // Les entrées présentes avant l'encapsulation sont indexées
+    let mut cache = Cache::new(2);
+    cache.put("k", 10);
+    let mut indexed = IndexedCache::new(cache, |value: &i32| value % 3);
+    assert_eq!(indexed.get_by_secondary(&1), Some(&10));
+}
+
+#[test]
+fn test_ordered_cache_range_operations() {
+    use lru_cache::lru::ordered::OrderedCache;
+    use lru_cache::lru::traits::CacheRead;
+
+    let mut cache = OrderedCache::new(Cache::new(4));
+    for key in [40, 10, 30, 20] {
+        cache.put(key, key.to_string());
+    }
+    let keys: Vec<i32> = cache.range(15..=30).into_iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, vec![20, 30]);
+    assert_eq!(cache.key_bounds(), Some((10, 40)));
+
+    // L'éviction LRU s'applique toujours et met l'index à jour
+    cache.get(&40);
+    cache.put(50, "50".to_string());
+    assert_eq!(cache.key_bounds(), Some((20, 50)));
+
+    let removed = cache.remove_range(..45);
+    assert_eq!(removed, vec![(20, "20".to_string()), (30, "30".to_string()), (40, "40".to_string())]);
+    assert_eq!(cache.len(), 1);
+    assert!(cache.range(..45).is_empty());
+
+    cache.clear();
+    assert_eq!(cache.key_bounds(), None);
+}
+
+#[test]
+fn test_registry_enforces_global_budget() {
+    use lru_cache::lru::registry::{CacheRegistry, RegistryBudget};
+    use lru_cache::lru::traits::CacheRead;
+
+    let stats_cache = |capacity| Cache::<u64, u64>::builder().capacity(capacity).record_stats().build().unwrap();
+    let mut registry = CacheRegistry::new(RegistryBudget::Entries(6));
+    registry.insert("hot", stats_cache(10));
+    registry.insert("cold", stats_cache(10));
+    assert!(registry.cache::<u64, String>("hot").is_none());
+
+    {
+        let mut hot = registry.cache_mut::<u64, u64>("hot").unwrap();
+        for key in 0..4 {
+            hot.put(key, 0);
+            hot.get(&key);
+        }
+    }
+    {
+        let mut cold = registry.cache_mut::<u64, u64>("cold").unwrap();
+        for key in 0..4 {
+            cold.put(key, 0);
+        }
+        cold.get(&0);
+        cold.get(&100);
+    }
+    // Le cache le moins utile cède ses entrées les moins récemment utilisées
+    let cold = registry.cache::<u64, u64>("cold").unwrap();
+    assert_eq!(cold.len(), 2);
+    assert!(cold.contains(&3) && !cold.contains(&1));
+    let stats = registry.stats();
+    assert_eq!((stats.entries, stats.totals.hits, stats.totals.evictions), (6, 5, 2));
+    assert_eq!(stats.caches["hot"].hits, 4);
+
+    // Budget en octets : 16 octets (clé et valeur) par entrée
+    registry.set_budget(RegistryBudget::Bytes(80));
+    assert_eq!(registry.stats().bytes, 80);
+    assert_eq!(registry.cache::<u64, u64>("cold").unwrap().len(), 1);
+
+    let names: Vec<_> = registry.names().collect();
+    assert_eq!(names, vec!["cold", "hot"]);
+    assert!(registry.remove::<u64, String>("hot").is_none());
+    assert_eq!(registry.remove::<u64, u64>("hot").unwrap().len(), 4);
+    assert_eq!(registry.stats().entries, 1);
+
+    // Une entrée épinglée ne peut être évincée : l'application du budget
+    // passe aux autres caches puis s'arrête, sans boucler
+    {
+        let cold = registry.cache_mut::<u64, u64>("cold").unwrap();
+        assert!(cold.pin(&0));
+    }
+    registry.insert("other", stats_cache(10));
+    {
+        let mut other = registry.cache_mut::<u64, u64>("other").unwrap();
+        other.put(1, 1);
+        other.get(&1);
+    }
+    registry.set_budget(RegistryBudget::Entries(0));
+    assert_eq!(registry.cache::<u64, u64>("other").unwrap().len(), 0);
+    assert!(registry.cache::<u64, u64>("cold").unwrap().contains(&0));
+    assert_eq!(registry.stats().entries, 1);
+}
+
+#[test]
+fn test_diagnostics_registry_lists_live_caches() {
+    use lru_cache::lru::diagnostics;
+    use lru_cache::lru::sync::SyncCache;
+
+    // Le registre est global : les noms sont propres à ce test
+    let sessions: SyncCache<u32, u32> = SyncCache::new(10);
+    let pages: SyncCache<String, Vec<u8>> = SyncCache::new(5);
+    diagnostics::register("diag_sessions", &sessions);
+    diagnostics::register("diag_pages", &pages);
+    sessions.put(1, 1).unwrap();
+    sessions.put(2, 2).unwrap();
+
+    let reports: Vec<_> = diagnostics::snapshot()
+        .into_iter()
+        .filter(|report| report.name.starts_with("diag_"))
+        .map(|report| (report.name, report.len, report.capacity))
+        .collect();
+    assert_eq!(reports, vec![("diag_sessions".to_string(), 2, 10), ("diag_pages".to_string(), 0, 5)]);
+
+    // Une nouvelle inscription sous le même nom remplace la précédente
+    let replacement: SyncCache<u32, u32> = SyncCache::new(3);
+    diagnostics::register("diag_sessions", &replacement);
+    let report = diagnostics::snapshot().into_iter().find(|report| report.name == "diag_sessions").unwrap();
+    assert_eq!(report.capacity, 3);
+
+    drop(pages);
+    assert!(!diagnostics::names().contains(&"diag_pages".to_string()));
+    assert!(diagnostics::unregister("diag_sessions"));
+    assert!(!diagnostics::unregister("diag_sessions"));
+}
+
+#[test]
+fn test_deadline_aware_reads_give_up_on_contention_and_slow_loads() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::sharded::ShardedCache;
+    use lru_cache::lru::sync::SyncCache;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let cache: SyncCache<u32, u32> = SyncCache::new(10);
+    cache.put(1, 10).unwrap();
+
+    // Un autre thread détient le verrou plus longtemps que l'échéance
+    let (locked, wait) = mpsc::channel();
+    let holder = {
+        let cache = cache.clone();
+        thread::spawn(move || {
+            cache
+                .with_lock(|_| {
+                    locked.send(()).unwrap();
+                    thread::sleep(Duration::from_millis(100));
+                })
+                .unwrap();
+        })
+    };
+    wait.recv().unwrap();
+    let deadline = Instant::now() + Duration::from_millis(10);
+    assert!(matches!(cache.get_with_deadline(&1, deadline), Err(CacheError::Timeout { .. })));
+    assert!(Instant::now() < deadline + Duration::from_millis(50));
+    holder.join().unwrap();
+    assert_eq!(cache.get_with_deadline(&1, Instant::now() + Duration::from_millis(10)).unwrap(), Some(10));
+
+    // Un chargement trop lent est abandonné, mais sa valeur arrive dans le cache
+    let slow = |key: &u32| -> Result<u32, CacheError> {
+        thread::sleep(Duration::from_millis(50));
+        Ok(key * 100)
+    };
+    let deadline = Instant::now() + Duration::from_millis(5);
+    assert!(matches!(cache.get_or_load_with_deadline(2, deadline, slow), Err(CacheError::Timeout { .. })));
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(cache.get_or_load_with_deadline(2, Instant::now(), slow).unwrap(), 200);
+    let fast = |key: &u32| -> Result<u32, CacheError> { Ok(key + 1) };
+    let deadline = Instant::now() + Duration::from_secs(5);
+    assert_eq!(cache.get_or_load_with_deadline(3, deadline, fast).unwrap(), 4);
+
+    let sharded: ShardedCache<u32, u32> = ShardedCache::new(10, 2);
+    sharded.put(5, 50).unwrap();
+    let deadline = Instant::now() + Duration::from_millis(10);
+    assert_eq!(sharded.get_with_deadline(&5, deadline).unwrap(), Some(50));
+    sharded
+        .with_shard(&5, |_| {
+            let deadline = Instant::now() + Duration::from_millis(5);
+            assert!(matches!(sharded.get_with_deadline(&5, deadline), Err(CacheError::Timeout { .. })));
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_deadline_loads_stay_bounded_against_a_hung_source() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::sync::SyncCache;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let cache: SyncCache<u32, u32> = SyncCache::new(100).with_max_deadline_loads(3);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let gate = Arc::new((Mutex::new(false), Condvar::new()));
+    let hung = {
+        let calls = Arc::clone(&calls);
+        let gate = Arc::clone(&gate);
+        move |key: &u32| -> Result<u32, CacheError> {
+            calls.fetch_add(1, Ordering::SeqCst);
+            let (open, opened) = &*gate;
+            let _open = opened.wait_while(open.lock().unwrap(), |open| !*open).unwrap();
+            Ok(*key)
+        }
+    };
+
+    // Des appels concurrents sur des clés distinctes ne lancent jamais plus
+    // de chargements que la borne, même sans partager de chargeur
+    let callers: Vec<_> = (0..32)
+        .map(|key| {
+            let cache = cache.clone();
+            let hung = hung.clone();
+            thread::spawn(move || {
+                let deadline = Instant::now() + Duration::from_millis(20);
+                cache.get_or_load_with_deadline(key, deadline, hung)
+            })
+        })
+        .collect();
+    for caller in callers {
+        assert!(matches!(caller.join().unwrap(), Err(CacheError::Timeout { .. })));
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(cache.deadline_loads_in_flight(), 3);
+
+    // Une échéance passée ne lance aucun chargement
+    assert!(matches!(
+        cache.get_or_load_with_deadline(100, Instant::now(), hung.clone()),
+        Err(CacheError::Timeout { .. })
+    ));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    let (open, opened) = &*gate;
+    *open.lock().unwrap() = true;
+    opened.notify_all();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while cache.deadline_loads_in_flight() > 0 {
+        assert!(Instant::now() < deadline, "chargements non terminés");
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(cache.len().unwrap(), 3);
+}
+
+#[test]
+fn test_thundering_herd_loads_once() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::coalesce::SchedulePoint;
+    use lru_cache::lru::sync::SyncCache;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    const THREADS: usize = 8;
+    // Le crochet compte les threads en attente : le chargeur ne se termine
+    // qu'une fois que tous les autres attendent son résultat
+    let waiting = Arc::new(AtomicUsize::new(0));
+    let cache: SyncCache<u32, String> = SyncCache::new(10).with_schedule_hook({
+        let waiting = Arc::clone(&waiting);
+        move |point| {
+            if point == SchedulePoint::Waiting {
+                waiting.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let cache = cache.clone();
+            let calls = Arc::clone(&calls);
+            let waiting = Arc::clone(&waiting);
+            thread::spawn(move || {
+                let loader = |key: &u32| -> Result<String, CacheError> {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    while waiting.load(Ordering::SeqCst) < THREADS - 1 {
+                        thread::yield_now();
+                    }
+                    Ok(format!("valeur_{}", key))
+                };
+                cache.get_or_load(7, &loader).unwrap()
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), "valeur_7");
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Un échec est transmis aux threads qui attendaient
+    let failing = |_: &u32| -> Result<String, CacheError> { Err(CacheError::ParseError("indisponible".to_string())) };
+    assert!(matches!(cache.get_or_load(8, &failing), Err(CacheError::ParseError(_))));
+    assert_eq!(cache.get(&8).unwrap(), None);
+}
+
+#[test]
+fn test_thundering_herd_waiters_see_load_failure() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::coalesce::SchedulePoint;
+    use lru_cache::lru::sync::SyncCache;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    let waiting = Arc::new(AtomicUsize::new(0));
+    let cache: SyncCache<u32, String> = SyncCache::new(10).with_schedule_hook({
+        let waiting = Arc::clone(&waiting);
+        move |point| {
+            if point == SchedulePoint::Waiting {
+                waiting.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let cache = cache.clone();
+            let waiting = Arc::clone(&waiting);
+            thread::spawn(move || {
+                let failing = |_: &u32| -> Result<String, CacheError> {
+                    while waiting.load(Ordering::SeqCst) < 1 {
+                        thread::yield_now();
+                    }
+                    Err(CacheError::ParseError("indisponible".to_string()))
+                };
+                cache.get_or_load(8, &failing)
+            })
+        })
+        .collect();
+    let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+    // Le thread qui a chargé reçoit l'erreur du chargeur, celui qui attendait
+    // un échec de chargement sans délai de carence
+    assert_eq!(results.iter().filter(|r| matches!(r, Err(CacheError::ParseError(_)))).count(), 1);
+    let failure = results.iter().find_map(|r| match r {
+        Err(CacheError::LoadFailed { reason }) => Some(reason.clone()),
+        _ => None,
+    });
+    assert!(failure.unwrap().contains("indisponible"));
+}
+
+#[test]
+fn test_early_expiration_spreads_recomputations() {
+    use lru_cache::error::CacheError;
+    use lru_cache::lru::clock::ManualClock;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let clock = ManualClock::new();
+    let mut cache = Cache::builder()
+        .capacity(100)
+        .early_expiration(1.0)
+        .deterministic(42)
+        .record_stats()
+        .clock(Arc::new(clock.clone()))
+        .build()
+        .unwrap();
+    let ttl = Duration::from_secs(100);
+    for key in 0..50 {
+        cache.put_with_cost(key, key, ttl, Duration::from_secs(10));
+    }
+    // Une entrée sans coût n'est jamais anticipée
+    cache.put_with_ttl(1000, 0, ttl);
+
+    // Les entrées insérées ensemble sont recalculées à des instants différents
+    let mut first_refresh = Vec::new();
+    for second in 0..100 {
+        for key in 0..50 {
+            if !first_refresh.iter().any(|&(k, _)| k == key) && cache.get_fresh(&key).is_none() {
+                first_refresh.push((key, second));
+            }
+        }
+        assert!(cache.get_fresh(&1000).is_some());
+        if second == 95 {
+            // Les entrées restent servies aux lectures ordinaires
+            assert_eq!(cache.get(&3), Some(&3));
+        }
+        clock.advance(Duration::from_secs(1));
+    }
+    assert_eq!(first_refresh.len(), 50);
+    let earliest = first_refresh.iter().map(|&(_, s)| s).min().unwrap();
+    let latest = first_refresh.iter().map(|&(_, s)| s).max().unwrap();
+    assert!(earliest < latest);
+    assert!(earliest >= 20);
+    assert!(cache.stats().misses >= 50);
+
+    let mut calls = 0;
+    let mut compute = |key: &u32| {
+        calls += 1;
+        key * 2
+    };
+    assert_eq!(cache.get_or_recompute_with(500, ttl, &mut compute), Some(&1000));
+    assert_eq!(cache.get_or_recompute_with(500, ttl, &mut compute), Some(&1000));
+    assert_eq!(calls, 1);
+
+    let invalid = Cache::<u32, u32>::builder().capacity(1).early_expiration(f64::NAN).build();
+    assert!(matches!(invalid, Err(CacheError::CapacityError(_))));
+}
+
+#[test]
+fn test_scan_pages_through_cache_under_mutation() {
+    use lru_cache::lru::scan::Cursor;
+    use lru_cache::lru::sync::SyncCache;
+    use std::collections::HashSet;
+
+    let cache = SyncCache::from_cache(Cache::builder().capacity(2000).indexed_scan().build().unwrap());
+    for key in 0..1000u32 {
+        cache.put(key, key * 2).unwrap();
+    }
+
+    let mut seen = Vec::new();
+    let mut cursor = Cursor::START;
+    let mut pages = 0;
+    loop {
+        let (page, next) = cache.scan(cursor, 64).unwrap();
+        assert!(page.len() <= 64);
+        assert!(page.iter().all(|&(key, value)| value == key * 2));
+        seen.extend(page.into_iter().map(|(key, _)| key));
+        pages += 1;
+        // Mutations entre deux pages : ajouts et retraits de clés hors de
+        // l'ensemble stable, plus des lectures qui réordonnent le cache
+        cache.put(10_000 + pages, (10_000 + pages) * 2).unwrap();
+        cache.with_lock(|inner| inner.remove(&(10_000 + pages - 1))).unwrap();
+        cache.get(&(pages * 7 % 1000)).unwrap();
+        if next.is_start() {
+            break;
+        }
+        cursor = next;
+    }
+    assert!(pages >= 1000 / 64);
+
+    // Chaque clé présente tout au long du parcours est vue exactement une fois
+    let stable: Vec<u32> = seen.iter().copied().filter(|&key| key < 1000).collect();
+    let unique: HashSet<u32> = stable.iter().copied().collect();
+    assert_eq!(stable.len(), 1000);
+    assert_eq!(unique.len(), 1000);
+
+    let empty = SyncCache::<u32, u32>::new(10);
+    let (page, next) = empty.scan(Cursor::START, 10).unwrap();
+    assert!(page.is_empty());
+    assert!(next.is_start());
+}
+
+#[test]
+fn test_scan_follows_every_way_keys_enter_and_leave() {
+    use lru_cache::lru::scan::Cursor;
+
+    let full_scan = |cache: &Cache<u32, u32>| {
+        let mut keys = Vec::new();
+        let mut cursor = Cursor::START;
+        loop {
+            let (page, next) = cache.scan(cursor, 3);
+            keys.extend(page.into_iter().map(|(key, _)| *key));
+            if next.is_start() {
+                break;
+            }
+            cursor = next;
+        }
+        keys.sort();
+        keys
+    };
+
+    // Le parcours de la table et celui de l'index voient les mêmes clés
+    for builder in [Cache::builder(), Cache::builder().indexed_scan()] {
+        let mut cache: Cache<u32, u32> = builder.capacity(8).deterministic(7).build().unwrap();
+        for key in 0..10 {
+            cache.put(key, key);
+        }
+        assert_eq!(full_scan(&cache), (2..10).collect::<Vec<_>>());
+
+        cache.remove(&2);
+        cache.get_migrated(&3, &30);
+        cache.transaction(|tx| {
+            tx.put(40, 40);
+            tx.remove(&4);
+            Ok(())
+        })
+        .unwrap();
+        cache.warm(vec![(50, 50)]);
+        assert_eq!(full_scan(&cache), [5, 6, 7, 8, 9, 30, 40, 50]);
+
+        cache.clear();
+        assert!(full_scan(&cache).is_empty());
+    }
+}
+
+#[test]
+fn test_tiered_cache_stores_small_values_inline() {
+    use lru_cache::lru::memory::MemSize;
+    use lru_cache::lru::tiered::{TieredCache, TieredValue, DEFAULT_INLINE};
+
+    let mut cache: TieredCache<u32> = TieredCache::new(Cache::builder().capacity(10)).unwrap();
+    assert_eq!(cache.threshold(), DEFAULT_INLINE);
+    cache.put(1, &[]).unwrap();
+    cache.put(2, &[7; DEFAULT_INLINE]).unwrap();
+    cache.put(3, &[9; DEFAULT_INLINE + 1]).unwrap();
+    assert_eq!(cache.get(&1), Some(&[][..]));
+    assert_eq!(cache.get(&2), Some(&[7; DEFAULT_INLINE][..]));
+    assert_eq!(cache.get(&3), Some(&[9; DEFAULT_INLINE + 1][..]));
+
+    let stats = cache.stats();
+    assert_eq!((stats.entries, stats.inline_entries, stats.spilled_entries), (3, 2, 1));
+    assert_eq!((stats.inline_bytes, stats.spilled_bytes), (DEFAULT_INLINE, DEFAULT_INLINE + 1));
+    assert!((stats.inline_ratio() - 2.0 / 3.0).abs() < 1e-9);
+
+    // Une mise à jour peut changer le stockage de la valeur
+    cache.put(3, b"court").unwrap();
+    assert_eq!(cache.stats().spilled_entries, 0);
+    assert!(cache.remove(&3));
+    assert_eq!(cache.len(), 2);
+
+    // Seuil choisi par le paramètre constant ; le poids est la taille
+    let mut small: TieredCache<&str, 4> = TieredCache::new(Cache::builder().capacity(10).max_weight(10)).unwrap();
+    small.put("a", b"1234").unwrap();
+    small.put("b", b"12345").unwrap();
+    small.put("c", b"12").unwrap();
+    assert!(!small.contains(&"a"));
+    assert_eq!(small.stats().spilled_entries, 1);
+
+    let inline = TieredValue::<4>::from(&b"abc"[..]);
+    let spilled = TieredValue::<2>::from(&b"abc"[..]);
+    assert!(inline.is_inline() && !spilled.is_inline());
+    assert_eq!((inline.heap_size(), spilled.heap_size()), (0, 3));
+    assert_eq!(&*inline, &*spilled);
+}
+
+#[test]
+fn test_buffered_handles_merge_local_writes() {
+    use lru_cache::lru::sync::SyncCache;
+    use std::thread;
+    use std::time::Duration;
+
+    let cache = SyncCache::new(10_000);
+    let threads: Vec<_> = (0..8u32)
+        .map(|t| {
+            let mut handle = cache.buffered_handle(32);
+            thread::spawn(move || {
+                for i in 0..500 {
+                    handle.put(t * 1000 + i, i).unwrap();
+                    if i % 10 == 0 {
+                        handle.remove(t * 1000 + i).unwrap();
+                    }
+                    assert!(handle.pending() < 32);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(cache.len().unwrap(), 8 * 450);
+    assert_eq!(cache.get(&7001).unwrap(), Some(1));
+    assert_eq!(cache.get(&7010).unwrap(), None);
+
+    // La poignée lit ses propres écritures ; les autres attendent le versement
+    let mut handle = cache.buffered_handle(100);
+    handle.put(9999, 42).unwrap();
+    handle.remove(7001).unwrap();
+    assert_eq!(handle.get(&9999).unwrap(), Some(42));
+    assert_eq!(handle.get(&7001).unwrap(), None);
+    assert_eq!(cache.get(&9999).unwrap(), None);
+    assert_eq!(cache.get(&7001).unwrap(), Some(1));
+    assert_eq!(handle.flush().unwrap(), 2);
+    assert_eq!(cache.get(&9999).unwrap(), Some(42));
+    assert_eq!(cache.get(&7001).unwrap(), None);
+
+    // Verrou occupé : le versement opportuniste renonce
+    handle.put(9998, 2).unwrap();
+    cache.with_lock(|_| assert!(!handle.try_flush().unwrap())).unwrap();
+    assert!(handle.try_flush().unwrap());
+    assert_eq!(handle.pending(), 0);
+
+    // Délai maximal : versé à l'opération suivante
+    let mut handle = handle.with_max_delay(Duration::from_millis(10));
+    handle.put(9997, 3).unwrap();
+    thread::sleep(Duration::from_millis(20));
+    handle.get(&9999).unwrap();
+    assert_eq!(handle.pending(), 0);
+    assert_eq!(cache.get(&9997).unwrap(), Some(3));
+
+    handle.put(9996, 4).unwrap();
+    drop(handle);
+    assert_eq!(cache.get(&9996).unwrap(), Some(4));
+}
+
+#[cfg(feature = "read-mostly")]
+#[test]
+fn test_read_mostly_cache_publishes_snapshots() {
+    use lru_cache::lru::read_mostly::ReadMostlyCache;
+    use lru_cache::lru::traits::CacheRead;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    let cache = ReadMostlyCache::new(Cache::new(3));
+    cache.update(|master| {
+        for key in 0..3 {
+            master.put(key, key * 10);
+        }
+    });
+    assert_eq!(cache.len(), 3);
+
+    // Les lecteurs voient toujours un état publié complet : jamais un
+    // mélange de deux générations
+    let stop = Arc::new(AtomicBool::new(false));
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let cache = cache.clone();
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                let mut reads = 0;
+                while !stop.load(Ordering::Relaxed) || reads == 0 {
+                    let snapshot = cache.load();
+                    let generation = snapshot.peek(&0).copied().unwrap();
+                    for key in 1..3 {
+                        assert_eq!(snapshot.peek(&key), Some(&(generation + key * 10)));
+                    }
+                    reads += 1;
+                }
+            })
+        })
+        .collect();
+    for generation in 1..50 {
+        cache.update(|master| {
+            for key in 0..3 {
+                master.put(key, generation * 1000 + key * 10);
+            }
+        });
+    }
+    stop.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(cache.get(&2), Some(49_020));
+
+    // Un instantané gardé survit aux écritures ; la capacité du maître
+    // s'applique aux publications
+    let before = cache.load();
+    cache.put(3, 30);
+    assert!(!cache.contains(&0));
+    assert_eq!(before.peek(&0), Some(&49_000));
+    assert_eq!(cache.remove(&3), Some(30));
+    assert_eq!(cache.len(), 2);
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(before.len(), 3);
+}
+
+#[test]
+fn test_recency_updates_scale_to_large_capacities() {
+    use lru_cache::lru::traits::CacheRead;
+
+    // Avec un ordre d'utilisation linéaire, ce test prendrait des minutes
+    let capacity = 100_000u32;
+    let mut cache = Cache::new(capacity as usize);
+    for key in 0..capacity {
+        cache.put(key, key);
+    }
+    for round in 0..3u32 {
+        for key in (0..capacity).step_by(2) {
+            assert_eq!(cache.get(&key), Some(&key), "tour {}", round);
+        }
+    }
+    // Les clés impaires, jamais relues, sont évincées les premières
+    for key in capacity..capacity + capacity / 2 {
+        cache.put(key, key);
+    }
+    assert_eq!(cache.len(), capacity as usize);
+    assert!((1..capacity).step_by(2).all(|key| !cache.contains(&key)));
+    assert!((0..capacity).step_by(2).all(|key| cache.contains(&key)));
+
+    let order: Vec<u32> = cache.iter().map(|(key, _)| *key).take(3).collect();
+    assert_eq!(order, [0, 2, 4]);
+    cache.remove(&2);
+    cache.get(&0);
+    let order: Vec<u32> = cache.iter().map(|(key, _)| *key).take(2).collect();
+    assert_eq!(order, [4, 6]);
+    assert_eq!(cache.iter().last().map(|(key, _)| *key), Some(0));
+}
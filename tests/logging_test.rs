@@ -0,0 +1,59 @@
+use lru_cache::lru::{Cache, traits::CacheTrait};
+use lru_cache::lru::logging::LoggingCache;
+use lru_cache::messages;
+use std::sync::Mutex;
+
+///////////////////////////////////////////////////////////////////////////////
+// Journal de test capturant les messages émis
+///////////////////////////////////////////////////////////////////////////////
+
+static RECORDS: Mutex<Vec<(log::Level, String)>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        RECORDS.lock().unwrap().push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger;
+
+///////////////////////////////////////////////////////////////////////////////
+// Test du décorateur de journalisation
+///////////////////////////////////////////////////////////////////////////////
+
+#[test]
+fn test_logging_cache_records_operations() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let mut cache = LoggingCache::new(Cache::new(1))
+        .with_level(log::Level::Info)
+        .with_name("test");
+
+    cache.put(1, "one");
+    cache.get(&1);
+    cache.get(&2);
+    cache.put(2, "two");
+
+    let records = RECORDS.lock().unwrap();
+    let lines: Vec<&str> = records.iter().map(|(_, line)| line.as_str()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            format!("[test] {}: 1", messages::LOG_PUT),
+            format!("[test] {}: 1", messages::LOG_HIT),
+            format!("[test] {}: 2", messages::LOG_MISS),
+            format!("[test] {}: 2", messages::LOG_PUT),
+            format!("[test] {}", messages::LOG_EVICTION),
+        ]
+    );
+    assert!(records.iter().all(|(level, _)| *level == log::Level::Info));
+}
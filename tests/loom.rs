@@ -0,0 +1,60 @@
+//! Vérification de modèle du cache partagé par loom.
+//!
+//! Ces tests ne sont compilés qu'avec `RUSTFLAGS="--cfg loom"` :
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --test loom
+//! ```
+#![cfg(loom)]
+
+use loom::sync::Arc;
+use loom::sync::atomic::{AtomicUsize, Ordering};
+use loom::thread;
+use lru_cache::error::CacheError;
+use lru_cache::lru::sync::SyncCache;
+
+#[test]
+fn loom_concurrent_misses_load_once() {
+    loom::model(|| {
+        let cache: SyncCache<u32, u32> = SyncCache::new(4);
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let cache = cache.clone();
+                let calls = Arc::clone(&calls);
+                thread::spawn(move || {
+                    let loader = |key: &u32| -> Result<u32, CacheError> {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        Ok(key * 10)
+                    };
+                    cache.get_or_load(1, &loader).unwrap()
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 10);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.get(&1).unwrap(), Some(10));
+    });
+}
+
+#[test]
+fn loom_failed_load_reaches_waiters() {
+    loom::model(|| {
+        let cache: SyncCache<u32, u32> = SyncCache::new(4);
+        let waiter = {
+            let cache = cache.clone();
+            thread::spawn(move || {
+                let loader = |_: &u32| -> Result<u32, CacheError> { Err(CacheError::Poisoned) };
+                cache.get_or_load(1, &loader).is_err()
+            })
+        };
+        let loader = |_: &u32| -> Result<u32, CacheError> { Err(CacheError::Poisoned) };
+        assert!(cache.get_or_load(1, &loader).is_err());
+        assert!(waiter.join().unwrap());
+        assert_eq!(cache.len().unwrap(), 0);
+    });
+}